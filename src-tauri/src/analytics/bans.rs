@@ -0,0 +1,176 @@
+use crate::lcu::timeline::{DraftTimelineStore, TimelineEvent, TimelineEventKind};
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+/// Champions listed per ban ranking.
+const TOP_BANS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BanCount {
+    pub champion_id: i64,
+    pub drafts: u32, // Drafts it was banned in
+    pub rate: f32,   // Of drafts with a ban phase
+}
+
+/// Ban phases of the drafts the app recorded.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BanStats {
+    pub drafts: u32,                      // Recorded drafts with at least one ban
+    pub banned_against_me: Vec<BanCount>, // By the enemy team, most banned first
+    pub my_bans: Vec<BanCount>,
+    pub pool_targeted: u32, // Drafts where the enemy banned a champion from my pool
+    pub pool_target_rate: Option<f32>, // None without drafts or a pool
+}
+
+/// Per champion, the drafts it was banned in by anyone, and the number of
+/// drafts with a ban phase.
+pub fn ban_counts<'a>(
+    timelines: impl IntoIterator<Item = &'a Vec<TimelineEvent>>,
+) -> (u32, BTreeMap<i64, u32>) {
+    let mut drafts = 0;
+    let mut counts: BTreeMap<i64, u32> = BTreeMap::new();
+    for events in timelines {
+        let banned: HashSet<i64> = events
+            .iter()
+            .filter(|e| e.kind == TimelineEventKind::Ban)
+            .map(|e| e.champion_id)
+            .collect();
+        if banned.is_empty() {
+            continue;
+        }
+        drafts += 1;
+        for champion_id in banned {
+            *counts.entry(champion_id).or_default() += 1;
+        }
+    }
+    (drafts, counts)
+}
+
+fn ranking(counts: BTreeMap<i64, u32>, drafts: u32) -> Vec<BanCount> {
+    let mut ranking: Vec<BanCount> = counts
+        .into_iter()
+        .map(|(champion_id, count)| BanCount {
+            champion_id,
+            drafts: count,
+            rate: count as f32 / drafts as f32,
+        })
+        .collect();
+    ranking.sort_by_key(|b| std::cmp::Reverse(b.drafts));
+    ranking.truncate(TOP_BANS);
+    ranking
+}
+
+pub fn ban_stats<'a>(
+    timelines: impl IntoIterator<Item = &'a Vec<TimelineEvent>>,
+    pool: &[i64],
+) -> BanStats {
+    let mut drafts = 0;
+    let mut pool_targeted = 0;
+    let mut against_me: BTreeMap<i64, u32> = BTreeMap::new();
+    let mut mine: BTreeMap<i64, u32> = BTreeMap::new();
+
+    for events in timelines {
+        let bans: Vec<&TimelineEvent> = events
+            .iter()
+            .filter(|e| e.kind == TimelineEventKind::Ban)
+            .collect();
+        if bans.is_empty() {
+            continue;
+        }
+        drafts += 1;
+
+        let my_team = events
+            .iter()
+            .find(|e| e.is_local_player)
+            .and_then(|e| e.team_id);
+        let enemy_bans: HashSet<i64> = bans
+            .iter()
+            .filter(|e| my_team.is_some() && e.team_id.is_some() && e.team_id != my_team)
+            .map(|e| e.champion_id)
+            .collect();
+        let my_bans: HashSet<i64> = bans
+            .iter()
+            .filter(|e| e.is_local_player)
+            .map(|e| e.champion_id)
+            .collect();
+
+        pool_targeted += enemy_bans.iter().any(|id| pool.contains(id)) as u32;
+        for champion_id in enemy_bans {
+            *against_me.entry(champion_id).or_default() += 1;
+        }
+        for champion_id in my_bans {
+            *mine.entry(champion_id).or_default() += 1;
+        }
+    }
+
+    BanStats {
+        drafts,
+        banned_against_me: ranking(against_me, drafts),
+        my_bans: ranking(mine, drafts),
+        pool_targeted,
+        pool_target_rate: (drafts > 0 && !pool.is_empty())
+            .then(|| pool_targeted as f32 / drafts as f32),
+    }
+}
+
+#[tauri::command]
+pub async fn get_ban_stats(
+    drafts: tauri::State<'_, Mutex<DraftTimelineStore>>,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<BanStats, String> {
+    let pool = settings
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .get()
+        .champion_pool
+        .clone();
+    let drafts_guard = drafts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(ban_stats(drafts_guard.timelines(), &pool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ban(team_id: i64, champion_id: i64, is_local_player: bool) -> TimelineEvent {
+        TimelineEvent {
+            elapsed_ms: 0,
+            kind: TimelineEventKind::Ban,
+            cell_id: None,
+            team_id: Some(team_id),
+            champion_id,
+            is_local_player,
+            phase: "BAN_PICK".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_bans_split_by_side() {
+        let timelines = vec![
+            vec![
+                ban(100, 238, true),
+                ban(200, 64, false),
+                ban(200, 157, false),
+            ],
+            vec![ban(200, 238, true), ban(100, 64, false)],
+            vec![ban(100, 55, true), ban(200, 1, false)],
+            // Blind pick: no ban phase
+            vec![],
+        ];
+
+        let stats = ban_stats(&timelines, &[64, 99]);
+        assert_eq!(stats.drafts, 3);
+        assert_eq!(stats.banned_against_me[0].champion_id, 64);
+        assert_eq!(stats.banned_against_me[0].drafts, 2);
+        assert_eq!(stats.banned_against_me.len(), 3);
+        assert_eq!(stats.my_bans[0].champion_id, 238);
+        assert!((stats.my_bans[0].rate - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(stats.pool_targeted, 2);
+        assert_eq!(ban_stats(&timelines, &[]).pool_target_rate, None);
+
+        let (drafts, counts) = ban_counts(&timelines);
+        assert_eq!((drafts, counts[&238], counts[&55]), (3, 2, 1));
+    }
+}
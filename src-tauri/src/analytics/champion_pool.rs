@@ -0,0 +1,219 @@
+use crate::lcu::api::LcuApi;
+use crate::lcu::cancel::{phase_token, until_cancelled};
+use crate::lcu::client::{LcuClient, MatchHistoryGame};
+use crate::storage::{MatchStore, StoredMatch, POSITIONS};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// Games on a champion in a role before it counts as part of the pool.
+pub const MIN_GAMES: u32 = 3;
+
+/// Champions lost with more often than this are left out of the pool.
+const MIN_WIN_RATE: f32 = 0.4;
+
+/// Only this many of the player's latest games are considered.
+const RECENT_GAMES: usize = 60;
+
+/// Games fetched from the client on top of the stored ones.
+const FETCHED_GAMES: usize = 20;
+
+/// One game of the player's, from the match store or the client's history.
+#[derive(Debug, Clone)]
+pub struct PoolGame {
+    pub game_id: i64,
+    pub game_creation: i64,
+    pub champion_id: i64,
+    pub position: Option<String>, // Client history has no lanes
+    pub win: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolChampion {
+    pub champion_id: i64,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolePool {
+    pub position: String,
+    pub champions: Vec<PoolChampion>, // Most played first
+}
+
+/// The player's games in stored matches, whoever's history they came from.
+pub fn stored_games<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    puuid: &str,
+) -> Vec<PoolGame> {
+    matches
+        .into_iter()
+        .filter_map(|m| {
+            let player = m.participants.iter().find(|p| p.puuid == puuid)?;
+            Some(PoolGame {
+                game_id: m.game_id,
+                game_creation: m.game_creation,
+                champion_id: player.champion_id,
+                position: player.position.clone(),
+                win: player.win,
+            })
+        })
+        .collect()
+}
+
+pub fn fetched_games(history: &[MatchHistoryGame]) -> Vec<PoolGame> {
+    history
+        .iter()
+        .map(|g| PoolGame {
+            game_id: g.game_id,
+            game_creation: g.game_creation,
+            champion_id: g.champion_id as i64,
+            position: None,
+            win: g.win,
+        })
+        .collect()
+}
+
+/// Champions the player reliably brings to each role: at least `MIN_GAMES`
+/// recent games there without a poor win rate. Games without a known lane
+/// count towards the role the champion is otherwise played in.
+pub fn infer_pool(mut games: Vec<PoolGame>) -> Vec<RolePool> {
+    // A game both stored and fetched keeps the copy that knows the lane
+    games.sort_by_key(|g| g.position.is_none());
+    let mut seen = HashSet::new();
+    let mut games: Vec<PoolGame> = games
+        .into_iter()
+        .filter(|g| seen.insert(g.game_id))
+        .collect();
+    games.sort_by_key(|g| std::cmp::Reverse(g.game_creation));
+    games.truncate(RECENT_GAMES);
+
+    let mut lanes: HashMap<i64, [u32; 5]> = HashMap::new();
+    for game in &games {
+        if let Some(slot) = game
+            .position
+            .as_deref()
+            .and_then(|p| POSITIONS.iter().position(|known| *known == p))
+        {
+            lanes.entry(game.champion_id).or_default()[slot] += 1;
+        }
+    }
+    let main_slot = |champion_id: i64| {
+        let counts = lanes.get(&champion_id)?;
+        let (slot, count) = counts.iter().enumerate().max_by_key(|(_, c)| **c)?;
+        (*count > 0).then_some(slot)
+    };
+
+    let mut records: HashMap<(usize, i64), (u32, u32)> = HashMap::new();
+    for game in &games {
+        let slot = game
+            .position
+            .as_deref()
+            .and_then(|p| POSITIONS.iter().position(|known| *known == p))
+            .or_else(|| main_slot(game.champion_id));
+        if let Some(slot) = slot {
+            let record = records.entry((slot, game.champion_id)).or_default();
+            record.0 += 1;
+            record.1 += game.win as u32;
+        }
+    }
+
+    POSITIONS
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, position)| {
+            let mut champions: Vec<PoolChampion> = records
+                .iter()
+                .filter(|((s, _), _)| *s == slot)
+                .map(|((_, champion_id), (games, wins))| PoolChampion {
+                    champion_id: *champion_id,
+                    games: *games,
+                    wins: *wins,
+                    win_rate: *wins as f32 / *games as f32,
+                })
+                .filter(|c| c.games >= MIN_GAMES && c.win_rate >= MIN_WIN_RATE)
+                .collect();
+            champions.sort_by(|a, b| {
+                b.games
+                    .cmp(&a.games)
+                    .then(b.win_rate.total_cmp(&a.win_rate))
+            });
+            (!champions.is_empty()).then(|| RolePool {
+                position: position.to_string(),
+                champions,
+            })
+        })
+        .collect()
+}
+
+/// A player's inferred pool per role, from stored matches plus their recent
+/// client history when the client can provide it.
+#[tauri::command]
+pub async fn get_inferred_pool(
+    app: AppHandle,
+    puuid: String,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<Vec<RolePool>, String> {
+    let token = phase_token(&app);
+    let fetched = until_cancelled(&token, async {
+        let mut client_guard = client.lock().await;
+        Ok(client_guard
+            .get_match_history_for(&puuid, 0, FETCHED_GAMES)
+            .await
+            .unwrap_or_default())
+    })
+    .await?;
+
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut games = stored_games(store_guard.matches(), &puuid);
+    games.extend(fetched_games(&fetched));
+    Ok(infer_pool(games))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(game_id: i64, champion_id: i64, position: Option<&str>, win: bool) -> PoolGame {
+        PoolGame {
+            game_id,
+            game_creation: game_id,
+            champion_id,
+            position: position.map(|p| p.to_string()),
+            win,
+        }
+    }
+
+    #[test]
+    fn test_infer_pool() {
+        let games = vec![
+            game(1, 64, Some("JUNGLE"), true),
+            game(2, 64, Some("JUNGLE"), false),
+            game(3, 64, None, true),           // Client history, lane unknown
+            game(3, 64, Some("JUNGLE"), true), // Same game from the store
+            game(4, 64, None, true),
+            game(5, 11, Some("JUNGLE"), false),
+            game(6, 11, Some("JUNGLE"), false),
+            game(7, 11, Some("JUNGLE"), false), // Three games but all lost
+            game(8, 99, Some("MIDDLE"), true),
+            game(9, 99, Some("MIDDLE"), true),
+            game(10, 99, Some("MIDDLE"), true),
+            game(11, 1, None, true), // Never seen with a lane
+        ];
+
+        let pool = infer_pool(games);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool[0].position, "JUNGLE");
+        let jungle: Vec<(i64, u32)> = pool[0]
+            .champions
+            .iter()
+            .map(|c| (c.champion_id, c.games))
+            .collect();
+        assert_eq!(jungle, vec![(64, 4)]);
+        assert_eq!(pool[1].position, "MIDDLE");
+        assert_eq!(pool[1].champions[0].wins, 3);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::lcu::draft::{Cell, ChampionBan, ChampionPick, DraftState, Team};
+use crate::lcu::timeline::{DraftTimelineStore, TimelineEvent, TimelineEventKind};
+use crate::model::{ChampionRecommendation, DraftRecommendationModel};
+use crate::startup::ensure_model_loaded;
+use crate::storage::MatchStore;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// How the owner's pick in one recorded draft compares with what the model
+/// would have picked at that moment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DraftGrade {
+    pub game_id: i64,
+    pub champion_id: i64,
+    pub best_champion_id: i64, // The model's top pick when the owner locked in
+    pub rank: Option<usize>,   // 1 for the model's top pick; None when the pick wasn't scored
+    pub grade: f32,            // The pick's score over the top pick's, 0 to 1
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReviewSummary {
+    pub drafts: u32,                // Graded drafts
+    pub top_pick_rate: Option<f32>, // Share of drafts where the owner took the model's top pick
+    pub top_pick_games: u32,        // Those drafts with a stored result
+    pub top_pick_win_rate: Option<f32>,
+    pub other_games: u32,
+    pub other_win_rate: Option<f32>,
+    pub average_grade: Option<f32>,
+    pub grades: Vec<DraftGrade>, // Most recent first
+}
+
+fn cell(cell_id: i64, champion_id: Option<i64>, selected_champion_id: Option<i64>) -> Cell {
+    Cell {
+        cell_id,
+        champion_id,
+        selected_champion_id,
+        assigned_position: None,
+        spell1_id: None,
+        spell2_id: None,
+        spell1_name: None,
+        spell2_name: None,
+        trade_available: false,
+        pick_swap_available: false,
+        position_swap_available: false,
+        pick_turn: None,
+        previous_hovers: Vec::new(),
+        previous_hover_names: Vec::new(),
+    }
+}
+
+/// The draft as it stood just before the owner locked in, rebuilt from its
+/// timeline, with the champion they locked. None when they never locked.
+pub fn draft_before_lock(events: &[TimelineEvent]) -> Option<(DraftState, i64)> {
+    let lock_index = events
+        .iter()
+        .position(|e| e.is_local_player && e.kind == TimelineEventKind::Lock)?;
+    let lock = &events[lock_index];
+    let local_cell = lock.cell_id?;
+    let local_team = lock.team_id.unwrap_or(100);
+
+    let mut teams: Vec<Team> = [100, 200]
+        .iter()
+        .map(|&team_id| Team {
+            team_id,
+            picks: Vec::new(),
+            bans: Vec::new(),
+            cells: Vec::new(),
+        })
+        .collect();
+    // Latest hover per cell not locked yet, with the cell's team
+    let mut hovers: BTreeMap<i64, (usize, i64)> = BTreeMap::new();
+
+    for event in &events[..lock_index] {
+        let team = event
+            .team_id
+            .and_then(|id| teams.iter().position(|t| t.team_id == id));
+        match (event.kind, team, event.cell_id) {
+            (TimelineEventKind::Ban, _, _) => teams[team.unwrap_or(0)].bans.push(ChampionBan {
+                champion_id: event.champion_id,
+                cell_id: event.cell_id,
+                completed: true,
+                is_ally_ban: event.team_id == Some(local_team),
+            }),
+            (TimelineEventKind::Lock, Some(team), Some(cell_id)) => {
+                hovers.remove(&cell_id);
+                teams[team].picks.push(ChampionPick {
+                    champion_id: event.champion_id,
+                    cell_id: Some(cell_id),
+                    completed: true,
+                    is_ally_pick: event.team_id == Some(local_team),
+                    position: None,
+                });
+                teams[team]
+                    .cells
+                    .push(cell(cell_id, Some(event.champion_id), None));
+            }
+            (TimelineEventKind::Hover, Some(team), Some(cell_id)) if cell_id != local_cell => {
+                hovers.insert(cell_id, (team, event.champion_id));
+            }
+            _ => {}
+        }
+    }
+    for (cell_id, (team, champion_id)) in hovers {
+        teams[team]
+            .cells
+            .push(cell(cell_id, None, Some(champion_id)));
+    }
+    if let Some(team) = teams.iter_mut().find(|t| t.team_id == local_team) {
+        team.cells.push(cell(local_cell, None, None));
+    }
+
+    let state = DraftState {
+        game_id: None,
+        timer: None,
+        phase: lock.phase.clone(),
+        teams,
+        actions: Vec::new(),
+        local_player_cell_id: Some(local_cell),
+        is_custom_game: false,
+        queue_id: None,
+        aram: None,
+        warnings: Vec::new(),
+        swap_offers: Vec::new(),
+        series_excluded: Vec::new(),
+        raw_session: None,
+    };
+    Some((state, lock.champion_id))
+}
+
+/// Grade `champion_id` against the model's scores, best first.
+pub fn grade(
+    game_id: i64,
+    champion_id: i64,
+    recommendations: &[ChampionRecommendation],
+) -> Option<DraftGrade> {
+    let best = recommendations.first()?;
+    let rank = recommendations
+        .iter()
+        .position(|r| r.champion_id as i64 == champion_id);
+    let score = rank.map_or(0.0, |i| recommendations[i].score);
+    Some(DraftGrade {
+        game_id,
+        champion_id,
+        best_champion_id: best.champion_id as i64,
+        rank: rank.map(|i| i + 1),
+        grade: if best.score > 0.0 {
+            (score / best.score).clamp(0.0, 1.0)
+        } else {
+            0.0
+        },
+    })
+}
+
+/// Aggregate grades, splitting win rates by whether the owner took the
+/// model's top pick. `wins` holds the result of each stored game.
+pub fn review_summary(mut grades: Vec<DraftGrade>, wins: &HashMap<i64, bool>) -> ReviewSummary {
+    grades.sort_by_key(|g| std::cmp::Reverse(g.game_id));
+    let drafts = grades.len() as u32;
+    let top_picks = grades.iter().filter(|g| g.rank == Some(1)).count() as u32;
+    let (mut top_pick_games, mut top_pick_wins, mut other_games, mut other_wins) = (0, 0, 0, 0);
+    for grade in &grades {
+        let Some(&win) = wins.get(&grade.game_id) else {
+            continue;
+        };
+        if grade.rank == Some(1) {
+            top_pick_games += 1;
+            top_pick_wins += win as u32;
+        } else {
+            other_games += 1;
+            other_wins += win as u32;
+        }
+    }
+    let rate = |part: u32, whole: u32| (whole > 0).then(|| part as f32 / whole as f32);
+
+    ReviewSummary {
+        drafts,
+        top_pick_rate: rate(top_picks, drafts),
+        top_pick_games,
+        top_pick_win_rate: rate(top_pick_wins, top_pick_games),
+        other_games,
+        other_win_rate: rate(other_wins, other_games),
+        average_grade: (drafts > 0)
+            .then(|| grades.iter().map(|g| g.grade).sum::<f32>() / drafts as f32),
+        grades,
+    }
+}
+
+/// Grade every recorded draft the owner locked in. Drafts are scored for the
+/// position the owner ended up playing when the game is stored.
+#[tauri::command]
+pub async fn get_review_summary(app: AppHandle) -> Result<ReviewSummary, String> {
+    ensure_model_loaded(&app);
+    let model = app
+        .state::<Mutex<Option<Arc<DraftRecommendationModel>>>>()
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .clone()
+        .ok_or_else(|| "Draft recommendation model is not available".to_string())?;
+
+    let timelines: Vec<(i64, Vec<TimelineEvent>)> = {
+        let drafts = app.state::<Mutex<DraftTimelineStore>>();
+        let drafts_guard = drafts.lock().map_err(|e| format!("Lock error: {}", e))?;
+        drafts_guard
+            .game_ids()
+            .filter_map(|id| Some((id, drafts_guard.get(id)?.clone())))
+            .collect()
+    };
+    let (positions, wins) = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut positions: HashMap<i64, String> = HashMap::new();
+        let mut wins: HashMap<i64, bool> = HashMap::new();
+        for m in store_guard.matches() {
+            if let Some(owner) = m.owner() {
+                wins.insert(m.game_id, owner.win);
+                if let Some(position) = &owner.position {
+                    positions.insert(m.game_id, position.clone());
+                }
+            }
+        }
+        (positions, wins)
+    };
+
+    // One inference per draft; keep it off the async runtime
+    tokio::task::spawn_blocking(move || {
+        let grades = timelines
+            .iter()
+            .filter_map(|(game_id, events)| {
+                let (state, champion_id) = draft_before_lock(events)?;
+                let scores = model
+                    .get_recommendations(
+                        &state,
+                        usize::MAX,
+                        positions.get(game_id).map(|p| p.as_str()),
+                    )
+                    .ok()?;
+                grade(*game_id, champion_id, &scores.recommendations)
+            })
+            .collect();
+        review_summary(grades, &wins)
+    })
+    .await
+    .map_err(|e| format!("Draft review failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: TimelineEventKind, cell_id: i64, champion_id: i64) -> TimelineEvent {
+        TimelineEvent {
+            elapsed_ms: 0,
+            kind,
+            cell_id: Some(cell_id),
+            team_id: Some(if cell_id < 5 { 100 } else { 200 }),
+            champion_id,
+            is_local_player: cell_id == 2,
+            phase: "BAN_PICK".to_string(),
+        }
+    }
+
+    fn scores(ids: &[u32]) -> Vec<ChampionRecommendation> {
+        ids.iter()
+            .enumerate()
+            .map(|(i, &champion_id)| ChampionRecommendation {
+                champion_id,
+                score: 1.0 / (i + 1) as f32,
+                coach: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_draft_is_rebuilt_up_to_the_owners_lock() {
+        use TimelineEventKind::*;
+        let events = [
+            event(Ban, 0, 157),
+            event(Ban, 7, 238),
+            event(Lock, 5, 64),
+            event(Hover, 1, 89),
+            event(Hover, 2, 103),
+            event(Lock, 2, 99),
+            event(Lock, 6, 1),
+        ];
+        let (state, champion_id) = draft_before_lock(&events).unwrap();
+        assert_eq!(champion_id, 99);
+        assert_eq!(state.local_player_cell_id, Some(2));
+        let bans: Vec<i64> = state
+            .teams
+            .iter()
+            .flat_map(|t| t.bans.iter().map(|b| b.champion_id))
+            .collect();
+        assert_eq!(bans, vec![157, 238]);
+        assert_eq!(state.teams[1].picks.len(), 1, "later picks left out");
+
+        let blue = &state.teams[0].cells;
+        assert_eq!(blue.len(), 2);
+        assert_eq!(blue[0].selected_champion_id, Some(89));
+        assert_eq!((blue[1].cell_id, blue[1].selected_champion_id), (2, None));
+
+        assert!(draft_before_lock(&events[..4]).is_none());
+    }
+
+    #[test]
+    fn test_summary_splits_win_rate_by_top_pick() {
+        let grades = vec![
+            grade(1, 99, &scores(&[99, 1, 2])).unwrap(),
+            grade(2, 2, &scores(&[99, 1, 2])).unwrap(),
+            grade(3, 99, &scores(&[99, 1])).unwrap(),
+            grade(4, 50, &scores(&[99])).unwrap(),
+        ];
+        assert_eq!(grades[1].rank, Some(3));
+        assert!((grades[1].grade - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!((grades[3].rank, grades[3].grade), (None, 0.0));
+
+        // Draft 3 was dodged
+        let wins = HashMap::from([(1, true), (2, false), (4, true)]);
+        let summary = review_summary(grades, &wins);
+        assert_eq!(summary.drafts, 4);
+        assert_eq!(summary.top_pick_rate, Some(0.5));
+        assert_eq!(
+            (summary.top_pick_games, summary.top_pick_win_rate),
+            (1, Some(1.0))
+        );
+        assert_eq!(
+            (summary.other_games, summary.other_win_rate),
+            (2, Some(0.5))
+        );
+        assert_eq!(summary.grades[0].game_id, 4);
+    }
+}
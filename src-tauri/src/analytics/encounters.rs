@@ -0,0 +1,327 @@
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::LcuClient;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Games against a player before their picks are worth showing.
+const MIN_GAMES_AGAINST: u32 = 2;
+
+/// A player who showed up in my stored games, with my record alongside and against them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Encounter {
+    pub puuid: String,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub games: u32,
+    pub wins_with: u32,
+    pub losses_with: u32,
+    pub wins_against: u32,
+    pub losses_against: u32,
+    pub last_seen: i64, // game_creation of the most recent shared game
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EncounterFilter {
+    pub role: Option<String>, // Only games where I played this position
+    pub queue_id: Option<i32>,
+}
+
+impl EncounterFilter {
+    fn matches(&self, stored: &StoredMatch) -> bool {
+        if self.queue_id.is_some_and(|q| q != stored.queue_id) {
+            return false;
+        }
+        match &self.role {
+            Some(role) => stored
+                .owner()
+                .and_then(|o| o.position.as_deref())
+                .is_some_and(|p| p.eq_ignore_ascii_case(role)),
+            None => true,
+        }
+    }
+}
+
+/// Everyone I've played with or against, most frequent first.
+pub fn recent_encounters<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    filter: &EncounterFilter,
+) -> Vec<Encounter> {
+    let mut encounters: HashMap<String, Encounter> = HashMap::new();
+
+    for stored in matches.into_iter().filter(|m| filter.matches(m)) {
+        let Some(owner) = stored.owner() else {
+            continue;
+        };
+
+        for player in &stored.participants {
+            if player.puuid == stored.owner_puuid || player.puuid.is_empty() {
+                continue;
+            }
+
+            let entry = encounters
+                .entry(player.puuid.clone())
+                .or_insert_with(|| Encounter {
+                    puuid: player.puuid.clone(),
+                    game_name: None,
+                    tag_line: None,
+                    games: 0,
+                    wins_with: 0,
+                    losses_with: 0,
+                    wins_against: 0,
+                    losses_against: 0,
+                    last_seen: 0,
+                });
+
+            entry.games += 1;
+            match (player.team_id == owner.team_id, owner.win) {
+                (true, true) => entry.wins_with += 1,
+                (true, false) => entry.losses_with += 1,
+                (false, true) => entry.wins_against += 1,
+                (false, false) => entry.losses_against += 1,
+            }
+            // Keep the most recent name: Riot IDs can change between games
+            if stored.game_creation >= entry.last_seen {
+                entry.last_seen = stored.game_creation;
+                entry.game_name = player.game_name.clone();
+                entry.tag_line = player.tag_line.clone();
+            }
+        }
+    }
+
+    let mut result: Vec<Encounter> = encounters.into_values().collect();
+    result.sort_by(|a, b| b.games.cmp(&a.games).then(b.last_seen.cmp(&a.last_seen)));
+    result
+}
+
+#[tauri::command]
+pub async fn get_recent_encounters(
+    role: Option<String>,
+    queue_id: Option<i32>,
+    limit: Option<usize>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Vec<Encounter>, String> {
+    let filter = EncounterFilter { role, queue_id };
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut encounters = recent_encounters(store_guard.matches(), &filter);
+    encounters.truncate(limit.unwrap_or(50));
+    Ok(encounters)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChampionTendency {
+    pub champion_id: i64,
+    pub games: u32,
+    pub wins: u32, // Their wins, i.e. my losses
+}
+
+/// What a frequent opponent picked in my stored games against them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpponentTendencies {
+    pub puuid: String,
+    pub cell_id: Option<i64>, // Their cell in the current draft
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub games_against: u32,
+    pub champions: Vec<ChampionTendency>, // Most picked first
+}
+
+/// Champions `puuid` played on the other team in my stored games. None when
+/// we met fewer than `MIN_GAMES_AGAINST` times.
+pub fn opponent_tendencies<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    puuid: &str,
+) -> Option<OpponentTendencies> {
+    let mut tendencies = OpponentTendencies {
+        puuid: puuid.to_string(),
+        cell_id: None,
+        game_name: None,
+        tag_line: None,
+        games_against: 0,
+        champions: Vec::new(),
+    };
+    let mut last_seen = 0;
+
+    for stored in matches {
+        let (Some(owner), Some(player)) = (
+            stored.owner(),
+            stored.participants.iter().find(|p| p.puuid == puuid),
+        ) else {
+            continue;
+        };
+        if player.team_id == owner.team_id || player.puuid == stored.owner_puuid {
+            continue;
+        }
+
+        tendencies.games_against += 1;
+        match tendencies
+            .champions
+            .iter_mut()
+            .find(|c| c.champion_id == player.champion_id)
+        {
+            Some(champion) => {
+                champion.games += 1;
+                champion.wins += player.win as u32;
+            }
+            None => tendencies.champions.push(ChampionTendency {
+                champion_id: player.champion_id,
+                games: 1,
+                wins: player.win as u32,
+            }),
+        }
+        if stored.game_creation >= last_seen {
+            last_seen = stored.game_creation;
+            tendencies.game_name = player.game_name.clone();
+            tendencies.tag_line = player.tag_line.clone();
+        }
+    }
+
+    if tendencies.games_against < MIN_GAMES_AGAINST {
+        return None;
+    }
+    tendencies
+        .champions
+        .sort_by(|a, b| b.games.cmp(&a.games).then(b.wins.cmp(&a.wins)));
+    Some(tendencies)
+}
+
+/// Tendencies of the frequent opponents in the current champ select. Players
+/// the client hides, as on the enemy side of ranked drafts, are left out.
+#[tauri::command]
+pub async fn get_lobby_tendencies(
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Vec<OpponentTendencies>, String> {
+    let session = client.lock().await.get_draft_session().await?;
+    let lobby: Vec<(i64, String)> = session
+        .my_team
+        .iter()
+        .chain(session.their_team.iter())
+        .flatten()
+        .filter(|m| m.cell_id != session.local_player_cell_id)
+        .filter_map(|m| Some((m.cell_id?, m.puuid.clone().filter(|p| !p.is_empty())?)))
+        .collect();
+
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(lobby
+        .into_iter()
+        .filter_map(|(cell_id, puuid)| {
+            let mut tendencies = opponent_tendencies(store_guard.matches(), &puuid)?;
+            tendencies.cell_id = Some(cell_id);
+            Some(tendencies)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_recent_encounters() {
+        let matches: Vec<StoredMatch> = [
+            game_details(
+                1,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("duo", 64, 100, "JUNGLE", "NONE"),
+                    ("rival", 157, 200, "TOP", "SOLO"),
+                ],
+            ),
+            game_details(
+                2,
+                420,
+                &[
+                    ("me", 266, 200, "TOP", "SOLO"),
+                    ("duo", 64, 200, "JUNGLE", "NONE"),
+                ],
+            ),
+            game_details(
+                3,
+                440,
+                &[
+                    ("me", 103, 100, "MIDDLE", "SOLO"),
+                    ("rival", 157, 100, "TOP", "SOLO"),
+                ],
+            ),
+        ]
+        .iter()
+        .map(|g| parse_game_details(g, "me").unwrap())
+        .collect();
+
+        let all = recent_encounters(&matches, &EncounterFilter::default());
+        assert_eq!(all.len(), 2);
+        let duo = all.iter().find(|e| e.puuid == "duo").unwrap();
+        assert_eq!((duo.games, duo.wins_with, duo.losses_with), (2, 1, 1));
+        let rival = all.iter().find(|e| e.puuid == "rival").unwrap();
+        assert_eq!((rival.wins_against, rival.wins_with), (1, 1));
+        assert_eq!(rival.last_seen, 3000);
+
+        let top_solo = recent_encounters(
+            &matches,
+            &EncounterFilter {
+                role: Some("top".to_string()),
+                queue_id: Some(420),
+            },
+        );
+        let rival = top_solo.iter().find(|e| e.puuid == "rival").unwrap();
+        assert_eq!(rival.games, 1);
+    }
+
+    #[test]
+    fn test_opponent_tendencies() {
+        let matches: Vec<StoredMatch> = [
+            game_details(
+                1,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("rival", 157, 200, "TOP", "SOLO"),
+                ],
+            ),
+            game_details(
+                2,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("rival", 157, 200, "TOP", "SOLO"),
+                ],
+            ),
+            game_details(
+                3,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("rival", 92, 200, "TOP", "SOLO"),
+                ],
+            ),
+            // Same team: not a game against them
+            game_details(
+                4,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("rival", 64, 100, "JUNGLE", "NONE"),
+                ],
+            ),
+        ]
+        .iter()
+        .map(|g| parse_game_details(g, "me").unwrap())
+        .collect();
+
+        let rival = opponent_tendencies(&matches, "rival").unwrap();
+        assert_eq!(rival.games_against, 3);
+        assert_eq!(rival.champions.len(), 2);
+        assert_eq!(
+            (rival.champions[0].champion_id, rival.champions[0].games),
+            (157, 2)
+        );
+        assert_eq!(rival.champions[0].wins, 0);
+        assert!(opponent_tendencies(&matches[..1], "rival").is_none());
+        assert!(opponent_tendencies(&matches, "nobody").is_none());
+    }
+}
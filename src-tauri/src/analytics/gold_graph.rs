@@ -0,0 +1,145 @@
+use crate::storage::timelines::{MatchTimeline, MatchTimelineStore};
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// One minute of the graph. Differences are the owner's side minus the other.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoldGraphPoint {
+    pub minute: u32,
+    pub team_gold_diff: i32,
+    pub team_xp_diff: i32,
+    pub lane_gold_diff: Option<i32>, // None without a known lane opponent
+    pub lane_xp_diff: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoldGraph {
+    pub game_id: i64,
+    pub lane_opponent_champion_id: Option<i64>,
+    pub points: Vec<GoldGraphPoint>,
+}
+
+/// Team and lane gold and XP differences for every timeline frame, from the
+/// owner's side. `None` when the owner or their team can't be linked to the
+/// timeline (games stored before participant ids were kept).
+pub fn gold_graph(stored: &StoredMatch, timeline: &MatchTimeline) -> Option<GoldGraph> {
+    let owner = stored.owner()?;
+    let owner_id = owner.participant_id?;
+    let opponent = stored.lane_opponent();
+    let opponent_id = opponent.and_then(|p| p.participant_id);
+    // participant id -> +1 on the owner's team, -1 on the other
+    let sides: Vec<(i64, i32)> = stored
+        .participants
+        .iter()
+        .filter_map(|p| {
+            let side = if p.team_id == owner.team_id { 1 } else { -1 };
+            Some((p.participant_id?, side))
+        })
+        .collect();
+    let side_of = |id: i64| sides.iter().find(|(p, _)| *p == id).map(|(_, s)| *s);
+
+    let points = timeline
+        .frames
+        .iter()
+        .map(|frame| {
+            let (mut team_gold_diff, mut team_xp_diff) = (0, 0);
+            for p in &frame.participants {
+                if let Some(side) = side_of(p.participant_id) {
+                    team_gold_diff += side * p.total_gold;
+                    team_xp_diff += side * p.xp;
+                }
+            }
+            let find = |id: i64| frame.participants.iter().find(|p| p.participant_id == id);
+            let lane = find(owner_id).zip(opponent_id.and_then(find));
+            GoldGraphPoint {
+                minute: (frame.timestamp / 60_000) as u32,
+                team_gold_diff,
+                team_xp_diff,
+                lane_gold_diff: lane.map(|(me, them)| me.total_gold - them.total_gold),
+                lane_xp_diff: lane.map(|(me, them)| me.xp - them.xp),
+            }
+        })
+        .collect();
+
+    Some(GoldGraph {
+        game_id: stored.game_id,
+        lane_opponent_champion_id: opponent.map(|p| p.champion_id),
+        points,
+    })
+}
+
+#[tauri::command]
+pub async fn get_gold_graph(
+    game_id: i64,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+    timelines: tauri::State<'_, Mutex<MatchTimelineStore>>,
+) -> Result<GoldGraph, String> {
+    let timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let timeline = timelines_guard
+        .get(game_id)
+        .ok_or_else(|| "No timeline stored for this game".to_string())?;
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let stored = store_guard
+        .matches()
+        .find(|m| m.game_id == game_id)
+        .ok_or_else(|| "Game not found in match history".to_string())?;
+    gold_graph(stored, timeline).ok_or_else(|| "Game can't be matched to its timeline".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+    use crate::storage::timelines::parse_timeline;
+    use serde_json::json;
+
+    #[test]
+    fn test_team_and_lane_differences() {
+        let stored = parse_game_details(
+            &game_details(
+                1,
+                420,
+                &[
+                    ("me", 266, 100, "TOP", "SOLO"),
+                    ("ally", 64, 100, "JUNGLE", "NONE"),
+                    ("them", 157, 200, "TOP", "SOLO"),
+                    ("jungler", 11, 200, "JUNGLE", "NONE"),
+                ],
+            ),
+            "me",
+        )
+        .unwrap();
+        let frame = |ts: i64, gold: [i64; 4]| {
+            json!({
+                "timestamp": ts,
+                "participantFrames": {
+                    "1": { "totalGold": gold[0], "xp": 100 },
+                    "2": { "totalGold": gold[1], "xp": 100 },
+                    "3": { "totalGold": gold[2], "xp": 150 },
+                    "4": { "totalGold": gold[3], "xp": 100 }
+                }
+            })
+        };
+        let timeline = parse_timeline(
+            1,
+            &json!({ "frames": [frame(0, [500; 4]), frame(60_021, [900, 700, 800, 900])] }),
+        )
+        .unwrap();
+
+        let graph = gold_graph(&stored, &timeline).unwrap();
+        assert_eq!(graph.lane_opponent_champion_id, Some(157));
+        assert_eq!(graph.points[0].team_gold_diff, 0);
+        assert_eq!(
+            graph.points[1],
+            GoldGraphPoint {
+                minute: 1,
+                team_gold_diff: -100,
+                team_xp_diff: -50,
+                lane_gold_diff: Some(100),
+                lane_xp_diff: Some(-50),
+            }
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// The owner's record on one champion against one lane opponent's champion.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MatchupStats {
+    pub my_champion_id: i64,
+    pub their_champion_id: i64,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub average_kda: f32,
+    pub average_gold_diff_at_10: Option<f32>, // Over games with gold at ten for both players
+    pub last_played: i64,                     // Game creation of the latest game
+}
+
+#[derive(Default)]
+struct Tally {
+    games: u32,
+    wins: u32,
+    kda_sum: f32,
+    gold_diffs: Vec<i32>,
+    last_played: i64,
+}
+
+/// Personal matchup history against `their_champion_id` in lane, one entry
+/// per champion the owner played into it, most played first. With
+/// `my_champion_id` only that champion's entry is returned.
+pub fn matchups_against<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    my_champion_id: Option<i64>,
+    their_champion_id: i64,
+) -> Vec<MatchupStats> {
+    let mut tallies: BTreeMap<i64, Tally> = BTreeMap::new();
+    for stored in matches {
+        let (Some(owner), Some(opponent)) = (stored.owner(), stored.lane_opponent()) else {
+            continue;
+        };
+        if opponent.champion_id != their_champion_id
+            || my_champion_id.is_some_and(|id| id != owner.champion_id)
+        {
+            continue;
+        }
+        let tally = tallies.entry(owner.champion_id).or_default();
+        tally.games += 1;
+        tally.wins += owner.win as u32;
+        tally.kda_sum += (owner.kills + owner.assists) as f32 / owner.deaths.max(1) as f32;
+        if let (Some(mine), Some(theirs)) = (owner.gold_at_10, opponent.gold_at_10) {
+            tally.gold_diffs.push(mine - theirs);
+        }
+        tally.last_played = tally.last_played.max(stored.game_creation);
+    }
+
+    let mut stats: Vec<MatchupStats> = tallies
+        .into_iter()
+        .map(|(my_champion_id, t)| MatchupStats {
+            my_champion_id,
+            their_champion_id,
+            games: t.games,
+            wins: t.wins,
+            win_rate: t.wins as f32 / t.games as f32,
+            average_kda: t.kda_sum / t.games as f32,
+            average_gold_diff_at_10: (!t.gold_diffs.is_empty())
+                .then(|| t.gold_diffs.iter().sum::<i32>() as f32 / t.gold_diffs.len() as f32),
+            last_played: t.last_played,
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then(b.win_rate.total_cmp(&a.win_rate))
+    });
+    stats
+}
+
+#[tauri::command]
+pub async fn get_matchup_stats(
+    my_champion_id: Option<i64>,
+    their_champion_id: i64,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Vec<MatchupStats>, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(matchups_against(
+        store_guard.matches(),
+        my_champion_id,
+        their_champion_id,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_matchups_against_lane_opponent() {
+        // (my champion, my team, their top laner); team 100 wins
+        let games = [
+            (266, 100, 157),
+            (266, 200, 157),
+            (266, 100, 157),
+            (92, 100, 157),
+            (266, 100, 86), // Different opponent
+        ];
+        let matches: Vec<StoredMatch> = games
+            .iter()
+            .enumerate()
+            .map(|(i, (mine, team, theirs))| {
+                let mut stored = parse_game_details(
+                    &game_details(
+                        i as i64,
+                        420,
+                        &[
+                            ("me", *mine, *team, "TOP", "SOLO"),
+                            ("mid", 1, 300 - team, "MIDDLE", "SOLO"), // Not the lane opponent
+                            ("them", *theirs, 300 - team, "TOP", "SOLO"),
+                        ],
+                    ),
+                    "me",
+                )
+                .unwrap();
+                stored.participants[0].gold_at_10 = Some(3500);
+                stored.participants[2].gold_at_10 = Some(3200 + i as i32 * 100);
+                stored
+            })
+            .collect();
+
+        let stats = matchups_against(&matches, None, 157);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].my_champion_id, 266);
+        assert_eq!((stats[0].games, stats[0].wins), (3, 2));
+        assert_eq!(stats[0].average_gold_diff_at_10, Some(200.0));
+        assert_eq!(stats[0].last_played, 2000);
+
+        let one = matchups_against(&matches, Some(92), 157);
+        assert_eq!(one.len(), 1);
+        assert_eq!(one[0].win_rate, 1.0);
+        assert!(matchups_against(&matches, None, 1).is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::champions::cache::version_key;
+use crate::storage::usage::data_dir;
+use crate::storage::{MatchStore, StoredMatch, POSITIONS};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Games a patch needs in the match store before it's compared to the previous one.
+const MIN_PATCH_GAMES: usize = 20;
+
+/// Games a champion needs in a role on both patches to count as a mover.
+const MIN_CHAMPION_GAMES: u32 = 3;
+
+const MOVERS_PER_ROLE: usize = 3;
+
+/// The major.minor patch of a version: "14.3" for "14.3.567.1234".
+pub fn patch_of(version: &str) -> Option<String> {
+    let parts: Vec<&str> = version.split('.').take(2).collect();
+    (parts.len() == 2).then(|| parts.join("."))
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Record {
+    games: u32,
+    wins: u32,
+}
+
+impl Record {
+    fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+}
+
+/// Games and wins per (position, champion) over every participant of `matches`.
+fn role_stats(matches: &[&StoredMatch]) -> HashMap<(String, i64), Record> {
+    let mut stats: HashMap<(String, i64), Record> = HashMap::new();
+    for participant in matches.iter().flat_map(|m| &m.participants) {
+        let Some(position) = &participant.position else {
+            continue;
+        };
+        let record = stats
+            .entry((position.clone(), participant.champion_id))
+            .or_default();
+        record.games += 1;
+        record.wins += participant.win as u32;
+    }
+    stats
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaMover {
+    pub champion_id: i64,
+    pub win_rate_before: f64,
+    pub win_rate_after: f64,
+    pub games: u32, // On the new patch
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleShift {
+    pub position: String,
+    pub risers: Vec<MetaMover>,  // Biggest win rate gain first
+    pub fallers: Vec<MetaMover>, // Biggest win rate loss first
+}
+
+/// Payload of the `meta-shift` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaShiftReport {
+    pub patch: String,
+    pub previous_patch: String,
+    pub roles: Vec<RoleShift>,
+    #[serde(default)]
+    pub seen: bool,
+}
+
+fn compare_patches(
+    before: &HashMap<(String, i64), Record>,
+    after: &HashMap<(String, i64), Record>,
+) -> Vec<RoleShift> {
+    POSITIONS
+        .iter()
+        .filter_map(|position| {
+            let mut movers: Vec<MetaMover> = after
+                .iter()
+                .filter(|((p, _), record)| p == position && record.games >= MIN_CHAMPION_GAMES)
+                .filter_map(|(key, record)| {
+                    let previous = before.get(key).filter(|r| r.games >= MIN_CHAMPION_GAMES)?;
+                    Some(MetaMover {
+                        champion_id: key.1,
+                        win_rate_before: previous.win_rate(),
+                        win_rate_after: record.win_rate(),
+                        games: record.games,
+                    })
+                })
+                .collect();
+            let delta = |m: &MetaMover| m.win_rate_after - m.win_rate_before;
+            movers.sort_by(|a, b| {
+                delta(b)
+                    .total_cmp(&delta(a))
+                    .then(a.champion_id.cmp(&b.champion_id))
+            });
+
+            let risers: Vec<MetaMover> = movers
+                .iter()
+                .filter(|m| delta(m) > 0.0)
+                .take(MOVERS_PER_ROLE)
+                .cloned()
+                .collect();
+            let fallers: Vec<MetaMover> = movers
+                .iter()
+                .rev()
+                .filter(|m| delta(m) < 0.0)
+                .take(MOVERS_PER_ROLE)
+                .cloned()
+                .collect();
+
+            (!risers.is_empty() || !fallers.is_empty()).then(|| RoleShift {
+                position: position.to_string(),
+                risers,
+                fallers,
+            })
+        })
+        .collect()
+}
+
+/// Compare the newest patch in `matches` with the one before it, once the newest
+/// has enough games. Matches without a recorded version are ignored.
+pub fn build_report(matches: &[&StoredMatch]) -> Option<MetaShiftReport> {
+    let mut by_patch: BTreeMap<Vec<u32>, (String, Vec<&StoredMatch>)> = BTreeMap::new();
+    for stored in matches {
+        let Some(patch) = stored.game_version.as_deref().and_then(patch_of) else {
+            continue;
+        };
+        by_patch
+            .entry(version_key(&patch))
+            .or_insert_with(|| (patch, Vec::new()))
+            .1
+            .push(stored);
+    }
+
+    let mut patches = by_patch.into_values().rev();
+    let (patch, current) = patches.next()?;
+    let (previous_patch, previous) = patches.next()?;
+    if current.len() < MIN_PATCH_GAMES {
+        return None;
+    }
+
+    Some(MetaShiftReport {
+        patch,
+        previous_patch,
+        roles: compare_patches(&role_stats(&previous), &role_stats(&current)),
+        seen: false,
+    })
+}
+
+/// Meta shift reports by patch, so each patch is reported once.
+pub struct MetaShiftStore {
+    reports: BTreeMap<String, MetaShiftReport>,
+    store_path: PathBuf,
+}
+
+impl MetaShiftStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("meta_shifts.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            reports: BTreeMap::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read meta shifts: {}", e))?;
+        self.reports = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse meta shifts: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.reports)
+            .map_err(|e| format!("Failed to serialize meta shifts: {}", e))?;
+
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write meta shifts: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Add a report, returning false if its patch was already reported.
+    pub fn insert(&mut self, report: MetaShiftReport) -> bool {
+        if self.reports.contains_key(&report.patch) {
+            return false;
+        }
+        self.reports.insert(report.patch.clone(), report);
+        true
+    }
+
+    /// The newest report the user hasn't seen yet, marking it seen.
+    pub fn take_unseen(&mut self) -> Option<MetaShiftReport> {
+        let report = self
+            .reports
+            .values_mut()
+            .max_by_key(|r| version_key(&r.patch))
+            .filter(|r| !r.seen)?;
+        report.seen = true;
+        Some(report.clone())
+    }
+}
+
+/// Post-game hook: build a report for the newest patch once it has enough games,
+/// and emit `meta-shift` the first time it's built.
+pub fn check_meta_shift(app: &AppHandle) -> Result<Option<MetaShiftReport>, String> {
+    let report = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let matches: Vec<&StoredMatch> = store_guard.matches().collect();
+        build_report(&matches)
+    };
+    let Some(report) = report else {
+        return Ok(None);
+    };
+
+    let shifts = app.state::<Mutex<MetaShiftStore>>();
+    let mut shifts_guard = shifts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if !shifts_guard.insert(report.clone()) {
+        return Ok(None);
+    }
+    shifts_guard.save()?;
+
+    let _ = app.emit("meta-shift", &report);
+    Ok(Some(report))
+}
+
+/// The latest meta shift report if it hasn't been shown yet. Each report is
+/// returned once.
+#[tauri::command]
+pub async fn get_unseen_meta_shift(
+    shifts: tauri::State<'_, Mutex<MetaShiftStore>>,
+) -> Result<Option<MetaShiftReport>, String> {
+    let mut shifts_guard = shifts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let report = shifts_guard.take_unseen();
+    if report.is_some() {
+        shifts_guard.save()?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    /// A game on `version` where `winner` (top, team 100) beats `loser` (top, team 200).
+    fn game(game_id: i64, version: &str, winner: i64, loser: i64) -> StoredMatch {
+        let details = game_details(
+            game_id,
+            420,
+            &[
+                ("me", winner, 100, "TOP", "SOLO"),
+                ("enemy", loser, 200, "TOP", "SOLO"),
+            ],
+        );
+        let mut stored = parse_game_details(&details, "me").unwrap();
+        stored.game_version = Some(version.to_string());
+        stored
+    }
+
+    #[test]
+    fn test_build_report_compares_latest_patches() {
+        let mut matches = Vec::new();
+        // 14.3: Aatrox (266) beats Darius (122) every game
+        for id in 0..5 {
+            matches.push(game(id, "14.3.1.1", 266, 122));
+        }
+        // 14.10: the matchup flips, plus filler games that don't qualify as movers
+        for id in 10..15 {
+            matches.push(game(id, "14.10.2.1", 122, 266));
+        }
+        let refs: Vec<&StoredMatch> = matches.iter().collect();
+        assert!(build_report(&refs).is_none(), "too few games on 14.10");
+
+        for id in 20..40 {
+            matches.push(game(id, "14.10.2.1", 1, 2));
+        }
+        let refs: Vec<&StoredMatch> = matches.iter().collect();
+        let report = build_report(&refs).unwrap();
+        assert_eq!(report.patch, "14.10");
+        assert_eq!(report.previous_patch, "14.3");
+
+        let top = &report.roles[0];
+        assert_eq!(top.position, "TOP");
+        assert_eq!(top.risers[0].champion_id, 122);
+        assert_eq!(top.risers[0].win_rate_after, 1.0);
+        assert_eq!(top.fallers[0].champion_id, 266);
+
+        let mut store = MetaShiftStore::with_path(PathBuf::from("unused.json"));
+        assert!(store.insert(report.clone()));
+        assert!(!store.insert(report));
+        assert!(store.take_unseen().is_some());
+        assert!(store.take_unseen().is_none());
+    }
+}
@@ -0,0 +1,26 @@
+pub mod bans;
+pub mod champion_pool;
+pub mod draft_review;
+pub mod encounters;
+pub mod gold_graph;
+pub mod matchups;
+pub mod meta_shift;
+pub mod pool_health;
+pub mod queues;
+pub mod rank_changes;
+pub mod runes;
+pub mod schedule;
+pub mod spells;
+pub mod split;
+pub mod tilt;
+pub mod trends;
+pub mod vision;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,288 @@
+use crate::analytics::champion_pool::PoolChampion;
+use crate::champions::similar::find_similar;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// Only this many of the owner's latest games are considered by default.
+const DEFAULT_RECENT_GAMES: usize = 50;
+
+/// Champions the pool is "mostly" made of.
+const TOP_CHAMPIONS: usize = 3;
+
+/// A player whose most played champion has this share of their games is a
+/// one-trick.
+const ONE_TRICK_SHARE: f32 = 0.6;
+
+/// Similar champions looked up per top champion, and additions kept overall.
+const SIMILAR_PER_CHAMPION: usize = 3;
+const MAX_ADDITIONS: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContestedChampion {
+    pub champion_id: i64,
+    pub taken_rate: f32, // Share of the owner's other games where someone else played it
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolAddition {
+    pub champion_id: i64,
+    pub name: String,
+    pub similarity: f32,
+    pub covers: Vec<i64>, // Top champions it can stand in for
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolHealth {
+    pub games: u32,
+    pub distinct_champions: usize,
+    pub top_share: f32,     // Share of games on the top champions
+    pub concentration: f32, // Sum of squared pick shares: 1 for a single champion, near 0 for a wide pool
+    pub one_trick: bool,
+    pub top: Vec<PoolChampion>, // Most played first
+    pub top_win_rate: Option<f32>,
+    pub rest: Vec<PoolChampion>,
+    pub rest_games: u32,
+    pub rest_win_rate: Option<f32>,
+    pub contested: Vec<ContestedChampion>, // Top champions, most often taken first
+    pub additions: Vec<PoolAddition>,
+}
+
+fn win_rate(games: u32, wins: u32) -> Option<f32> {
+    (games > 0).then(|| wins as f32 / games as f32)
+}
+
+/// How concentrated the owner's recent picks are, and how the top champions do
+/// against the rest. Bans aren't stored, so "taken" only counts games where
+/// another player picked the champion.
+pub fn pool_health<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    recent: usize,
+) -> PoolHealth {
+    let mut games: Vec<&StoredMatch> = matches
+        .into_iter()
+        .filter(|m| m.owner().is_some())
+        .collect();
+    games.sort_by_key(|m| std::cmp::Reverse(m.game_creation));
+    games.truncate(recent);
+
+    let mut records: HashMap<i64, (u32, u32)> = HashMap::new();
+    for owner in games.iter().filter_map(|m| m.owner()) {
+        let record = records.entry(owner.champion_id).or_default();
+        record.0 += 1;
+        record.1 += owner.win as u32;
+    }
+    let total = games.len() as u32;
+
+    let mut champions: Vec<PoolChampion> = records
+        .iter()
+        .map(|(champion_id, (games, wins))| PoolChampion {
+            champion_id: *champion_id,
+            games: *games,
+            wins: *wins,
+            win_rate: *wins as f32 / *games as f32,
+        })
+        .collect();
+    champions.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then(b.win_rate.total_cmp(&a.win_rate))
+            .then(a.champion_id.cmp(&b.champion_id))
+    });
+
+    let share = |n: u32| {
+        if total > 0 {
+            n as f32 / total as f32
+        } else {
+            0.0
+        }
+    };
+    let rest = champions.split_off(champions.len().min(TOP_CHAMPIONS));
+    let top = champions;
+    let sum = |list: &[PoolChampion]| {
+        list.iter()
+            .fold((0, 0), |(g, w), c| (g + c.games, w + c.wins))
+    };
+    let (top_games, top_wins) = sum(&top);
+    let (rest_games, rest_wins) = sum(&rest);
+
+    let mut contested: Vec<ContestedChampion> = top
+        .iter()
+        .map(|c| {
+            let others: Vec<&&StoredMatch> = games
+                .iter()
+                .filter(|m| m.owner().is_some_and(|o| o.champion_id != c.champion_id))
+                .collect();
+            let taken = others
+                .iter()
+                .filter(|m| {
+                    m.participants
+                        .iter()
+                        .any(|p| p.champion_id == c.champion_id)
+                })
+                .count();
+            ContestedChampion {
+                champion_id: c.champion_id,
+                taken_rate: if others.is_empty() {
+                    0.0
+                } else {
+                    taken as f32 / others.len() as f32
+                },
+            }
+        })
+        .collect();
+    contested.sort_by(|a, b| b.taken_rate.total_cmp(&a.taken_rate));
+
+    PoolHealth {
+        games: total,
+        distinct_champions: top.len() + rest.len(),
+        top_share: share(top_games),
+        concentration: top
+            .iter()
+            .chain(&rest)
+            .map(|c| share(c.games).powi(2))
+            .sum(),
+        one_trick: top
+            .first()
+            .is_some_and(|c| share(c.games) >= ONE_TRICK_SHARE),
+        top_win_rate: win_rate(top_games, top_wins),
+        top,
+        rest_win_rate: win_rate(rest_games, rest_wins),
+        rest,
+        rest_games,
+        contested,
+        additions: Vec::new(),
+    }
+}
+
+/// Champions like the top ones that the owner hasn't played recently, to fall
+/// back on when a main is banned or taken. Most contested mains are covered first.
+fn suggest_additions(
+    app: &AppHandle,
+    health: &PoolHealth,
+    played: &HashSet<i64>,
+) -> Vec<PoolAddition> {
+    let mut additions: Vec<PoolAddition> = Vec::new();
+    for contested in &health.contested {
+        let similar = match find_similar(app, contested.champion_id, played, SIMILAR_PER_CHAMPION) {
+            Ok(similar) => similar,
+            Err(e) => {
+                eprintln!(
+                    "Finding additions for {} failed: {}",
+                    contested.champion_id, e
+                );
+                continue;
+            }
+        };
+        for s in similar {
+            match additions
+                .iter_mut()
+                .find(|a| a.champion_id == s.champion_id)
+            {
+                Some(existing) => {
+                    existing.similarity = existing.similarity.max(s.similarity);
+                    existing.covers.push(contested.champion_id);
+                }
+                None => additions.push(PoolAddition {
+                    champion_id: s.champion_id,
+                    name: s.name,
+                    similarity: s.similarity,
+                    covers: vec![contested.champion_id],
+                }),
+            }
+        }
+    }
+    // Covering more mains beats a slightly closer match for one
+    additions.sort_by(|a, b| {
+        b.covers
+            .len()
+            .cmp(&a.covers.len())
+            .then(b.similarity.total_cmp(&a.similarity))
+    });
+    additions.truncate(MAX_ADDITIONS);
+    additions
+}
+
+#[tauri::command]
+pub async fn get_pool_health(
+    app: AppHandle,
+    recent: Option<usize>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<PoolHealth, String> {
+    let mut health = {
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        pool_health(
+            store_guard.matches(),
+            recent.unwrap_or(DEFAULT_RECENT_GAMES),
+        )
+    };
+    let played: HashSet<i64> = health
+        .top
+        .iter()
+        .chain(&health.rest)
+        .map(|c| c.champion_id)
+        .collect();
+    health.additions = suggest_additions(&app, &health, &played);
+    Ok(health)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_pool_health_of_one_trick() {
+        // (champion, win, enemy champion)
+        let picks = [
+            (266, true, 64),
+            (266, true, 64),
+            (266, true, 11),
+            (266, false, 11),
+            (266, true, 11),
+            (266, false, 11),
+            (266, true, 11),
+            (64, false, 266), // Aatrox taken by the enemy
+            (11, true, 64),
+            (99, false, 64),
+        ];
+        let matches: Vec<StoredMatch> = picks
+            .iter()
+            .enumerate()
+            .map(|(i, (champion, win, enemy))| {
+                let team = if *win { 100 } else { 200 };
+                let enemy_team = 300 - team;
+                parse_game_details(
+                    &game_details(
+                        i as i64,
+                        420,
+                        &[
+                            ("me", *champion, team, "TOP", "SOLO"),
+                            ("them", *enemy, enemy_team, "TOP", "SOLO"),
+                        ],
+                    ),
+                    "me",
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let health = pool_health(&matches, 50);
+        assert_eq!(health.games, 10);
+        assert_eq!(health.distinct_champions, 4);
+        assert!(health.one_trick);
+        let top: Vec<i64> = health.top.iter().map(|c| c.champion_id).collect();
+        assert_eq!(top, vec![266, 11, 64]);
+        assert_eq!(health.top_share, 0.9);
+        assert_eq!(health.rest_games, 1);
+        assert_eq!(health.rest_win_rate, Some(0.0));
+        assert_eq!(health.contested[0].champion_id, 11, "taken in 5 of 9");
+        assert!((health.contested[0].taken_rate - 5.0 / 9.0).abs() < 1e-6);
+        assert!((health.concentration - 0.52).abs() < 1e-6);
+
+        assert!(!pool_health(&matches[7..], 50).one_trick);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::analytics::trends::MIN_GAME_SECONDS;
+use crate::lcu::timeline::DraftTimelineStore;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+/// Queues where players pick two roles and get assigned one: normal draft,
+/// ranked solo/duo and ranked flex.
+const ROLE_QUEUES: [i32; 3] = [400, 420, 440];
+
+/// How the owner's games in one queue tend to go.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueStats {
+    pub queue_id: i32,
+    pub games: u32,
+    pub remakes: u32,                  // Games shorter than five minutes
+    pub average_duration_seconds: f32, // Remakes left out
+}
+
+/// How often the owner plays outside their usual roles in a role queue.
+/// The client doesn't report role preferences after the fact, so the two
+/// most played positions stand in for them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AutofillStats {
+    pub queue_id: i32,
+    pub preferred_positions: Vec<String>, // Most played first
+    pub games: u32,                       // Games with a reported position
+    pub autofilled: u32,
+    pub autofill_rate: f32,
+}
+
+/// Champion selects recorded by the app against the games they became.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DraftConversion {
+    pub drafts: u32,
+    pub games: u32,
+    pub dodges: u32,
+    pub conversion_rate: Option<f32>, // None before any draft was recorded
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueInsights {
+    pub queues: Vec<QueueStats>, // Most played first
+    pub autofill: Vec<AutofillStats>,
+    pub drafts: DraftConversion,
+}
+
+pub fn queue_stats<'a>(matches: impl IntoIterator<Item = &'a StoredMatch>) -> Vec<QueueStats> {
+    // queue id -> (games, remakes, seconds over full games)
+    let mut tallies: BTreeMap<i32, (u32, u32, i64)> = BTreeMap::new();
+    for stored in matches {
+        let tally = tallies.entry(stored.queue_id).or_default();
+        tally.0 += 1;
+        if stored.game_duration < MIN_GAME_SECONDS {
+            tally.1 += 1;
+        } else {
+            tally.2 += stored.game_duration as i64;
+        }
+    }
+
+    let mut stats: Vec<QueueStats> = tallies
+        .into_iter()
+        .map(|(queue_id, (games, remakes, seconds))| QueueStats {
+            queue_id,
+            games,
+            remakes,
+            average_duration_seconds: if games > remakes {
+                seconds as f32 / (games - remakes) as f32
+            } else {
+                0.0
+            },
+        })
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.games));
+    stats
+}
+
+pub fn autofill_stats<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+) -> Vec<AutofillStats> {
+    let mut positions: BTreeMap<i32, BTreeMap<&str, u32>> = BTreeMap::new();
+    for stored in matches {
+        if !ROLE_QUEUES.contains(&stored.queue_id) {
+            continue;
+        }
+        if let Some(position) = stored.owner().and_then(|o| o.position.as_deref()) {
+            *positions
+                .entry(stored.queue_id)
+                .or_default()
+                .entry(position)
+                .or_default() += 1;
+        }
+    }
+
+    positions
+        .into_iter()
+        .map(|(queue_id, counts)| {
+            let mut by_games: Vec<(&str, u32)> = counts.into_iter().collect();
+            by_games.sort_by_key(|(_, games)| std::cmp::Reverse(*games));
+            let games: u32 = by_games.iter().map(|(_, g)| g).sum();
+            let autofilled: u32 = by_games.iter().skip(2).map(|(_, g)| g).sum();
+            AutofillStats {
+                queue_id,
+                preferred_positions: by_games
+                    .iter()
+                    .take(2)
+                    .map(|(p, _)| p.to_string())
+                    .collect(),
+                games,
+                autofilled,
+                autofill_rate: autofilled as f32 / games as f32,
+            }
+        })
+        .collect()
+}
+
+/// Recorded drafts that never became a stored game were dodged. Drafts newer
+/// than the latest stored game may not be synced yet, so they're left out.
+pub fn draft_conversion<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    draft_game_ids: impl IntoIterator<Item = i64>,
+) -> DraftConversion {
+    let played: HashSet<i64> = matches.into_iter().map(|m| m.game_id).collect();
+    let latest = played.iter().copied().max().unwrap_or(i64::MIN);
+    let (mut drafts, mut games) = (0, 0);
+    for game_id in draft_game_ids.into_iter().filter(|id| *id <= latest) {
+        drafts += 1;
+        games += played.contains(&game_id) as u32;
+    }
+    DraftConversion {
+        drafts,
+        games,
+        dodges: drafts - games,
+        conversion_rate: (drafts > 0).then(|| games as f32 / drafts as f32),
+    }
+}
+
+#[tauri::command]
+pub async fn get_queue_insights(
+    store: tauri::State<'_, Mutex<MatchStore>>,
+    drafts: tauri::State<'_, Mutex<DraftTimelineStore>>,
+) -> Result<QueueInsights, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let drafts_guard = drafts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(QueueInsights {
+        queues: queue_stats(store_guard.matches()),
+        autofill: autofill_stats(store_guard.matches()),
+        drafts: draft_conversion(store_guard.matches(), drafts_guard.game_ids()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_queue_insights() {
+        // (game id, queue, lane, role)
+        let games = [
+            (10, 420, "MIDDLE", "SOLO"),
+            (20, 420, "MIDDLE", "SOLO"),
+            (30, 420, "MIDDLE", "SOLO"),
+            (40, 420, "TOP", "SOLO"),
+            (50, 420, "TOP", "SOLO"),
+            (60, 420, "BOTTOM", "DUO_SUPPORT"),
+            (70, 450, "MIDDLE", "SOLO"),
+        ];
+        let mut matches: Vec<StoredMatch> = games
+            .iter()
+            .map(|(id, queue, lane, role)| {
+                parse_game_details(
+                    &game_details(*id, *queue, &[("me", 1, 100, lane, role)]),
+                    "me",
+                )
+                .unwrap()
+            })
+            .collect();
+        matches[5].game_duration = 200; // Remake
+
+        let queues = queue_stats(&matches);
+        assert_eq!(
+            queues[0],
+            QueueStats {
+                queue_id: 420,
+                games: 6,
+                remakes: 1,
+                average_duration_seconds: 1800.0,
+            }
+        );
+        assert_eq!(queues[1].queue_id, 450);
+
+        let autofill = autofill_stats(&matches);
+        assert_eq!(autofill.len(), 1);
+        assert_eq!(autofill[0].preferred_positions, ["MIDDLE", "TOP"]);
+        assert_eq!((autofill[0].games, autofill[0].autofilled), (6, 1));
+
+        // 25 was dodged, 80 is newer than anything synced
+        let drafts = draft_conversion(&matches, [20, 25, 30, 80]);
+        assert_eq!((drafts.drafts, drafts.games, drafts.dodges), (3, 2, 1));
+        assert!(draft_conversion(&matches, []).conversion_rate.is_none());
+    }
+}
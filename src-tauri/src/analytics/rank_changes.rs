@@ -0,0 +1,178 @@
+use super::now_millis;
+use super::split::rank_value;
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::{LcuClient, RankedStats};
+use crate::settings::SettingsStore;
+use crate::storage::snapshots::{RankChangeKind, RankSnapshot, RankSnapshotStore};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Payload of the `rank-changed` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct RankChange {
+    pub kind: RankChangeKind,
+    pub queue_type: String,
+    pub before: RankSnapshot,
+    pub after: RankSnapshot,
+}
+
+/// Classify the step between two consecutive snapshots of the same queue.
+/// LP movement inside a division is not a rank change.
+pub fn detect_rank_change(before: &RankSnapshot, after: &RankSnapshot) -> Option<RankChangeKind> {
+    // Compare at division granularity, ignoring LP
+    let before_value = rank_value(&before.tier, &before.rank, 0);
+    let after_value = rank_value(&after.tier, &after.rank, 0);
+
+    match (before_value, after_value) {
+        (Some(b), Some(a)) if a > b => Some(RankChangeKind::Promoted),
+        (Some(b), Some(a)) if a < b => Some(RankChangeKind::Demoted),
+        _ if after.in_promos && !before.in_promos => Some(RankChangeKind::EnteredPromos),
+        _ => None,
+    }
+}
+
+/// Record new standings and return the rank changes they represent, flagging
+/// them in the snapshot history.
+pub fn record_standings(
+    store: &mut RankSnapshotStore,
+    stats: &[RankedStats],
+    timestamp: i64,
+) -> Vec<RankChange> {
+    let mut changes = Vec::new();
+
+    for queue in stats {
+        let previous = store.latest(&queue.queue_type).cloned();
+        let Some(mut after) = store.record(std::slice::from_ref(queue), timestamp).pop() else {
+            continue;
+        };
+        let Some(before) = previous else {
+            continue;
+        };
+
+        if let Some(kind) = detect_rank_change(&before, &after) {
+            store.mark_change(&queue.queue_type, kind);
+            after.change = Some(kind);
+            changes.push(RankChange {
+                kind,
+                queue_type: queue.queue_type.clone(),
+                before,
+                after,
+            });
+        }
+    }
+
+    changes
+}
+
+fn title_case(tier: &str) -> String {
+    let lower = tier.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn describe(change: &RankChange) -> String {
+    let after = format!("{} {}", title_case(&change.after.tier), change.after.rank);
+    match change.kind {
+        RankChangeKind::Promoted => format!("Promoted to {}", after.trim_end()),
+        RankChangeKind::Demoted => format!("Demoted to {}", after.trim_end()),
+        RankChangeKind::EnteredPromos => {
+            format!("Promotion series started in {}", after.trim_end())
+        }
+    }
+}
+
+/// Snapshot the given standings, then emit `rank-changed` for every change (and
+/// a desktop notification if the user opted in).
+pub fn record_and_announce(
+    app: &AppHandle,
+    stats: &[RankedStats],
+) -> Result<Vec<RankChange>, String> {
+    let changes = {
+        let snapshots = app.state::<Mutex<RankSnapshotStore>>();
+        let mut store_guard = snapshots.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let changes = record_standings(&mut store_guard, stats, now_millis());
+        store_guard.save()?;
+        changes
+    };
+
+    let notify = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().rank_change_notifications))
+        .unwrap_or(false);
+
+    for change in &changes {
+        let _ = app.emit("rank-changed", change);
+        if notify {
+            let _ = app
+                .notification()
+                .builder()
+                .title("Trackimo")
+                .body(describe(change))
+                .show();
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Post-game hook: fetch fresh standings and announce any rank change.
+pub async fn check_rank_changes(app: &AppHandle) -> Result<Vec<RankChange>, String> {
+    let stats = {
+        let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+        let mut client_guard = client.lock().await;
+        client_guard.get_ranked_stats(false).await?
+    };
+    record_and_announce(app, &stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::client::Miniseries;
+    use std::path::PathBuf;
+
+    fn stats(tier: &str, rank: &str, lp: i32, promos: bool) -> RankedStats {
+        RankedStats {
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: tier.to_string(),
+            rank: rank.to_string(),
+            league_points: lp,
+            miniseries: promos.then(|| Miniseries {
+                progress: "NNN".to_string(),
+                wins: 0,
+                losses: 0,
+                target: 2,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_record_standings_detects_changes() {
+        let mut store = RankSnapshotStore::with_path(PathBuf::from("unused.json"));
+        // First snapshot has nothing to compare against
+        assert!(record_standings(&mut store, &[stats("GOLD", "I", 80, false)], 1).is_empty());
+        // LP gain alone is not a change
+        assert!(record_standings(&mut store, &[stats("GOLD", "I", 99, false)], 2).is_empty());
+
+        let changes = record_standings(&mut store, &[stats("GOLD", "I", 100, true)], 3);
+        assert_eq!(changes[0].kind, RankChangeKind::EnteredPromos);
+
+        let changes = record_standings(&mut store, &[stats("PLATINUM", "IV", 0, false)], 4);
+        assert_eq!(changes[0].kind, RankChangeKind::Promoted);
+        assert_eq!(changes[0].before.tier, "GOLD");
+        assert_eq!(describe(&changes[0]), "Promoted to Platinum IV");
+        assert_eq!(
+            store.latest("RANKED_SOLO_5x5").unwrap().change,
+            Some(RankChangeKind::Promoted)
+        );
+
+        let changes = record_standings(&mut store, &[stats("GOLD", "I", 75, false)], 5);
+        assert_eq!(changes[0].kind, RankChangeKind::Demoted);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RuneStyleStats {
+    pub style_id: i64,
+    pub games: u32,
+    pub wins: u32,
+}
+
+/// The owner's record with one keystone on one champion. Names and icons
+/// come from the patch's static data on the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeystoneStats {
+    pub keystone_id: i64,
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub average_kda: f32,
+    pub secondary_styles: Vec<RuneStyleStats>, // Most played first
+}
+
+#[derive(Default)]
+struct Tally {
+    games: u32,
+    wins: u32,
+    kda_sum: f32,
+    secondary: BTreeMap<i64, (u32, u32)>,
+}
+
+/// Keystones the owner ran on `champion_id`, most played first. Games stored
+/// before runes were kept are left out.
+pub fn keystone_stats<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    champion_id: i64,
+) -> Vec<KeystoneStats> {
+    let mut tallies: BTreeMap<i64, Tally> = BTreeMap::new();
+    for owner in matches.into_iter().filter_map(|m| m.owner()) {
+        if owner.champion_id != champion_id {
+            continue;
+        }
+        let Some(keystone) = owner.keystone() else {
+            continue;
+        };
+        let tally = tallies.entry(keystone).or_default();
+        tally.games += 1;
+        tally.wins += owner.win as u32;
+        tally.kda_sum += (owner.kills + owner.assists) as f32 / owner.deaths.max(1) as f32;
+        if let Some(style) = owner.secondary_style() {
+            let record = tally.secondary.entry(style).or_default();
+            record.0 += 1;
+            record.1 += owner.win as u32;
+        }
+    }
+
+    let mut stats: Vec<KeystoneStats> = tallies
+        .into_iter()
+        .map(|(keystone_id, t)| {
+            let mut secondary_styles: Vec<RuneStyleStats> = t
+                .secondary
+                .into_iter()
+                .map(|(style_id, (games, wins))| RuneStyleStats {
+                    style_id,
+                    games,
+                    wins,
+                })
+                .collect();
+            secondary_styles.sort_by_key(|s| std::cmp::Reverse(s.games));
+            KeystoneStats {
+                keystone_id,
+                games: t.games,
+                wins: t.wins,
+                win_rate: t.wins as f32 / t.games as f32,
+                average_kda: t.kda_sum / t.games as f32,
+                secondary_styles,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then(b.win_rate.total_cmp(&a.win_rate))
+    });
+    stats
+}
+
+#[tauri::command]
+pub async fn get_rune_stats(
+    champion_id: i64,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Vec<KeystoneStats>, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(keystone_stats(store_guard.matches(), champion_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_keystone_stats() {
+        // (keystone, secondary style, my team); team 100 wins
+        let games = [
+            (8010, 8400, 100),
+            (8010, 8400, 200),
+            (8010, 8300, 100),
+            (8437, 8000, 100),
+        ];
+        let mut matches: Vec<StoredMatch> = games
+            .iter()
+            .enumerate()
+            .map(|(i, (keystone, secondary, team))| {
+                let mut stored = parse_game_details(
+                    &game_details(i as i64, 420, &[("me", 266, *team, "TOP", "SOLO")]),
+                    "me",
+                )
+                .unwrap();
+                let primary = keystone / 100 * 100;
+                stored.participants[0].runes = vec![primary, *secondary, *keystone, 9111, 9104];
+                stored
+            })
+            .collect();
+        // Another champion, and a game stored before runes were kept
+        matches.push(
+            parse_game_details(
+                &game_details(9, 420, &[("me", 92, 100, "TOP", "SOLO")]),
+                "me",
+            )
+            .unwrap(),
+        );
+        matches[3].participants[0].runes.clear();
+
+        let stats = keystone_stats(&matches, 266);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].keystone_id, 8010);
+        assert_eq!((stats[0].games, stats[0].wins), (3, 2));
+        assert_eq!(
+            stats[0].secondary_styles[0],
+            RuneStyleStats {
+                style_id: 8400,
+                games: 2,
+                wins: 1
+            }
+        );
+        assert_eq!(keystone_stats(&matches, 92)[0].keystone_id, 8005);
+    }
+}
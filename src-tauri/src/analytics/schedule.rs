@@ -0,0 +1,224 @@
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// Buckets with fewer games than this get no winrate and no insights.
+const DEFAULT_MIN_GAMES: usize = 10;
+
+/// Winrate gap, in percentage points, worth pointing out.
+const NOTABLE_GAP: f64 = 5.0;
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+const HOUR_MILLIS: i64 = 60 * 60 * 1000;
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Parts of the day as (label, first hour, last hour exclusive).
+const PERIODS: [(&str, u32, u32); 4] = [
+    ("late at night (00:00-06:00)", 0, 6),
+    ("in the morning (06:00-12:00)", 6, 12),
+    ("in the afternoon (12:00-18:00)", 12, 18),
+    ("in the evening (18:00-24:00)", 18, 24),
+];
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TimeBucket {
+    pub label: String,
+    pub games: usize,
+    pub wins: usize,
+    pub winrate: Option<f64>, // Percent; None below the minimum sample
+    pub average_kda: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInsights {
+    pub by_hour: Vec<TimeBucket>,    // 24 entries, "00:00" first
+    pub by_weekday: Vec<TimeBucket>, // Monday first
+    pub by_period: Vec<TimeBucket>,
+    pub insights: Vec<String>, // e.g. "You win 58% in the afternoon (12:00-18:00) and 43% late at night (00:00-06:00)"
+    pub min_games: usize,
+}
+
+/// Local hour and weekday (Monday = 0) a game started at. The offset is the
+/// current one, so games from before a DST change are off by an hour.
+fn local_time(game_creation: i64, utc_offset_minutes: i32) -> (u32, usize) {
+    let local = game_creation + utc_offset_minutes as i64 * 60 * 1000;
+    let days = local.div_euclid(DAY_MILLIS);
+    let hour = local.rem_euclid(DAY_MILLIS) / HOUR_MILLIS;
+    // 1970-01-01 was a Thursday
+    (hour as u32, (days + 3).rem_euclid(7) as usize)
+}
+
+#[derive(Default)]
+struct Tally {
+    games: usize,
+    wins: usize,
+    kda_sum: f64,
+}
+
+impl Tally {
+    fn add(&mut self, stored: &StoredMatch) {
+        let Some(owner) = stored.owner() else {
+            return;
+        };
+        self.games += 1;
+        self.wins += owner.win as usize;
+        self.kda_sum += (owner.kills + owner.assists) as f64 / owner.deaths.max(1) as f64;
+    }
+
+    fn bucket(&self, label: &str, min_games: usize) -> TimeBucket {
+        let enough = self.games >= min_games && self.games > 0;
+        TimeBucket {
+            label: label.to_string(),
+            games: self.games,
+            wins: self.wins,
+            winrate: enough.then(|| self.wins as f64 * 100.0 / self.games as f64),
+            average_kda: enough.then(|| self.kda_sum / self.games as f64),
+        }
+    }
+}
+
+/// "You win 58% <best> and 43% <worst>" when the gap is worth mentioning.
+fn compare(buckets: &[TimeBucket]) -> Option<String> {
+    let rated: Vec<(&TimeBucket, f64)> = buckets
+        .iter()
+        .filter_map(|b| b.winrate.map(|w| (b, w)))
+        .collect();
+    let best = rated.iter().max_by(|a, b| a.1.total_cmp(&b.1))?;
+    let worst = rated.iter().min_by(|a, b| a.1.total_cmp(&b.1))?;
+    (best.1 - worst.1 >= NOTABLE_GAP).then(|| {
+        format!(
+            "You win {:.0}% {} and {:.0}% {}",
+            best.1, best.0.label, worst.1, worst.0.label
+        )
+    })
+}
+
+/// Winrate and KDA by local hour, weekday and part of the day.
+pub fn schedule_insights<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    utc_offset_minutes: i32,
+    min_games: usize,
+) -> ScheduleInsights {
+    let mut hours: Vec<Tally> = (0..24).map(|_| Tally::default()).collect();
+    let mut weekdays: Vec<Tally> = (0..7).map(|_| Tally::default()).collect();
+    let mut periods: Vec<Tally> = PERIODS.iter().map(|_| Tally::default()).collect();
+
+    for stored in matches {
+        let (hour, weekday) = local_time(stored.game_creation, utc_offset_minutes);
+        hours[hour as usize].add(stored);
+        weekdays[weekday].add(stored);
+        if let Some(i) = PERIODS
+            .iter()
+            .position(|(_, from, to)| (*from..*to).contains(&hour))
+        {
+            periods[i].add(stored);
+        }
+    }
+
+    let by_hour: Vec<TimeBucket> = hours
+        .iter()
+        .enumerate()
+        .map(|(h, t)| t.bucket(&format!("{:02}:00", h), min_games))
+        .collect();
+    let by_weekday: Vec<TimeBucket> = weekdays
+        .iter()
+        .zip(WEEKDAYS)
+        .map(|(t, day)| t.bucket(&format!("on {}s", day), min_games))
+        .collect();
+    let by_period: Vec<TimeBucket> = periods
+        .iter()
+        .zip(PERIODS)
+        .map(|(t, (label, _, _))| t.bucket(label, min_games))
+        .collect();
+
+    let insights = [compare(&by_period), compare(&by_weekday)]
+        .into_iter()
+        .flatten()
+        .collect();
+    ScheduleInsights {
+        by_hour,
+        by_weekday,
+        by_period,
+        insights,
+        min_games,
+    }
+}
+
+/// `utc_offset_minutes` is the local offset, east positive (the negation of
+/// JavaScript's `getTimezoneOffset()`).
+#[tauri::command]
+pub async fn get_schedule_insights(
+    utc_offset_minutes: i32,
+    min_games: Option<usize>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<ScheduleInsights, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(schedule_insights(
+        store_guard.matches(),
+        utc_offset_minutes,
+        min_games.unwrap_or(DEFAULT_MIN_GAMES),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_local_time() {
+        // 2024-01-01 was a Monday
+        let monday_2330_utc = 1_704_151_800_000;
+        assert_eq!(local_time(monday_2330_utc, 0), (23, 0));
+        assert_eq!(local_time(monday_2330_utc, 60), (0, 1), "next day in UTC+1");
+        assert_eq!(local_time(monday_2330_utc, -300), (18, 0));
+    }
+
+    #[test]
+    fn test_winrate_by_part_of_day() {
+        let monday = 1_704_067_200_000; // 2024-01-01 00:00 UTC
+        let game = |id: i64, hour: i64, win: bool| {
+            let team = if win { 100 } else { 200 };
+            let mut stored = parse_game_details(
+                &game_details(id, 420, &[("me", 64, team, "TOP", "SOLO")]),
+                "me",
+            )
+            .unwrap();
+            stored.game_creation = monday + hour * HOUR_MILLIS;
+            stored
+        };
+        // Afternoons 3/4, late nights 1/4, one lone morning game
+        let matches = vec![
+            game(1, 14, true),
+            game(2, 15, true),
+            game(3, 16, true),
+            game(4, 17, false),
+            game(5, 1, true),
+            game(6, 2, false),
+            game(7, 3, false),
+            game(8, 4, false),
+            game(9, 9, true),
+        ];
+
+        let report = schedule_insights(&matches, 0, 4);
+        assert_eq!(report.by_period[1].games, 1);
+        assert_eq!(report.by_period[1].winrate, None, "below the minimum");
+        assert_eq!(report.by_period[2].winrate, Some(75.0));
+        assert_eq!(
+            report.insights,
+            vec!["You win 75% in the afternoon (12:00-18:00) and 25% late at night (00:00-06:00)"]
+        );
+        assert_eq!(report.by_weekday[0].games, 9);
+        assert!(schedule_insights(&matches, 0, 10).insights.is_empty());
+    }
+}
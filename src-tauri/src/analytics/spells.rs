@@ -0,0 +1,245 @@
+use crate::champions::cache::ChampionCache;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+const FLASH: i64 = 4;
+
+/// Games with a setup before its win rate is judged.
+const MIN_GAMES: u32 = 3;
+
+/// Setups taken in less than this share of a champion's games are unusual.
+const UNUSUAL_SHARE: f32 = 0.2;
+
+/// A setup losing this much more often than the champion overall is flagged.
+const LOSING_GAP: f32 = 0.15;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpellSetupStats {
+    pub spells: [i64; 2], // Lowest id first, so D/F order doesn't split a setup
+    pub games: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub share: f32, // Of the champion's games in the role
+    pub no_flash: bool,
+    pub unusual: bool,
+    pub losing: bool, // Enough games, and well below the champion's overall win rate
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpellUsage {
+    pub champion_id: i64,
+    pub position: Option<String>, // None covers every role
+    pub games: u32,
+    pub win_rate: f32,
+    pub setups: Vec<SpellSetupStats>, // Most played first
+}
+
+/// Payload of the `spell-insight` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpellInsight {
+    pub game_id: i64,
+    pub champion_id: i64,
+    pub setup: SpellSetupStats,
+    pub message: String, // e.g. "Ghost and Ignite on Darius: 1 win in 5 games, 56% overall"
+}
+
+fn setup_of(spells: &[i64]) -> Option<[i64; 2]> {
+    let [a, b] = spells else {
+        return None;
+    };
+    Some([*a.min(b), *a.max(b)])
+}
+
+/// The owner's summoner spell setups on `champion_id`, in `position` when
+/// given, each flagged when unusual or historically losing.
+pub fn spell_usage<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    champion_id: i64,
+    position: Option<&str>,
+) -> Option<SpellUsage> {
+    let mut records: BTreeMap<[i64; 2], (u32, u32)> = BTreeMap::new();
+    for owner in matches.into_iter().filter_map(|m| m.owner()) {
+        if owner.champion_id != champion_id
+            || position.is_some_and(|p| owner.position.as_deref() != Some(p))
+        {
+            continue;
+        }
+        if let Some(setup) = setup_of(&owner.spells) {
+            let record = records.entry(setup).or_default();
+            record.0 += 1;
+            record.1 += owner.win as u32;
+        }
+    }
+
+    let games: u32 = records.values().map(|(g, _)| g).sum();
+    let wins: u32 = records.values().map(|(_, w)| w).sum();
+    if games == 0 {
+        return None;
+    }
+    let win_rate = wins as f32 / games as f32;
+
+    let mut setups: Vec<SpellSetupStats> = records
+        .into_iter()
+        .map(|(spells, (setup_games, setup_wins))| {
+            let setup_rate = setup_wins as f32 / setup_games as f32;
+            let share = setup_games as f32 / games as f32;
+            SpellSetupStats {
+                spells,
+                games: setup_games,
+                wins: setup_wins,
+                win_rate: setup_rate,
+                share,
+                no_flash: !spells.contains(&FLASH),
+                unusual: share < UNUSUAL_SHARE,
+                losing: setup_games >= MIN_GAMES && setup_rate + LOSING_GAP < win_rate,
+            }
+        })
+        .collect();
+    setups.sort_by_key(|s| std::cmp::Reverse(s.games));
+
+    Some(SpellUsage {
+        champion_id,
+        position: position.map(|p| p.to_string()),
+        games,
+        win_rate,
+        setups,
+    })
+}
+
+/// The setup the owner took in `game_id`, when it's one worth pointing out:
+/// a losing setup, or an unusual one without Flash.
+pub fn flagged_setup(matches: &[&StoredMatch], game_id: i64) -> Option<SpellSetupStats> {
+    let game = matches.iter().find(|m| m.game_id == game_id)?;
+    let owner = game.owner()?;
+    let setup = setup_of(&owner.spells)?;
+    let usage = spell_usage(
+        matches.iter().copied(),
+        owner.champion_id,
+        owner.position.as_deref(),
+    )?;
+    usage
+        .setups
+        .into_iter()
+        .find(|s| s.spells == setup)
+        .filter(|s| s.losing || (s.unusual && s.no_flash))
+}
+
+/// Post-game: emit `spell-insight` when the game's spells are a setup that
+/// tends to lose, or an unusual one without Flash.
+pub fn check_spells(app: &AppHandle, game_id: i64) -> Result<Option<SpellInsight>, String> {
+    let (setup, champion_id, overall) = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let matches: Vec<&StoredMatch> = store_guard.matches().collect();
+        let Some(setup) = flagged_setup(&matches, game_id) else {
+            return Ok(None);
+        };
+        let owner = matches
+            .iter()
+            .find(|m| m.game_id == game_id)
+            .and_then(|m| m.owner())
+            .ok_or_else(|| "Game not found in match history".to_string())?;
+        let overall = spell_usage(
+            matches.iter().copied(),
+            owner.champion_id,
+            owner.position.as_deref(),
+        )
+        .map(|u| u.win_rate)
+        .unwrap_or(0.0);
+        (setup, owner.champion_id, overall)
+    };
+
+    let cache = app.try_state::<Mutex<ChampionCache>>();
+    let cache_guard = cache.as_ref().and_then(|c| c.lock().ok());
+    let spell_name = |id: i64| {
+        cache_guard
+            .as_ref()
+            .and_then(|c| c.get_summoner_spell_name(id))
+            .unwrap_or_else(|| format!("spell {}", id))
+    };
+    let champion = cache_guard
+        .as_ref()
+        .and_then(|c| c.get_champion_by_id(champion_id))
+        .map(|c| c.name)
+        .unwrap_or_else(|| format!("champion {}", champion_id));
+    let message = format!(
+        "{} and {} on {}: {} {} in {} games, {:.0}% overall",
+        spell_name(setup.spells[0]),
+        spell_name(setup.spells[1]),
+        champion,
+        setup.wins,
+        if setup.wins == 1 { "win" } else { "wins" },
+        setup.games,
+        overall * 100.0
+    );
+
+    let insight = SpellInsight {
+        game_id,
+        champion_id,
+        setup,
+        message,
+    };
+    let _ = app.emit("spell-insight", &insight);
+    Ok(Some(insight))
+}
+
+#[tauri::command]
+pub async fn get_spell_stats(
+    champion_id: i64,
+    position: Option<String>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Option<SpellUsage>, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(spell_usage(
+        store_guard.matches(),
+        champion_id,
+        position.as_deref(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_flags_losing_and_flashless_setups() {
+        // (spells, my team); team 100 wins. Flash + Ignite wins 7 of 9,
+        // Teleport + Flash loses all 3, Ghost + Ignite is a one-off.
+        let mut games = vec![(vec![4, 14], 100); 7];
+        games.extend([(vec![14, 4], 200), (vec![4, 14], 200)]);
+        games.extend(vec![(vec![12, 4], 200); 3]);
+        games.push((vec![6, 14], 100));
+
+        let matches: Vec<StoredMatch> = games
+            .iter()
+            .enumerate()
+            .map(|(i, (spells, team))| {
+                let mut stored = parse_game_details(
+                    &game_details(i as i64, 420, &[("me", 122, *team, "TOP", "SOLO")]),
+                    "me",
+                )
+                .unwrap();
+                stored.participants[0].spells = spells.clone();
+                stored
+            })
+            .collect();
+        let refs: Vec<&StoredMatch> = matches.iter().collect();
+
+        let usage = spell_usage(refs.iter().copied(), 122, Some("TOP")).unwrap();
+        assert_eq!(usage.games, 13);
+        assert_eq!(usage.setups[0].spells, [4, 14]);
+        assert_eq!((usage.setups[0].games, usage.setups[0].wins), (9, 7));
+        assert!(!usage.setups[0].losing);
+
+        assert!(flagged_setup(&refs, 0).is_none());
+        assert!(flagged_setup(&refs, 9).unwrap().losing);
+        let ghost = flagged_setup(&refs, 12).unwrap();
+        assert!(ghost.no_flash && ghost.unusual && !ghost.losing);
+        assert!(spell_usage(refs.iter().copied(), 122, Some("JUNGLE")).is_none());
+    }
+}
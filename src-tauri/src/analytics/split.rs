@@ -0,0 +1,174 @@
+use super::rank_changes::record_and_announce;
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::{LcuClient, RankedSplit, RankedStats};
+use crate::storage::snapshots::{RankSnapshot, RankSnapshotStore};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const TIERS: [&str; 10] = [
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+
+/// Split points awarded for a ranked win (losses award none).
+const SPLIT_POINTS_PER_WIN: i64 = 10;
+
+/// Single number that orders ranked standings: 400 per tier, 100 per division, plus LP.
+/// Apex tiers have no divisions, so their LP is added on top of the tier base.
+/// Returns `None` for unranked or unknown tiers.
+pub fn rank_value(tier: &str, division: &str, league_points: i32) -> Option<i32> {
+    let tier_index = TIERS.iter().position(|t| t.eq_ignore_ascii_case(tier))? as i32;
+    let division_index = match division {
+        "IV" => 0,
+        "III" => 1,
+        "II" => 2,
+        "I" => 3,
+        _ => 0, // Apex tiers report "" or "NA"
+    };
+    Some(tier_index * 400 + division_index * 100 + league_points)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueSplitProgress {
+    pub queue_type: String,
+    pub current: RankedStats,
+    pub peak: Option<RankSnapshot>, // Highest standing recorded during the split
+    pub wins_this_split: Option<i32>, // None until a snapshot from this split exists
+    pub losses_this_split: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitProgress {
+    pub split: RankedSplit,
+    pub queues: Vec<QueueSplitProgress>,
+    pub next_reward_points: Option<i64>, // Threshold of the next unearned reward, if any remain
+    pub wins_needed_for_next_reward: Option<i64>,
+}
+
+pub fn split_progress(
+    split: &RankedSplit,
+    current: &[RankedStats],
+    snapshots: &[&RankSnapshot],
+) -> SplitProgress {
+    let queues = current
+        .iter()
+        .map(|stats| {
+            let in_queue: Vec<&RankSnapshot> = snapshots
+                .iter()
+                .copied()
+                .filter(|s| s.queue_type == stats.queue_type)
+                .collect();
+            let peak = in_queue
+                .iter()
+                .filter_map(|s| rank_value(&s.tier, &s.rank, s.league_points).map(|v| (v, *s)))
+                .max_by_key(|(v, _)| *v)
+                .map(|(_, s)| s.clone());
+            let first = in_queue.first();
+
+            QueueSplitProgress {
+                queue_type: stats.queue_type.clone(),
+                current: stats.clone(),
+                peak,
+                wins_this_split: first.map(|s| stats.wins - s.wins),
+                losses_this_split: first.map(|s| stats.losses - s.losses),
+            }
+        })
+        .collect();
+
+    let next_reward_points = split
+        .reward_thresholds
+        .iter()
+        .copied()
+        .find(|t| *t > split.split_points);
+    let wins_needed_for_next_reward = next_reward_points.map(|t| {
+        let remaining = t - split.split_points;
+        (remaining + SPLIT_POINTS_PER_WIN - 1) / SPLIT_POINTS_PER_WIN
+    });
+
+    SplitProgress {
+        split: split.clone(),
+        queues,
+        next_reward_points,
+        wins_needed_for_next_reward,
+    }
+}
+
+#[tauri::command]
+pub async fn get_split_progress(
+    app: tauri::AppHandle,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    snapshots: tauri::State<'_, Mutex<RankSnapshotStore>>,
+) -> Result<SplitProgress, String> {
+    let (split, current) = {
+        let mut client_guard = client.lock().await;
+        let split = client_guard.get_ranked_split().await?;
+        let current = client_guard.get_ranked_stats(false).await?;
+        (split, current)
+    };
+
+    record_and_announce(&app, &current)?;
+
+    let store_guard = snapshots.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let in_split = store_guard.between(split.split_start, split.split_end);
+    Ok(split_progress(&split, &current, &in_split))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn stats(tier: &str, rank: &str, lp: i32, wins: i32, losses: i32) -> RankedStats {
+        RankedStats {
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: tier.to_string(),
+            rank: rank.to_string(),
+            league_points: lp,
+            wins,
+            losses,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rank_value_ordering() {
+        assert!(rank_value("GOLD", "I", 99) < rank_value("PLATINUM", "IV", 0));
+        assert!(rank_value("MASTER", "", 0) > rank_value("DIAMOND", "I", 99));
+        assert_eq!(rank_value("UNRANKED", "", 0), None);
+    }
+
+    #[test]
+    fn test_split_progress_tracks_peak_and_rewards() {
+        let mut store = RankSnapshotStore::with_path(PathBuf::from("unused.json"));
+        store.record(&[stats("GOLD", "II", 50, 10, 10)], 1_000);
+        store.record(&[stats("GOLD", "I", 80, 14, 10)], 2_000);
+        store.record(&[stats("GOLD", "I", 80, 14, 10)], 2_500); // Unchanged, not recorded
+        store.record(&[stats("GOLD", "II", 20, 14, 14)], 3_000);
+
+        let split = RankedSplit {
+            split_start: Some(500),
+            split_points: 45,
+            reward_thresholds: vec![20, 100, 300],
+            ..Default::default()
+        };
+        let current = [stats("GOLD", "II", 20, 14, 14)];
+        let in_split = store.between(split.split_start, split.split_end);
+        assert_eq!(in_split.len(), 3);
+
+        let progress = split_progress(&split, &current, &in_split);
+        let solo = &progress.queues[0];
+        assert_eq!(solo.peak.as_ref().unwrap().rank, "I");
+        assert_eq!(solo.wins_this_split, Some(4));
+        assert_eq!(solo.losses_this_split, Some(4));
+        assert_eq!(progress.next_reward_points, Some(100));
+        assert_eq!(progress.wins_needed_for_next_reward, Some(6));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::storage::StoredMatch;
+
+/// Consecutive losses ending with the most recent stored game. Games where the
+/// owner can't be found are skipped rather than breaking the streak.
+pub fn current_loss_streak(matches: &[&StoredMatch]) -> u32 {
+    let mut by_recency: Vec<&StoredMatch> = matches.to_vec();
+    by_recency.sort_by_key(|m| std::cmp::Reverse(m.game_creation));
+
+    by_recency
+        .iter()
+        .filter_map(|m| m.owner())
+        .take_while(|owner| !owner.win)
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_loss_streak_counts_from_latest_game() {
+        // Team 100 wins every game; ids decide the creation order
+        let matches: Vec<StoredMatch> = [(1, 100), (2, 200), (3, 100), (4, 200), (5, 200)]
+            .iter()
+            .map(|(id, team)| {
+                parse_game_details(
+                    &game_details(*id, 420, &[("me", 64, *team, "TOP", "SOLO")]),
+                    "me",
+                )
+                .unwrap()
+            })
+            .collect();
+        let mut refs: Vec<&StoredMatch> = matches.iter().collect();
+        refs.reverse();
+
+        assert_eq!(current_loss_streak(&refs), 2);
+        assert_eq!(current_loss_streak(&refs[2..]), 0);
+        assert_eq!(current_loss_streak(&[]), 0);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::storage::{MatchStore, StoredMatch};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Games shorter than this are remakes; their per-minute numbers are noise.
+pub(crate) const MIN_GAME_SECONDS: i32 = 5 * 60;
+
+const DEFAULT_WINDOW: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendMetric {
+    Kda,
+    CsPerMin,
+    VisionPerMin,
+    DamageShare, // Share of the team's damage to champions, 0..1
+    FirstBlood,  // 1 when the owner got or assisted first blood, so the average is a rate
+    GoldAt10,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendGrouping {
+    Role,
+    Champion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendPoint {
+    pub game_id: i64,
+    pub game_creation: i64, // x axis
+    pub value: f64,         // This game alone
+    pub average: f64,       // Rolling average over the window ending at this game
+}
+
+/// One line on the chart: a metric for all games, or for one role or champion.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendSeries {
+    pub metric: TrendMetric,
+    pub role: Option<String>,
+    pub champion_id: Option<i64>,
+    pub points: Vec<TrendPoint>, // Oldest first
+}
+
+/// The metric for the owner in one game, or `None` when the game didn't
+/// record what it needs (games stored before the stat was kept, or without a
+/// timeline for gold at ten).
+pub fn metric_value(stored: &StoredMatch, metric: TrendMetric) -> Option<f64> {
+    let owner = stored.owner()?;
+    let minutes = stored.game_duration as f64 / 60.0;
+    match metric {
+        TrendMetric::Kda => Some((owner.kills + owner.assists) as f64 / owner.deaths.max(1) as f64),
+        TrendMetric::CsPerMin => owner.cs.map(|cs| cs as f64 / minutes),
+        TrendMetric::VisionPerMin => owner.vision_score.map(|v| v as f64 / minutes),
+        TrendMetric::DamageShare => {
+            let team_damage: i64 = stored
+                .participants
+                .iter()
+                .filter(|p| p.team_id == owner.team_id)
+                .map(|p| p.damage_to_champions.unwrap_or(0))
+                .sum();
+            owner
+                .damage_to_champions
+                .filter(|_| team_damage > 0)
+                .map(|damage| damage as f64 / team_damage as f64)
+        }
+        TrendMetric::FirstBlood => owner.first_blood.map(|fb| if fb { 1.0 } else { 0.0 }),
+        TrendMetric::GoldAt10 => owner.gold_at_10.map(f64::from),
+    }
+}
+
+/// Per-game values with a trailing average over the last `window` games.
+fn rolling(values: Vec<(&StoredMatch, f64)>, window: usize) -> Vec<TrendPoint> {
+    let window = window.max(1);
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, (stored, value))| {
+            let recent = &values[(i + 1).saturating_sub(window)..=i];
+            TrendPoint {
+                game_id: stored.game_id,
+                game_creation: stored.game_creation,
+                value: *value,
+                average: recent.iter().map(|(_, v)| v).sum::<f64>() / recent.len() as f64,
+            }
+        })
+        .collect()
+}
+
+/// Rolling averages of each metric over the owner's games, one series per
+/// metric, or per metric and role or champion when grouped.
+pub fn performance_trends<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    metrics: &[TrendMetric],
+    window: usize,
+    group_by: Option<TrendGrouping>,
+) -> Vec<TrendSeries> {
+    let mut games: Vec<&StoredMatch> = matches
+        .into_iter()
+        .filter(|m| m.game_duration >= MIN_GAME_SECONDS && m.owner().is_some())
+        .collect();
+    games.sort_by_key(|m| m.game_creation);
+
+    // Group key: (role, champion), with the unused half left as None
+    let mut groups: BTreeMap<(Option<String>, Option<i64>), Vec<&StoredMatch>> = BTreeMap::new();
+    for stored in games {
+        let Some(owner) = stored.owner() else {
+            continue;
+        };
+        let key = match group_by {
+            None => (None, None),
+            Some(TrendGrouping::Role) => match &owner.position {
+                Some(position) => (Some(position.clone()), None),
+                None => continue,
+            },
+            Some(TrendGrouping::Champion) => (None, Some(owner.champion_id)),
+        };
+        groups.entry(key).or_default().push(stored);
+    }
+
+    let mut series = Vec::new();
+    for metric in metrics {
+        for ((role, champion_id), games) in &groups {
+            let values: Vec<(&StoredMatch, f64)> = games
+                .iter()
+                .filter_map(|m| metric_value(m, *metric).map(|v| (*m, v)))
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            series.push(TrendSeries {
+                metric: *metric,
+                role: role.clone(),
+                champion_id: *champion_id,
+                points: rolling(values, window),
+            });
+        }
+    }
+    series
+}
+
+#[tauri::command]
+pub async fn get_performance_trends(
+    metrics: Vec<TrendMetric>,
+    window: Option<usize>,
+    group_by: Option<TrendGrouping>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Vec<TrendSeries>, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(performance_trends(
+        store_guard.matches(),
+        &metrics,
+        window.unwrap_or(DEFAULT_WINDOW),
+        group_by,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_rolling_trends_by_role() {
+        let matches: Vec<StoredMatch> = (1..=4)
+            .map(|id| {
+                let lane = if id == 4 { "MIDDLE" } else { "TOP" };
+                let game = game_details(
+                    id,
+                    420,
+                    &[
+                        ("me", 266, 100, lane, "SOLO"),
+                        ("ally", 64, 100, "JUNGLE", "NONE"),
+                    ],
+                );
+                let mut stored = parse_game_details(&game, "me").unwrap();
+                stored.participants[0].kills = id as i32 * 2; // KDA 2.5, 3.5, 4.5, 5.5
+                stored
+            })
+            .collect();
+
+        let overall = performance_trends(
+            &matches,
+            &[TrendMetric::Kda, TrendMetric::DamageShare],
+            2,
+            None,
+        );
+        assert_eq!(overall.len(), 2);
+        let kda = &overall[0].points;
+        assert_eq!(
+            kda.iter().map(|p| p.value).collect::<Vec<_>>(),
+            vec![2.5, 3.5, 4.5, 5.5]
+        );
+        assert_eq!(kda[0].average, 2.5);
+        assert_eq!(kda[3].average, 5.0);
+        assert_eq!(overall[1].points[0].value, 0.5, "even split with one ally");
+
+        let by_role = performance_trends(
+            &matches,
+            &[TrendMetric::CsPerMin],
+            10,
+            Some(TrendGrouping::Role),
+        );
+        assert_eq!(by_role.len(), 2);
+        assert_eq!(by_role[0].role.as_deref(), Some("MIDDLE"));
+        assert_eq!(by_role[1].points.len(), 3);
+        assert_eq!(by_role[1].points[0].value, 6.0, "180 cs in 30 minutes");
+    }
+}
@@ -0,0 +1,220 @@
+use crate::analytics::trends::{metric_value, TrendMetric, MIN_GAME_SECONDS};
+use crate::settings::SettingsStore;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Vision score per minute that counts as par, by position, when settings
+/// don't say otherwise.
+const DEFAULT_BENCHMARKS: [(&str, f64); 5] = [
+    ("TOP", 0.7),
+    ("JUNGLE", 1.1),
+    ("MIDDLE", 0.7),
+    ("BOTTOM", 0.6),
+    ("UTILITY", 2.2),
+];
+
+/// Earlier games in the same role the rolling average covers.
+const ROLLING_GAMES: usize = 10;
+
+/// Below par in this many of the last `CALLOUT_GAMES` games in a role is
+/// "consistently" below par.
+const CALLOUT_GAMES: usize = 5;
+const CALLOUT_BELOW: usize = 4;
+
+/// Roles where vision is part of the job, and so worth calling out.
+const CALLOUT_POSITIONS: [&str; 2] = ["JUNGLE", "UTILITY"];
+
+/// Payload of the `vision-report` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct VisionReport {
+    pub game_id: i64,
+    pub position: String,
+    pub vision_score: i32,
+    pub vision_per_min: f64,
+    pub benchmark: f64,
+    pub rolling_average: Option<f64>, // Earlier games in the role; None for the first
+    pub below_par: bool,
+    pub callout: Option<String>, // Set when below par in most recent jungle or support games
+}
+
+fn benchmark_for(benchmarks: &BTreeMap<String, f64>, position: &str) -> Option<f64> {
+    benchmarks.get(position).copied().or_else(|| {
+        DEFAULT_BENCHMARKS
+            .iter()
+            .find(|(p, _)| *p == position)
+            .map(|(_, b)| *b)
+    })
+}
+
+/// The owner's vision in `game_id` against the benchmark for their role and
+/// their own earlier games there. `None` when the game has no role or no
+/// vision score stored, or was a remake.
+pub fn vision_report<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    game_id: i64,
+    benchmarks: &BTreeMap<String, f64>,
+) -> Option<VisionReport> {
+    let mut matches: Vec<&StoredMatch> = matches.into_iter().collect();
+    matches.sort_by_key(|m| m.game_creation);
+    let index = matches.iter().position(|m| m.game_id == game_id)?;
+    let game = matches[index];
+    let owner = game.owner()?;
+    let position = owner.position.clone()?;
+    let benchmark = benchmark_for(benchmarks, &position)?;
+    if game.game_duration < MIN_GAME_SECONDS {
+        return None;
+    }
+    let vision_per_min = metric_value(game, TrendMetric::VisionPerMin)?;
+
+    // Up to and including this game, newest first
+    let in_role: Vec<f64> = matches[..=index]
+        .iter()
+        .rev()
+        .filter(|m| m.game_duration >= MIN_GAME_SECONDS)
+        .filter(|m| m.owner().and_then(|o| o.position.as_deref()) == Some(&position))
+        .filter_map(|m| metric_value(m, TrendMetric::VisionPerMin))
+        .collect();
+    let earlier: Vec<f64> = in_role
+        .iter()
+        .skip(1)
+        .take(ROLLING_GAMES)
+        .copied()
+        .collect();
+    let rolling_average =
+        (!earlier.is_empty()).then(|| earlier.iter().sum::<f64>() / earlier.len() as f64);
+
+    let recent_below = in_role
+        .iter()
+        .take(CALLOUT_GAMES)
+        .filter(|v| **v < benchmark)
+        .count();
+    let role_name = if position == "UTILITY" {
+        "support"
+    } else {
+        "jungle"
+    };
+    let callout = (CALLOUT_POSITIONS.contains(&position.as_str()) && recent_below >= CALLOUT_BELOW)
+        .then(|| {
+            format!(
+                "Vision below par in {} of your last {} {} games (par is {:.1} per minute)",
+                recent_below,
+                in_role.len().min(CALLOUT_GAMES),
+                role_name,
+                benchmark
+            )
+        });
+
+    Some(VisionReport {
+        game_id,
+        vision_score: owner.vision_score?,
+        position,
+        vision_per_min,
+        benchmark,
+        rolling_average,
+        below_par: vision_per_min < benchmark,
+        callout,
+    })
+}
+
+/// Post-game: emit `vision-report` for the game just stored, and notify when
+/// it comes with a callout and callouts are on.
+pub fn check_vision(app: &AppHandle, game_id: i64) -> Result<Option<VisionReport>, String> {
+    let (benchmarks, callouts) = {
+        let settings = app.state::<Mutex<SettingsStore>>();
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let s = settings_guard.get();
+        (s.vision_benchmarks.clone(), s.vision_callouts)
+    };
+    let report = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        vision_report(store_guard.matches(), game_id, &benchmarks)
+    };
+    let Some(report) = report else {
+        return Ok(None);
+    };
+
+    if let Some(callout) = report.callout.as_deref().filter(|_| callouts) {
+        let _ = app
+            .notification()
+            .builder()
+            .title("Ward more")
+            .body(callout)
+            .show();
+    }
+    let _ = app.emit("vision-report", &report);
+    Ok(Some(report))
+}
+
+/// Vision report for a stored game, or the latest one.
+#[tauri::command]
+pub async fn get_vision_report(
+    game_id: Option<i64>,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<Option<VisionReport>, String> {
+    let benchmarks = {
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        settings_guard.get().vision_benchmarks.clone()
+    };
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let Some(game_id) = game_id.or_else(|| {
+        store_guard
+            .matches()
+            .max_by_key(|m| m.game_creation)
+            .map(|m| m.game_id)
+    }) else {
+        return Ok(None);
+    };
+    Ok(vision_report(store_guard.matches(), game_id, &benchmarks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    #[test]
+    fn test_support_vision_callout() {
+        // 30 minute games; 66 vision is 2.2 per minute, par for support
+        let matches: Vec<StoredMatch> = [80, 66, 50, 40, 45, 30]
+            .iter()
+            .enumerate()
+            .map(|(i, vision)| {
+                let mut stored = parse_game_details(
+                    &game_details(i as i64, 420, &[("me", 412, 100, "BOTTOM", "DUO_SUPPORT")]),
+                    "me",
+                )
+                .unwrap();
+                stored.participants[0].vision_score = Some(*vision);
+                stored
+            })
+            .collect();
+
+        let defaults = BTreeMap::new();
+        let first = vision_report(&matches, 0, &defaults).unwrap();
+        assert!(!first.below_par);
+        assert_eq!(first.rolling_average, None);
+        assert!(first.callout.is_none());
+
+        let latest = vision_report(&matches, 5, &defaults).unwrap();
+        assert_eq!(latest.position, "UTILITY");
+        assert!(latest.below_par);
+        assert!((latest.rolling_average.unwrap() - 56.2 / 30.0).abs() < 1e-9);
+        assert_eq!(
+            latest.callout.as_deref(),
+            Some("Vision below par in 4 of your last 5 support games (par is 2.2 per minute)")
+        );
+
+        let lenient = BTreeMap::from([("UTILITY".to_string(), 1.0)]);
+        assert!(vision_report(&matches, 5, &lenient)
+            .unwrap()
+            .callout
+            .is_none());
+    }
+}
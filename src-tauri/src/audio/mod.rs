@@ -0,0 +1,259 @@
+use crate::settings::{AppSettings, SettingsStore};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Voice chat apps whose running process means the player is probably in a
+/// call, matched case-insensitively against the start of the process name.
+const DEFAULT_VOICE_APPS: &[&str] = &["discord", "vesktop", "teamspeak", "ts3client", "mumble"];
+
+/// Alert volume while in voice, when the settings don't say.
+const DEFAULT_DUCKED_VOLUME: f32 = 0.3;
+
+/// An output device alerts can be routed to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioDevice {
+    pub id: String,   // What `audio_output_device` stores
+    pub name: String, // As shown to the user, and matched against the webview's device labels
+    pub is_default: bool,
+}
+
+/// Payload of the `sound-alert` event; the webview plays it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SoundAlert {
+    pub sound: String,
+    pub device: Option<String>, // None plays on the system default
+    pub volume: f32,            // 0 to 1
+    pub ducked: bool,           // Lowered because a voice app is running
+}
+
+/// The alert to play for `sound`, or None when sound alerts are off.
+pub fn alert_for(settings: &AppSettings, sound: &str, in_voice: bool) -> Option<SoundAlert> {
+    if !settings.sound_alerts {
+        return None;
+    }
+    let ducked = in_voice && settings.voice_ducking;
+    Some(SoundAlert {
+        sound: sound.to_string(),
+        device: settings
+            .audio_output_device
+            .clone()
+            .filter(|d| !d.is_empty()),
+        volume: if ducked {
+            settings
+                .voice_ducking_volume
+                .unwrap_or(DEFAULT_DUCKED_VOLUME)
+                .clamp(0.0, 1.0)
+        } else {
+            1.0
+        },
+        ducked,
+    })
+}
+
+/// Whether any of `processes` is a voice app from `apps`, or from the
+/// defaults when `apps` is empty.
+pub fn is_voice_app_running<'a>(
+    processes: impl IntoIterator<Item = &'a str>,
+    apps: &[String],
+) -> bool {
+    let apps: Vec<String> = if apps.is_empty() {
+        DEFAULT_VOICE_APPS.iter().map(|a| a.to_string()).collect()
+    } else {
+        apps.iter().map(|a| a.to_lowercase()).collect()
+    };
+    processes.into_iter().any(|name| {
+        let name = name.to_lowercase();
+        apps.iter().any(|app| name.starts_with(app.as_str()))
+    })
+}
+
+fn in_voice(settings: &AppSettings) -> bool {
+    let mut system = System::new();
+    system.refresh_processes();
+    let names: Vec<&str> = system.processes().values().map(|p| p.name()).collect();
+    is_voice_app_running(names, &settings.voice_apps)
+}
+
+/// Emit `sound-alert` for `sound` if sound alerts are on. Returns whether it
+/// was emitted, so callers can leave the sound off their notification.
+pub fn play_alert(app: &AppHandle, sound: &str) -> bool {
+    let Some(settings) = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+    else {
+        return false;
+    };
+    if !settings.sound_alerts {
+        return false;
+    }
+    // Listing processes is only worth it when ducking could apply
+    let in_voice = settings.voice_ducking && in_voice(&settings);
+    match alert_for(&settings, sound, in_voice) {
+        Some(alert) => app.emit("sound-alert", alert).is_ok(),
+        None => false,
+    }
+}
+
+/// Sinks from `pactl list sinks`, with the default from `pactl get-default-sink`.
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+pub fn parse_pactl_sinks(output: &str, default_sink: &str) -> Vec<AudioDevice> {
+    let mut devices: Vec<AudioDevice> = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(id) = line.strip_prefix("Name: ") {
+            devices.push(AudioDevice {
+                id: id.to_string(),
+                name: id.to_string(),
+                is_default: id == default_sink.trim(),
+            });
+        } else if let (Some(name), Some(device)) =
+            (line.strip_prefix("Description: "), devices.last_mut())
+        {
+            device.name = name.to_string();
+        }
+    }
+    devices
+}
+
+/// Output devices from `system_profiler SPAudioDataType -json`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn parse_system_profiler(json: &serde_json::Value) -> Vec<AudioDevice> {
+    json["SPAudioDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|group| group["_items"].as_array())
+        .flatten()
+        .filter(|d| !d["coreaudio_device_output"].is_null())
+        .filter_map(|d| {
+            let name = d["_name"].as_str()?.to_string();
+            Some(AudioDevice {
+                id: name.clone(),
+                name,
+                is_default: d["coreaudio_default_audio_output_device"].as_str()
+                    == Some("spaudio_yes"),
+            })
+        })
+        .collect()
+}
+
+/// Render endpoints from `Get-PnpDevice -Class AudioEndpoint`, printed as
+/// `InstanceId|FriendlyName`. Capture endpoints (microphones) are dropped.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn parse_audio_endpoints(output: &str) -> Vec<AudioDevice> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().split_once('|'))
+        .filter(|(id, _)| id.contains("{0.0.0.00000000}"))
+        .map(|(_, name)| AudioDevice {
+            id: name.to_string(),
+            name: name.to_string(),
+            is_default: false,
+        })
+        .collect()
+}
+
+/// The output devices the OS reports, empty where they can't be listed.
+pub fn list_output_devices() -> Vec<AudioDevice> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+        let Ok(output) = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Get-PnpDevice -Class AudioEndpoint -Status OK | ForEach-Object { $_.InstanceId + '|' + $_.FriendlyName }",
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+        else {
+            return Vec::new();
+        };
+        parse_audio_endpoints(&String::from_utf8_lossy(&output.stdout))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("system_profiler")
+            .args(["SPAudioDataType", "-json"])
+            .output()
+            .ok()
+            .and_then(|o| serde_json::from_slice(&o.stdout).ok())
+            .map(|json| parse_system_profiler(&json))
+            .unwrap_or_default()
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let run = |args: &[&str]| {
+            std::process::Command::new("pactl")
+                .args(args)
+                .output()
+                .ok()
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        };
+        let Some(sinks) = run(&["list", "sinks"]) else {
+            return Vec::new();
+        };
+        parse_pactl_sinks(&sinks, &run(&["get-default-sink"]).unwrap_or_default())
+    }
+}
+
+#[tauri::command]
+pub async fn list_audio_devices() -> Result<Vec<AudioDevice>, String> {
+    tokio::task::spawn_blocking(list_output_devices)
+        .await
+        .map_err(|e| format!("Listing audio devices failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_alert_routing_and_ducking() {
+        let mut settings = AppSettings::default();
+        assert!(alert_for(&settings, "Default", false).is_none());
+
+        settings.sound_alerts = true;
+        settings.audio_output_device = Some("Headphones".to_string());
+        let alert = alert_for(&settings, "Default", true).unwrap();
+        assert_eq!(alert.device.as_deref(), Some("Headphones"));
+        assert_eq!((alert.volume, alert.ducked), (1.0, false), "ducking is off");
+
+        settings.voice_ducking = true;
+        let alert = alert_for(&settings, "Reminder", true).unwrap();
+        assert_eq!((alert.volume, alert.ducked), (DEFAULT_DUCKED_VOLUME, true));
+        assert_eq!(alert_for(&settings, "Reminder", false).unwrap().volume, 1.0);
+
+        assert!(is_voice_app_running(["init", "Discord.exe"], &[]));
+        assert!(!is_voice_app_running(
+            ["Discord.exe"],
+            &["Mumble".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_device_listings() {
+        let pactl = "Sink #0\n\tState: SUSPENDED\n\tName: alsa_output.pci.analog-stereo\n\tDescription: Built-in Audio\nSink #1\n\tName: bluez_output.headset\n\tDescription: Headset\n";
+        let devices = parse_pactl_sinks(pactl, "bluez_output.headset\n");
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "Built-in Audio");
+        assert!(!devices[0].is_default && devices[1].is_default);
+
+        let profiler = json!({ "SPAudioDataType": [{ "_items": [
+            { "_name": "MacBook Speakers", "coreaudio_device_output": 2, "coreaudio_default_audio_output_device": "spaudio_yes" },
+            { "_name": "MacBook Microphone", "coreaudio_device_input": 1 }
+        ]}]});
+        let devices = parse_system_profiler(&profiler);
+        assert_eq!(devices.len(), 1);
+        assert!(devices[0].is_default);
+
+        let endpoints = "SWD\\MMDEVAPI\\{0.0.0.00000000}.{a1}|Speakers (Realtek Audio)\r\nSWD\\MMDEVAPI\\{0.0.1.00000000}.{b2}|Microphone (Realtek Audio)\r\n";
+        let devices = parse_audio_endpoints(endpoints);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "Speakers (Realtek Audio)");
+    }
+}
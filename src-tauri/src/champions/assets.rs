@@ -0,0 +1,390 @@
+use super::cache::ChampionCache;
+use super::client::Champion;
+use crate::downloads::{DownloadTracker, CANCELLED};
+use crate::lcu::draft::DraftState;
+use crate::storage::usage::image_cache_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::task::JoinSet;
+
+/// Downloads running at the same time during a prefetch.
+const MAX_CONCURRENT: usize = 6;
+
+/// Left in an asset directory while its prefetch is incomplete, so the next
+/// start can pick it up again.
+const PENDING_MARKER: &str = ".prefetch-pending";
+
+/// How often a paused background prefetch checks whether it may continue.
+const PRIORITY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Files being fetched for the current draft. Background prefetch starts no new
+/// downloads while any are in flight.
+#[derive(Default)]
+pub struct PriorityDownloads {
+    in_flight: BTreeSet<PathBuf>,
+}
+
+impl PriorityDownloads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn priority_active(app: &AppHandle) -> bool {
+    app.try_state::<Mutex<PriorityDownloads>>()
+        .and_then(|downloads| downloads.lock().ok().map(|d| !d.in_flight.is_empty()))
+        .unwrap_or(false)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetKind {
+    ChampionIcons,
+    LoadingScreens,
+}
+
+impl AssetKind {
+    const ALL: [AssetKind; 2] = [AssetKind::ChampionIcons, AssetKind::LoadingScreens];
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            AssetKind::ChampionIcons => "champion",
+            AssetKind::LoadingScreens => "loading",
+        }
+    }
+
+    pub fn dir(self) -> Result<PathBuf, String> {
+        Ok(image_cache_dir()?.join(self.dir_name()))
+    }
+
+    fn file_and_url(self, champion: &Champion, version: &str) -> (String, String) {
+        match self {
+            AssetKind::ChampionIcons => (
+                format!("{}.png", champion.id),
+                format!(
+                    "https://ddragon.leagueoflegends.com/cdn/{}/img/champion/{}.png",
+                    version, champion.id
+                ),
+            ),
+            AssetKind::LoadingScreens => (
+                format!("{}_0.jpg", champion.id),
+                format!(
+                    "https://ddragon.leagueoflegends.com/cdn/img/champion/loading/{}_0.jpg",
+                    champion.id
+                ),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrefetchSummary {
+    pub downloaded: u32,
+    pub skipped: u32, // Already on disk from an earlier run
+    pub failed: u32,
+    pub directory: String,
+}
+
+/// Files still missing from `dir`, as (path, url) pairs.
+pub fn plan_prefetch(
+    kind: AssetKind,
+    champions: &[Champion],
+    version: &str,
+    dir: &Path,
+) -> Vec<(PathBuf, String)> {
+    champions
+        .iter()
+        .map(|c| kind.file_and_url(c, version))
+        .map(|(file, url)| (dir.join(file), url))
+        .filter(|(path, _)| !path.exists())
+        .collect()
+}
+
+/// Download to a `.part` file and rename, so an interrupted download never
+/// looks like a finished one.
+async fn download_file(client: reqwest::Client, url: String, path: PathBuf) -> Result<u64, String> {
+    let bytes = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Download interrupted: {}", e))?;
+
+    let part = path.with_extension("part");
+    tokio::fs::write(&part, &bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", part.display(), e))?;
+    tokio::fs::rename(&part, &path)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(bytes.len() as u64)
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn champion_list(app: &AppHandle) -> Result<(Vec<Champion>, String), String> {
+    let cache = app.state::<Mutex<ChampionCache>>();
+    let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    if let Some(version) = cache_guard.get_version() {
+        return Ok((cache_guard.get_all_champions(), version));
+    }
+    match cache_guard.load_from_cache()? {
+        Some(data) => Ok((data.champions.into_values().collect(), data.version)),
+        None => Err("Champion data has not been downloaded yet".to_string()),
+    }
+}
+
+async fn prefetch(app: &AppHandle, kind: AssetKind) -> Result<PrefetchSummary, String> {
+    let (champions, version) = champion_list(app)?;
+    let dir = kind.dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create image cache: {}", e))?;
+    let marker = dir.join(PENDING_MARKER);
+    let _ = fs::write(&marker, &version);
+
+    let jobs = plan_prefetch(kind, &champions, &version, &dir);
+    let mut summary = PrefetchSummary {
+        skipped: (champions.len() - jobs.len()) as u32,
+        directory: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let client = http_client()?;
+    let mut tracker = DownloadTracker::start(
+        app,
+        &format!("prefetch-{}", kind.dir_name()),
+        match kind {
+            AssetKind::ChampionIcons => "Champion icons",
+            AssetKind::LoadingScreens => "Loading screen art",
+        },
+    );
+    tracker.set_file_count(jobs.len() as u64);
+
+    let mut pending = jobs.into_iter();
+    let mut in_flight = JoinSet::new();
+    loop {
+        while in_flight.len() < MAX_CONCURRENT && !priority_active(app) && !tracker.is_cancelled() {
+            let Some((path, url)) = pending.next() else {
+                break;
+            };
+            in_flight.spawn(download_file(client.clone(), url, path));
+        }
+        let Some(finished) = in_flight.join_next().await else {
+            if pending.len() > 0 && !tracker.is_cancelled() {
+                // Paused for a draft's downloads
+                tokio::time::sleep(PRIORITY_BACKOFF).await;
+                continue;
+            }
+            break;
+        };
+        match finished {
+            Ok(Ok(bytes)) => {
+                summary.downloaded += 1;
+                tracker.file_done(bytes);
+            }
+            _ => {
+                summary.failed += 1;
+                tracker.file_done(0);
+            }
+        }
+    }
+
+    let result = if tracker.is_cancelled() {
+        Err(CANCELLED.to_string())
+    } else {
+        Ok(summary)
+    };
+    // Failed files are retried on the next start; a cancelled run is not resumed
+    if matches!(&result, Ok(s) if s.failed == 0) || tracker.is_cancelled() {
+        let _ = fs::remove_file(&marker);
+    }
+    tracker.complete(&result);
+    result
+}
+
+/// Champions seen in the current draft whose art has already been requested.
+#[derive(Default)]
+pub struct DraftAssets {
+    requested: HashSet<i64>,
+}
+
+impl DraftAssets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Champions picked, banned or hovered in `state` that weren't requested
+    /// earlier in this draft.
+    pub fn new_champions(&mut self, state: &DraftState) -> Vec<i64> {
+        let team_ids = state.teams.iter().flat_map(|team| {
+            let picks = team.picks.iter().map(|p| Some(p.champion_id));
+            let bans = team.bans.iter().map(|b| Some(b.champion_id));
+            let cells = team
+                .cells
+                .iter()
+                .flat_map(|c| [c.champion_id, c.selected_champion_id]);
+            picks.chain(bans).chain(cells)
+        });
+        let action_ids = state.actions.iter().map(|a| a.champion_id);
+
+        team_ids
+            .chain(action_ids)
+            .flatten()
+            .filter(|id| *id > 0 && self.requested.insert(*id))
+            .collect()
+    }
+
+    /// Forget the requests of a finished draft.
+    pub fn reset(&mut self) {
+        self.requested.clear();
+    }
+}
+
+/// Fetch loading screen art for the given champions ahead of any background
+/// prefetch, which waits until these downloads are done.
+pub async fn fetch_draft_assets(
+    app: AppHandle,
+    champion_ids: Vec<i64>,
+) -> Result<PrefetchSummary, String> {
+    let (champions, version) = champion_list(&app)?;
+    let involved: Vec<Champion> = champions
+        .into_iter()
+        .filter(|c| champion_ids.contains(&c.key))
+        .collect();
+    let kind = AssetKind::LoadingScreens;
+    let dir = kind.dir()?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create image cache: {}", e))?;
+    let client = http_client()?;
+
+    // Skip files another draft request is already fetching
+    let jobs: Vec<_> = {
+        let priority = app.state::<Mutex<PriorityDownloads>>();
+        let mut priority_guard = priority.lock().map_err(|e| format!("Lock error: {}", e))?;
+        plan_prefetch(kind, &involved, &version, &dir)
+            .into_iter()
+            .filter(|(path, _)| priority_guard.in_flight.insert(path.clone()))
+            .collect()
+    };
+    let mut summary = PrefetchSummary {
+        skipped: (involved.len() - jobs.len()) as u32,
+        directory: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let mut downloads = JoinSet::new();
+    for (path, url) in jobs {
+        let client = client.clone();
+        let app = app.clone();
+        downloads.spawn(async move {
+            let result = download_file(client, url, path.clone()).await;
+            if let Ok(mut priority_guard) = app.state::<Mutex<PriorityDownloads>>().lock() {
+                priority_guard.in_flight.remove(&path);
+            }
+            result
+        });
+    }
+    while let Some(finished) = downloads.join_next().await {
+        match finished {
+            Ok(Ok(_)) => summary.downloaded += 1,
+            _ => summary.failed += 1,
+        }
+    }
+    Ok(summary)
+}
+
+/// Continue prefetches that didn't finish before the app last closed.
+pub async fn resume_pending_prefetches(app: &AppHandle) {
+    for kind in AssetKind::ALL {
+        let pending = kind
+            .dir()
+            .map(|dir| dir.join(PENDING_MARKER).exists())
+            .unwrap_or(false);
+        if pending {
+            if let Err(e) = prefetch(app, kind).await {
+                eprintln!("Resuming asset prefetch failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Download every champion's art of `kind` into the image cache. Files already
+/// on disk are skipped, so calling this again resumes an interrupted run.
+#[tauri::command]
+pub async fn prefetch_assets(app: AppHandle, kind: AssetKind) -> Result<PrefetchSummary, String> {
+    prefetch(&app, kind).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::draft::parse_draft_session;
+
+    #[test]
+    fn test_plan_prefetch_skips_existing_files() {
+        let dir = std::env::temp_dir().join("trackimo-prefetch-test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Aatrox.png"), b"png").unwrap();
+        let _ = fs::remove_file(dir.join("Ahri.png"));
+
+        let champion = |id: &str, key| Champion {
+            id: id.to_string(),
+            key,
+            name: id.to_string(),
+            title: String::new(),
+            tags: vec![],
+            info: None,
+        };
+        let champions = vec![champion("Aatrox", 266), champion("Ahri", 103)];
+
+        let jobs = plan_prefetch(AssetKind::ChampionIcons, &champions, "14.3.1", &dir);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].0, dir.join("Ahri.png"));
+        assert!(jobs[0].1.ends_with("/14.3.1/img/champion/Ahri.png"));
+
+        let jobs = plan_prefetch(AssetKind::LoadingScreens, &champions, "14.3.1", &dir);
+        assert_eq!(jobs.len(), 2);
+    }
+
+    #[test]
+    fn test_draft_assets_requests_each_champion_once() {
+        let session = |hover: i64| {
+            serde_json::json!({
+                "gameId": 1,
+                "localPlayerCellId": 0,
+                "timer": { "phase": "BAN_PICK" },
+                "myTeam": [{ "cellId": 0, "championId": 266, "championPickIntent": 0 }],
+                "theirTeam": [{ "cellId": 5, "championId": 0, "championPickIntent": hover }],
+                "actions": [[
+                    { "id": 1, "actorCellId": 5, "championId": 157, "completed": true, "type": "ban" }
+                ]]
+            })
+        };
+        let mut assets = DraftAssets::new();
+
+        let state = parse_draft_session(&session(103)).unwrap();
+        let mut first = assets.new_champions(&state);
+        first.sort();
+        assert_eq!(first, vec![103, 157, 266]);
+        assert!(assets.new_champions(&state).is_empty());
+
+        let state = parse_draft_session(&session(64)).unwrap();
+        assert_eq!(assets.new_champions(&state), vec![64]);
+
+        assets.reset();
+        assert_eq!(assets.new_champions(&state).len(), 3);
+    }
+}
@@ -1,37 +1,89 @@
 use super::client::{Champion, ChampionData};
+use crate::storage::usage::{cache_root, champion_data_dir};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+/// How many patches of champion data are kept on disk.
+const RETAINED_PATCHES: usize = 3;
+
 pub struct ChampionCache {
     data: Arc<Mutex<Option<ChampionData>>>,
-    cache_path: PathBuf,
+    cache_dir: PathBuf,   // One subdirectory per Data Dragon version
+    legacy_path: PathBuf, // Unversioned file written by older builds
+}
+
+/// Numeric ordering for Data Dragon versions like "14.3.1"; non-numeric parts sort first.
+pub(crate) fn version_key(version: &str) -> Vec<u32> {
+    version.split('.').map(|p| p.parse().unwrap_or(0)).collect()
+}
+
+/// Whether two versions belong to the same major.minor patch ("14.3.1" and "14.3.567.1234").
+pub fn same_patch(a: &str, b: &str) -> bool {
+    let (a, b) = (version_key(a), version_key(b));
+    a.len() >= 2 && b.len() >= 2 && a[..2] == b[..2]
+}
+
+/// Pick the cached version to use for a game played on `game_version` (as reported
+/// by the client, e.g. "14.3.567.1234"): the same major.minor patch if cached,
+/// otherwise the newest patch that isn't newer, otherwise the oldest we have.
+pub fn resolve_version(cached: &[String], game_version: &str) -> Option<String> {
+    let target = version_key(game_version);
+    let patch = &target[..target.len().min(2)];
+
+    let mut sorted: Vec<&String> = cached.iter().collect();
+    sorted.sort_by_key(|v| std::cmp::Reverse(version_key(v)));
+
+    sorted
+        .iter()
+        .find(|v| version_key(v).starts_with(patch))
+        .or_else(|| sorted.iter().find(|v| version_key(v)[..] <= target[..]))
+        .or_else(|| sorted.last())
+        .map(|v| v.to_string())
 }
 
 impl ChampionCache {
     pub fn new() -> Result<Self, String> {
-        let cache_dir = dirs::cache_dir()
-            .ok_or_else(|| "Failed to get cache directory".to_string())?
-            .join("trackimo-desktop");
+        let cache_dir = champion_data_dir()?;
 
         fs::create_dir_all(&cache_dir)
             .map_err(|e| format!("Failed to create cache directory: {}", e))?;
 
-        let cache_path = cache_dir.join("champions.json");
-
         Ok(Self {
             data: Arc::new(Mutex::new(None)),
-            cache_path,
+            legacy_path: cache_root()?.join("champions.json"),
+            cache_dir,
         })
     }
 
-    pub fn load_from_cache(&self) -> Result<Option<ChampionData>, String> {
-        if !self.cache_path.exists() {
+    fn version_path(&self, version: &str) -> PathBuf {
+        self.cache_dir.join(version).join("champions.json")
+    }
+
+    /// Versions with champion data on disk, newest first.
+    pub fn cached_versions(&self) -> Vec<String> {
+        let mut versions: Vec<String> = fs::read_dir(&self.cache_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .filter(|v| self.version_path(v).exists())
+                    .collect()
+            })
+            .unwrap_or_default();
+        versions.sort_by_key(|v| std::cmp::Reverse(version_key(v)));
+        versions
+    }
+
+    /// Champion data of one specific patch, if it is cached.
+    pub fn load_version(&self, version: &str) -> Result<Option<ChampionData>, String> {
+        let path = self.version_path(version);
+        if !path.exists() {
             return Ok(None);
         }
 
-        let contents = fs::read_to_string(&self.cache_path)
-            .map_err(|e| format!("Failed to read cache: {}", e))?;
+        let contents =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read cache: {}", e))?;
 
         let data: ChampionData =
             serde_json::from_str(&contents).map_err(|e| format!("Failed to parse cache: {}", e))?;
@@ -39,11 +91,39 @@ impl ChampionCache {
         Ok(Some(data))
     }
 
+    /// Load the newest cached patch. A cache file from before versioning is
+    /// moved into its version directory first.
+    pub fn load_from_cache(&self) -> Result<Option<ChampionData>, String> {
+        if self.legacy_path.exists() {
+            let contents = fs::read_to_string(&self.legacy_path)
+                .map_err(|e| format!("Failed to read cache: {}", e))?;
+            if let Ok(data) = serde_json::from_str::<ChampionData>(&contents) {
+                self.save_to_cache(&data)?;
+            }
+            let _ = fs::remove_file(&self.legacy_path);
+        }
+
+        match self.cached_versions().first() {
+            Some(version) => self.load_version(version),
+            None => Ok(None),
+        }
+    }
+
+    /// Write `data` under its version and drop patches beyond the retained few.
     pub fn save_to_cache(&self, data: &ChampionData) -> Result<(), String> {
         let json = serde_json::to_string_pretty(data)
             .map_err(|e| format!("Failed to serialize data: {}", e))?;
 
-        fs::write(&self.cache_path, json).map_err(|e| format!("Failed to write cache: {}", e))?;
+        let path = self.version_path(&data.version);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+        }
+        fs::write(&path, json).map_err(|e| format!("Failed to write cache: {}", e))?;
+
+        for old in self.cached_versions().iter().skip(RETAINED_PATCHES) {
+            let _ = fs::remove_dir_all(self.cache_dir.join(old));
+        }
 
         Ok(())
     }
@@ -55,6 +135,15 @@ impl ChampionCache {
         Ok(())
     }
 
+    /// Load the newest cached patch into memory and return its version.
+    pub fn hydrate(&self) -> Result<Option<String>, String> {
+        let loaded = self.load_from_cache()?;
+        let version = loaded.as_ref().map(|d| d.version.clone());
+        let mut guard = self.data.lock().map_err(|e| format!("Lock error: {}", e))?;
+        *guard = loaded;
+        Ok(version)
+    }
+
     pub fn get_champion_by_id(&self, id: i64) -> Option<Champion> {
         let guard = self.data.lock().ok()?;
         let data = guard.as_ref()?;
@@ -65,6 +154,16 @@ impl ChampionCache {
             .cloned()
     }
 
+    pub fn get_summoner_spell_name(&self, id: i64) -> Option<String> {
+        let guard = self.data.lock().ok()?;
+        let data = guard.as_ref()?;
+
+        data.summoner_spells
+            .values()
+            .find(|spell| spell.key == id)
+            .map(|spell| spell.name.clone())
+    }
+
     pub fn get_all_champions(&self) -> Vec<Champion> {
         if let Ok(guard) = self.data.lock() {
             if let Some(data) = guard.as_ref() {
@@ -74,6 +173,19 @@ impl ChampionCache {
         vec![]
     }
 
+    /// When the data for `version` was written to disk, in epoch millis.
+    pub fn updated_at(&self, version: &str) -> Option<i64> {
+        let modified = fs::metadata(self.version_path(version))
+            .ok()?
+            .modified()
+            .ok()?;
+        let millis = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_millis();
+        Some(millis as i64)
+    }
+
     pub fn get_version(&self) -> Option<String> {
         if let Ok(guard) = self.data.lock() {
             if let Some(data) = guard.as_ref() {
@@ -104,6 +216,20 @@ pub async fn get_all_champions(
     Ok(cache_guard.get_all_champions())
 }
 
+/// Champion data matching the patch a game was played on, for rendering old matches.
+/// Falls back to the closest cached patch.
+#[tauri::command]
+pub async fn get_champion_data_for_version(
+    cache: State<'_, Mutex<ChampionCache>>,
+    game_version: String,
+) -> Result<Option<ChampionData>, String> {
+    let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+    match resolve_version(&cache_guard.cached_versions(), &game_version) {
+        Some(version) => cache_guard.load_version(&version),
+        None => Ok(None),
+    }
+}
+
 #[tauri::command]
 pub async fn get_champion_version(
     cache: State<'_, Mutex<ChampionCache>>,
@@ -111,3 +237,23 @@ pub async fn get_champion_version(
     let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
     Ok(cache_guard.get_version())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_version_prefers_same_patch() {
+        let cached: Vec<String> = ["14.1.1", "14.3.1", "13.24.1"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+
+        let resolve = |game: &str| resolve_version(&cached, game);
+        assert_eq!(resolve("14.3.567.1234").as_deref(), Some("14.3.1"));
+        // 14.2 isn't cached; the newest older patch is used
+        assert_eq!(resolve("14.2.555.1").as_deref(), Some("14.1.1"));
+        assert_eq!(resolve("13.1.1").as_deref(), Some("13.24.1"));
+        assert_eq!(resolve_version(&[], "14.3.1"), None);
+    }
+}
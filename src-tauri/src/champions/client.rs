@@ -1,3 +1,5 @@
+use super::static_data::{parse_items, parse_runes, StaticData};
+use crate::downloads::{fetch_bytes, DownloadTracker};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -11,6 +13,17 @@ pub struct Champion {
     pub name: String,
     pub title: String,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub info: Option<ChampionInfo>, // Missing from data cached by older builds
+}
+
+/// Data Dragon's 0-10 ratings of a champion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChampionInfo {
+    pub attack: u8,
+    pub defense: u8,
+    pub magic: u8,
+    pub difficulty: u8,
 }
 
 fn deserialize_key<'de, D>(deserializer: D) -> Result<i64, D::Error>
@@ -32,10 +45,20 @@ where
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummonerSpell {
+    pub id: String,
+    #[serde(deserialize_with = "deserialize_key")]
+    pub key: i64,
+    pub name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChampionData {
     pub version: String,
     pub champions: HashMap<String, Champion>,
+    #[serde(default)]
+    pub summoner_spells: HashMap<String, SummonerSpell>,
 }
 
 pub struct RiotApiClient {
@@ -62,18 +85,55 @@ impl RiotApiClient {
         }
     }
 
-    pub async fn fetch_champion_data(&self) -> Result<ChampionData, String> {
-        // First, get the latest version
-        let versions_url = "https://ddragon.leagueoflegends.com/api/versions.json";
-        let versions: Vec<String> = self
-            .client
-            .get(versions_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch versions: {}", e))?
-            .json()
+    async fn fetch_json(
+        &self,
+        url: &str,
+        what: &str,
+        tracker: &mut DownloadTracker,
+    ) -> Result<serde_json::Value, String> {
+        let body = fetch_bytes(&self.client, url, tracker)
             .await
-            .map_err(|e| format!("Failed to parse versions: {}", e))?;
+            .map_err(|e| format!("Failed to fetch {}: {}", what, e))?;
+        serde_json::from_slice(&body).map_err(|e| format!("Failed to parse {} JSON: {}", what, e))
+    }
+
+    /// Every Data Dragon version, newest first.
+    pub async fn fetch_versions(
+        &self,
+        tracker: &mut DownloadTracker,
+    ) -> Result<Vec<String>, String> {
+        let versions_url = "https://ddragon.leagueoflegends.com/api/versions.json";
+        serde_json::from_value(self.fetch_json(versions_url, "versions", tracker).await?)
+            .map_err(|e| format!("Failed to parse versions: {}", e))
+    }
+
+    /// Items and runes of one Data Dragon version.
+    pub async fn fetch_static_data(
+        &self,
+        version: &str,
+        tracker: &mut DownloadTracker,
+    ) -> Result<StaticData, String> {
+        let items_url = format!("{}/{}/data/en_US/item.json", self.base_url, version);
+        let items = self.fetch_json(&items_url, "items", tracker).await?;
+        let runes_url = format!(
+            "{}/{}/data/en_US/runesReforged.json",
+            self.base_url, version
+        );
+        let runes = self.fetch_json(&runes_url, "runes", tracker).await?;
+
+        Ok(StaticData {
+            version: version.to_string(),
+            items: parse_items(&items),
+            runes: parse_runes(&runes),
+        })
+    }
+
+    pub async fn fetch_champion_data(
+        &self,
+        tracker: &mut DownloadTracker,
+    ) -> Result<ChampionData, String> {
+        // First, get the latest version
+        let versions = self.fetch_versions(tracker).await?;
 
         let version = versions
             .first()
@@ -83,15 +143,9 @@ impl RiotApiClient {
         let champions_url = format!("{}/{}/data/en_US/champion.json", self.base_url, version);
 
         // We need to manually deserialize because Champion.key can be string or number
-        let json_value: serde_json::Value = self
-            .client
-            .get(&champions_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch champions: {}", e))?
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse champions JSON: {}", e))?;
+        let json_value = self
+            .fetch_json(&champions_url, "champions", tracker)
+            .await?;
 
         let mut champions = HashMap::new();
         if let Some(data_obj) = json_value.get("data").and_then(|v| v.as_object()) {
@@ -102,20 +156,40 @@ impl RiotApiClient {
             }
         }
 
+        // Fetch summoner spells (same static data set, keyed like champions)
+        let spells_url = format!("{}/{}/data/en_US/summoner.json", self.base_url, version);
+        let spells_value = self
+            .fetch_json(&spells_url, "summoner spells", tracker)
+            .await?;
+
+        let mut summoner_spells = HashMap::new();
+        if let Some(data_obj) = spells_value.get("data").and_then(|v| v.as_object()) {
+            for (spell_id, spell_data) in data_obj {
+                if let Ok(spell) = serde_json::from_value::<SummonerSpell>(spell_data.clone()) {
+                    summoner_spells.insert(spell_id.clone(), spell);
+                }
+            }
+        }
+
         Ok(ChampionData {
             version: version.clone(),
             champions,
+            summoner_spells,
         })
     }
 }
 
 #[tauri::command]
 pub async fn fetch_champion_data(
+    app: tauri::AppHandle,
     api_key: Option<String>,
     cache: tauri::State<'_, std::sync::Mutex<super::cache::ChampionCache>>,
 ) -> Result<ChampionData, String> {
     let client = RiotApiClient::new(api_key);
-    let data = client.fetch_champion_data().await?;
+    let mut tracker = DownloadTracker::start(&app, "champion-data", "Champion data");
+    let result = client.fetch_champion_data(&mut tracker).await;
+    tracker.complete(&result);
+    let data = result?;
 
     // Save to cache
     let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
@@ -0,0 +1,193 @@
+use super::cache::ChampionCache;
+use super::client::Champion;
+use crate::lcu::{api::LcuApi, client::LcuClient, draft::DraftState};
+use crate::model::{ChampionRecommendation, DraftRecommendationModel};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// One tile of the champion select grid, with everything the UI needs to render it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChampionGridEntry {
+    pub champion_id: i64,
+    pub id: String,
+    pub name: String,
+    pub banned: bool,
+    pub picked: bool,
+    pub hovered: bool,
+    pub unavailable: bool, // Banned, picked, or neither owned nor free: cannot be locked by the player
+    pub recommended_rank: Option<usize>, // 1-based position in the current recommendations
+    pub owned: Option<bool>, // None when ownership couldn't be read from the client
+    pub free_to_play: bool, // In this week's free rotation
+}
+
+/// Annotate every champion with its draft status in a single pass.
+pub fn build_champion_grid(
+    champions: &[Champion],
+    draft_state: &DraftState,
+    recommendations: &[ChampionRecommendation],
+    owned: Option<&HashSet<i64>>,
+    free_rotation: &HashSet<i64>,
+) -> Vec<ChampionGridEntry> {
+    let banned: HashSet<i64> = draft_state
+        .teams
+        .iter()
+        .flat_map(|t| t.bans.iter().map(|b| b.champion_id))
+        .collect();
+    let picked: HashSet<i64> = draft_state
+        .teams
+        .iter()
+        .flat_map(|t| t.cells.iter().filter_map(|c| c.champion_id))
+        .collect();
+    let hovered: HashSet<i64> = draft_state
+        .teams
+        .iter()
+        .flat_map(|t| t.cells.iter())
+        .filter(|c| c.champion_id.is_none())
+        .filter_map(|c| c.selected_champion_id)
+        .collect();
+    let ranks: HashMap<i64, usize> = recommendations
+        .iter()
+        .enumerate()
+        .map(|(i, rec)| (rec.champion_id as i64, i + 1))
+        .collect();
+
+    let mut grid: Vec<ChampionGridEntry> = champions
+        .iter()
+        .map(|champion| {
+            let champion_id = champion.key;
+            let is_banned = banned.contains(&champion_id);
+            let is_picked = picked.contains(&champion_id);
+            let is_owned = owned.map(|o| o.contains(&champion_id));
+            let is_free = free_rotation.contains(&champion_id);
+            ChampionGridEntry {
+                champion_id,
+                id: champion.id.clone(),
+                name: champion.name.clone(),
+                banned: is_banned,
+                picked: is_picked,
+                hovered: hovered.contains(&champion_id),
+                unavailable: is_banned || is_picked || (is_owned == Some(false) && !is_free),
+                recommended_rank: ranks.get(&champion_id).copied(),
+                owned: is_owned,
+                free_to_play: is_free,
+            }
+        })
+        .collect();
+
+    grid.sort_by(|a, b| a.name.cmp(&b.name));
+    grid
+}
+
+#[tauri::command]
+pub async fn get_champion_grid(
+    player_role: Option<String>,
+    top_k: Option<usize>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    cache: tauri::State<'_, std::sync::Mutex<ChampionCache>>,
+    model: tauri::State<'_, std::sync::Mutex<Option<Arc<DraftRecommendationModel>>>>,
+) -> Result<Vec<ChampionGridEntry>, String> {
+    let (draft_state, owned, free_rotation) = {
+        let mut client_guard = client.lock().await;
+        let draft_state = client_guard.get_draft_state().await?;
+        // Ownership is a nice-to-have; the grid is still useful without it
+        let owned: Option<HashSet<i64>> = client_guard
+            .get_owned_champion_ids()
+            .await
+            .ok()
+            .map(|ids| ids.into_iter().collect());
+        let free_rotation: HashSet<i64> = client_guard
+            .get_free_rotation()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        (draft_state, owned, free_rotation)
+    };
+
+    let recommendations = {
+        let model_guard = model
+            .lock()
+            .map_err(|e| format!("Failed to lock model state: {:?}", e))?;
+        match model_guard.as_ref() {
+            Some(model) => model
+                .get_recommendations(&draft_state, top_k.unwrap_or(10), player_role.as_deref())
+                .map(|r| r.recommendations)
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    };
+
+    let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let champions = cache_guard.get_all_champions();
+
+    Ok(build_champion_grid(
+        &champions,
+        &draft_state,
+        &recommendations,
+        owned.as_ref(),
+        &free_rotation,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::draft::parse_draft_session;
+
+    fn champion(key: i64, name: &str) -> Champion {
+        Champion {
+            id: name.replace(' ', ""),
+            key,
+            name: name.to_string(),
+            title: String::new(),
+            tags: Vec::new(),
+            info: None,
+        }
+    }
+
+    #[test]
+    fn test_build_champion_grid() {
+        let session = serde_json::json!({
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [{ "cellId": 0, "championId": 266 }, { "cellId": 1, "championPickIntent": 64 }],
+            "theirTeam": [{ "cellId": 5, "championId": 0 }],
+            "actions": [[{ "id": 1, "actorCellId": 5, "championId": 157, "completed": true, "type": "ban" }]]
+        });
+        let state = parse_draft_session(&session).unwrap();
+        let champions = vec![
+            champion(266, "Aatrox"),
+            champion(103, "Ahri"),
+            champion(64, "Lee Sin"),
+            champion(157, "Yasuo"),
+            champion(238, "Zed"),
+            champion(141, "Kayn"),
+        ];
+        let recommendations = vec![ChampionRecommendation {
+            champion_id: 103,
+            score: 0.4,
+            coach: None,
+        }];
+        let owned: HashSet<i64> = [266, 103, 64, 157].into_iter().collect();
+        let free_rotation: HashSet<i64> = [141].into_iter().collect();
+
+        let grid = build_champion_grid(
+            &champions,
+            &state,
+            &recommendations,
+            Some(&owned),
+            &free_rotation,
+        );
+        let by_id = |id: i64| grid.iter().find(|e| e.champion_id == id).unwrap();
+
+        assert!(by_id(266).picked && by_id(266).unavailable);
+        assert!(by_id(157).banned && by_id(157).unavailable);
+        assert!(by_id(64).hovered && !by_id(64).unavailable);
+        assert_eq!(by_id(103).recommended_rank, Some(1));
+        assert_eq!(by_id(238).owned, Some(false));
+        assert!(by_id(238).unavailable);
+        // Free rotation champions are pickable without owning them
+        assert!(by_id(141).free_to_play && !by_id(141).unavailable);
+        assert_eq!(grid[0].name, "Aatrox");
+    }
+}
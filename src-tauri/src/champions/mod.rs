@@ -1,2 +1,6 @@
+pub mod assets;
 pub mod cache;
 pub mod client;
+pub mod grid;
+pub mod similar;
+pub mod static_data;
@@ -0,0 +1,239 @@
+use super::cache::ChampionCache;
+use super::client::Champion;
+use crate::lcu::draft::DraftState;
+use crate::model::DraftRecommendationModel;
+use crate::storage::{MatchStore, POSITIONS};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+const TAG_WEIGHT: f32 = 0.25;
+const DAMAGE_WEIGHT: f32 = 0.15;
+const POSITION_WEIGHT: f32 = 0.25;
+const EMBEDDING_WEIGHT: f32 = 0.35;
+
+/// What similarity is measured on. Anything but tags may be unknown, in which
+/// case that part is left out of the comparison.
+#[derive(Debug, Clone, Default)]
+pub struct ChampionProfile {
+    pub champion_id: i64,
+    pub tags: Vec<String>,
+    pub physical_share: Option<f32>, // Attack rating over attack plus magic
+    pub positions: Option<[f32; 5]>, // Share of stored games per position, in `POSITIONS` order
+    pub embedding: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarChampion {
+    pub champion_id: i64,
+    pub name: String,
+    pub similarity: f32, // 0 to 1
+}
+
+fn tag_similarity(a: &[String], b: &[String]) -> f32 {
+    let shared = a.iter().filter(|tag| b.contains(tag)).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f32 / total as f32
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    (norms > 0.0).then(|| dot / norms)
+}
+
+/// Weighted similarity over the parts both profiles have.
+pub fn similarity(a: &ChampionProfile, b: &ChampionProfile) -> f32 {
+    let mut parts = vec![(tag_similarity(&a.tags, &b.tags), TAG_WEIGHT)];
+    if let (Some(x), Some(y)) = (a.physical_share, b.physical_share) {
+        parts.push((1.0 - (x - y).abs(), DAMAGE_WEIGHT));
+    }
+    if let Some(cos) = a
+        .positions
+        .zip(b.positions)
+        .and_then(|(x, y)| cosine(&x, &y))
+    {
+        parts.push((cos, POSITION_WEIGHT));
+    }
+    if let Some(cos) = a
+        .embedding
+        .as_deref()
+        .zip(b.embedding.as_deref())
+        .and_then(|(x, y)| cosine(x, y))
+    {
+        // Embeddings can point away from each other; map -1..1 onto 0..1
+        parts.push(((cos + 1.0) / 2.0, EMBEDDING_WEIGHT));
+    }
+
+    let weight: f32 = parts.iter().map(|(_, w)| w).sum();
+    parts.iter().map(|(s, w)| s * w).sum::<f32>() / weight
+}
+
+/// The `limit` profiles most similar to `target`, best first, skipping `excluded`.
+pub fn rank_similar<'a>(
+    target: &ChampionProfile,
+    profiles: &'a [ChampionProfile],
+    excluded: &HashSet<i64>,
+    limit: usize,
+) -> Vec<(&'a ChampionProfile, f32)> {
+    let mut ranked: Vec<_> = profiles
+        .iter()
+        .filter(|p| p.champion_id != target.champion_id && !excluded.contains(&p.champion_id))
+        .map(|p| (p, similarity(target, p)))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Share of each champion's stored games per position, over every participant.
+pub(crate) fn position_shares(store: &MatchStore) -> HashMap<i64, [f32; 5]> {
+    let mut counts: HashMap<i64, [u32; 5]> = HashMap::new();
+    for participant in store.matches().flat_map(|m| &m.participants) {
+        let slot = participant
+            .position
+            .as_deref()
+            .and_then(|p| POSITIONS.iter().position(|known| *known == p));
+        if let Some(slot) = slot {
+            counts.entry(participant.champion_id).or_default()[slot] += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(champion_id, counts)| {
+            let total: u32 = counts.iter().sum();
+            (champion_id, counts.map(|c| c as f32 / total as f32))
+        })
+        .collect()
+}
+
+fn build_profiles(
+    champions: &[Champion],
+    positions: &HashMap<i64, [f32; 5]>,
+    model: Option<&DraftRecommendationModel>,
+) -> Vec<ChampionProfile> {
+    champions
+        .iter()
+        .map(|champion| ChampionProfile {
+            champion_id: champion.key,
+            tags: champion.tags.clone(),
+            physical_share: champion
+                .info
+                .as_ref()
+                .filter(|info| info.attack + info.magic > 0)
+                .map(|info| info.attack as f32 / (info.attack + info.magic) as f32),
+            positions: positions.get(&champion.key).copied(),
+            embedding: model
+                .and_then(|m| m.champion_embedding(champion.key))
+                .map(|e| e.to_vec()),
+        })
+        .collect()
+}
+
+/// The `limit` champions most like `champion_id`, using the champion cache, stored
+/// matches and the recommendation model's embeddings, skipping `excluded`.
+pub fn find_similar(
+    app: &AppHandle,
+    champion_id: i64,
+    excluded: &HashSet<i64>,
+    limit: usize,
+) -> Result<Vec<SimilarChampion>, String> {
+    let champions = {
+        let cache = app.state::<Mutex<ChampionCache>>();
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        cache_guard.get_all_champions()
+    };
+    let positions = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        position_shares(&store_guard)
+    };
+    // Not managed until setup has tried to load the model
+    let model = app
+        .try_state::<Mutex<Option<Arc<DraftRecommendationModel>>>>()
+        .and_then(|m| m.lock().ok().and_then(|guard| guard.clone()));
+
+    let profiles = build_profiles(&champions, &positions, model.as_deref());
+    let target = profiles
+        .iter()
+        .find(|p| p.champion_id == champion_id)
+        .ok_or_else(|| format!("Unknown champion {}", champion_id))?;
+
+    let names: HashMap<i64, &str> = champions.iter().map(|c| (c.key, c.name.as_str())).collect();
+    let similar = rank_similar(target, &profiles, excluded, limit)
+        .into_iter()
+        .map(|(profile, similarity)| SimilarChampion {
+            champion_id: profile.champion_id,
+            name: names
+                .get(&profile.champion_id)
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            similarity,
+        })
+        .collect();
+    Ok(similar)
+}
+
+/// Champions that play most like `champion_id`, for "if X is banned, try Y".
+/// With a draft state, champions already banned or picked are left out.
+#[tauri::command]
+pub async fn get_similar_champions(
+    app: AppHandle,
+    champion_id: i64,
+    limit: Option<usize>,
+    draft_state: Option<DraftState>,
+) -> Result<Vec<SimilarChampion>, String> {
+    let excluded = draft_state
+        .map(|state| state.unavailable_champions())
+        .unwrap_or_default();
+    find_similar(&app, champion_id, &excluded, limit.unwrap_or(5))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(
+        champion_id: i64,
+        tags: &[&str],
+        physical: f32,
+        embedding: Option<Vec<f32>>,
+    ) -> ChampionProfile {
+        ChampionProfile {
+            champion_id,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            physical_share: Some(physical),
+            positions: None,
+            embedding,
+        }
+    }
+
+    #[test]
+    fn test_rank_similar_uses_available_signals() {
+        let target = profile(92, &["Fighter", "Assassin"], 0.8, Some(vec![1.0, 0.0]));
+        let profiles = vec![
+            target.clone(),
+            profile(266, &["Fighter", "Tank"], 0.8, Some(vec![0.9, 0.1])),
+            profile(103, &["Mage", "Assassin"], 0.2, Some(vec![0.0, 1.0])),
+            profile(39, &["Fighter", "Assassin"], 0.8, None),
+        ];
+
+        let ranked = rank_similar(&target, &profiles, &HashSet::new(), 3);
+        let ids: Vec<i64> = ranked.iter().map(|(p, _)| p.champion_id).collect();
+        // 39 matches on everything it has; no embedding doesn't count against it
+        assert_eq!(ids, vec![39, 266, 103]);
+        assert!((ranked[0].1 - 1.0).abs() < 1e-6);
+
+        let ranked = rank_similar(&target, &profiles, &HashSet::from([39]), 1);
+        assert_eq!(ranked[0].0.champion_id, 266);
+    }
+}
@@ -0,0 +1,178 @@
+use super::cache::same_patch;
+use super::client::RiotApiClient;
+use crate::downloads::DownloadTracker;
+use crate::storage::usage::champion_data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaticEntry {
+    pub name: String,
+    pub icon: String, // Item: file under `img/item/`; rune: path under `img/`
+}
+
+/// Items and runes as they were on one patch, so old games show the build they had.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaticData {
+    pub version: String,
+    pub items: HashMap<i64, StaticEntry>,
+    pub runes: HashMap<i64, StaticEntry>, // Rune styles and individual runes
+}
+
+/// Parse Data Dragon's `item.json`, keyed by item id.
+pub fn parse_items(json_value: &serde_json::Value) -> HashMap<i64, StaticEntry> {
+    let Some(data) = json_value["data"].as_object() else {
+        return HashMap::new();
+    };
+    data.iter()
+        .filter_map(|(id, item)| {
+            Some((
+                id.parse().ok()?,
+                StaticEntry {
+                    name: item["name"].as_str()?.to_string(),
+                    icon: item["image"]["full"].as_str().unwrap_or("").to_string(),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Parse Data Dragon's `runesReforged.json`: styles and every rune in their slots.
+pub fn parse_runes(json_value: &serde_json::Value) -> HashMap<i64, StaticEntry> {
+    let entry = |rune: &serde_json::Value| {
+        Some((
+            rune["id"].as_i64()?,
+            StaticEntry {
+                name: rune["name"].as_str()?.to_string(),
+                icon: rune["icon"].as_str().unwrap_or("").to_string(),
+            },
+        ))
+    };
+
+    let mut runes = HashMap::new();
+    for style in json_value.as_array().into_iter().flatten() {
+        runes.extend(entry(style));
+        for slot in style["slots"].as_array().into_iter().flatten() {
+            runes.extend(
+                slot["runes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(entry),
+            );
+        }
+    }
+    runes
+}
+
+fn static_path(version: &str) -> Result<PathBuf, String> {
+    Ok(champion_data_dir()?.join(version).join("static.json"))
+}
+
+/// Cached static data for `version`, or for any cached version of the same patch.
+fn load_cached(version: &str) -> Option<StaticData> {
+    let dir = champion_data_dir().ok()?;
+    let mut candidates = vec![version.to_string()];
+    if let Ok(entries) = fs::read_dir(&dir) {
+        candidates.extend(
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|v| same_patch(v, version)),
+        );
+    }
+
+    candidates.iter().find_map(|v| {
+        let contents = fs::read_to_string(static_path(v).ok()?).ok()?;
+        serde_json::from_str(&contents).ok()
+    })
+}
+
+fn save_cached(data: &StaticData) -> Result<(), String> {
+    let path = static_path(&data.version)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    }
+    let json = serde_json::to_string(data)
+        .map_err(|e| format!("Failed to serialize static data: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write static data: {}", e))
+}
+
+/// The Data Dragon version for `version`, which may already be one or may be a
+/// client game version such as "14.3.567.1234".
+pub fn ddragon_version(versions: &[String], version: &str) -> Option<String> {
+    versions
+        .iter()
+        .find(|v| v.as_str() == version)
+        .or_else(|| versions.iter().find(|v| same_patch(v, version)))
+        .cloned()
+}
+
+async fn fetch_static_data(
+    client: &RiotApiClient,
+    version: &str,
+    tracker: &mut DownloadTracker,
+) -> Result<StaticData, String> {
+    let versions = client.fetch_versions(tracker).await?;
+    let ddragon = ddragon_version(&versions, version)
+        .ok_or_else(|| format!("No Data Dragon version for {}", version))?;
+    let data = client.fetch_static_data(&ddragon, tracker).await?;
+    save_cached(&data)?;
+    Ok(data)
+}
+
+/// Items and runes for a patch, fetched from Data Dragon the first time a
+/// version is asked for and served from disk afterwards.
+#[tauri::command]
+pub async fn get_static_data(app: tauri::AppHandle, version: String) -> Result<StaticData, String> {
+    if let Some(data) = load_cached(&version) {
+        return Ok(data);
+    }
+
+    let client = RiotApiClient::new(None);
+    let mut tracker = DownloadTracker::start(
+        &app,
+        &format!("static-data-{}", version),
+        &format!("Item and rune data ({})", version),
+    );
+    let result = fetch_static_data(&client, &version, &mut tracker).await;
+    tracker.complete(&result);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_static_data_and_versions() {
+        let items = parse_items(&json!({
+            "data": { "3031": { "name": "Infinity Edge", "image": { "full": "3031.png" } } }
+        }));
+        assert_eq!(items[&3031].icon, "3031.png");
+
+        let runes = parse_runes(&json!([{
+            "id": 8000, "name": "Precision", "icon": "perk-images/Styles/7201_Precision.png",
+            "slots": [{ "runes": [{ "id": 8005, "name": "Press the Attack", "icon": "pta.png" }] }]
+        }]));
+        assert_eq!(runes.len(), 2);
+        assert_eq!(runes[&8005].name, "Press the Attack");
+
+        let versions: Vec<String> = ["14.4.1", "14.3.1", "14.2.1"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            ddragon_version(&versions, "14.3.567.1234").as_deref(),
+            Some("14.3.1")
+        );
+        assert_eq!(
+            ddragon_version(&versions, "14.2.1").as_deref(),
+            Some("14.2.1")
+        );
+        assert_eq!(ddragon_version(&versions, "9.1.1"), None);
+    }
+}
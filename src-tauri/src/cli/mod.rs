@@ -0,0 +1,247 @@
+use crate::lcu::draft::{parse_draft_session, DraftState};
+use crate::model::{DraftRecommendationModel, SessionOptions};
+use crate::storage::{MatchStore, POSITIONS};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage:
+  trackimo recommend --draft <file> [--role <role>] [--top <n>] [--model <dir>]
+  trackimo export --matches [--out <file>]
+
+recommend  Print draft recommendations as JSON. The draft file is a saved
+           champ select session or a parsed draft state. --model points at a
+           folder with model.onnx and metadata.json (default: ./model, then
+           the model folder next to the executable).
+export     Print the stored match history as JSON, or write it to --out.
+
+Without a command the app starts normally.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Recommend {
+        draft: PathBuf,
+        role: Option<String>,
+        top_k: usize,
+        model_dir: Option<PathBuf>,
+    },
+    ExportMatches {
+        out: Option<PathBuf>,
+    },
+    Help,
+}
+
+/// Accept the usual short lane names as well as the model's position names.
+fn normalize_role(role: &str) -> Result<String, String> {
+    let role = role.to_uppercase();
+    let position = match role.as_str() {
+        "MID" => "MIDDLE",
+        "JG" | "JUNGLER" => "JUNGLE",
+        "BOT" | "ADC" => "BOTTOM",
+        "SUP" | "SUPP" | "SUPPORT" => "UTILITY",
+        other => other,
+    };
+    if POSITIONS.contains(&position) {
+        Ok(position.to_string())
+    } else {
+        Err(format!("Unknown role {}", role))
+    }
+}
+
+/// Read the command from the arguments after the program name. `None` when
+/// the first argument isn't a command, so the window opens as usual.
+pub fn parse_args(args: &[String]) -> Result<Option<Command>, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(None);
+    };
+    // Anything that isn't a command goes to the app untouched, options included
+    let known: &[&str] = match command.as_str() {
+        "recommend" => &["--draft", "--role", "--top", "--model"],
+        "export" => &["--matches", "--out"],
+        "help" | "--help" | "-h" => return Ok(Some(Command::Help)),
+        _ => return Ok(None),
+    };
+
+    let mut options: Vec<(&str, Option<&str>)> = Vec::new();
+    let mut iter = rest.iter().peekable();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            return Err(format!("Unexpected argument {}", arg));
+        }
+        let value = iter.next_if(|next| !next.starts_with("--"));
+        options.push((arg.as_str(), value.map(String::as_str)));
+    }
+    let value = |name: &str| -> Result<Option<&str>, String> {
+        match options.iter().find(|(option, _)| *option == name) {
+            Some((_, Some(value))) => Ok(Some(value)),
+            Some((_, None)) => Err(format!("{} needs a value", name)),
+            None => Ok(None),
+        }
+    };
+    if let Some((option, _)) = options.iter().find(|(option, _)| !known.contains(option)) {
+        return Err(format!("Unknown option {}", option));
+    }
+
+    match command.as_str() {
+        "recommend" => {
+            let draft = value("--draft")?.ok_or("recommend needs --draft <file>")?;
+            let top_k = match value("--top")? {
+                Some(n) => n
+                    .parse()
+                    .map_err(|_| format!("--top must be a number, got {}", n))?,
+                None => 5,
+            };
+            Ok(Some(Command::Recommend {
+                draft: PathBuf::from(draft),
+                role: value("--role")?.map(normalize_role).transpose()?,
+                top_k,
+                model_dir: value("--model")?.map(PathBuf::from),
+            }))
+        }
+        "export" => {
+            if !options.iter().any(|(option, _)| *option == "--matches") {
+                return Err("export needs --matches".to_string());
+            }
+            Ok(Some(Command::ExportMatches {
+                out: value("--out")?.map(PathBuf::from),
+            }))
+        }
+        _ => unreachable!("commands are matched above"),
+    }
+}
+
+/// Release builds on Windows are GUI programs with no console of their own, so
+/// borrow the one of the shell that started us before printing anything.
+#[cfg(windows)]
+pub fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // Fails when there is no parent console or one is attached already; either
+    // way there is nothing better to print to
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn attach_console() {}
+
+/// A saved LCU session like the ones in `fixtures/draft`, or a `DraftState`
+/// as the app emits it.
+fn read_draft(path: &Path) -> Result<DraftState, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    match serde_json::from_value::<DraftState>(json.clone()) {
+        Ok(state) => Ok(state),
+        Err(_) => parse_draft_session(&json),
+    }
+}
+
+fn model_dir(requested: Option<PathBuf>) -> Result<PathBuf, String> {
+    if let Some(dir) = requested {
+        return Ok(dir);
+    }
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.join("model")));
+    std::iter::once(PathBuf::from("model"))
+        .chain(exe_dir)
+        .find(|dir| dir.join("model.onnx").exists() && dir.join("metadata.json").exists())
+        .ok_or_else(|| "Model files not found; pass --model <dir>".to_string())
+}
+
+fn write_output(json: &str, out: Option<&Path>) -> Result<(), String> {
+    match out {
+        Some(path) => {
+            fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        }
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
+
+pub fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Recommend {
+            draft,
+            role,
+            top_k,
+            model_dir: requested,
+        } => {
+            let draft = read_draft(&draft)?;
+            let dir = model_dir(requested)?;
+            let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+            let model = DraftRecommendationModel::new(
+                &path("model.onnx"),
+                &path("metadata.json"),
+                &SessionOptions::default(),
+            )
+            .map_err(|e| format!("Failed to load draft model: {}", e))?;
+            let recommendations = model
+                .get_recommendations(&draft, top_k, role.as_deref())
+                .map_err(|e| e.to_string())?;
+            let json = serde_json::to_string_pretty(&recommendations)
+                .map_err(|e| format!("Failed to serialize recommendations: {}", e))?;
+            write_output(&json, None)
+        }
+        Command::ExportMatches { out } => {
+            let mut store = MatchStore::new()?;
+            store.load()?;
+            let matches: Vec<_> = store.matches().collect();
+            let json = serde_json::to_string_pretty(&matches)
+                .map_err(|e| format!("Failed to serialize matches: {}", e))?;
+            write_output(&json, out.as_deref())
+        }
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Command>, String> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]), Ok(None));
+        assert_eq!(
+            parse(&["-psn_0_12345"]),
+            Ok(None),
+            "unknown args open the app"
+        );
+        assert_eq!(parse(&["trackimo://join", "extra", "--flag"]), Ok(None));
+        assert_eq!(
+            parse(&["recommend", "--draft", "d.json", "--role", "mid"]),
+            Ok(Some(Command::Recommend {
+                draft: PathBuf::from("d.json"),
+                role: Some("MIDDLE".to_string()),
+                top_k: 5,
+                model_dir: None,
+            }))
+        );
+        assert_eq!(
+            parse(&["export", "--matches", "--out", "m.json"]),
+            Ok(Some(Command::ExportMatches {
+                out: Some(PathBuf::from("m.json"))
+            }))
+        );
+        assert!(parse(&["recommend", "--role", "MID"]).is_err());
+        assert!(parse(&["recommend", "--draft", "d.json", "--role", "CARRY"]).is_err());
+        assert!(parse(&["recommend", "--draft", "d.json", "--top"]).is_err());
+        assert!(parse(&["export"]).is_err());
+    }
+}
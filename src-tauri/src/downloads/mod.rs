@@ -0,0 +1,248 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Error returned by a download that was stopped through `cancel_download`.
+pub const CANCELLED: &str = "Download cancelled";
+
+/// Emit at most once per this many bytes, plus on start and completion.
+const EMIT_EVERY_BYTES: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    Started,
+    Downloading,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+/// Payload of the `download-progress` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub id: String,
+    pub label: String,
+    pub bytes: u64,
+    pub total: Option<u64>, // None while the size of at least one file is unknown
+    pub phase: DownloadPhase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_done: Option<u64>, // Set by batch downloads whose byte total isn't known upfront
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_total: Option<u64>,
+}
+
+/// Cancellation flags of running downloads, keyed by download id.
+#[derive(Default)]
+pub struct DownloadRegistry {
+    running: HashMap<String, Arc<AtomicBool>>,
+}
+
+impl DownloadRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&mut self, id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.running.insert(id.to_string(), flag.clone());
+        flag
+    }
+
+    fn unregister(&mut self, id: &str) {
+        self.running.remove(id);
+    }
+
+    /// Ids of the downloads still running, sorted.
+    pub fn running(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.running.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Ask a running download to stop. Returns false if no such download is running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.running.get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn should_emit(last_emitted: u64, bytes: u64, total: Option<u64>) -> bool {
+    bytes - last_emitted >= EMIT_EVERY_BYTES || total == Some(bytes)
+}
+
+/// Progress reporting for one logical download, which may span several files.
+/// Emits `download-progress` as bytes arrive and once more when completed.
+pub struct DownloadTracker {
+    app: AppHandle,
+    progress: DownloadProgress,
+    cancelled: Arc<AtomicBool>,
+    size_known: bool,
+    last_emitted: u64,
+}
+
+impl DownloadTracker {
+    pub fn start(app: &AppHandle, id: &str, label: &str) -> Self {
+        let cancelled = match app.try_state::<Mutex<DownloadRegistry>>() {
+            Some(registry) => match registry.lock() {
+                Ok(mut guard) => guard.register(id),
+                Err(_) => Arc::new(AtomicBool::new(false)),
+            },
+            None => Arc::new(AtomicBool::new(false)),
+        };
+
+        let tracker = Self {
+            app: app.clone(),
+            progress: DownloadProgress {
+                id: id.to_string(),
+                label: label.to_string(),
+                bytes: 0,
+                total: Some(0),
+                phase: DownloadPhase::Started,
+                files_done: None,
+                files_total: None,
+            },
+            cancelled,
+            size_known: true,
+            last_emitted: 0,
+        };
+        tracker.emit();
+        tracker
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Announce another file of `size` bytes (None if the server didn't say).
+    pub fn add_file(&mut self, size: Option<u64>) {
+        match size {
+            Some(size) if self.size_known => {
+                self.progress.total = Some(self.progress.total.unwrap_or(0) + size);
+            }
+            _ => {
+                self.size_known = false;
+                self.progress.total = None;
+            }
+        }
+    }
+
+    pub fn advance(&mut self, bytes: u64) {
+        self.progress.bytes += bytes;
+        self.progress.phase = DownloadPhase::Downloading;
+        if should_emit(self.last_emitted, self.progress.bytes, self.progress.total) {
+            self.last_emitted = self.progress.bytes;
+            self.emit();
+        }
+    }
+
+    /// Count progress in files for a batch of `total` files.
+    pub fn set_file_count(&mut self, total: u64) {
+        self.progress.total = None;
+        self.progress.files_done = Some(0);
+        self.progress.files_total = Some(total);
+        self.emit();
+    }
+
+    /// Record one finished file of a batch and its size.
+    pub fn file_done(&mut self, bytes: u64) {
+        self.progress.bytes += bytes;
+        self.progress.phase = DownloadPhase::Downloading;
+        self.progress.files_done = Some(self.progress.files_done.unwrap_or(0) + 1);
+        self.emit();
+    }
+
+    /// Emit the final phase for `result` and stop tracking the download.
+    pub fn complete<T>(mut self, result: &Result<T, String>) {
+        self.progress.phase = match result {
+            Ok(_) => DownloadPhase::Finished,
+            Err(_) if self.is_cancelled() => DownloadPhase::Cancelled,
+            Err(_) => DownloadPhase::Failed,
+        };
+        self.emit();
+
+        if let Some(registry) = self.app.try_state::<Mutex<DownloadRegistry>>() {
+            if let Ok(mut guard) = registry.lock() {
+                guard.unregister(&self.progress.id);
+            }
+        }
+    }
+
+    fn emit(&self) {
+        let _ = self.app.emit("download-progress", &self.progress);
+    }
+}
+
+/// GET `url` into memory, reporting every chunk to `tracker`. Stops with
+/// [`CANCELLED`] as soon as the download is cancelled.
+pub async fn fetch_bytes(
+    client: &reqwest::Client,
+    url: &str,
+    tracker: &mut DownloadTracker,
+) -> Result<Vec<u8>, String> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    tracker.add_file(response.content_length());
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download interrupted: {}", e))?
+    {
+        if tracker.is_cancelled() {
+            return Err(CANCELLED.to_string());
+        }
+        body.extend_from_slice(&chunk);
+        tracker.advance(chunk.len() as u64);
+    }
+
+    Ok(body)
+}
+
+#[tauri::command]
+pub async fn cancel_download(
+    id: String,
+    downloads: tauri::State<'_, Mutex<DownloadRegistry>>,
+) -> Result<bool, String> {
+    let downloads_guard = downloads.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(downloads_guard.cancel(&id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_and_emit_throttle() {
+        let mut registry = DownloadRegistry::new();
+        let flag = registry.register("champions");
+        assert!(registry.cancel("champions"));
+        assert!(flag.load(Ordering::SeqCst));
+        registry.unregister("champions");
+        assert!(!registry.cancel("champions"));
+
+        assert!(!should_emit(0, 1024, None));
+        assert!(should_emit(0, EMIT_EVERY_BYTES, None));
+        // The last chunk is always reported so the UI reaches 100%
+        assert!(should_emit(
+            EMIT_EVERY_BYTES,
+            EMIT_EVERY_BYTES + 10,
+            Some(EMIT_EVERY_BYTES + 10)
+        ));
+    }
+}
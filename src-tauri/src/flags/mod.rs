@@ -0,0 +1,208 @@
+use crate::settings::SettingsStore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::time::Duration;
+
+/// Features that ship disabled until they're proven. Each can be switched on
+/// per user from settings, or for everyone from the remote flags file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    AutoHover,
+    GpuInference,
+}
+
+impl Flag {
+    pub const ALL: [Flag; 2] = [Flag::AutoHover, Flag::GpuInference];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Flag::AutoHover => "auto_hover",
+            Flag::GpuInference => "gpu_inference",
+        }
+    }
+
+    fn default_enabled(self) -> bool {
+        match self {
+            Flag::AutoHover | Flag::GpuInference => false,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Flag::AutoHover => "Hover the top recommendation when an idle pick turn is running out",
+            Flag::GpuInference => "Run the draft model on the GPU when one is available",
+        }
+    }
+}
+
+/// Overrides from the remote flags file, fetched at startup.
+#[derive(Default)]
+pub struct RemoteFlags {
+    flags: BTreeMap<String, bool>,
+}
+
+impl RemoteFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    Default,
+    Remote,
+    User,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagState {
+    pub key: String,
+    pub description: String,
+    pub enabled: bool,
+    pub source: FlagSource,
+}
+
+/// The user's setting wins over the remote file, which wins over the default.
+pub fn resolve(
+    flag: Flag,
+    user: &BTreeMap<String, bool>,
+    remote: &BTreeMap<String, bool>,
+) -> (bool, FlagSource) {
+    if let Some(enabled) = user.get(flag.key()) {
+        (*enabled, FlagSource::User)
+    } else if let Some(enabled) = remote.get(flag.key()) {
+        (*enabled, FlagSource::Remote)
+    } else {
+        (flag.default_enabled(), FlagSource::Default)
+    }
+}
+
+fn user_overrides(app: &AppHandle) -> BTreeMap<String, bool> {
+    app.try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().feature_flags.clone()))
+        .unwrap_or_default()
+}
+
+fn remote_overrides(app: &AppHandle) -> BTreeMap<String, bool> {
+    app.try_state::<Mutex<RemoteFlags>>()
+        .and_then(|r| r.lock().ok().map(|g| g.flags.clone()))
+        .unwrap_or_default()
+}
+
+pub fn is_enabled(app: &AppHandle, flag: Flag) -> bool {
+    resolve(flag, &user_overrides(app), &remote_overrides(app)).0
+}
+
+/// Fetch the remote flags file (a JSON object of flag key to bool) when one
+/// is configured. Unknown keys are kept so older builds ignore newer flags.
+pub async fn refresh_remote(app: &AppHandle) -> Result<(), String> {
+    let url = app.try_state::<Mutex<SettingsStore>>().and_then(|s| {
+        s.lock()
+            .ok()
+            .and_then(|g| g.get().feature_flags_url.clone())
+    });
+    let Some(url) = url.filter(|u| !u.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    let flags: BTreeMap<String, bool> = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .get(url.trim())
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse feature flags: {}", e))?;
+
+    let remote = app.state::<Mutex<RemoteFlags>>();
+    let mut remote_guard = remote.lock().map_err(|e| format!("Lock error: {}", e))?;
+    remote_guard.flags = flags;
+    Ok(())
+}
+
+fn flag_states(app: &AppHandle) -> Vec<FlagState> {
+    let user = user_overrides(app);
+    let remote = remote_overrides(app);
+    Flag::ALL
+        .iter()
+        .map(|flag| {
+            let (enabled, source) = resolve(*flag, &user, &remote);
+            FlagState {
+                key: flag.key().to_string(),
+                description: flag.description().to_string(),
+                enabled,
+                source,
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_feature_flags(app: AppHandle) -> Result<Vec<FlagState>, String> {
+    Ok(flag_states(&app))
+}
+
+/// Override a flag for this user, or with `None` go back to the remote or
+/// default value.
+#[tauri::command]
+pub async fn set_feature_flag(
+    key: String,
+    enabled: Option<bool>,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<(), String> {
+    if !Flag::ALL.iter().any(|f| f.key() == key) {
+        return Err(format!("Unknown feature flag {}", key));
+    }
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut updated = settings_guard.get().clone();
+    match enabled {
+        Some(enabled) => updated.feature_flags.insert(key, enabled),
+        None => updated.feature_flags.remove(&key),
+    };
+    settings_guard.set(updated)
+}
+
+#[tauri::command]
+pub async fn refresh_feature_flags(app: AppHandle) -> Result<Vec<FlagState>, String> {
+    refresh_remote(&app).await?;
+    Ok(flag_states(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_precedence() {
+        let mut user = BTreeMap::new();
+        let mut remote = BTreeMap::new();
+        assert_eq!(
+            resolve(Flag::AutoHover, &user, &remote),
+            (false, FlagSource::Default)
+        );
+
+        remote.insert("auto_hover".to_string(), true);
+        assert_eq!(
+            resolve(Flag::AutoHover, &user, &remote),
+            (true, FlagSource::Remote)
+        );
+
+        user.insert("auto_hover".to_string(), false);
+        assert_eq!(
+            resolve(Flag::AutoHover, &user, &remote),
+            (false, FlagSource::User)
+        );
+        assert_eq!(
+            resolve(Flag::GpuInference, &user, &remote),
+            (false, FlagSource::Default)
+        );
+    }
+}
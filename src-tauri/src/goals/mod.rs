@@ -0,0 +1,363 @@
+use crate::analytics::now_millis;
+use crate::analytics::split::rank_value;
+use crate::storage::snapshots::{RankSnapshot, RankSnapshotStore};
+use crate::storage::usage::data_dir;
+use crate::storage::{MatchStore, StoredMatch, StoredParticipant};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// What a goal measures. Match-based goals only count games inside the goal's window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GoalKind {
+    /// Reach a tier (and optionally division) in a ranked queue, e.g. "reach Platinum".
+    ReachRank {
+        queue_type: String,
+        tier: String,
+        division: Option<String>,
+    },
+    /// Play a number of games, optionally restricted to a role and/or queue.
+    GamesPlayed {
+        count: u32,
+        role: Option<String>,
+        queue_id: Option<i32>,
+    },
+    /// Average at most `max_deaths` per game over at least `min_games` games.
+    MaxAverageDeaths { max_deaths: f64, min_games: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub id: String,
+    pub title: String,
+    pub kind: GoalKind,
+    pub window_start: i64, // Epoch millis; defaults to creation time
+    pub window_end: Option<i64>,
+    pub completed_at: Option<i64>,
+}
+
+impl Goal {
+    fn in_window(&self, timestamp: i64) -> bool {
+        timestamp >= self.window_start && self.window_end.is_none_or(|end| timestamp <= end)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub current: f64,
+    pub target: f64,
+    pub completed: bool,
+}
+
+/// Measure one goal against stored matches and ranked history.
+pub fn evaluate_goal(
+    goal: &Goal,
+    matches: &[&StoredMatch],
+    snapshots: &[&RankSnapshot],
+) -> GoalProgress {
+    let owner_games: Vec<(&StoredMatch, &StoredParticipant)> = matches
+        .iter()
+        .filter(|m| goal.in_window(m.game_creation))
+        .filter_map(|m| m.owner().map(|o| (*m, o)))
+        .collect();
+
+    let (current, target, completed) = match &goal.kind {
+        GoalKind::ReachRank {
+            queue_type,
+            tier,
+            division,
+        } => {
+            let target = rank_value(tier, division.as_deref().unwrap_or("IV"), 0).unwrap_or(0);
+            let best = snapshots
+                .iter()
+                .filter(|s| &s.queue_type == queue_type && goal.in_window(s.timestamp))
+                .filter_map(|s| rank_value(&s.tier, &s.rank, s.league_points))
+                .max()
+                .unwrap_or(0);
+            (best as f64, target as f64, best >= target)
+        }
+        GoalKind::GamesPlayed {
+            count,
+            role,
+            queue_id,
+        } => {
+            let played = owner_games
+                .iter()
+                .filter(|(m, _)| queue_id.is_none_or(|q| q == m.queue_id))
+                .filter(|(_, o)| {
+                    role.as_deref().is_none_or(|r| {
+                        o.position
+                            .as_deref()
+                            .is_some_and(|p| p.eq_ignore_ascii_case(r))
+                    })
+                })
+                .count();
+            (played as f64, *count as f64, played as u32 >= *count)
+        }
+        GoalKind::MaxAverageDeaths {
+            max_deaths,
+            min_games,
+        } => {
+            let games = owner_games.len();
+            let average = if games == 0 {
+                0.0
+            } else {
+                owner_games
+                    .iter()
+                    .map(|(_, o)| o.deaths as f64)
+                    .sum::<f64>()
+                    / games as f64
+            };
+            let completed = games as u32 >= *min_games && average <= *max_deaths;
+            (average, *max_deaths, completed)
+        }
+    };
+
+    GoalProgress {
+        goal: goal.clone(),
+        current,
+        target,
+        completed: goal.completed_at.is_some() || completed,
+    }
+}
+
+pub struct GoalStore {
+    goals: Vec<Goal>,
+    store_path: PathBuf,
+}
+
+impl GoalStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("goals.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            goals: Vec::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read goals: {}", e))?;
+        self.goals =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse goals: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.goals)
+            .map_err(|e| format!("Failed to serialize goals: {}", e))?;
+
+        fs::write(&self.store_path, json).map_err(|e| format!("Failed to write goals: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn add(&mut self, goal: Goal) {
+        self.goals.push(goal);
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.goals.len();
+        self.goals.retain(|g| g.id != id);
+        self.goals.len() != before
+    }
+
+    /// Evaluate every goal, stamping newly completed ones. Returns progress for
+    /// all goals and, separately, the ones that completed just now.
+    pub fn evaluate(
+        &mut self,
+        matches: &[&StoredMatch],
+        snapshots: &[&RankSnapshot],
+        now: i64,
+    ) -> (Vec<GoalProgress>, Vec<GoalProgress>) {
+        let mut all = Vec::new();
+        let mut newly_completed = Vec::new();
+
+        for goal in &mut self.goals {
+            let mut progress = evaluate_goal(goal, matches, snapshots);
+            if progress.completed && goal.completed_at.is_none() {
+                goal.completed_at = Some(now);
+                progress.goal.completed_at = Some(now);
+                newly_completed.push(progress.clone());
+            }
+            all.push(progress);
+        }
+
+        (all, newly_completed)
+    }
+}
+
+fn evaluate_all(app: &AppHandle) -> Result<(Vec<GoalProgress>, Vec<GoalProgress>), String> {
+    let match_store = app.state::<Mutex<MatchStore>>();
+    let match_guard = match_store
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+    let snapshot_store = app.state::<Mutex<RankSnapshotStore>>();
+    let snapshot_guard = snapshot_store
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+    let goal_store = app.state::<Mutex<GoalStore>>();
+    let mut goal_guard = goal_store
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+
+    let matches: Vec<&StoredMatch> = match_guard.matches().collect();
+    let snapshots = snapshot_guard.between(None, None);
+    let (all, newly_completed) = goal_guard.evaluate(&matches, &snapshots, now_millis());
+    if !newly_completed.is_empty() {
+        goal_guard.save()?;
+    }
+    Ok((all, newly_completed))
+}
+
+/// Post-game hook: re-evaluate goals and emit `goal-completed` for each one that
+/// was just reached.
+pub fn check_goals(app: &AppHandle) -> Result<(), String> {
+    let (_, newly_completed) = evaluate_all(app)?;
+    for progress in &newly_completed {
+        let _ = app.emit("goal-completed", progress);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_goal(
+    title: String,
+    kind: GoalKind,
+    window_start: Option<i64>,
+    window_end: Option<i64>,
+    goals: tauri::State<'_, Mutex<GoalStore>>,
+) -> Result<Goal, String> {
+    let now = now_millis();
+    let goal = Goal {
+        id: format!("goal-{}", now),
+        title,
+        kind,
+        window_start: window_start.unwrap_or(now),
+        window_end,
+        completed_at: None,
+    };
+
+    let mut goals_guard = goals.lock().map_err(|e| format!("Lock error: {}", e))?;
+    goals_guard.add(goal.clone());
+    goals_guard.save()?;
+    Ok(goal)
+}
+
+#[tauri::command]
+pub async fn remove_goal(
+    id: String,
+    goals: tauri::State<'_, Mutex<GoalStore>>,
+) -> Result<bool, String> {
+    let mut goals_guard = goals.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let removed = goals_guard.remove(&id);
+    if removed {
+        goals_guard.save()?;
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn get_goal_progress(app: AppHandle) -> Result<Vec<GoalProgress>, String> {
+    let (all, newly_completed) = evaluate_all(&app)?;
+    for progress in &newly_completed {
+        let _ = app.emit("goal-completed", progress);
+    }
+    Ok(all)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    fn goal(kind: GoalKind) -> Goal {
+        Goal {
+            id: "g".to_string(),
+            title: String::new(),
+            kind,
+            window_start: 1500,
+            window_end: None,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_match_goals_respect_window_and_filters() {
+        let matches: Vec<StoredMatch> = (1..=4)
+            .map(|id| {
+                let lane = if id % 2 == 0 { "JUNGLE" } else { "TOP" };
+                parse_game_details(
+                    &game_details(id, 420, &[("me", 64, 100, lane, "NONE")]),
+                    "me",
+                )
+                .unwrap()
+            })
+            .collect();
+        let refs: Vec<&StoredMatch> = matches.iter().collect();
+
+        // Game 1 (created at 1000) is before the window
+        let jungle = evaluate_goal(
+            &goal(GoalKind::GamesPlayed {
+                count: 2,
+                role: Some("JUNGLE".to_string()),
+                queue_id: None,
+            }),
+            &refs,
+            &[],
+        );
+        assert_eq!(jungle.current, 2.0);
+        assert!(jungle.completed);
+
+        let deaths = evaluate_goal(
+            &goal(GoalKind::MaxAverageDeaths {
+                max_deaths: 2.0,
+                min_games: 4,
+            }),
+            &refs,
+            &[],
+        );
+        assert_eq!(deaths.current, 2.0);
+        assert!(!deaths.completed);
+    }
+
+    #[test]
+    fn test_completion_is_stamped_once() {
+        let mut store = GoalStore::with_path(PathBuf::from("unused.json"));
+        store.add(goal(GoalKind::ReachRank {
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: "PLATINUM".to_string(),
+            division: None,
+        }));
+        let snapshot = RankSnapshot {
+            timestamp: 2000,
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: "PLATINUM".to_string(),
+            rank: "IV".to_string(),
+            league_points: 0,
+            wins: 0,
+            losses: 0,
+            in_promos: false,
+            change: None,
+        };
+
+        let (_, done) = store.evaluate(&[], &[&snapshot], 3000);
+        assert_eq!(done.len(), 1);
+        let (all, done) = store.evaluate(&[], &[&snapshot], 4000);
+        assert!(done.is_empty());
+        assert_eq!(all[0].goal.completed_at, Some(3000));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::LcuClient;
+use crate::lcu::draft::{DraftAction, DraftState};
+use crate::lcu::error::LcuError;
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How soon a second lock request has to follow the first to confirm it.
+const LOCK_CONFIRM_WINDOW: Duration = Duration::from_secs(2);
+
+/// The champion of the last unconfirmed lock request, and when it came in.
+#[derive(Default)]
+pub struct PendingLock {
+    pending: Option<(i64, Instant)>,
+}
+
+impl PendingLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// What a lock request did.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockOutcome {
+    Locked,
+    AwaitingConfirmation, // Hovered; request again within the window to lock
+    BanPhase,             // Hovered only, since bans are still going
+}
+
+/// Whether a lock request for `champion_id` at `now` confirms the pending one.
+/// An unconfirmed request becomes the new pending one.
+pub fn confirm_lock(pending: &mut Option<(i64, Instant)>, champion_id: i64, now: Instant) -> bool {
+    let confirmed = pending.is_some_and(|(id, at)| {
+        id == champion_id && now.saturating_duration_since(at) <= LOCK_CONFIRM_WINDOW
+    });
+    *pending = (!confirmed).then_some((champion_id, now));
+    confirmed
+}
+
+/// Whether a ban turn is running anywhere in the draft.
+pub fn in_ban_phase(state: &DraftState) -> bool {
+    state
+        .actions
+        .iter()
+        .any(|a| a.action_type == "ban" && a.is_in_progress && !a.completed)
+}
+
+/// Whether an engine acting on its own may lock in on `state`. With the
+/// lock-in guard on, nothing is auto-locked while bans are going.
+pub fn auto_lock_allowed(state: &DraftState, lock_confirmation: bool) -> bool {
+    !(lock_confirmation && in_ban_phase(state))
+}
+
+/// Lock `champion_id` behind the confirmation guard: the first request only
+/// hovers, and nothing is locked while bans are going.
+pub async fn guarded_lock<C: LcuApi>(
+    client: &mut C,
+    champion_id: i64,
+    pending: &mut Option<(i64, Instant)>,
+    now: Instant,
+) -> Result<LockOutcome, LcuError> {
+    let state = client.get_draft_state().await?;
+    if in_ban_phase(&state) {
+        *pending = None;
+        apply_action(client, "pick", champion_id, false).await?;
+        return Ok(LockOutcome::BanPhase);
+    }
+    if !confirm_lock(pending, champion_id, now) {
+        apply_action(client, "pick", champion_id, false).await?;
+        return Ok(LockOutcome::AwaitingConfirmation);
+    }
+    apply_action(client, "pick", champion_id, true).await?;
+    Ok(LockOutcome::Locked)
+}
+
+/// The local player's open action of `action_type`. A pick can be hovered
+/// before its turn comes up, so any pick not completed yet qualifies unless
+/// `in_progress` asks for the current turn only.
+pub fn local_action<'a>(
+    state: &'a DraftState,
+    action_type: &str,
+    in_progress: bool,
+) -> Option<&'a DraftAction> {
+    let local_cell = state.local_player_cell_id?;
+    state.actions.iter().find(|a| {
+        a.action_type == action_type
+            && a.actor_cell_id == Some(local_cell)
+            && !a.completed
+            && (a.is_in_progress || !in_progress)
+    })
+}
+
+/// Hover, lock or ban `champion_id` on the local player's matching action.
+/// Returns the action acted on.
+pub async fn apply_action<C: LcuApi>(
+    client: &mut C,
+    action_type: &str,
+    champion_id: i64,
+    lock: bool,
+) -> Result<i64, LcuError> {
+    let state = client.get_draft_state().await?;
+    let action_id = local_action(&state, action_type, lock)
+        .map(|a| a.id)
+        .ok_or_else(|| LcuError::Other(format!("It isn't your turn to {}", action_type)))?;
+    client
+        .patch_draft_action(action_id, champion_id, lock)
+        .await?;
+    Ok(action_id)
+}
+
+/// Hover a champion on the local player's pick without locking it.
+#[tauri::command]
+pub async fn hover_champion(
+    champion_id: i64,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<(), LcuError> {
+    let mut client_guard = client.lock().await;
+    apply_action(&mut *client_guard, "pick", champion_id, false).await?;
+    Ok(())
+}
+
+/// Lock a champion on the local player's pick. With `lock_confirmation` on,
+/// the request has to be repeated within two seconds.
+#[tauri::command]
+pub async fn lock_champion(
+    champion_id: i64,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    pending_lock: tauri::State<'_, Mutex<PendingLock>>,
+) -> Result<LockOutcome, LcuError> {
+    let guarded = settings
+        .lock()
+        .map(|g| g.get().lock_confirmation)
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+    let mut client_guard = client.lock().await;
+    if !guarded {
+        apply_action(&mut *client_guard, "pick", champion_id, true).await?;
+        return Ok(LockOutcome::Locked);
+    }
+
+    let mut pending = pending_lock
+        .lock()
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?
+        .pending;
+    let outcome = guarded_lock(
+        &mut *client_guard,
+        champion_id,
+        &mut pending,
+        Instant::now(),
+    )
+    .await;
+    if let Ok(mut guard) = pending_lock.lock() {
+        guard.pending = pending;
+    }
+    outcome
+}
+
+#[tauri::command]
+pub async fn ban_champion(
+    champion_id: i64,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<(), LcuError> {
+    let mut client_guard = client.lock().await;
+    apply_action(&mut *client_guard, "ban", champion_id, true).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcuClient, SessionBuilder};
+
+    fn session(ban_in_progress: bool, pick_in_progress: bool) -> serde_json::Value {
+        let mark = |session: SessionBuilder, in_progress: bool| {
+            if in_progress {
+                session.in_progress()
+            } else {
+                session.pending()
+            }
+        };
+        let session = SessionBuilder::new()
+            .local_cell(2)
+            .ally(2, 0, 0)
+            .enemy(7, 0, 0);
+        let session = mark(session.ban(1, 2, 0), ban_in_progress);
+        let session = mark(session.ban(2, 7, 0), ban_in_progress);
+        mark(session.turn().pick(3, 2, 0), pick_in_progress).build()
+    }
+
+    #[tokio::test]
+    async fn actions_target_the_local_players_turn() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(true, false));
+        mock.push_draft_session(session(true, false));
+        mock.push_draft_session(session(false, false));
+
+        assert_eq!(apply_action(&mut mock, "ban", 157, true).await, Ok(1));
+        // The pick isn't up yet but can already be hovered
+        assert_eq!(apply_action(&mut mock, "pick", 64, false).await, Ok(3));
+        assert!(apply_action(&mut mock, "pick", 64, true).await.is_err());
+        assert_eq!(mock.patched_actions, vec![(1, 157, true), (3, 64, false)]);
+    }
+
+    #[tokio::test]
+    async fn guarded_lock_needs_a_quick_second_request() {
+        let mut mock = MockLcuClient::new();
+        let pick_turn = session(false, true);
+        // Each request reads the draft once for the guard and once to act
+        mock.push_draft_session(session(true, false));
+        mock.push_draft_session(session(true, false));
+        for _ in 0..6 {
+            mock.push_draft_session(pick_turn.clone());
+        }
+        let mut pending = None;
+        let start = Instant::now();
+        let mut outcomes = Vec::new();
+        for secs in [0, 0, 3, 4] {
+            let now = start + Duration::from_secs(secs);
+            outcomes.push(
+                guarded_lock(&mut mock, 64, &mut pending, now)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        // The third request came too late and started over
+        assert_eq!(
+            outcomes,
+            vec![
+                LockOutcome::BanPhase,
+                LockOutcome::AwaitingConfirmation,
+                LockOutcome::AwaitingConfirmation,
+                LockOutcome::Locked
+            ]
+        );
+        assert_eq!(mock.patched_actions.last(), Some(&(3, 64, true)));
+        assert_eq!(mock.patched_actions.iter().filter(|a| a.2).count(), 1);
+    }
+}
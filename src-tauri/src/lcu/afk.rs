@@ -0,0 +1,245 @@
+use crate::audio::play_alert;
+use crate::lcu::pool::local_position;
+use crate::lcu::{
+    api::LcuApi,
+    draft::{DraftAction, DraftState},
+    error::LcuError,
+};
+use crate::model::DraftRecommendationModel;
+use crate::settings::AppSettings;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Seconds left at which to nudge when none are configured, first to last.
+pub const DEFAULT_NUDGE_SECONDS: [u32; 2] = [10, 5];
+
+/// Recommendations tried in order when auto-hovering, in case the first
+/// isn't owned.
+const AUTO_HOVER_CANDIDATES: usize = 3;
+
+/// Notification sounds; the last nudge should stand out from the others.
+const NUDGE_SOUND: &str = "Default";
+const NUDGE_SOUND_FINAL: &str = "Reminder";
+
+/// Payload of the `afk-nudge` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AfkNudge {
+    pub level: usize, // 1 for the first nudge of a turn, rising as time runs out
+    pub final_level: bool,
+    pub action_type: String, // "pick" or "ban"
+    pub seconds_left: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AfkStep {
+    Nudge(AfkNudge),
+    AutoHover(i64), // Action id to hover the top recommendation on
+}
+
+/// The local player's action if it's their turn and nothing is hovered.
+fn idle_action(state: &DraftState) -> Option<&DraftAction> {
+    let local_cell = state.local_player_cell_id?;
+    let action = state
+        .actions
+        .iter()
+        .find(|a| a.actor_cell_id == Some(local_cell) && a.is_in_progress && !a.completed)?;
+    let hovered = action.champion_id.is_some()
+        || (action.action_type == "pick"
+            && state
+                .local_cell()
+                .is_some_and(|c| c.selected_champion_id.is_some()));
+    (!hovered).then_some(action)
+}
+
+/// Follows the local player's turns and decides when to nudge or hover, at
+/// most once per threshold per turn.
+#[derive(Default)]
+pub struct AfkWatcher {
+    action_id: Option<i64>,
+    nudged: usize, // Thresholds already passed this turn
+    hovered: bool,
+}
+
+impl AfkWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, state: &DraftState, settings: &AppSettings) -> Vec<AfkStep> {
+        if !settings.afk_nudge_enabled {
+            return Vec::new();
+        }
+        let Some(action) = idle_action(state) else {
+            return Vec::new();
+        };
+        if self.action_id != Some(action.id) {
+            *self = Self {
+                action_id: Some(action.id),
+                ..Self::default()
+            };
+        }
+        let Some(seconds_left) = state.timer else {
+            return Vec::new();
+        };
+
+        let mut thresholds = if settings.afk_nudge_seconds.is_empty() {
+            DEFAULT_NUDGE_SECONDS.to_vec()
+        } else {
+            settings.afk_nudge_seconds.clone()
+        };
+        thresholds.sort_unstable_by(|a, b| b.cmp(a));
+        let mut steps = Vec::new();
+
+        // A slow poll can pass several thresholds at once; nudge once, at the latest
+        let reached = thresholds
+            .iter()
+            .filter(|t| seconds_left <= **t as f64)
+            .count();
+        if reached > self.nudged {
+            self.nudged = reached;
+            steps.push(AfkStep::Nudge(AfkNudge {
+                level: reached,
+                final_level: reached == thresholds.len(),
+                action_type: action.action_type.clone(),
+                seconds_left: seconds_left.max(0.0).ceil() as u32,
+            }));
+        }
+
+        let hover_due = settings
+            .afk_auto_hover_seconds
+            .is_some_and(|s| seconds_left <= s as f64);
+        if hover_due && action.action_type == "pick" && !self.hovered {
+            self.hovered = true;
+            steps.push(AfkStep::AutoHover(action.id));
+        }
+        steps
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Notify and emit `afk-nudge`, more urgently at the last threshold.
+pub fn announce_nudge(app: &AppHandle, nudge: &AfkNudge) {
+    let title = if nudge.final_level {
+        format!("Your {} is about to time out!", nudge.action_type)
+    } else {
+        format!("It's your turn to {}", nudge.action_type)
+    };
+    let sound = if nudge.final_level {
+        NUDGE_SOUND_FINAL
+    } else {
+        NUDGE_SOUND
+    };
+    let mut notification = app
+        .notification()
+        .builder()
+        .title(title)
+        .body(format!("{}s left and nothing hovered", nudge.seconds_left));
+    // Played by the app on the chosen device instead
+    if !play_alert(app, sound) {
+        notification = notification.sound(sound);
+    }
+    let _ = notification.show();
+    let _ = app.emit("afk-nudge", nudge);
+}
+
+/// The model's top picks for the local player's position.
+pub fn top_picks(app: &AppHandle, state: &DraftState) -> Vec<i64> {
+    let Some(model) = app
+        .try_state::<Mutex<Option<Arc<DraftRecommendationModel>>>>()
+        .and_then(|m| m.lock().ok().and_then(|g| g.clone()))
+    else {
+        return Vec::new();
+    };
+    let position = local_position(state);
+    model
+        .get_recommendations(state, AUTO_HOVER_CANDIDATES, position.as_deref())
+        .map(|r| {
+            r.recommendations
+                .iter()
+                .map(|rec| rec.champion_id as i64)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Hover the first candidate the client accepts, if the turn is still open
+/// and still empty. Never locks in. Returns the hovered champion.
+pub async fn auto_hover<C: LcuApi>(
+    client: &tokio::sync::Mutex<C>,
+    action_id: i64,
+    candidates: &[i64],
+) -> Result<Option<i64>, LcuError> {
+    let mut client_guard = client.lock().await;
+    let state = client_guard.get_draft_state().await?;
+    if idle_action(&state).map(|a| a.id) != Some(action_id) {
+        return Ok(None);
+    }
+
+    let mut last_error = None;
+    for champion_id in candidates {
+        match client_guard
+            .patch_draft_action(action_id, *champion_id, false)
+            .await
+        {
+            Ok(()) => return Ok(Some(*champion_id)),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    last_error.map_or(Ok(None), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::{MockLcuClient, SessionBuilder};
+
+    fn session(seconds_left: f64, my_hover: i64) -> SessionBuilder {
+        SessionBuilder::new()
+            .timer_ms(seconds_left * 1000.0)
+            .ally(0, 0, 0)
+            .enemy(5, 0, 0)
+            .pick(7, 0, my_hover)
+            .in_progress()
+    }
+
+    #[test]
+    fn test_nudges_escalate_then_hover() {
+        let settings = AppSettings {
+            afk_nudge_enabled: true,
+            afk_nudge_seconds: vec![5, 15],
+            afk_auto_hover_seconds: Some(3),
+            ..Default::default()
+        };
+        let mut watcher = AfkWatcher::new();
+        let mut at =
+            |seconds: f64, hover: i64| watcher.update(&session(seconds, hover).state(), &settings);
+
+        assert!(at(20.0, 0).is_empty());
+        assert!(matches!(&at(14.5, 0)[..], [AfkStep::Nudge(n)] if n.level == 1 && !n.final_level));
+        assert!(at(12.0, 0).is_empty(), "each threshold nudges once");
+        assert!(
+            at(4.0, 91).is_empty(),
+            "hovering something stops the nudges"
+        );
+        assert!(matches!(&at(4.0, 0)[..], [AfkStep::Nudge(n)] if n.final_level));
+        assert_eq!(at(2.5, 0), vec![AfkStep::AutoHover(7)]);
+        assert!(at(1.0, 0).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auto_hover_skips_taken_turn() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(2.0, 91).build());
+        mock.push_draft_session(session(2.0, 0).build());
+        let client = tokio::sync::Mutex::new(mock);
+
+        assert_eq!(auto_hover(&client, 7, &[64]).await.unwrap(), None);
+        assert_eq!(auto_hover(&client, 7, &[64]).await.unwrap(), Some(64));
+        assert_eq!(client.lock().await.patched_actions, vec![(7, 64, false)]);
+    }
+}
@@ -0,0 +1,121 @@
+use super::client::{ChampionMastery, MatchHistoryGame, RankedSplit, RankedStats, SummonerInfo};
+use super::draft::{draft_state, DraftState};
+use super::dto::{ChampSelectSession, Friend};
+use super::error::LcuError;
+use super::missions::Mission;
+use async_trait::async_trait;
+
+/// Everything the rest of the app needs from the League client.
+///
+/// `LcuClient` talks to the real LCU over HTTPS; `MockLcuClient` serves canned
+/// responses so the monitor and other consumers can be tested without a client.
+#[async_trait]
+pub trait LcuApi: Send {
+    async fn get_gameflow_phase(&mut self) -> Result<String, LcuError>;
+
+    async fn get_draft_session(&mut self) -> Result<ChampSelectSession, LcuError>;
+
+    /// The champ select session as the client sent it, for debugging. Right
+    /// after `get_draft_session` this is the same response, not a new request.
+    async fn get_draft_session_json(&mut self) -> Result<serde_json::Value, LcuError>;
+
+    async fn get_draft_state(&mut self) -> Result<DraftState, LcuError> {
+        let session = self.get_draft_session().await?;
+        Ok(draft_state(&session))
+    }
+
+    async fn get_current_summoner(&mut self) -> Result<SummonerInfo, LcuError>;
+
+    /// Ranked standings for Summoner's Rift, plus TFT/Arena when `include_all_queues` is set.
+    async fn get_ranked_stats(
+        &mut self,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError>;
+
+    async fn get_match_history(&mut self) -> Result<Vec<MatchHistoryGame>, LcuError>;
+
+    async fn get_match_history_paginated(
+        &mut self,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError>;
+
+    /// Ids of the champions the account owns (rentals and free rotation excluded).
+    async fn get_owned_champion_ids(&mut self) -> Result<Vec<i64>, LcuError>;
+
+    /// Ids of this week's free-to-play champions.
+    async fn get_free_rotation(&mut self) -> Result<Vec<i64>, LcuError>;
+
+    /// Resolve a Riot ID to the player's summoner on the current server.
+    async fn lookup_summoner(
+        &mut self,
+        game_name: &str,
+        tag_line: &str,
+    ) -> Result<SummonerInfo, LcuError>;
+
+    async fn get_ranked_stats_for(
+        &mut self,
+        puuid: &str,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError>;
+
+    async fn get_top_masteries(
+        &mut self,
+        summoner_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ChampionMastery>, LcuError>;
+
+    async fn get_match_history_for(
+        &mut self,
+        puuid: &str,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError>;
+
+    /// Full details of one game, including all ten participants.
+    async fn get_game_details(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError>;
+
+    /// Per-minute frames of one game, with each participant's gold and XP.
+    async fn get_game_timeline(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError>;
+
+    /// Stats block of the game that just ended, as shown on the end-of-game screen.
+    async fn get_eog_stats_block(&mut self) -> Result<serde_json::Value, LcuError>;
+
+    async fn get_ranked_split(&mut self) -> Result<RankedSplit, LcuError>;
+
+    /// Owned champions that can still grant a mastery chest this season.
+    async fn get_chest_eligible_champion_ids(&mut self) -> Result<Vec<i64>, LcuError>;
+
+    async fn get_first_win_available(&mut self) -> Result<bool, LcuError>;
+
+    /// Active missions, expired ones excluded.
+    async fn get_missions(&mut self) -> Result<Vec<Mission>, LcuError>;
+
+    /// Queue of the current lobby or game, if any.
+    async fn get_current_queue_id(&mut self) -> Result<Option<i32>, LcuError>;
+
+    /// The local player's lobby role picks, first then second, as upper-case
+    /// positions (or "FILL"). Empty outside role queues.
+    async fn get_position_preferences(&mut self) -> Result<Vec<String>, LcuError>;
+
+    /// Hover a champion on a champ select action, or lock it in when `complete` is set.
+    async fn patch_draft_action(
+        &mut self,
+        action_id: i64,
+        champion_id: i64,
+        complete: bool,
+    ) -> Result<(), LcuError>;
+
+    /// Leave the end-of-game screen and return to the previous lobby.
+    async fn play_again(&mut self) -> Result<(), LcuError>;
+
+    async fn start_matchmaking(&mut self) -> Result<(), LcuError>;
+
+    async fn accept_ready_check(&mut self) -> Result<(), LcuError>;
+
+    /// Friends on the chat list, with their League presence.
+    async fn get_friends(&mut self) -> Result<Vec<Friend>, LcuError>;
+
+    /// Start spectating the live game of the player with `puuid`.
+    async fn spectate(&mut self, puuid: &str) -> Result<(), LcuError>;
+}
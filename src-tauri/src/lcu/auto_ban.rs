@@ -0,0 +1,237 @@
+use crate::lcu::actions::auto_lock_allowed;
+use crate::lcu::auto_draft::candidates;
+use crate::lcu::pool::local_position;
+use crate::lcu::{api::LcuApi, draft::DraftState};
+use crate::settings::AppSettings;
+use std::collections::HashSet;
+
+/// Used when the user enabled auto-ban without choosing a delay.
+pub const DEFAULT_AUTO_BAN_DELAY_MS: u64 = 2000;
+
+/// The local player's ban action if it is their turn and they haven't hovered
+/// anything themselves. Returns the action id.
+fn open_ban_action(state: &DraftState) -> Option<i64> {
+    let local_cell = state.local_player_cell_id?;
+    state
+        .actions
+        .iter()
+        .find(|a| {
+            a.action_type == "ban"
+                && a.actor_cell_id == Some(local_cell)
+                && a.is_in_progress
+                && !a.completed
+                && a.champion_id.is_none()
+        })
+        .map(|a| a.id)
+}
+
+/// Champions that must not be banned: an ally hovers or locked them, an ally is
+/// already hovering them as their own ban, or they are banned or picked anywhere.
+/// The local player's own ban hover doesn't count.
+pub fn ban_protected(state: &DraftState, local_cell: i64) -> HashSet<i64> {
+    let mut protected: HashSet<i64> = HashSet::new();
+    for team in &state.teams {
+        let is_ally_team = team.cells.iter().any(|c| c.cell_id == local_cell);
+        for cell in &team.cells {
+            protected.extend(cell.champion_id);
+            if is_ally_team {
+                protected.extend(cell.selected_champion_id);
+            }
+        }
+        protected.extend(
+            team.bans
+                .iter()
+                .filter(|b| b.completed || b.cell_id != Some(local_cell))
+                .map(|b| b.champion_id),
+        );
+    }
+    for action in &state.actions {
+        let own_hover = !action.completed && action.actor_cell_id == Some(local_cell);
+        if action.completed
+            || (action.is_ally_action == Some(true) && action.action_type == "ban" && !own_hover)
+        {
+            protected.extend(action.champion_id);
+        }
+    }
+    protected
+}
+
+/// Pick the first configured champion that is safe to ban right now.
+pub fn choose_auto_ban(state: &DraftState, candidates: &[i64]) -> Option<(i64, i64)> {
+    let action_id = open_ban_action(state)?;
+    let protected = ban_protected(state, state.local_player_cell_id?);
+
+    candidates
+        .iter()
+        .find(|id| !protected.contains(id))
+        .map(|id| (action_id, *id))
+}
+
+/// Champions to try on the local player's ban turn: the auto-ban list, then the
+/// auto-draft ban rules for their position. Empty when neither is enabled.
+pub fn ban_candidates(state: &DraftState, settings: &AppSettings) -> Vec<i64> {
+    let mut ids = Vec::new();
+    if settings.auto_ban_enabled {
+        ids.extend(&settings.auto_ban_champion_ids);
+    }
+    if settings.auto_draft_enabled {
+        let position = local_position(state);
+        for id in candidates(&settings.auto_draft_bans, position.as_deref()) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
+/// Remembers which ban actions were already handed to auto-ban, so each turn is
+/// acted on at most once even though the state is seen many times. The only
+/// engine that bans: auto-draft hands its ban rules over through
+/// `ban_candidates`.
+#[derive(Default)]
+pub struct AutoBanner {
+    scheduled: HashSet<i64>,
+}
+
+impl AutoBanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ban action to schedule for this state, if there is something to ban
+    /// and a new ban turn just started.
+    pub fn should_schedule(&mut self, state: &DraftState, settings: &AppSettings) -> Option<i64> {
+        if ban_candidates(state, settings).is_empty() {
+            return None;
+        }
+        let action_id = open_ban_action(state)?;
+        self.scheduled.insert(action_id).then_some(action_id)
+    }
+
+    /// Forget scheduled actions; action ids are reused between drafts.
+    pub fn reset(&mut self) {
+        self.scheduled.clear();
+    }
+}
+
+/// Re-check the draft and lock in a ban. The protection rules are applied to a
+/// fresh state, since allies may have hovered something during the delay.
+/// With the lock-in guard on the ban is only hovered, for the player to lock.
+/// Returns the banned champion, or `None` if banning was no longer safe.
+pub async fn execute_auto_ban<C: LcuApi>(
+    client: &tokio::sync::Mutex<C>,
+    action_id: i64,
+    candidates: &[i64],
+    lock_confirmation: bool,
+) -> Result<Option<i64>, String> {
+    let mut client_guard = client.lock().await;
+    let state = client_guard.get_draft_state().await?;
+
+    match choose_auto_ban(&state, candidates) {
+        Some((current_action, champion_id)) if current_action == action_id => {
+            let lock = auto_lock_allowed(&state, lock_confirmation);
+            client_guard
+                .patch_draft_action(action_id, champion_id, lock)
+                .await?;
+            Ok(Some(champion_id))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::auto_draft::ANY_POSITION;
+    use crate::lcu::mock::{MockLcuClient, SessionBuilder};
+    use std::collections::BTreeMap;
+
+    fn session(ally_hover: i64, my_ban_hover: i64) -> SessionBuilder {
+        SessionBuilder::new()
+            .ally(0, 0, 0)
+            .ally(1, 0, ally_hover)
+            .enemy(5, 0, 238)
+            .ban(1, 5, 157)
+            .ban(2, 0, my_ban_hover)
+            .in_progress()
+    }
+
+    #[test]
+    fn test_protected_champions_are_skipped() {
+        let state = session(64, 0).state();
+        // 157 is banned, 64 is hovered by an ally; enemy hovers don't protect
+        assert_eq!(choose_auto_ban(&state, &[157, 64, 238]), Some((2, 238)));
+        assert_eq!(choose_auto_ban(&state, &[157, 64]), None);
+
+        // Never override a ban the player is already hovering
+        let state = session(64, 91).state();
+        assert_eq!(choose_auto_ban(&state, &[238]), None);
+
+        let settings = AppSettings {
+            auto_ban_enabled: true,
+            auto_ban_champion_ids: vec![238],
+            ..Default::default()
+        };
+        let state = session(0, 0).state();
+        let mut banner = AutoBanner::new();
+        assert_eq!(banner.should_schedule(&state, &settings), Some(2));
+        assert_eq!(banner.should_schedule(&state, &settings), None);
+    }
+
+    #[test]
+    fn test_ban_rules_go_through_auto_ban() {
+        let state = session(0, 0).state();
+        let mut settings = AppSettings {
+            auto_draft_enabled: true,
+            auto_draft_bans: BTreeMap::from([(ANY_POSITION.to_string(), vec![238, 157])]),
+            ..Default::default()
+        };
+        assert_eq!(ban_candidates(&state, &settings), vec![238, 157]);
+        assert_eq!(
+            AutoBanner::new().should_schedule(&state, &settings),
+            Some(2)
+        );
+
+        // The auto-ban list comes first
+        settings.auto_ban_enabled = true;
+        settings.auto_ban_champion_ids = vec![157, 91];
+        assert_eq!(ban_candidates(&state, &settings), vec![157, 91, 238]);
+
+        settings.auto_ban_enabled = false;
+        settings.auto_draft_enabled = false;
+        assert_eq!(AutoBanner::new().should_schedule(&state, &settings), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_rechecks_before_banning() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(238, 0).build());
+        mock.push_draft_session(session(0, 0).build());
+        let client = tokio::sync::Mutex::new(mock);
+
+        // An ally started hovering the champion during the delay
+        assert_eq!(
+            execute_auto_ban(&client, 2, &[238], false).await.unwrap(),
+            None
+        );
+        assert_eq!(
+            execute_auto_ban(&client, 2, &[238], false).await.unwrap(),
+            Some(238)
+        );
+        assert_eq!(client.lock().await.patched_actions, vec![(2, 238, true)]);
+    }
+
+    #[tokio::test]
+    async fn test_lock_confirmation_only_hovers_the_ban() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(0, 0).build());
+        let client = tokio::sync::Mutex::new(mock);
+
+        assert_eq!(
+            execute_auto_ban(&client, 2, &[238], true).await.unwrap(),
+            Some(238)
+        );
+        assert_eq!(client.lock().await.patched_actions, vec![(2, 238, false)]);
+    }
+}
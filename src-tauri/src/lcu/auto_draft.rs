@@ -0,0 +1,228 @@
+use crate::lcu::actions::auto_lock_allowed;
+use crate::lcu::pool::local_position;
+use crate::lcu::{api::LcuApi, draft::DraftState, error::LcuError};
+use crate::settings::{AppSettings, SettingsStore};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+/// Lock at this many seconds left when none is configured.
+pub const DEFAULT_LOCK_SECONDS: u32 = 5;
+
+/// Rule list tried after the position's own, and the only one when positions
+/// aren't assigned.
+pub const ANY_POSITION: &str = "ANY";
+
+/// What the rules engine did on one of the player's pick turns. Payload of the
+/// `auto-draft-applied` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AutoDraftStep {
+    pub action_id: i64,
+    pub action_type: String, // Always "pick"; ban rules are applied by auto-ban
+    pub champion_id: i64,
+    pub locked: bool, // False for a hover
+    pub position: Option<String>,
+}
+
+/// Candidates for `position` in priority order, followed by the "ANY" list.
+pub fn candidates(rules: &BTreeMap<String, Vec<i64>>, position: Option<&str>) -> Vec<i64> {
+    let own = position.and_then(|p| rules.get(p)).into_iter().flatten();
+    let any = rules.get(ANY_POSITION).into_iter().flatten();
+    let mut seen = HashSet::new();
+    own.chain(any)
+        .copied()
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
+
+/// Champions the local player can't pick: banned, locked, or hovered by an ally.
+fn pick_blocked(state: &DraftState, local_cell: i64) -> HashSet<i64> {
+    let mut blocked = state.unavailable_champions();
+    if let Some(team) = state.local_team() {
+        blocked.extend(
+            team.cells
+                .iter()
+                .filter(|c| c.cell_id != local_cell)
+                .filter_map(|c| c.selected_champion_id),
+        );
+    }
+    blocked
+}
+
+/// Follows the local player's pick turns and decides what to hover and when to
+/// lock. A turn is left alone once the player hovers something themselves.
+/// Ban turns belong to `AutoBanner`, which also applies the ban rules.
+#[derive(Default)]
+pub struct AutoDrafter {
+    action_id: Option<i64>,
+    hovered: Option<i64>, // Champion the engine hovered this turn
+    done: bool,           // Locked, or taken over by the player
+}
+
+impl AutoDrafter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, state: &DraftState, settings: &AppSettings) -> Option<AutoDraftStep> {
+        if !settings.auto_draft_enabled {
+            self.reset();
+            return None;
+        }
+        let local_cell = state.local_player_cell_id?;
+        let action = state.actions.iter().find(|a| {
+            a.actor_cell_id == Some(local_cell)
+                && a.is_in_progress
+                && !a.completed
+                && a.action_type == "pick"
+        })?;
+        if self.action_id != Some(action.id) {
+            *self = Self {
+                action_id: Some(action.id),
+                ..Self::default()
+            };
+        }
+        if self.done {
+            return None;
+        }
+        if action.champion_id.is_some() && action.champion_id != self.hovered {
+            self.done = true;
+            return None;
+        }
+
+        let position = local_position(state);
+        let blocked = pick_blocked(state, local_cell);
+        // Keep the current hover while it's still available, so allies see a
+        // steady intent instead of the engine flipping between candidates
+        let champion_id = self
+            .hovered
+            .filter(|id| !blocked.contains(id))
+            .or_else(|| {
+                candidates(&settings.auto_draft_picks, position.as_deref())
+                    .into_iter()
+                    .find(|id| !blocked.contains(id))
+            })?;
+
+        let lock_seconds = settings
+            .auto_draft_lock_seconds
+            .unwrap_or(DEFAULT_LOCK_SECONDS);
+        let locked = state.timer.is_some_and(|t| t <= lock_seconds as f64)
+            && auto_lock_allowed(state, settings.lock_confirmation);
+        if locked {
+            self.done = true;
+        } else if self.hovered == Some(champion_id) {
+            return None;
+        }
+        self.hovered = Some(champion_id);
+        Some(AutoDraftStep {
+            action_id: action.id,
+            action_type: action.action_type.clone(),
+            champion_id,
+            locked,
+            position,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Hover or lock the step's champion.
+pub async fn apply_step<C: LcuApi>(
+    client: &tokio::sync::Mutex<C>,
+    step: &AutoDraftStep,
+) -> Result<(), LcuError> {
+    let mut client_guard = client.lock().await;
+    client_guard
+        .patch_draft_action(step.action_id, step.champion_id, step.locked)
+        .await
+}
+
+#[tauri::command]
+pub async fn set_auto_draft(
+    enabled: bool,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<(), String> {
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut updated = settings_guard.get().clone();
+    updated.auto_draft_enabled = enabled;
+    settings_guard.set(updated)
+}
+
+#[tauri::command]
+pub async fn get_auto_draft(
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<bool, String> {
+    let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(settings_guard.get().auto_draft_enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+
+    fn session(timer_ms: f64, my_hover: i64, ally_hover: i64) -> DraftState {
+        SessionBuilder::new()
+            .timer_ms(timer_ms)
+            .ally(0, 0, 0)
+            .position("middle")
+            .ally(1, 0, ally_hover)
+            .enemy(5, 0, 0)
+            .ban(1, 5, 157)
+            .turn()
+            .pick(4, 0, my_hover)
+            .in_progress()
+            .state()
+    }
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            auto_draft_enabled: true,
+            auto_draft_picks: BTreeMap::from([
+                ("MIDDLE".to_string(), vec![157, 103]),
+                (ANY_POSITION.to_string(), vec![99]),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hovers_then_locks_at_threshold() {
+        let settings = settings();
+        let mut drafter = AutoDrafter::new();
+
+        // 157 is banned, so the next middle pick is hovered
+        let step = drafter.update(&session(25000.0, 0, 0), &settings).unwrap();
+        assert_eq!((step.champion_id, step.locked), (103, false));
+        assert_eq!(step.position.as_deref(), Some("MIDDLE"));
+        assert_eq!(drafter.update(&session(20000.0, 103, 0), &settings), None);
+
+        // An ally starts hovering it; fall through to the ANY list
+        let step = drafter
+            .update(&session(10000.0, 103, 103), &settings)
+            .unwrap();
+        assert_eq!((step.champion_id, step.locked), (99, false));
+
+        let step = drafter
+            .update(&session(4000.0, 99, 103), &settings)
+            .unwrap();
+        assert_eq!(
+            (step.action_id, step.champion_id, step.locked),
+            (4, 99, true)
+        );
+        assert_eq!(drafter.update(&session(3000.0, 99, 103), &settings), None);
+    }
+
+    #[test]
+    fn test_player_hover_takes_over() {
+        let settings = settings();
+        let mut drafter = AutoDrafter::new();
+        assert_eq!(drafter.update(&session(25000.0, 64, 0), &settings), None);
+        assert_eq!(drafter.update(&session(2000.0, 64, 0), &settings), None);
+
+        let disabled = AppSettings::default();
+        assert_eq!(drafter.update(&session(25000.0, 0, 0), &disabled), None);
+    }
+}
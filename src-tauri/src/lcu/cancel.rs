@@ -0,0 +1,90 @@
+use std::future::Future;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio_util::sync::CancellationToken;
+
+pub const CANCELLED: &str = "Cancelled: the gameflow phase changed";
+
+/// One token per gameflow phase. Work tied to a phase takes the current token
+/// and is cancelled when the phase ends, so its late responses never land on
+/// the next phase's state.
+#[derive(Default)]
+pub struct PhaseTokens {
+    phase: Option<String>,
+    token: CancellationToken,
+}
+
+impl PhaseTokens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token for work started now, cancelled when the phase changes.
+    pub fn token(&self) -> CancellationToken {
+        self.token.child_token()
+    }
+
+    /// Cancel everything started in the previous phase.
+    pub fn enter(&mut self, phase: Option<String>) {
+        if phase == self.phase {
+            return;
+        }
+        self.token.cancel();
+        self.token = CancellationToken::new();
+        self.phase = phase;
+    }
+}
+
+/// The token of the phase the gameflow watcher saw last.
+pub fn phase_token(app: &AppHandle) -> CancellationToken {
+    match app.try_state::<Mutex<PhaseTokens>>() {
+        Some(tokens) => tokens.lock().unwrap_or_else(|e| e.into_inner()).token(),
+        None => CancellationToken::new(),
+    }
+}
+
+/// Called on every gameflow transition.
+pub fn enter_phase(app: &AppHandle, phase: Option<String>) {
+    if let Some(tokens) = app.try_state::<Mutex<PhaseTokens>>() {
+        tokens
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .enter(phase);
+    }
+}
+
+/// Run `work` until it finishes or `token` is cancelled. Dropping the work
+/// also releases whatever it held, such as the client lock.
+pub async fn until_cancelled<T>(
+    token: &CancellationToken,
+    work: impl Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    tokio::select! {
+        _ = token.cancelled() => Err(CANCELLED.to_string()),
+        result = work => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_phase_change_cancels_earlier_work() {
+        let mut tokens = PhaseTokens::new();
+        tokens.enter(Some("ChampSelect".to_string()));
+        let champ_select = tokens.token();
+
+        tokens.enter(Some("ChampSelect".to_string()));
+        assert!(!champ_select.is_cancelled(), "same phase again");
+        assert_eq!(until_cancelled(&champ_select, async { Ok(1) }).await, Ok(1));
+
+        tokens.enter(Some("InProgress".to_string()));
+        assert!(champ_select.is_cancelled());
+        assert_eq!(
+            until_cancelled(&champ_select, std::future::pending::<Result<(), String>>()).await,
+            Err(CANCELLED.to_string())
+        );
+        assert!(!tokens.token().is_cancelled());
+    }
+}
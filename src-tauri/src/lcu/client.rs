@@ -1,7 +1,24 @@
-use super::lockfile::{read_lockfile, LockfileData};
+use super::api::LcuApi;
+use super::dto::{
+    Alias, ChampSelectSession, Friend, GameflowSession, MatchHistoryResponse,
+    RankedStatsResponse,
+};
+use super::dto;
+use super::error::LcuError;
+use super::missions::{parse_missions, Mission};
+use crate::analytics::now_millis;
+use crate::settings::{AppSettings, SettingsStore};
+use super::lockfile::{detect_clients, expand_home, find_client, ClientProfile, LockfileData};
+use super::request_stats::{RequestCounters, RequestStats};
+use async_trait::async_trait;
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SummonerInfo {
@@ -17,7 +34,7 @@ pub struct SummonerInfo {
     pub xp_until_next_level: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RankedStats {
     pub queue_type: String,
     pub tier: String,
@@ -25,6 +42,29 @@ pub struct RankedStats {
     pub league_points: i32,
     pub wins: i32,
     pub losses: i32,
+    #[serde(default)]
+    pub highest_tier: Option<String>, // Peak this season
+    #[serde(default)]
+    pub highest_division: Option<String>,
+    #[serde(default)]
+    pub previous_season_end_tier: Option<String>,
+    #[serde(default)]
+    pub previous_season_end_division: Option<String>,
+    #[serde(default)]
+    pub is_provisional: bool,
+    #[serde(default)]
+    pub provisional_games_remaining: i32,
+    #[serde(default)]
+    pub miniseries: Option<Miniseries>, // Present only while in a promotion series
+}
+
+/// Promotion series state. `progress` is one char per game: W, L or N (not played yet).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Miniseries {
+    pub progress: String,
+    pub wins: i32,
+    pub losses: i32,
+    pub target: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,30 +81,280 @@ pub struct MatchHistoryGame {
     pub assists: i32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChampionMastery {
+    pub champion_id: i64,
+    pub champion_level: i32,
+    pub champion_points: i64,
+    pub last_play_time: i64,
+    #[serde(default)]
+    pub chest_granted: bool,
+}
+
+/// Where the current ranked split stands, as reported by the client.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RankedSplit {
+    pub season_id: Option<i64>,
+    pub split_id: Option<i64>,
+    pub split_start: Option<i64>, // Epoch millis
+    pub split_end: Option<i64>,
+    pub split_points: i64,
+    pub reward_thresholds: Vec<i64>, // Split points required for each reward on the track, ascending
+}
+
+/// A running client the app could bind to, for picking between several.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedClient {
+    pub process_id: u32,
+    pub port: u16,
+    pub source: String, // Lockfile path or process
+    pub profile: ClientProfile,
+    pub selected: bool,
+}
+
+/// GET responses are shared with callers asking for the same endpoint within
+/// this long, so the draft monitor, the overlay and a frontend command polling
+/// in the same tick cost one request.
+const COALESCE_WINDOW: Duration = Duration::from_millis(100);
+
+/// Recent successful GET responses, keyed by path and query.
+#[derive(Default)]
+struct RecentResponses {
+    entries: HashMap<String, (Instant, serde_json::Value)>,
+}
+
+impl RecentResponses {
+    fn key(path: &str, query: &[(&str, &str)]) -> String {
+        let mut key = path.to_string();
+        for (name, value) in query {
+            key += &format!("&{}={}", name, value);
+        }
+        key
+    }
+
+    fn get(&self, key: &str, now: Instant) -> Option<serde_json::Value> {
+        self.entries
+            .get(key)
+            .filter(|(at, _)| now.duration_since(*at) < COALESCE_WINDOW)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn insert(&mut self, key: String, now: Instant, value: serde_json::Value) {
+        self.entries
+            .retain(|_, (at, _)| now.duration_since(*at) < COALESCE_WINDOW);
+        self.entries.insert(key, (now, value));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Endpoints grouped by how long they may reasonably take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointClass {
+    Polling, // Gameflow, champ select and other quick reads polled in a loop
+    History, // Match history, game details and timelines
+}
+
+impl EndpointClass {
+    pub fn of(path: &str) -> Self {
+        if path.starts_with("/lol-match-history/") {
+            EndpointClass::History
+        } else {
+            EndpointClass::Polling
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub timeout: Duration,
+    pub retries: u32,      // Attempts after the first; credentials are refreshed before each
+    pub backoff: Duration, // Wait before the first retry, doubling for each one after
+}
+
+impl RetryPolicy {
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        self.backoff.saturating_mul(1 << retry.min(16))
+    }
+}
+
+/// Timeouts and retries per endpoint class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicies {
+    pub polling: RetryPolicy,
+    pub history: RetryPolicy,
+}
+
+impl Default for RetryPolicies {
+    fn default() -> Self {
+        Self {
+            // A poll that takes longer than this is stale by the time it lands
+            polling: RetryPolicy {
+                timeout: Duration::from_secs(2),
+                retries: 1,
+                backoff: Duration::from_millis(50),
+            },
+            history: RetryPolicy {
+                timeout: Duration::from_secs(10),
+                retries: 2,
+                backoff: Duration::from_millis(500),
+            },
+        }
+    }
+}
+
+impl RetryPolicies {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        let defaults = Self::default();
+        let policy = |default: RetryPolicy,
+                      timeout_ms: Option<u64>,
+                      retries: Option<u32>,
+                      backoff_ms: Option<u64>| RetryPolicy {
+            timeout: timeout_ms.map_or(default.timeout, Duration::from_millis),
+            retries: retries.unwrap_or(default.retries),
+            backoff: backoff_ms.map_or(default.backoff, Duration::from_millis),
+        };
+        Self {
+            polling: policy(
+                defaults.polling,
+                settings.lcu_polling_timeout_ms,
+                settings.lcu_polling_retries,
+                settings.lcu_polling_backoff_ms,
+            ),
+            history: policy(
+                defaults.history,
+                settings.lcu_history_timeout_ms,
+                settings.lcu_history_retries,
+                settings.lcu_history_backoff_ms,
+            ),
+        }
+    }
+
+    pub fn get(&self, class: EndpointClass) -> RetryPolicy {
+        match class {
+            EndpointClass::Polling => self.polling,
+            EndpointClass::History => self.history,
+        }
+    }
+}
+
+/// The policies from the current settings.
+pub fn retry_policies(app: &AppHandle) -> RetryPolicies {
+    app.try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| RetryPolicies::from_settings(g.get())))
+        .unwrap_or_default()
+}
+
+/// Run `$attempt` under the retry policy for `$class`. Only transient errors
+/// are retried; the rest are returned at once. Rejected credentials are
+/// re-read from the lockfile once, without waiting or spending a retry, since
+/// a restarted client hands out a new password; rejected again, the call
+/// fails with `AuthExpired`. A failed connection also drops the credentials,
+/// in case the client came back on another port.
+macro_rules! with_retries {
+    ($client:ident, $class:expr, $attempt:expr) => {{
+        use $crate::lcu::error::LcuError;
+        let policy = $client.policies.get($class);
+        let mut refreshed = false;
+        let mut retry = 0;
+        loop {
+            match $attempt {
+                Err(LcuError::Unauthorized) if !refreshed => {
+                    refreshed = true;
+                    $client.clear_credentials();
+                }
+                Err(LcuError::Unauthorized) => break Err(LcuError::AuthExpired),
+                Err(e) if e.is_transient() && retry < policy.retries => {
+                    if matches!(e, LcuError::ClientNotRunning(_)) {
+                        $client.clear_credentials();
+                    }
+                    tokio::time::sleep(policy.delay(retry)).await;
+                    retry += 1;
+                }
+                result => break result,
+            }
+        }
+    }};
+}
+#[cfg(test)] // The mock retries polled requests the same way
+pub(crate) use with_retries;
+
+/// Deserialize a response from `path` into its DTO, counting the values that
+/// matched none of the known shapes.
+fn decode<T: DeserializeOwned>(
+    value: &serde_json::Value,
+    path: &str,
+    stats: &mut RequestCounters,
+) -> Result<T, LcuError> {
+    let (result, unknown) = dto::count_unknown_shapes(|| T::deserialize(value));
+    stats.record_unknown_shapes(unknown);
+    result.map_err(|e| LcuError::Deserialize(format!("{} from {}", e, path)))
+}
+
+/// Sends a request and records how it went in the request stats.
+trait SendTimed {
+    async fn send_timed(self, stats: &mut RequestCounters) -> reqwest::Result<reqwest::Response>;
+}
+
+impl SendTimed for reqwest::RequestBuilder {
+    async fn send_timed(self, stats: &mut RequestCounters) -> reqwest::Result<reqwest::Response> {
+        let started = Instant::now();
+        let response = self.send().await;
+        let ok = response.as_ref().is_ok_and(|r| r.status().is_success());
+        stats.record_request(started.elapsed(), ok);
+        response
+    }
+}
+
 pub struct LcuClient {
     client: Client,
     lockfile_data: Option<LockfileData>,
+    preferred_port: Option<u16>, // Chosen client when several are running
+    profile: ClientProfile,      // Of the client the credentials belong to
+    recent: RecentResponses,
+    policies: RetryPolicies,
+    wine_prefixes: Vec<PathBuf>, // Linux: extra prefixes from settings to look for lockfiles in
+    stats: RequestCounters,
 }
 
 impl LcuClient {
     pub fn new() -> Self {
+        // One client for the app's lifetime so polls reuse a warm connection
+        // instead of paying a TLS handshake each time. The client is local, so
+        // a connect that takes longer than a second won't succeed.
         let client = Client::builder()
             .danger_accept_invalid_certs(true)
             .timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_secs(1))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .pool_max_idle_per_host(4)
+            .tcp_keepalive(Duration::from_secs(30))
+            .tcp_nodelay(true)
             .build()
             .expect("Failed to create HTTP client");
 
         Self {
             client,
             lockfile_data: None,
+            preferred_port: None,
+            profile: ClientProfile::Live,
+            recent: RecentResponses::default(),
+            policies: RetryPolicies::default(),
+            wine_prefixes: Vec::new(),
+            stats: RequestCounters::default(),
         }
     }
 
     /// Get LCU credentials, always tries to fetch fresh credentials if not cached
-    pub fn get_lockfile(&mut self) -> Result<&LockfileData, String> {
+    pub fn get_lockfile(&mut self) -> Result<&LockfileData, LcuError> {
         if self.lockfile_data.is_none() {
-            let data = read_lockfile()?;
-            self.lockfile_data = Some(data);
+            self.stats.record_credential_refresh();
+            let found = find_client(self.preferred_port, &self.wine_prefixes)
+                .map_err(LcuError::ClientNotRunning)?;
+            self.profile = found.profile;
+            self.lockfile_data = Some(found.credentials);
         }
         Ok(self.lockfile_data.as_ref().unwrap())
     }
@@ -72,22 +362,92 @@ impl LcuClient {
     /// Clear cached credentials (useful when League client restarts)
     pub fn clear_credentials(&mut self) {
         self.lockfile_data = None;
+        self.recent.clear();
     }
 
-    pub async fn get_gameflow_phase(&mut self) -> Result<String, String> {
-        // Try with current credentials, refresh if connection fails
-        let result = self.try_get_gameflow_phase().await;
+    /// Profile of the bound client, binding first if needed.
+    pub fn profile(&mut self) -> Result<ClientProfile, LcuError> {
+        self.get_lockfile()?;
+        Ok(self.profile)
+    }
 
-        // If we got a connection error, try refreshing credentials once
-        if result.is_err() {
-            self.clear_credentials();
-            return self.try_get_gameflow_phase().await;
+    /// Bind to the client on `port` from now on, or with `None` to the first
+    /// lockfile found.
+    pub fn select_port(&mut self, port: Option<u16>) {
+        self.preferred_port = port;
+        self.clear_credentials();
+    }
+
+    pub fn set_policies(&mut self, policies: RetryPolicies) {
+        self.policies = policies;
+    }
+
+    /// Look for lockfiles in these wine prefixes too, from the next bind on.
+    pub fn set_wine_prefixes(&mut self, prefixes: &[String]) {
+        self.wine_prefixes = prefixes.iter().map(|p| expand_home(p)).collect();
+    }
+
+    pub fn wine_prefixes(&self) -> &[PathBuf] {
+        &self.wine_prefixes
+    }
+
+    pub fn request_stats(&self) -> RequestStats {
+        self.stats.report()
+    }
+
+    fn timeout(&self, class: EndpointClass) -> Duration {
+        self.policies.get(class).timeout
+    }
+
+    /// GET an LCU endpoint and parse the JSON body, retrying with fresh
+    /// credentials under the endpoint's policy. A response fetched moments ago
+    /// is reused.
+    async fn get_json(&mut self, path: &str) -> Result<serde_json::Value, LcuError> {
+        self.get_json_with_query(path, &[]).await
+    }
+
+    /// GET an LCU endpoint and deserialize it into its DTO.
+    async fn get_typed<T: DeserializeOwned>(&mut self, path: &str) -> Result<T, LcuError> {
+        self.get_typed_with_query(path, &[]).await
+    }
+
+    async fn get_typed_with_query<T: DeserializeOwned>(
+        &mut self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T, LcuError> {
+        let value = self.get_json_with_query(path, query).await?;
+        decode(&value, path, &mut self.stats)
+    }
+
+    async fn get_json_with_query(
+        &mut self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<serde_json::Value, LcuError> {
+        let key = RecentResponses::key(path, query);
+        if let Some(value) = self.recent.get(&key, Instant::now()) {
+            self.stats.record_coalesced();
+            return Ok(value);
         }
 
+        let result = with_retries!(
+            self,
+            EndpointClass::of(path),
+            self.try_get_json(path, query).await
+        );
+
+        if let Ok(value) = &result {
+            self.recent.insert(key, Instant::now(), value.clone());
+        }
         result
     }
 
-    async fn try_get_gameflow_phase(&mut self) -> Result<String, String> {
+    async fn try_get_json(
+        &mut self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<serde_json::Value, LcuError> {
         let protocol;
         let port;
         let password;
@@ -97,43 +457,56 @@ impl LcuClient {
             port = lockfile.port;
             password = lockfile.password.clone();
         }
-        let base_url = format!("{}://127.0.0.1:{}", protocol, port);
-        let url = format!("{}/lol-gameflow/v1/gameflow-phase", base_url);
+        let url = format!("{}://127.0.0.1:{}{}", protocol, port, path);
 
         let response = self
             .client
             .get(&url)
+            .query(query)
+            .timeout(self.timeout(EndpointClass::of(path)))
             .basic_auth("riot", Some(&password))
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+            .send_timed(&mut self.stats)
+            .await?;
 
         if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
+            return Err(LcuError::from_status(path, response.status()));
         }
 
-        let phase = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
-
-        Ok(phase.trim_matches('"').to_string())
+        Ok(response.json::<serde_json::Value>().await?)
     }
 
-    pub async fn get_draft_session(&mut self) -> Result<serde_json::Value, String> {
-        // Try with current credentials, refresh if connection fails
-        let result = self.try_get_draft_session().await;
-
-        // If we got a connection error, try refreshing credentials once
-        if result.is_err() {
-            self.clear_credentials();
-            return self.try_get_draft_session().await;
+    /// Send a write request with an optional JSON body. Writes are not retried,
+    /// since repeating one the client already applied could act twice; stale
+    /// credentials are dropped so the next call picks up fresh ones. A write
+    /// rejected for its credentials wasn't applied, so it gets one more try
+    /// with fresh ones.
+    async fn send_json(
+        &mut self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<(), LcuError> {
+        match self.try_send_json(method.clone(), path, body).await {
+            Err(LcuError::Unauthorized) => {
+                self.clear_credentials();
+                match self.try_send_json(method, path, body).await {
+                    Err(LcuError::Unauthorized) => Err(LcuError::AuthExpired),
+                    result => result,
+                }
+            }
+            result => result,
         }
-
-        result
     }
 
-    async fn try_get_draft_session(&mut self) -> Result<serde_json::Value, String> {
+    async fn try_send_json(
+        &mut self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<(), LcuError> {
+        // Whatever was read before the write may no longer hold
+        self.recent.clear();
+
         let protocol;
         let port;
         let password;
@@ -143,372 +516,624 @@ impl LcuClient {
             port = lockfile.port;
             password = lockfile.password.clone();
         }
-        let base_url = format!("{}://127.0.0.1:{}", protocol, port);
-        let url = format!("{}/lol-champ-select/v1/session", base_url);
+        let url = format!("{}://127.0.0.1:{}{}", protocol, port, path);
 
-        let response = self
+        let mut request = self
             .client
-            .get(&url)
-            .basic_auth("riot", Some(&password))
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+            .request(method, &url)
+            .timeout(self.timeout(EndpointClass::of(path)))
+            .basic_auth("riot", Some(&password));
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let response = request.send_timed(&mut self.stats).await.map_err(|e| {
+            self.clear_credentials();
+            LcuError::from(e)
+        })?;
 
         if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
+            return Err(LcuError::from_status(path, response.status()));
         }
 
-        let session = response
-            .json::<serde_json::Value>()
-            .await
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        Ok(session)
+        Ok(())
     }
+}
 
-    pub async fn get_draft_state(&mut self) -> Result<super::draft::DraftState, String> {
-        let session = self.get_draft_session().await?;
-        super::draft::parse_draft_session(&session)
+fn parse_summoner(summoner: dto::Summoner) -> SummonerInfo {
+    SummonerInfo {
+        summoner_id: summoner.summoner_id,
+        account_id: summoner.account_id,
+        puuid: summoner.puuid.unwrap_or_default(),
+        display_name: summoner
+            .display_name
+            .unwrap_or_else(|| "Unknown".to_string()),
+        game_name: summoner.game_name,
+        tag_line: summoner.tag_line,
+        summoner_level: summoner.summoner_level.unwrap_or(0),
+        profile_icon_id: summoner.profile_icon_id.unwrap_or(0),
+        xp_since_last_level: summoner.xp_since_last_level.unwrap_or(0),
+        xp_until_next_level: summoner.xp_until_next_level.unwrap_or(0),
     }
+}
 
-    pub async fn get_current_summoner(&mut self) -> Result<SummonerInfo, String> {
-        // Try with current credentials, refresh if connection fails
-        let result = self.try_get_current_summoner().await;
+fn parse_masteries(json_value: &serde_json::Value) -> Vec<ChampionMastery> {
+    // Returned either bare or wrapped in { masteries: [...] } depending on client version
+    let entries = json_value
+        .as_array()
+        .or_else(|| json_value["masteries"].as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    entries
+        .iter()
+        .map(|m| ChampionMastery {
+            champion_id: m["championId"].as_i64().unwrap_or(0),
+            champion_level: m["championLevel"].as_i64().unwrap_or(0) as i32,
+            champion_points: m["championPoints"].as_i64().unwrap_or(0),
+            last_play_time: m["lastPlayTime"].as_i64().unwrap_or(0),
+            chest_granted: m["chestGranted"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
 
-        // If we got a connection error, try refreshing credentials once
-        if result.is_err() {
-            self.clear_credentials();
-            return self.try_get_current_summoner().await;
-        }
+/// Owned champions that haven't granted a chest this season. Champions never
+/// played have no mastery entry and are eligible too.
+pub fn chest_eligible(owned: &[i64], masteries: &[ChampionMastery]) -> Vec<i64> {
+    let granted: std::collections::HashSet<i64> = masteries
+        .iter()
+        .filter(|m| m.chest_granted)
+        .map(|m| m.champion_id)
+        .collect();
+    owned
+        .iter()
+        .copied()
+        .filter(|id| !granted.contains(id))
+        .collect()
+}
 
-        result
-    }
+/// First win of the day is tracked as a daily mission; it's available until that
+/// mission completes. Assume available if the client doesn't list it.
+fn parse_first_win_available(missions: &serde_json::Value) -> bool {
+    let is_first_win = |m: &&serde_json::Value| {
+        let name = m["internalName"].as_str().unwrap_or("").to_uppercase();
+        name.contains("FWOTD") || name.contains("FIRST_WIN")
+    };
+    missions
+        .as_array()
+        .and_then(|missions| missions.iter().find(is_first_win))
+        .map(|m| m["status"].as_str() != Some("COMPLETED"))
+        .unwrap_or(true)
+}
 
-    async fn try_get_current_summoner(&mut self) -> Result<SummonerInfo, String> {
-        let protocol;
-        let port;
-        let password;
-        {
-            let lockfile = self.get_lockfile()?;
-            protocol = lockfile.protocol.clone();
-            port = lockfile.port;
-            password = lockfile.password.clone();
-        }
-        let base_url = format!("{}://127.0.0.1:{}", protocol, port);
-        
-        // First, get the current summoner info
-        let url = format!("{}/lol-summoner/v1/current-summoner", base_url);
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth("riot", Some(&password))
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+/// Ids of the entries in `/lol-champions/v1/owned-champions-minimal` matching `keep`.
+fn filter_champion_ids(
+    json_value: &serde_json::Value,
+    keep: impl Fn(&serde_json::Value) -> bool,
+) -> Vec<i64> {
+    json_value
+        .as_array()
+        .map(|champions| {
+            champions
+                .iter()
+                .filter(|c| keep(c))
+                .filter_map(|c| c["id"].as_i64())
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
+/// Summoner's Rift queues are always reported; TFT, Arena and any other rated
+/// queues only when `include_all_queues` is set.
+pub fn is_summoners_rift_queue(queue_type: &str) -> bool {
+    queue_type == "RANKED_SOLO_5x5" || queue_type == "RANKED_FLEX_SR"
+}
+
+fn non_empty_str(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .filter(|s| !s.is_empty() && *s != "NONE" && *s != "NA")
+        .map(|s| s.to_string())
+}
+
+fn parse_ranked_stats(stats: &RankedStatsResponse, include_all_queues: bool) -> Vec<RankedStats> {
+    let mut ranked_stats = Vec::new();
+
+    for queue in &stats.queues {
+        let Some(queue_type) = queue.queue_type.as_deref() else {
+            continue;
+        };
+        if !include_all_queues && !is_summoners_rift_queue(queue_type) {
+            continue;
         }
 
-        let json_value: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        // Try to get gameName and tagLine from current-summoner response first
-        let mut game_name = json_value["gameName"].as_str().map(|s| s.to_string());
-        let mut tag_line = json_value["tagLine"].as_str().map(|s| s.to_string());
-        
-        let puuid = json_value["puuid"].as_str().unwrap_or("").to_string();
-        
-        // If not found in current-summoner, try alias lookup using puuid
-        if game_name.is_none() || tag_line.is_none() {
-            if !puuid.is_empty() {
-                let alias_url = format!("{}/lol-summoner/v1/alias/lookup?puuid={}", base_url, puuid);
-                if let Ok(alias_response) = self
-                    .client
-                    .get(&alias_url)
-                    .basic_auth("riot", Some(&password))
-                    .send()
-                    .await
-                {
-                    if alias_response.status().is_success() {
-                        if let Ok(alias_json) = alias_response.json::<serde_json::Value>().await {
-                            if game_name.is_none() {
-                                game_name = alias_json["gameName"].as_str().map(|s| s.to_string());
-                            }
-                            if tag_line.is_none() {
-                                tag_line = alias_json["tagLine"].as_str().map(|s| s.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+        // Hyper Roll and similar queues use a rated ladder instead of divisions
+        let rated_tier = non_empty_str(&queue.rated_tier);
+        let tier = queue.tier.clone().unwrap_or_else(|| "UNRANKED".to_string());
+        let (tier, league_points) = match (tier.as_str(), rated_tier) {
+            ("NONE" | "", Some(rated)) => (rated, queue.rated_rating.unwrap_or(0)),
+            _ => (tier, queue.league_points.unwrap_or(0)),
+        };
+        if tier == "NONE" {
+            continue;
         }
 
-        Ok(SummonerInfo {
-            summoner_id: json_value["summonerId"].as_str().unwrap_or("").to_string(),
-            account_id: json_value["accountId"].as_str().unwrap_or("").to_string(),
-            puuid,
-            display_name: json_value["displayName"]
-                .as_str()
-                .unwrap_or("Unknown")
-                .to_string(),
-            game_name,
-            tag_line,
-            summoner_level: json_value["summonerLevel"].as_i64().unwrap_or(0),
-            profile_icon_id: json_value["profileIconId"].as_i64().unwrap_or(0),
-            xp_since_last_level: json_value["xpSinceLastLevel"].as_i64().unwrap_or(0),
-            xp_until_next_level: json_value["xpUntilNextLevel"].as_i64().unwrap_or(0),
+        let miniseries = non_empty_str(&queue.mini_series_progress).map(|progress| Miniseries {
+            wins: progress.chars().filter(|c| *c == 'W').count() as i32,
+            losses: progress.chars().filter(|c| *c == 'L').count() as i32,
+            target: queue
+                .mini_series_target
+                .unwrap_or(progress.len() as i64 / 2 + 1) as i32,
+            progress,
+        });
+
+        ranked_stats.push(RankedStats {
+            queue_type: queue_type.to_string(),
+            tier,
+            rank: queue.division.clone().unwrap_or_default(),
+            league_points: league_points as i32,
+            wins: queue.wins.unwrap_or(0) as i32,
+            losses: queue.losses.unwrap_or(0) as i32,
+            highest_tier: non_empty_str(&queue.highest_tier),
+            highest_division: non_empty_str(&queue.highest_division),
+            previous_season_end_tier: non_empty_str(&queue.previous_season_end_tier),
+            previous_season_end_division: non_empty_str(&queue.previous_season_end_division),
+            is_provisional: queue.is_provisional,
+            provisional_games_remaining: queue.provisional_games_remaining.unwrap_or(0) as i32,
+            miniseries,
+        });
+    }
+
+    ranked_stats
+}
+
+/// Combine the season block of `/lol-ranked/v1/current-ranked-stats` with
+/// `/lol-ranked/v1/splits-config` (which may be `Null` if the client didn't serve it).
+fn parse_ranked_split(
+    stats: &serde_json::Value,
+    splits_config: &serde_json::Value,
+) -> RankedSplit {
+    let season = &stats["seasons"]["RANKED_SOLO_5x5"];
+    let season_id = season["currentSeasonId"]
+        .as_i64()
+        .or_else(|| splits_config["currentSeasonId"].as_i64());
+    let split_id = splits_config["currentSplitId"].as_i64();
+
+    let split = splits_config["splits"].as_array().and_then(|splits| {
+        splits
+            .iter()
+            .find(|s| s["splitId"].as_i64() == split_id)
+    });
+
+    // splitsProgress is keyed by split id as a string
+    let split_points = split_id
+        .and_then(|id| stats["splitsProgress"][id.to_string()].as_i64())
+        .or_else(|| stats["currentSeasonSplitPoints"].as_i64())
+        .unwrap_or(0);
+
+    let mut reward_thresholds: Vec<i64> = split
+        .and_then(|s| s["rewardTrack"].as_array())
+        .map(|track| {
+            track
+                .iter()
+                .filter_map(|r| r["pointsRequired"].as_i64())
+                .collect()
         })
+        .unwrap_or_default();
+    reward_thresholds.sort_unstable();
+
+    RankedSplit {
+        season_id,
+        split_id,
+        split_start: split.and_then(|s| s["startTimeMillis"].as_i64()),
+        split_end: split
+            .and_then(|s| s["endTimeMillis"].as_i64())
+            .or_else(|| season["currentSeasonEnd"].as_i64().filter(|t| *t > 0)),
+        split_points,
+        reward_thresholds,
     }
+}
 
-    pub async fn get_ranked_stats(&mut self) -> Result<Vec<RankedStats>, String> {
-        // Try with current credentials, refresh if connection fails
-        let result = self.try_get_ranked_stats().await;
+fn parse_match_history(history: &MatchHistoryResponse, puuid: &str) -> Vec<MatchHistoryGame> {
+    let mut games = Vec::new();
+
+    for game in history.games() {
+        let Some(identity) = game
+            .participant_identities
+            .iter()
+            .find(|i| i.player.puuid.as_deref() == Some(puuid))
+        else {
+            continue;
+        };
+        let Some(participant) = game
+            .participants
+            .iter()
+            .find(|p| p.participant_id.unwrap_or(0) == identity.participant_id.unwrap_or(0))
+        else {
+            continue;
+        };
+        let stats = &participant.stats;
+
+        games.push(MatchHistoryGame {
+            game_id: game.game_id.unwrap_or(0),
+            queue_id: game.queue_id.unwrap_or(0) as i32,
+            champion_id: participant.champion_id.unwrap_or(0) as i32,
+            game_mode: game.game_mode.clone().unwrap_or_default(),
+            game_creation: game.game_creation.unwrap_or(0),
+            game_duration: game.game_duration.unwrap_or(0) as i32,
+            win: stats.win,
+            kills: stats.kills.unwrap_or(0) as i32,
+            deaths: stats.deaths.unwrap_or(0) as i32,
+            assists: stats.assists.unwrap_or(0) as i32,
+        });
+    }
 
-        // If we got a connection error, try refreshing credentials once
-        if result.is_err() {
-            self.clear_credentials();
-            return self.try_get_ranked_stats().await;
-        }
+    games
+}
 
-        result
+#[async_trait]
+impl LcuApi for LcuClient {
+    async fn get_gameflow_phase(&mut self) -> Result<String, LcuError> {
+        self.get_typed("/lol-gameflow/v1/gameflow-phase").await
     }
 
-    async fn try_get_ranked_stats(&mut self) -> Result<Vec<RankedStats>, String> {
-        let protocol;
-        let port;
-        let password;
+    async fn get_draft_session(&mut self) -> Result<ChampSelectSession, LcuError> {
+        self.get_typed("/lol-champ-select/v1/session").await
+    }
+
+    async fn get_draft_session_json(&mut self) -> Result<serde_json::Value, LcuError> {
+        self.get_json("/lol-champ-select/v1/session").await
+    }
+
+    async fn get_current_summoner(&mut self) -> Result<SummonerInfo, LcuError> {
+        let current: dto::Summoner = self.get_typed("/lol-summoner/v1/current-summoner").await?;
+        let mut summoner = parse_summoner(current);
+
+        // Some clients leave the Riot ID out of the summoner; the alias lookup has it
+        if (summoner.game_name.is_none() || summoner.tag_line.is_none())
+            && !summoner.puuid.is_empty()
         {
-            let lockfile = self.get_lockfile()?;
-            protocol = lockfile.protocol.clone();
-            port = lockfile.port;
-            password = lockfile.password.clone();
+            let puuid = summoner.puuid.clone();
+            if let Ok(alias) = self
+                .get_typed_with_query::<Alias>(
+                    "/lol-summoner/v1/alias/lookup",
+                    &[("puuid", &puuid)],
+                )
+                .await
+            {
+                summoner.game_name = summoner.game_name.or(alias.game_name);
+                summoner.tag_line = summoner.tag_line.or(alias.tag_line);
+            }
         }
-        let base_url = format!("{}://127.0.0.1:{}", protocol, port);
-        let url = format!("{}/lol-ranked/v1/current-ranked-stats", base_url);
+        Ok(summoner)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth("riot", Some(&password))
-            .send()
-            .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+    async fn get_ranked_stats(
+        &mut self,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError> {
+        let stats: RankedStatsResponse = self
+            .get_typed("/lol-ranked/v1/current-ranked-stats")
+            .await?;
+        Ok(parse_ranked_stats(&stats, include_all_queues))
+    }
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
-        }
+    async fn get_match_history(&mut self) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        self.get_match_history_paginated(0, 10).await
+    }
 
-        let json_value: serde_json::Value = response
-            .json()
+    /// The summoner is looked up once, under its own policy; only the history
+    /// request itself is retried under the history one.
+    async fn get_match_history_paginated(
+        &mut self,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        let summoner = self.get_current_summoner().await?;
+        self.get_match_history_for(&summoner.puuid, beg_index, end_index)
             .await
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        let mut ranked_stats = Vec::new();
-
-        if let Some(queues) = json_value["queues"].as_array() {
-            for queue in queues {
-                if let Some(queue_type) = queue["queueType"].as_str() {
-                    let tier = queue["tier"].as_str().unwrap_or("UNRANKED").to_string();
-                    if tier != "NONE"
-                        && (queue_type == "RANKED_SOLO_5x5" || queue_type == "RANKED_FLEX_SR")
-                    {
-                        ranked_stats.push(RankedStats {
-                            queue_type: queue_type.to_string(),
-                            tier,
-                            rank: queue["division"].as_str().unwrap_or("").to_string(),
-                            league_points: queue["leaguePoints"].as_i64().unwrap_or(0) as i32,
-                            wins: queue["wins"].as_i64().unwrap_or(0) as i32,
-                            losses: queue["losses"].as_i64().unwrap_or(0) as i32,
-                        });
-                    }
-                }
-            }
-        }
+    }
+
+    async fn get_owned_champion_ids(&mut self) -> Result<Vec<i64>, LcuError> {
+        let json_value = self
+            .get_json("/lol-champions/v1/owned-champions-minimal")
+            .await?;
 
-        Ok(ranked_stats)
+        Ok(filter_champion_ids(&json_value, |c| {
+            c["ownership"]["owned"].as_bool().unwrap_or(false)
+        }))
     }
 
-    pub async fn get_match_history(&mut self) -> Result<Vec<MatchHistoryGame>, String> {
-        // Try with current credentials, refresh if connection fails
-        let result = self.try_get_match_history().await;
+    async fn get_free_rotation(&mut self) -> Result<Vec<i64>, LcuError> {
+        let json_value = self
+            .get_json("/lol-champions/v1/owned-champions-minimal")
+            .await?;
 
-        // If we got a connection error, try refreshing credentials once
-        if result.is_err() {
-            self.clear_credentials();
-            return self.try_get_match_history().await;
-        }
+        Ok(filter_champion_ids(&json_value, |c| {
+            c["freeToPlay"].as_bool().unwrap_or(false)
+        }))
+    }
 
-        result
+    async fn lookup_summoner(
+        &mut self,
+        game_name: &str,
+        tag_line: &str,
+    ) -> Result<SummonerInfo, LcuError> {
+        let alias: Alias = self
+            .get_typed_with_query(
+                "/lol-summoner/v1/alias/lookup",
+                &[("gameName", game_name), ("tagLine", tag_line)],
+            )
+            .await?;
+        let puuid = alias
+            .puuid
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| {
+                LcuError::Other(format!("No player found for {}#{}", game_name, tag_line))
+            })?;
+
+        let found: dto::Summoner = self
+            .get_typed(&format!("/lol-summoner/v2/summoners/puuid/{}", puuid))
+            .await?;
+        let mut summoner = parse_summoner(found);
+        summoner.game_name.get_or_insert_with(|| game_name.to_string());
+        summoner.tag_line.get_or_insert_with(|| tag_line.to_string());
+        Ok(summoner)
+    }
+
+    async fn get_ranked_stats_for(
+        &mut self,
+        puuid: &str,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError> {
+        let stats: RankedStatsResponse = self
+            .get_typed(&format!("/lol-ranked/v1/ranked-stats/{}", puuid))
+            .await?;
+        Ok(parse_ranked_stats(&stats, include_all_queues))
     }
 
-    async fn try_get_match_history(&mut self) -> Result<Vec<MatchHistoryGame>, String> {
-        self.try_get_match_history_paginated(0, 10).await
+    async fn get_top_masteries(
+        &mut self,
+        summoner_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ChampionMastery>, LcuError> {
+        let limit = limit.to_string();
+        let json_value = self
+            .get_json_with_query(
+                &format!(
+                    "/lol-collections/v1/inventories/{}/champion-mastery/top",
+                    summoner_id
+                ),
+                &[("limit", &limit)],
+            )
+            .await?;
+
+        Ok(parse_masteries(&json_value))
     }
 
-    pub async fn try_get_match_history_paginated(&mut self, beg_index: usize, end_index: usize) -> Result<Vec<MatchHistoryGame>, String> {
-        // Get summoner PUUID first
+    async fn get_chest_eligible_champion_ids(&mut self) -> Result<Vec<i64>, LcuError> {
         let summoner = self.get_current_summoner().await?;
-        let puuid = summoner.puuid;
+        let owned = self.get_owned_champion_ids().await?;
+        let masteries = self
+            .get_json(&format!(
+                "/lol-collections/v1/inventories/{}/champion-mastery",
+                summoner.summoner_id
+            ))
+            .await?;
+        Ok(chest_eligible(&owned, &parse_masteries(&masteries)))
+    }
 
-        let protocol;
-        let port;
-        let password;
-        {
-            let lockfile = self.get_lockfile()?;
-            protocol = lockfile.protocol.clone();
-            port = lockfile.port;
-            password = lockfile.password.clone();
-        }
+    async fn get_first_win_available(&mut self) -> Result<bool, LcuError> {
+        let missions = self.get_json("/lol-missions/v1/missions").await?;
+        Ok(parse_first_win_available(&missions))
+    }
 
-        let base_url = format!("{}://127.0.0.1:{}", protocol, port);
-        let url = format!(
-            "{}/lol-match-history/v1/products/lol/{}/matches?begIndex={}&endIndex={}",
-            base_url, puuid, beg_index, end_index
-        );
+    async fn get_missions(&mut self) -> Result<Vec<Mission>, LcuError> {
+        let json_value = self.get_json("/lol-missions/v1/missions").await?;
+        Ok(parse_missions(&json_value, now_millis()))
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .basic_auth("riot", Some(&password))
-            .send()
+    async fn get_current_queue_id(&mut self) -> Result<Option<i32>, LcuError> {
+        let session: GameflowSession = self.get_typed("/lol-gameflow/v1/session").await?;
+        Ok(session
+            .game_data
+            .queue
+            .id
+            .filter(|id| *id > 0)
+            .map(|id| id as i32))
+    }
+
+    async fn get_position_preferences(&mut self) -> Result<Vec<String>, LcuError> {
+        let lobby = self.get_json("/lol-lobby/v2/lobby").await?;
+        let member = &lobby["localMember"];
+        Ok(["firstPositionPreference", "secondPositionPreference"]
+            .iter()
+            .filter_map(|key| member[*key].as_str())
+            .map(|p| p.to_uppercase())
+            .filter(|p| !p.is_empty() && p != "UNSELECTED")
+            .collect())
+    }
+
+    async fn get_match_history_for(
+        &mut self,
+        puuid: &str,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        let (beg_index, end_index) = (beg_index.to_string(), end_index.to_string());
+        let history: MatchHistoryResponse = self
+            .get_typed_with_query(
+                &format!("/lol-match-history/v1/products/lol/{}/matches", puuid),
+                &[("begIndex", &beg_index), ("endIndex", &end_index)],
+            )
+            .await?;
+        Ok(parse_match_history(&history, puuid))
+    }
+
+    async fn get_game_details(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError> {
+        self.get_json(&format!("/lol-match-history/v1/games/{}", game_id))
             .await
-            .map_err(|e| format!("Request failed: {}", e))?;
+    }
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()));
-        }
+    async fn get_game_timeline(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError> {
+        self.get_json(&format!("/lol-match-history/v1/game-timelines/{}", game_id))
+            .await
+    }
+
+    async fn get_eog_stats_block(&mut self) -> Result<serde_json::Value, LcuError> {
+        self.get_json("/lol-end-of-game/v1/eog-stats-block").await
+    }
 
-        let json_value: serde_json::Value = response
-            .json()
+    async fn get_ranked_split(&mut self) -> Result<RankedSplit, LcuError> {
+        let stats = self.get_json("/lol-ranked/v1/current-ranked-stats").await?;
+        // Older clients don't serve the splits config; dates then fall back to the season end
+        let splits_config = self
+            .get_json("/lol-ranked/v1/splits-config")
             .await
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
-
-        let mut games = Vec::new();
-
-        // Try different possible structures
-        let games_array = json_value["games"]["games"]
-            .as_array()
-            .or_else(|| json_value["games"].as_array());
-
-        if let Some(games_arr) = games_array {
-            for game in games_arr.iter() {
-                let game_id = game["gameId"].as_i64().unwrap_or(0);
-                let game_mode = game["gameMode"].as_str().unwrap_or("").to_string();
-                let game_creation = game["gameCreation"].as_i64().unwrap_or(0);
-                let game_duration = game["gameDuration"].as_i64().unwrap_or(0) as i32;
-                let queue_id = game["queueId"].as_i64().unwrap_or(0) as i32;
-
-                if let Some(participant_identities) = game["participantIdentities"].as_array() {
-                    let participants_stats = game["participants"].as_array();
-
-                    for identity in participant_identities {
-                        let player = &identity["player"];
-                        let player_puuid = player["puuid"].as_str();
-
-                        if player_puuid == Some(&puuid) {
-                            let participant_id = identity["participantId"].as_i64().unwrap_or(0);
-
-                            if let Some(stats_array) = participants_stats {
-                                if let Some(participant_stats) = stats_array.iter().find(|p| {
-                                    p["participantId"].as_i64().unwrap_or(0) == participant_id
-                                }) {
-                                    let stats = &participant_stats["stats"];
-                                    let champion_id =
-                                        participant_stats["championId"].as_i64().unwrap_or(0)
-                                            as i32;
-                                    // Win can be boolean or string "Win"/"Fail"
-                                    let win = stats["win"].as_bool()
-                                        .unwrap_or_else(|| {
-                                            stats["win"].as_str().map(|s| s == "Win").unwrap_or(false)
-                                        });
-
-                                    games.push(MatchHistoryGame {
-                                        game_id,
-                                        queue_id,
-                                        champion_id,
-                                        game_mode: game_mode.clone(),
-                                        game_creation,
-                                        game_duration,
-                                        win,
-                                        kills: stats["kills"].as_i64().unwrap_or(0) as i32,
-                                        deaths: stats["deaths"].as_i64().unwrap_or(0) as i32,
-                                        assists: stats["assists"].as_i64().unwrap_or(0) as i32,
-                                    });
-                                }
-                            }
-                            break;
-                        }
-                    }
-                }
-            }
-        }
+            .unwrap_or(serde_json::Value::Null);
+        Ok(parse_ranked_split(&stats, &splits_config))
+    }
+
+    async fn patch_draft_action(
+        &mut self,
+        action_id: i64,
+        champion_id: i64,
+        complete: bool,
+    ) -> Result<(), LcuError> {
+        let body = serde_json::json!({ "championId": champion_id, "completed": complete });
+        self.send_json(
+            reqwest::Method::PATCH,
+            &format!("/lol-champ-select/v1/session/actions/{}", action_id),
+            Some(&body),
+        )
+        .await
+    }
 
-        Ok(games)
+    async fn play_again(&mut self) -> Result<(), LcuError> {
+        self.send_json(reqwest::Method::POST, "/lol-lobby/v2/play-again", None)
+            .await
+    }
+
+    async fn start_matchmaking(&mut self) -> Result<(), LcuError> {
+        self.send_json(
+            reqwest::Method::POST,
+            "/lol-lobby/v2/lobby/matchmaking/search",
+            None,
+        )
+        .await
+    }
+
+    async fn accept_ready_check(&mut self) -> Result<(), LcuError> {
+        self.send_json(
+            reqwest::Method::POST,
+            "/lol-matchmaking/v1/ready-check/accept",
+            None,
+        )
+        .await
+    }
+
+    async fn get_friends(&mut self) -> Result<Vec<Friend>, LcuError> {
+        self.get_typed("/lol-chat/v1/friends").await
+    }
+
+    async fn spectate(&mut self, puuid: &str) -> Result<(), LcuError> {
+        let body = serde_json::json!({
+            "allowObserveMode": "ALL",
+            "dropInSpectateGameId": "",
+            "gameQueueType": "",
+            "puuid": puuid,
+        });
+        self.send_json(
+            reqwest::Method::POST,
+            "/lol-spectator/v1/spectate/launch",
+            Some(&body),
+        )
+        .await
     }
 }
 
 // Tauri commands
+use crate::state;
 use std::sync::Arc;
 use tauri::State;
 
 #[tauri::command]
-pub async fn get_gameflow_phase(
-    client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<String, String> {
-    let result = {
-        let mut client_guard = client.lock().await;
-        client_guard.get_gameflow_phase().await
-    };
-    result
+pub async fn get_gameflow_phase(app: AppHandle) -> Result<String, LcuError> {
+    state::gameflow_phase(&app).await
 }
 
 #[tauri::command]
 pub async fn get_draft_session(
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<serde_json::Value, String> {
-    let result = {
-        let mut client_guard = client.lock().await;
-        client_guard.get_draft_session().await
-    };
-    result
+) -> Result<ChampSelectSession, LcuError> {
+    client.lock().await.get_draft_session().await
 }
 
+/// The current draft. With `include_raw`, the LCU session it was parsed from
+/// comes along in `raw_session`, for fields the parser doesn't model yet; both
+/// come from the same fetch rather than the possibly stale store.
 #[tauri::command]
 pub async fn get_draft_state(
+    app: AppHandle,
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<super::draft::DraftState, String> {
-    let result = {
-        let mut client_guard = client.lock().await;
-        client_guard.get_draft_state().await
-    };
-    result
+    include_raw: Option<bool>,
+) -> Result<super::draft::DraftState, LcuError> {
+    if !include_raw.unwrap_or(false) {
+        let mut draft = state::draft_state(&app).await?;
+        draft.raw_session = None;
+        return Ok(draft);
+    }
+
+    let session = client.lock().await.get_draft_session_json().await?;
+    let mut draft = state::resolve_draft(&app, &ChampSelectSession::deserialize(&session)?)?;
+    state::publish(&app, state::StateKey::DraftState, &draft);
+    draft.raw_session = Some(session);
+    Ok(draft)
 }
 
 #[tauri::command]
-pub async fn get_current_summoner(
+pub async fn get_current_summoner(app: AppHandle) -> Result<SummonerInfo, LcuError> {
+    state::current_summoner(&app).await
+}
+
+#[tauri::command]
+pub async fn get_ranked_stats(
+    app: AppHandle,
+    include_all_queues: Option<bool>,
+) -> Result<Vec<RankedStats>, LcuError> {
+    state::ranked_stats(&app, include_all_queues.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub async fn get_match_history(
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<SummonerInfo, String> {
+) -> Result<Vec<MatchHistoryGame>, LcuError> {
     let mut client_guard = client.lock().await;
-    client_guard.get_current_summoner().await
+    client_guard.get_match_history().await
 }
 
 #[tauri::command]
-pub async fn get_ranked_stats(
+pub async fn get_free_rotation(
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<Vec<RankedStats>, String> {
+) -> Result<Vec<i64>, LcuError> {
     let mut client_guard = client.lock().await;
-    client_guard.get_ranked_stats().await
+    client_guard.get_free_rotation().await
 }
 
 #[tauri::command]
-pub async fn get_match_history(
+pub async fn get_chest_eligible_champions(
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<Vec<MatchHistoryGame>, String> {
+) -> Result<Vec<i64>, LcuError> {
     let mut client_guard = client.lock().await;
-    client_guard.get_match_history().await
+    client_guard.get_chest_eligible_champion_ids().await
+}
+
+#[tauri::command]
+pub async fn get_first_win_available(
+    client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<bool, LcuError> {
+    let mut client_guard = client.lock().await;
+    client_guard.get_first_win_available().await
 }
 
 #[tauri::command]
@@ -516,7 +1141,179 @@ pub async fn get_match_history_paginated(
     client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
     beg_index: usize,
     end_index: usize,
-) -> Result<Vec<MatchHistoryGame>, String> {
+) -> Result<Vec<MatchHistoryGame>, LcuError> {
     let mut client_guard = client.lock().await;
-    client_guard.try_get_match_history_paginated(beg_index, end_index).await
+    client_guard.get_match_history_paginated(beg_index, end_index).await
+}
+
+/// Every running client, e.g. live and PBE or two accounts side by side.
+#[tauri::command]
+pub async fn list_detected_clients(
+    client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<Vec<DetectedClient>, LcuError> {
+    let (bound_port, wine_prefixes) = {
+        let mut client_guard = client.lock().await;
+        let bound_port = client_guard.get_lockfile().ok().map(|l| l.port);
+        (bound_port, client_guard.wine_prefixes().to_vec())
+    };
+    let clients = tokio::task::spawn_blocking(move || detect_clients(&wine_prefixes))
+        .await
+        .map_err(|e| LcuError::Other(format!("Client detection failed: {}", e)))?;
+    Ok(clients
+        .into_iter()
+        .map(|c| DetectedClient {
+            process_id: c.credentials.process_id,
+            port: c.credentials.port,
+            source: c.source,
+            profile: c.profile,
+            selected: Some(c.credentials.port) == bound_port,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_client_profile(
+    client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<ClientProfile, LcuError> {
+    let mut client_guard = client.lock().await;
+    client_guard.profile()
+}
+
+/// Bind to the client on `port`. `None` goes back to the first lockfile found.
+#[tauri::command]
+pub async fn select_client(
+    client: State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    port: Option<u16>,
+) -> Result<(), LcuError> {
+    if let Some(port) = port {
+        let wine_prefixes = client.lock().await.wine_prefixes().to_vec();
+        let clients = tokio::task::spawn_blocking(move || detect_clients(&wine_prefixes))
+            .await
+            .map_err(|e| LcuError::Other(format!("Client detection failed: {}", e)))?;
+        if !clients.iter().any(|c| c.credentials.port == port) {
+            return Err(LcuError::ClientNotRunning(format!(
+                "No client is running on port {}",
+                port
+            )));
+        }
+    }
+    let mut client_guard = client.lock().await;
+    client_guard.select_port(port);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_recent_responses_expire_after_the_window() {
+        let mut recent = RecentResponses::default();
+        let start = Instant::now();
+        let session = RecentResponses::key("/lol-champ-select/v1/session", &[]);
+        recent.insert(session.clone(), start, json!({ "gameId": 1 }));
+
+        assert_eq!(recent.get(&session, start), Some(json!({ "gameId": 1 })));
+        let paged = RecentResponses::key(
+            "/lol-match-history/v1/products/lol/a/matches",
+            &[("begIndex", "0")],
+        );
+        assert!(recent.get(&paged, start).is_none());
+        assert!(recent.get(&session, start + COALESCE_WINDOW).is_none());
+
+        recent.insert(paged.clone(), start + COALESCE_WINDOW, json!([]));
+        assert_eq!(recent.entries.len(), 1, "expired entries are dropped");
+    }
+
+    #[test]
+    fn test_retry_policies_by_endpoint_class() {
+        let settings = AppSettings {
+            lcu_polling_timeout_ms: Some(500),
+            lcu_history_retries: Some(0),
+            ..Default::default()
+        };
+        let policies = RetryPolicies::from_settings(&settings);
+        let polling = policies.get(EndpointClass::of("/lol-champ-select/v1/session"));
+        assert_eq!(polling.timeout, Duration::from_millis(500));
+        assert_eq!(polling.retries, RetryPolicies::default().polling.retries);
+
+        let history = policies.get(EndpointClass::of("/lol-match-history/v1/games/1"));
+        assert_eq!(history.retries, 0);
+        assert_eq!(history.timeout, Duration::from_secs(10));
+        assert_eq!(history.delay(0), Duration::from_millis(500));
+        assert_eq!(history.delay(2), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_parse_ranked_stats_queues_and_promos() {
+        let payload = json!({
+            "queues": [
+                {
+                    "queueType": "RANKED_SOLO_5x5", "tier": "GOLD", "division": "I",
+                    "leaguePoints": 100, "wins": 20, "losses": 18,
+                    "highestTier": "PLATINUM", "highestDivision": "IV",
+                    "previousSeasonEndTier": "SILVER", "previousSeasonEndDivision": "II",
+                    "miniSeriesProgress": "WLN"
+                },
+                { "queueType": "RANKED_FLEX_SR", "tier": "NONE", "isProvisional": true },
+                { "queueType": "RANKED_TFT", "tier": "BRONZE", "division": "III", "leaguePoints": 12 },
+                { "queueType": "RANKED_TFT_TURBO", "tier": "NONE", "ratedTier": "GREEN", "ratedRating": 1700 }
+            ]
+        });
+
+        let payload: RankedStatsResponse = serde_json::from_value(payload).unwrap();
+        let rift = parse_ranked_stats(&payload, false);
+        assert_eq!(rift.len(), 1);
+        let solo = &rift[0];
+        assert_eq!(solo.highest_tier.as_deref(), Some("PLATINUM"));
+        assert_eq!(solo.previous_season_end_division.as_deref(), Some("II"));
+        let series = solo.miniseries.as_ref().unwrap();
+        assert_eq!((series.wins, series.losses, series.target), (1, 1, 2));
+
+        let all = parse_ranked_stats(&payload, true);
+        assert_eq!(all.len(), 3);
+        let turbo = all.iter().find(|q| q.queue_type == "RANKED_TFT_TURBO").unwrap();
+        assert_eq!((turbo.tier.as_str(), turbo.league_points), ("GREEN", 1700));
+    }
+
+    #[test]
+    fn test_unknown_history_shapes_reach_the_stats() {
+        let mut stats = RequestCounters::default();
+        let history: MatchHistoryResponse = decode(
+            &json!({ "games": { "games": [{ "gameId": 1, "participants": [
+                { "participantId": 1, "championId": "157", "stats": { "win": "Remake" } }
+            ] }] } }),
+            "/lol-match-history/v1/products/lol/a/matches",
+            &mut stats,
+        )
+        .unwrap();
+        assert_eq!(history.games().len(), 1);
+        assert_eq!(stats.report().unknown_shapes, 1);
+
+        let history: MatchHistoryResponse = decode(
+            &json!({ "games": { "list": [] } }),
+            "/lol-match-history/v1/products/lol/a/matches",
+            &mut stats,
+        )
+        .unwrap();
+        assert!(history.games().is_empty());
+        assert_eq!(stats.report().unknown_shapes, 2);
+    }
+
+    #[test]
+    fn test_chest_eligibility_and_first_win() {
+        let masteries = parse_masteries(&json!([
+            { "championId": 1, "chestGranted": true },
+            { "championId": 2, "chestGranted": false }
+        ]));
+        assert_eq!(chest_eligible(&[1, 2, 3], &masteries), vec![2, 3]);
+
+        let missions = json!([
+            { "internalName": "Event_Mission_1", "status": "PENDING" },
+            { "internalName": "fwotd_mission", "status": "COMPLETED" }
+        ]);
+        assert!(!parse_first_win_available(&missions));
+        assert!(parse_first_win_available(&json!([])));
+    }
 }
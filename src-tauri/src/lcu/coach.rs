@@ -0,0 +1,342 @@
+use crate::champions::cache::ChampionCache;
+use crate::lcu::composition::{find_gaps, CompGap};
+use crate::lcu::draft::DraftState;
+use crate::lcu::pool::{local_position, model_scores, suggest_alternatives};
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Model picks named when the player's turn comes, and remembered to notice the
+/// enemy taking one.
+const SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Actor {
+    Me,
+    Ally,
+    Enemy,
+}
+
+impl Actor {
+    fn subject(self) -> &'static str {
+        match self {
+            Actor::Me => "You",
+            Actor::Ally => "Your team",
+            Actor::Enemy => "The enemy",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftStep {
+    Ban { actor: Actor, champion_id: i64 },
+    Pick { actor: Actor, champion_id: i64 },
+    PickTurn, // The local player's pick just started
+}
+
+/// What the analysis modules found about a step, with champions already named.
+#[derive(Debug, Clone, Default)]
+pub struct StepContext {
+    pub champion: String,
+    pub in_pool: bool,
+    pub alternative: Option<String>, // Similar champion still available
+    pub took_suggestion: bool,       // An enemy lock of a champion the model suggested
+    pub suggestions: Vec<String>,    // Current model picks for the player, best first
+    pub new_gaps: Vec<CompGap>,
+    pub picks_left: usize,
+}
+
+/// One line of commentary for a step, from fixed templates.
+pub fn commentary(step: DraftStep, ctx: &StepContext) -> String {
+    let mut line = match step {
+        DraftStep::Ban { actor, .. } => format!("{} banned {}.", actor.subject(), ctx.champion),
+        DraftStep::Pick { actor, .. } => format!("{} locked {}.", actor.subject(), ctx.champion),
+        DraftStep::PickTurn => match ctx.suggestions.as_slice() {
+            [] => "Your pick is up.".to_string(),
+            [only] => format!("Your pick is up; {} looks strong here.", only),
+            [rest @ .., last] => {
+                format!("Your pick is up; consider {} or {}.", rest.join(", "), last)
+            }
+        },
+    };
+
+    if ctx.took_suggestion {
+        match ctx.suggestions.first() {
+            Some(next) => {
+                line += &format!(
+                    " That was one of your best picks; consider {} instead.",
+                    next
+                )
+            }
+            None => line += " That was one of your best picks.",
+        }
+    }
+    if ctx.in_pool {
+        match &ctx.alternative {
+            Some(alt) => line += &format!(" It's in your pool; {} plays similarly.", alt),
+            None => line += " It's in your pool.",
+        }
+    }
+    for gap in &ctx.new_gaps {
+        let advice = match gap {
+            CompGap::NoFrontline => "Nobody can frontline yet",
+            CompGap::FullAd => "The damage so far is all physical",
+            CompGap::FullAp => "The damage so far is all magic",
+        };
+        line += &match ctx.picks_left {
+            0 => format!(" {}.", advice),
+            1 => format!(" {}; the last pick can still fix it.", advice),
+            n => format!(" {}; {} picks left to fix it.", advice, n),
+        };
+    }
+    line
+}
+
+/// Payload of the `coach-commentary` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoachLine {
+    pub action_id: i64,
+    pub text: String,
+}
+
+/// Turns completed draft actions into steps, each one once.
+#[derive(Default)]
+pub struct CoachWatcher {
+    seen: HashSet<i64>,
+    suggested: Vec<i64>, // Model picks from the previous step
+    gaps: HashSet<CompGap>,
+}
+
+impl CoachWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Steps not commented on yet, with the action they came from.
+    pub fn new_steps(&mut self, state: &DraftState) -> Vec<(i64, DraftStep)> {
+        let local_cell = state.local_player_cell_id;
+        let allies: HashSet<i64> = state
+            .local_team()
+            .map(|t| t.cells.iter().map(|c| c.cell_id).collect())
+            .unwrap_or_default();
+        let actor = |cell: Option<i64>| match cell {
+            Some(cell) if Some(cell) == local_cell => Actor::Me,
+            Some(cell) if allies.contains(&cell) => Actor::Ally,
+            _ => Actor::Enemy,
+        };
+
+        let mut steps = Vec::new();
+        for action in &state.actions {
+            let step = match (action.action_type.as_str(), action.champion_id) {
+                ("ban", Some(champion_id)) if action.completed => DraftStep::Ban {
+                    actor: actor(action.actor_cell_id),
+                    champion_id,
+                },
+                ("pick", Some(champion_id)) if action.completed => DraftStep::Pick {
+                    actor: actor(action.actor_cell_id),
+                    champion_id,
+                },
+                ("pick", _) if action.is_in_progress && action.actor_cell_id == local_cell => {
+                    DraftStep::PickTurn
+                }
+                _ => continue,
+            };
+            // A turn and its lock are separate steps of the same action
+            let key = if step == DraftStep::PickTurn {
+                -action.id - 1
+            } else {
+                action.id
+            };
+            if self.seen.insert(key) {
+                steps.push((action.id, step));
+            }
+        }
+        steps
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn champion_name(cache: Option<&ChampionCache>, champion_id: i64) -> String {
+    cache
+        .and_then(|c| c.get_champion_by_id(champion_id))
+        .map(|c| c.name)
+        .unwrap_or_else(|| format!("champion {}", champion_id))
+}
+
+/// Emit `coach-commentary` for each new draft step when coach mode is on.
+pub fn announce_commentary(app: &AppHandle, watcher: &mut CoachWatcher, state: &DraftState) {
+    let pool = match app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+    {
+        Some(settings) if settings.coach_mode => settings.champion_pool,
+        _ => return,
+    };
+    let steps = watcher.new_steps(state);
+    if steps.is_empty() {
+        return;
+    }
+
+    let position = local_position(state);
+    let unavailable = state.unavailable_champions();
+    let mut scores: Vec<(i64, f32)> = model_scores(app, state, position.as_deref())
+        .into_iter()
+        .filter(|(id, _)| !unavailable.contains(id))
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let suggested: Vec<i64> = scores.iter().take(SUGGESTIONS).map(|(id, _)| *id).collect();
+
+    let cache = app.try_state::<Mutex<ChampionCache>>();
+    let (suggestions, allies, picks_left) = {
+        let cache_guard = cache.as_ref().and_then(|c| c.lock().ok());
+        let suggestions: Vec<String> = suggested
+            .iter()
+            .map(|id| champion_name(cache_guard.as_deref(), *id))
+            .collect();
+        let cells = state.local_team().map_or(&[][..], |t| &t.cells);
+        let locked: Vec<i64> = cells.iter().filter_map(|c| c.champion_id).collect();
+        let allies: Vec<_> = locked
+            .iter()
+            .filter_map(|id| cache_guard.as_ref()?.get_champion_by_id(*id))
+            .collect();
+        let picks_left = cells.len() - locked.len();
+        (suggestions, allies, picks_left)
+    };
+    let gaps = find_gaps(&allies);
+
+    for (action_id, step) in steps {
+        let mut ctx = StepContext {
+            suggestions: suggestions.clone(),
+            picks_left,
+            ..StepContext::default()
+        };
+        match step {
+            DraftStep::Ban { champion_id, .. } | DraftStep::Pick { champion_id, .. } => {
+                ctx.champion = {
+                    let cache_guard = cache.as_ref().and_then(|c| c.lock().ok());
+                    champion_name(cache_guard.as_deref(), champion_id)
+                };
+                ctx.in_pool = pool.contains(&champion_id)
+                    && !matches!(
+                        step,
+                        DraftStep::Pick {
+                            actor: Actor::Me,
+                            ..
+                        }
+                    );
+                if ctx.in_pool {
+                    ctx.alternative =
+                        suggest_alternatives(app, state, champion_id, position.as_deref())
+                            .into_iter()
+                            .next()
+                            .map(|alt| alt.name);
+                }
+                if let DraftStep::Pick { actor, .. } = step {
+                    ctx.took_suggestion =
+                        actor == Actor::Enemy && watcher.suggested.contains(&champion_id);
+                    if actor != Actor::Enemy {
+                        ctx.new_gaps = gaps
+                            .iter()
+                            .copied()
+                            .filter(|gap| watcher.gaps.insert(*gap))
+                            .collect();
+                    }
+                }
+            }
+            DraftStep::PickTurn => {}
+        }
+
+        let line = CoachLine {
+            action_id,
+            text: commentary(step, &ctx),
+        };
+        let _ = app.emit("coach-commentary", &line);
+    }
+    watcher.suggested = suggested;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::draft::parse_draft_session;
+    use serde_json::json;
+
+    #[test]
+    fn test_steps_and_templates() {
+        let session = json!({
+            "gameId": 1,
+            "localPlayerCellId": 0,
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [{ "cellId": 0, "championId": 0, "championPickIntent": 0 }],
+            "theirTeam": [{ "cellId": 5, "championId": 64, "championPickIntent": 0 }],
+            "actions": [
+                [{ "id": 1, "actorCellId": 5, "championId": 157, "completed": true, "type": "ban" }],
+                [{ "id": 2, "actorCellId": 5, "championId": 64, "completed": true, "type": "pick" }],
+                [{ "id": 3, "actorCellId": 0, "championId": 0, "completed": false, "isInProgress": true, "type": "pick" }]
+            ]
+        });
+        let state = parse_draft_session(&session).unwrap();
+        let mut watcher = CoachWatcher::new();
+        let steps: Vec<DraftStep> = watcher
+            .new_steps(&state)
+            .into_iter()
+            .map(|(_, s)| s)
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                DraftStep::Ban {
+                    actor: Actor::Enemy,
+                    champion_id: 157
+                },
+                DraftStep::Pick {
+                    actor: Actor::Enemy,
+                    champion_id: 64
+                },
+                DraftStep::PickTurn,
+            ]
+        );
+        assert!(watcher.new_steps(&state).is_empty());
+
+        let ctx = StepContext {
+            champion: "Lee Sin".to_string(),
+            took_suggestion: true,
+            suggestions: vec!["Amumu".to_string(), "Vi".to_string()],
+            ..StepContext::default()
+        };
+        assert_eq!(
+            commentary(steps[1], &ctx),
+            "The enemy locked Lee Sin. That was one of your best picks; consider Amumu instead."
+        );
+        let ctx = StepContext {
+            took_suggestion: false,
+            ..ctx
+        };
+        assert_eq!(
+            commentary(DraftStep::PickTurn, &ctx),
+            "Your pick is up; consider Amumu or Vi."
+        );
+
+        let ctx = StepContext {
+            champion: "Jinx".to_string(),
+            new_gaps: vec![CompGap::NoFrontline],
+            picks_left: 2,
+            ..StepContext::default()
+        };
+        assert_eq!(
+            commentary(
+                DraftStep::Pick {
+                    actor: Actor::Ally,
+                    champion_id: 222
+                },
+                &ctx
+            ),
+            "Your team locked Jinx. Nobody can frontline yet; 2 picks left to fix it."
+        );
+    }
+}
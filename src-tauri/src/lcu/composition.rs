@@ -0,0 +1,203 @@
+use crate::champions::cache::ChampionCache;
+use crate::champions::client::Champion;
+use crate::lcu::draft::DraftState;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Allied locks needed before gaps are judged; earlier ones are too noisy.
+const MIN_LOCKS: usize = 3;
+
+/// Data Dragon defense rating from which a fighter counts as frontline.
+const FRONTLINE_DEFENSE: u8 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompGap {
+    NoFrontline,
+    FullAd,
+    FullAp,
+}
+
+impl CompGap {
+    fn message(self) -> &'static str {
+        match self {
+            CompGap::NoFrontline => "No frontline: nobody on the team can soak damage",
+            CompGap::FullAd => "Full AD: all damage so far is physical",
+            CompGap::FullAp => "Full AP: all damage so far is magic",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DamageType {
+    Physical,
+    Magic,
+    Mixed,
+}
+
+fn damage_type(champion: &Champion) -> Option<DamageType> {
+    if let Some(info) = champion.info.as_ref().filter(|i| i.attack + i.magic > 0) {
+        let physical = info.attack as f32 / (info.attack + info.magic) as f32;
+        return Some(if physical >= 0.6 {
+            DamageType::Physical
+        } else if physical <= 0.4 {
+            DamageType::Magic
+        } else {
+            DamageType::Mixed
+        });
+    }
+    // Older cached data has no ratings; fall back on the class
+    let has = |tag: &str| champion.tags.iter().any(|t| t == tag);
+    if has("Mage") {
+        Some(DamageType::Magic)
+    } else if has("Marksman") {
+        Some(DamageType::Physical)
+    } else {
+        None
+    }
+}
+
+fn is_frontline(champion: &Champion) -> bool {
+    let has = |tag: &str| champion.tags.iter().any(|t| t == tag);
+    has("Tank")
+        || (has("Fighter")
+            && champion
+                .info
+                .as_ref()
+                .is_some_and(|i| i.defense >= FRONTLINE_DEFENSE))
+}
+
+/// Gaps in a team made of `champions`, once enough of them are locked.
+pub fn find_gaps(champions: &[Champion]) -> Vec<CompGap> {
+    if champions.len() < MIN_LOCKS {
+        return Vec::new();
+    }
+
+    let mut gaps = Vec::new();
+    if !champions.iter().any(is_frontline) {
+        gaps.push(CompGap::NoFrontline);
+    }
+    let damage: Vec<DamageType> = champions.iter().filter_map(damage_type).collect();
+    if !damage.is_empty() && damage.iter().all(|d| *d == DamageType::Physical) {
+        gaps.push(CompGap::FullAd);
+    }
+    if !damage.is_empty() && damage.iter().all(|d| *d == DamageType::Magic) {
+        gaps.push(CompGap::FullAp);
+    }
+    gaps
+}
+
+/// Payload of the `comp-gap` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompGapWarning {
+    pub gap: CompGap,
+    pub message: String,
+    pub locked: usize,
+    pub picks_left: usize,
+}
+
+/// Re-evaluates the allied composition after each lock and reports gaps the
+/// first time they appear in a draft.
+#[derive(Default)]
+pub struct CompGapWatcher {
+    locked: usize,
+    reported: HashSet<CompGap>,
+}
+
+impl CompGapWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(
+        &mut self,
+        state: &DraftState,
+        champion: impl Fn(i64) -> Option<Champion>,
+    ) -> Vec<CompGapWarning> {
+        let Some(team) = state.local_team() else {
+            return Vec::new();
+        };
+        let locked: Vec<i64> = team.cells.iter().filter_map(|c| c.champion_id).collect();
+        if locked.len() == self.locked {
+            return Vec::new();
+        }
+        self.locked = locked.len();
+
+        let champions: Vec<Champion> = locked.into_iter().filter_map(champion).collect();
+        find_gaps(&champions)
+            .into_iter()
+            .filter(|gap| self.reported.insert(*gap))
+            .map(|gap| CompGapWarning {
+                gap,
+                message: gap.message().to_string(),
+                locked: self.locked,
+                picks_left: team.cells.len() - self.locked,
+            })
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Emit `comp-gap` for gaps that appeared with the latest allied lock.
+pub fn announce_comp_gaps(app: &AppHandle, watcher: &mut CompGapWatcher, state: &DraftState) {
+    let Some(cache) = app.try_state::<Mutex<ChampionCache>>() else {
+        return;
+    };
+    let warnings = match cache.lock() {
+        Ok(cache_guard) => watcher.check(state, |id| cache_guard.get_champion_by_id(id)),
+        Err(_) => return,
+    };
+    for warning in warnings {
+        let _ = app.emit("comp-gap", &warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::client::ChampionInfo;
+
+    fn champion(key: i64, tags: &[&str], attack: u8, defense: u8, magic: u8) -> Champion {
+        Champion {
+            id: key.to_string(),
+            key,
+            name: key.to_string(),
+            title: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            info: Some(ChampionInfo {
+                attack,
+                defense,
+                magic,
+                difficulty: 5,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let jinx = champion(222, &["Marksman"], 9, 2, 4);
+        let zed = champion(238, &["Assassin"], 9, 2, 1);
+        let talon = champion(91, &["Assassin"], 9, 3, 1);
+        let darius = champion(122, &["Fighter", "Tank"], 9, 5, 1);
+        let lux = champion(99, &["Mage", "Support"], 2, 4, 9);
+
+        assert!(
+            find_gaps(&[jinx.clone(), zed.clone()]).is_empty(),
+            "too early"
+        );
+        assert_eq!(
+            find_gaps(&[jinx.clone(), zed.clone(), talon]),
+            vec![CompGap::NoFrontline, CompGap::FullAd]
+        );
+        assert_eq!(
+            find_gaps(&[jinx, zed, darius.clone()]),
+            vec![CompGap::FullAd]
+        );
+        assert!(find_gaps(&[darius, lux, champion(1, &["Mage"], 2, 3, 10)]).is_empty());
+    }
+}
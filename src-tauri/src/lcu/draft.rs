@@ -1,7 +1,10 @@
+use super::dto::{ChampSelectSession, SessionMember, SessionTrade};
+use crate::champions::cache::ChampionCache;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DraftState {
     pub game_id: Option<i64>,
     pub timer: Option<f64>,
@@ -9,9 +12,57 @@ pub struct DraftState {
     pub teams: Vec<Team>,
     pub actions: Vec<DraftAction>,
     pub local_player_cell_id: Option<i64>, // The current player's cell ID from LCU
+    #[serde(default)]
+    pub is_custom_game: bool,
+    #[serde(default)]
+    pub queue_id: Option<i32>, // None when the client doesn't send it
+    /// Things the parser had to skip or correct; empty for well-formed sessions
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Bench and rerolls, in modes with a shared bench like ARAM
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aram: Option<AramState>,
+    /// Trades and swaps offered to or by the local player, awaiting an answer
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub swap_offers: Vec<SwapOffer>,
+    /// Champions picked earlier in a fearless series, unavailable like bans
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub series_excluded: Vec<i64>,
+    /// The untouched LCU session, when asked for with `include_raw`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_session: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapKind {
+    Champion,  // A champion trade, after both have locked
+    PickOrder, // Exchanging pick turns
+    Position,  // Exchanging assigned positions
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapOffer {
+    pub id: Option<i64>,
+    pub kind: SwapKind,
+    pub cell_id: i64,   // The ally on the other end
+    pub incoming: bool, // Offered to the local player rather than by them
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AramState {
+    pub bench: Vec<BenchChampion>,
+    pub rerolls_remaining: i64,
+    pub can_reroll: bool, // Rerolling is allowed and one is left
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchChampion {
+    pub champion_id: i64,
+    pub is_priority: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
     pub team_id: i64,
     pub picks: Vec<ChampionPick>,
@@ -19,7 +70,7 @@ pub struct Team {
     pub cells: Vec<Cell>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Cell {
     pub cell_id: i64,
     pub champion_id: Option<i64>,          // Locked champion
@@ -27,9 +78,83 @@ pub struct Cell {
     pub assigned_position: Option<String>,
     pub spell1_id: Option<i64>,
     pub spell2_id: Option<i64>,
+    #[serde(default)]
+    pub spell1_name: Option<String>, // Resolved from the summoner spell static data
+    #[serde(default)]
+    pub spell2_name: Option<String>,
+    #[serde(default)]
+    pub trade_available: bool, // Whether this cell can trade champions with the local player
+    #[serde(default)]
+    pub pick_swap_available: bool, // Whether this cell can swap pick turns with the local player
+    #[serde(default)]
+    pub position_swap_available: bool,
+    #[serde(default)]
+    pub pick_turn: Option<usize>, // 1-based place in the team's pick order, after any swaps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_hovers: Vec<i64>, // Enemy champions hovered earlier in this draft, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_hover_names: Vec<String>,
+}
+
+impl DraftState {
+    /// The team the local player is on.
+    pub fn local_team(&self) -> Option<&Team> {
+        let local_cell = self.local_player_cell_id?;
+        self.teams
+            .iter()
+            .find(|t| t.cells.iter().any(|c| c.cell_id == local_cell))
+    }
+
+    pub fn local_cell(&self) -> Option<&Cell> {
+        let local_cell = self.local_player_cell_id?;
+        self.local_team()?
+            .cells
+            .iter()
+            .find(|c| c.cell_id == local_cell)
+    }
+
+    /// Champions nobody can pick anymore: completed bans and locked picks.
+    pub fn unavailable_champions(&self) -> HashSet<i64> {
+        self.teams
+            .iter()
+            .flat_map(|team| {
+                let bans = team
+                    .bans
+                    .iter()
+                    .filter(|b| b.completed)
+                    .map(|b| b.champion_id);
+                let picks = team.cells.iter().filter_map(|c| c.champion_id);
+                bans.chain(picks)
+            })
+            .collect()
+    }
+
+    /// Fill in summoner spell names for every cell from the cached static data.
+    /// Cells keep `None` when the spell data hasn't been fetched yet.
+    pub fn resolve_spell_names(&mut self, cache: &ChampionCache) {
+        for cell in self.teams.iter_mut().flat_map(|t| t.cells.iter_mut()) {
+            cell.spell1_name = cell
+                .spell1_id
+                .and_then(|id| cache.get_summoner_spell_name(id));
+            cell.spell2_name = cell
+                .spell2_id
+                .and_then(|id| cache.get_summoner_spell_name(id));
+        }
+    }
+
+    /// Fill in champion names for `previous_hovers`. Unknown ids are skipped.
+    pub fn resolve_hover_names(&mut self, cache: &ChampionCache) {
+        for cell in self.teams.iter_mut().flat_map(|t| t.cells.iter_mut()) {
+            cell.previous_hover_names = cell
+                .previous_hovers
+                .iter()
+                .filter_map(|id| cache.get_champion_by_id(*id).map(|c| c.name))
+                .collect();
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChampionPick {
     pub champion_id: i64,
     pub cell_id: Option<i64>,
@@ -38,7 +163,7 @@ pub struct ChampionPick {
     pub position: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ChampionBan {
     pub champion_id: i64,
     pub cell_id: Option<i64>,
@@ -46,7 +171,7 @@ pub struct ChampionBan {
     pub is_ally_ban: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DraftAction {
     pub id: i64,
     pub actor_cell_id: Option<i64>,
@@ -54,279 +179,708 @@ pub struct DraftAction {
     pub selected_champion_id: Option<i64>, // Rarely used by LCU, kept for compatibility
     pub completed: bool,
     pub is_in_progress: bool, // Whether this action is currently active
+    #[serde(default)]
+    pub is_ally_action: Option<bool>,
     #[serde(rename = "type")]
     pub action_type: String,
 }
 
-pub fn parse_draft_session(session: &serde_json::Value) -> Result<DraftState, String> {
-    let game_id = session["gameId"].as_i64();
+/// Cells the local player can currently offer each kind of swap to.
+struct SwapAvailability {
+    trade: HashSet<i64>,
+    pick_order: HashSet<i64>,
+    position: HashSet<i64>,
+}
 
-    // Timer can be in milliseconds, convert to seconds if > 1000
-    let timer_raw = session["timer"]["adjustedTimeLeftInPhase"]
-        .as_f64()
-        .or_else(|| session["timer"]["timeLeftInPhase"].as_f64());
-    let timer = timer_raw.map(|t| {
-        if t > 1000.0 {
-            t / 1000.0 // Convert milliseconds to seconds
-        } else {
-            t
-        }
-    });
+/// Cells whose offer is in `AVAILABLE` state. Offers are listed from the local
+/// player's point of view, one per other cell.
+fn available_cells(offers: &[SessionTrade]) -> HashSet<i64> {
+    offers
+        .iter()
+        .filter(|o| o.state.as_deref() == Some("AVAILABLE"))
+        .filter_map(|o| o.cell_id)
+        .collect()
+}
 
-    let phase = session["timer"]["phase"]
-        .as_str()
-        .unwrap_or("Unknown")
-        .to_string();
+/// Offers sent or received and not yet answered.
+fn pending_offers(kind: SwapKind, offers: &[SessionTrade]) -> impl Iterator<Item = SwapOffer> + '_ {
+    offers.iter().filter_map(move |o| {
+        let incoming = match o.state.as_deref()? {
+            "SENT" => false,
+            "RECEIVED" => true,
+            _ => return None,
+        };
+        Some(SwapOffer {
+            id: o.id,
+            kind,
+            cell_id: o.cell_id?,
+            incoming,
+        })
+    })
+}
 
-    let mut teams = Vec::new();
+/// Build one side of the lobby (`myTeam` or `theirTeam`).
+///
+/// Cell ids are taken as-is: customs with spectators and red-side lobbies don't
+/// follow the 0-4 / 5-9 layout, so membership in the array is what decides the team.
+fn parse_team(
+    members: &[SessionMember],
+    team_id: i64,
+    is_ally: bool,
+    swappable: &SwapAvailability,
+    seen_cell_ids: &mut HashSet<i64>,
+    warnings: &mut Vec<String>,
+) -> Team {
+    let mut picks = Vec::new();
+    let mut cells = Vec::new();
+
+    for member in members {
+        let Some(cell_id) = member.cell_id else {
+            warnings.push(format!("Skipped a team {} cell without a cellId", team_id));
+            continue;
+        };
+        if !seen_cell_ids.insert(cell_id) {
+            warnings.push(format!(
+                "Cell {} appears more than once; kept the first",
+                cell_id
+            ));
+            continue;
+        }
 
-    // Parse myTeam (team 100 - Blue side)
-    if let Some(my_team_array) = session["myTeam"].as_array() {
-        let mut picks = Vec::new();
-        let mut cells = Vec::new();
-
-        for cell_data in my_team_array {
-            let cell_id = cell_data["cellId"].as_i64().unwrap_or(0);
-            let champion_id = cell_data["championId"].as_i64().or_else(|| {
-                cell_data["championId"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-            });
+        let champion_id = member.champion_id;
+
+        // Try multiple fields for selected champion (LCU API varies)
+        let selected_champion_id = member.champion_pick_intent.or(member.selected_champion_id);
+
+        let assigned_position = member.assigned_position.clone().filter(|s| !s.is_empty());
+
+        cells.push(Cell {
+            cell_id,
+            champion_id,
+            selected_champion_id,
+            assigned_position: assigned_position.clone(),
+            spell1_id: member.spell1_id,
+            spell2_id: member.spell2_id,
+            spell1_name: None,
+            spell2_name: None,
+            trade_available: swappable.trade.contains(&cell_id),
+            pick_swap_available: swappable.pick_order.contains(&cell_id),
+            position_swap_available: swappable.position.contains(&cell_id),
+            pick_turn: None,
+            previous_hovers: Vec::new(),
+            previous_hover_names: Vec::new(),
+        });
 
-            // Try multiple fields for selected champion (LCU API varies)
-            let selected_champion_id = cell_data["championPickIntent"]
-                .as_i64()
-                .or_else(|| cell_data["selectedChampionId"].as_i64())
-                .or_else(|| {
-                    cell_data["championPickIntent"]
-                        .as_str()
-                        .and_then(|s| s.parse().ok())
-                })
-                .or_else(|| {
-                    cell_data["selectedChampionId"]
-                        .as_str()
-                        .and_then(|s| s.parse().ok())
-                });
-
-            cells.push(Cell {
-                cell_id,
-                champion_id,
-                selected_champion_id,
-                assigned_position: cell_data["assignedPosition"]
-                    .as_str()
-                    .map(|s| s.to_string()),
-                spell1_id: cell_data["spell1Id"].as_i64(),
-                spell2_id: cell_data["spell2Id"].as_i64(),
+        // If champion is locked (championId exists), add to picks
+        if let Some(champ_id) = champion_id {
+            picks.push(ChampionPick {
+                champion_id: champ_id,
+                cell_id: Some(cell_id),
+                completed: true,
+                is_ally_pick: is_ally,
+                position: assigned_position,
             });
-
-            // If champion is locked (championId exists), add to picks
-            if let Some(champ_id) = champion_id {
-                picks.push(ChampionPick {
-                    champion_id: champ_id,
-                    cell_id: Some(cell_id),
-                    completed: true,
-                    is_ally_pick: true,
-                    position: cell_data["assignedPosition"]
-                        .as_str()
-                        .map(|s| s.to_string()),
-                });
-            }
         }
+    }
 
-        teams.push(Team {
-            team_id: 100,
-            picks,
-            bans: Vec::new(),
-            cells,
-        });
+    Team {
+        team_id,
+        picks,
+        bans: Vec::new(),
+        cells,
     }
+}
 
-    // Parse theirTeam (team 200 - Red side)
-    if let Some(their_team_array) = session["theirTeam"].as_array() {
-        let mut picks = Vec::new();
-        let mut cells = Vec::new();
+/// Team id (100 blue, 200 red) of the side `members` play on. The client's
+/// `team` field decides; without it, cells 0-4 are blue and 5-9 red.
+fn team_side(members: &[SessionMember]) -> Option<i64> {
+    if let Some(team) = members.iter().find_map(|m| m.team) {
+        return match team {
+            1 => Some(100),
+            2 => Some(200),
+            _ => None,
+        };
+    }
+    let cell_ids: Vec<i64> = members.iter().filter_map(|m| m.cell_id).collect();
+    if cell_ids.is_empty() {
+        None
+    } else if cell_ids.iter().all(|id| (0..5).contains(id)) {
+        Some(100)
+    } else if cell_ids.iter().all(|id| (5..10).contains(id)) {
+        Some(200)
+    } else {
+        None
+    }
+}
 
-        for cell_data in their_team_array {
-            let cell_id = cell_data["cellId"].as_i64().unwrap_or(0);
-            let champion_id = cell_data["championId"].as_i64().or_else(|| {
-                cell_data["championId"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-            });
+fn other_side(team_id: i64) -> i64 {
+    if team_id == 100 {
+        200
+    } else {
+        100
+    }
+}
 
-            // Try multiple fields for selected champion (LCU API varies)
-            let selected_champion_id = cell_data["championPickIntent"]
-                .as_i64()
-                .or_else(|| cell_data["selectedChampionId"].as_i64())
-                .or_else(|| {
-                    cell_data["championPickIntent"]
-                        .as_str()
-                        .and_then(|s| s.parse().ok())
-                })
-                .or_else(|| {
-                    cell_data["selectedChampionId"]
-                        .as_str()
-                        .and_then(|s| s.parse().ok())
-                });
-
-            cells.push(Cell {
-                cell_id,
-                champion_id,
-                selected_champion_id,
-                assigned_position: cell_data["assignedPosition"]
-                    .as_str()
-                    .map(|s| s.to_string()),
-                spell1_id: cell_data["spell1Id"].as_i64(),
-                spell2_id: cell_data["spell2Id"].as_i64(),
-            });
+/// Parse a raw champ-select session, as saved in fixtures or received from
+/// something other than the client.
+pub fn parse_draft_session(session: &serde_json::Value) -> Result<DraftState, String> {
+    let session = ChampSelectSession::deserialize(session)
+        .map_err(|e| format!("Failed to parse champ select session: {}", e))?;
+    Ok(draft_state(&session))
+}
 
-            if let Some(champ_id) = champion_id {
-                picks.push(ChampionPick {
-                    champion_id: champ_id,
-                    cell_id: Some(cell_id),
-                    completed: true,
-                    is_ally_pick: false,
-                    position: cell_data["assignedPosition"]
-                        .as_str()
-                        .map(|s| s.to_string()),
-                });
-            }
+pub fn draft_state(session: &ChampSelectSession) -> DraftState {
+    let game_id = session.game_id;
+    let mut warnings = Vec::new();
+
+    // Timer can be in milliseconds, convert to seconds if > 1000
+    let timer_raw = session
+        .timer
+        .adjusted_time_left_in_phase
+        .or(session.timer.time_left_in_phase);
+    let timer = timer_raw.map(|t| {
+        if t > 1000.0 {
+            t / 1000.0 // Convert milliseconds to seconds
+        } else {
+            t
         }
+    });
 
-        teams.push(Team {
-            team_id: 200,
-            picks,
-            bans: Vec::new(),
-            cells,
-        });
+    let phase = session
+        .timer
+        .phase
+        .clone()
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let swappable = SwapAvailability {
+        trade: available_cells(&session.trades),
+        pick_order: available_cells(&session.pick_order_swaps),
+        position: available_cells(&session.position_swaps),
+    };
+    let swap_offers: Vec<SwapOffer> = pending_offers(SwapKind::Champion, &session.trades)
+        .chain(pending_offers(
+            SwapKind::PickOrder,
+            &session.pick_order_swaps,
+        ))
+        .chain(pending_offers(SwapKind::Position, &session.position_swaps))
+        .collect();
+
+    // myTeam is the local player's side whichever it is. Customs with
+    // spectators may not tell; blue is assumed then
+    let side = |members: &Option<Vec<SessionMember>>| members.as_deref().and_then(team_side);
+    let ally_team_id = side(&session.my_team)
+        .or_else(|| side(&session.their_team).map(other_side))
+        .unwrap_or(100);
+    let enemy_team_id = other_side(ally_team_id);
+
+    let mut teams = Vec::new();
+    let mut seen_cell_ids = HashSet::new();
+
+    if let Some(my_team) = &session.my_team {
+        teams.push(parse_team(
+            my_team,
+            ally_team_id,
+            true,
+            &swappable,
+            &mut seen_cell_ids,
+            &mut warnings,
+        ));
     }
 
-    // Parse bans from actions
-    let actions = session["actions"]
-        .as_array()
-        .map(|actions_array| {
-            actions_array
-                .iter()
-                .flat_map(|action_array| {
-                    action_array.as_array().map(|inner_array| {
-                        inner_array
-                            .iter()
-                            .filter_map(|action| {
-                                Some(DraftAction {
-                                    id: action["id"].as_i64()?,
-                                    actor_cell_id: action["actorCellId"].as_i64(),
-                                    champion_id: action["championId"].as_i64().or_else(|| {
-                                        action["championId"].as_str().and_then(|s| s.parse().ok())
-                                    }),
-                                    selected_champion_id: action["selectedChampionId"]
-                                        .as_i64()
-                                        .or_else(|| {
-                                            action["selectedChampionId"]
-                                                .as_str()
-                                                .and_then(|s| s.parse().ok())
-                                        }),
-                                    completed: action["completed"].as_bool().unwrap_or(false),
-                                    is_in_progress: action["isInProgress"]
-                                        .as_bool()
-                                        .unwrap_or(false),
-                                    action_type: action["type"].as_str()?.to_string(),
-                                })
-                            })
-                            .collect::<Vec<_>>()
-                    })
-                })
-                .flatten()
-                .collect::<Vec<_>>()
+    if let Some(their_team) = &session.their_team {
+        teams.push(parse_team(
+            their_team,
+            enemy_team_id,
+            false,
+            &swappable,
+            &mut seen_cell_ids,
+            &mut warnings,
+        ));
+    }
+
+    // Actions without an id or a type can't be acted on or attributed
+    let actions: Vec<DraftAction> = session
+        .actions
+        .iter()
+        .flatten()
+        .filter_map(|action| {
+            Some(DraftAction {
+                id: action.id?,
+                actor_cell_id: action.actor_cell_id,
+                champion_id: action.champion_id,
+                selected_champion_id: action.selected_champion_id,
+                completed: action.completed,
+                is_in_progress: action.is_in_progress,
+                is_ally_action: action.is_ally_action,
+                action_type: action.action_type.clone()?,
+            })
         })
-        .unwrap_or_default();
+        .collect();
 
     // Extract bans from actions and assign to correct teams
     // First, collect all cell_ids for each team
-    let team_100_cell_ids: HashSet<i64> = teams
+    let ally_cell_ids: HashSet<i64> = teams
         .iter()
-        .find(|t| t.team_id == 100)
+        .find(|t| t.team_id == ally_team_id)
         .map(|t| t.cells.iter().map(|c| c.cell_id).collect())
         .unwrap_or_default();
 
-    let team_200_cell_ids: HashSet<i64> = teams
+    let enemy_cell_ids: HashSet<i64> = teams
         .iter()
-        .find(|t| t.team_id == 200)
+        .find(|t| t.team_id == enemy_team_id)
         .map(|t| t.cells.iter().map(|c| c.cell_id).collect())
         .unwrap_or_default();
 
-    let mut team_100_bans = Vec::new();
-    let mut team_200_bans = Vec::new();
-
-    for action in &actions {
-        if action.action_type == "ban" {
-            if let Some(champ_id) = action.champion_id {
-                // Determine which team this ban belongs to based on actor_cell_id
-                let belongs_to_team_100 = if let Some(cell_id) = action.actor_cell_id {
-                    // Check if the cell_id belongs to team 100's cells
-                    if team_100_cell_ids.contains(&cell_id) {
-                        true
-                    } else if team_200_cell_ids.contains(&cell_id) {
-                        false
-                    } else {
-                        // Fallback: Cells 0-4 are typically team 100, 5-9 are team 200
-                        cell_id < 5
-                    }
-                } else {
-                    // If no actor_cell_id, can't determine team - skip this ban
-                    continue;
-                };
-
-                let ban = ChampionBan {
-                    champion_id: champ_id,
-                    cell_id: action.actor_cell_id,
-                    completed: action.completed,
-                    is_ally_ban: belongs_to_team_100,
-                };
-
-                if belongs_to_team_100 {
-                    team_100_bans.push(ban);
-                } else {
-                    team_200_bans.push(ban);
-                }
-            }
+    let mut ally_bans = Vec::new();
+    let mut enemy_bans = Vec::new();
+
+    for action in actions.iter().filter(|a| a.action_type == "ban") {
+        let Some(champ_id) = action.champion_id else {
+            continue;
+        };
+
+        // Determine which team this ban belongs to: the actor's cell decides, and the
+        // action's own isAllyAction flag covers actors missing from both teams
+        let is_ally = match action.actor_cell_id {
+            Some(cell_id) if ally_cell_ids.contains(&cell_id) => Some(true),
+            Some(cell_id) if enemy_cell_ids.contains(&cell_id) => Some(false),
+            _ => action.is_ally_action,
+        };
+        let Some(is_ally) = is_ally else {
+            warnings.push(format!(
+                "Ban action {} (champion {}) has no known actor; skipped",
+                action.id, champ_id
+            ));
+            continue;
+        };
+
+        let ban = ChampionBan {
+            champion_id: champ_id,
+            cell_id: action.actor_cell_id,
+            completed: action.completed,
+            is_ally_ban: is_ally,
+        };
+
+        if is_ally {
+            ally_bans.push(ban);
+        } else {
+            enemy_bans.push(ban);
         }
     }
 
     // Assign bans to the correct teams
     for team in teams.iter_mut() {
-        if team.team_id == 100 {
-            team.bans = team_100_bans.clone();
-        } else if team.team_id == 200 {
-            team.bans = team_200_bans.clone();
+        if team.team_id == ally_team_id {
+            team.bans = std::mem::take(&mut ally_bans);
+        } else if team.team_id == enemy_team_id {
+            team.bans = std::mem::take(&mut enemy_bans);
         }
     }
 
-    // Process preselection status - normalize and clean up
-    // For picks, the cell's selectedChampionId field from the LCU already contains
-    // the hovered champion. We just need to normalize 0 values to None.
-    // This is different from bans, where we read from actions.
+    // The client moves pick actions between cells on a pick order swap, so the
+    // actions decide each cell's turn and the order picks are listed in
     for team in teams.iter_mut() {
-        for cell in team.cells.iter_mut() {
-            // Normalize selected_champion_id = 0 to None
-            if cell.selected_champion_id == Some(0) {
-                cell.selected_champion_id = None;
-            }
-            // Normalize champion_id = 0 to None
-            if cell.champion_id == Some(0) {
-                cell.champion_id = None;
+        let mut turn = 0;
+        for actor in actions
+            .iter()
+            .filter(|a| a.action_type == "pick")
+            .filter_map(|a| a.actor_cell_id)
+        {
+            if let Some(cell) = team
+                .cells
+                .iter_mut()
+                .find(|c| c.cell_id == actor && c.pick_turn.is_none())
+            {
+                turn += 1;
+                cell.pick_turn = Some(turn);
             }
         }
+        let cells = &team.cells;
+        team.picks.sort_by_key(|p| {
+            let turn = cells
+                .iter()
+                .find(|c| Some(c.cell_id) == p.cell_id)
+                .and_then(|c| c.pick_turn);
+            (turn.is_none(), turn)
+        });
+    }
+
+    // A hover on a champion someone already locked is stale; two cells hovering the
+    // same champion is kept but reported, since only one of them can end up with it
+    let locked: HashSet<i64> = teams
+        .iter()
+        .flat_map(|t| t.cells.iter().filter_map(|c| c.champion_id))
+        .collect();
+    let mut hovered = HashSet::new();
+    for cell in teams.iter_mut().flat_map(|t| t.cells.iter_mut()) {
+        let Some(selected_id) = cell.selected_champion_id else {
+            continue;
+        };
+        if cell.champion_id.is_none() && locked.contains(&selected_id) {
+            warnings.push(format!(
+                "Cell {} hovers champion {} which is already locked; cleared",
+                cell.cell_id, selected_id
+            ));
+            cell.selected_champion_id = None;
+        } else if cell.champion_id.is_none() && !hovered.insert(selected_id) {
+            warnings.push(format!(
+                "Champion {} is hovered by more than one cell",
+                selected_id
+            ));
+        }
+    }
+
+    let mut locked_by = HashSet::new();
+    for pick in teams.iter().flat_map(|t| t.picks.iter()) {
+        if !locked_by.insert(pick.champion_id) {
+            warnings.push(format!(
+                "Champion {} is locked by more than one cell",
+                pick.champion_id
+            ));
+        }
     }
 
-    // Get local player's cell ID from the session
-    let local_player_cell_id = session["localPlayerCellId"].as_i64();
+    // Trades with allies are already on the cells; the bench is shared by the team
+    let aram = session.bench_enabled.then(|| {
+        let rerolls_remaining = session.rerolls_remaining.unwrap_or(0);
+        AramState {
+            bench: session
+                .bench_champions
+                .iter()
+                .filter_map(|b| {
+                    Some(BenchChampion {
+                        champion_id: b.champion_id?,
+                        is_priority: b.is_priority,
+                    })
+                })
+                .collect(),
+            rerolls_remaining,
+            can_reroll: session.allow_rerolling && rerolls_remaining > 0,
+        }
+    });
 
-    Ok(DraftState {
+    DraftState {
         game_id,
         timer,
         phase,
         teams,
         actions,
-        local_player_cell_id,
-    })
+        local_player_cell_id: session.local_player_cell_id,
+        is_custom_game: session.is_custom_game,
+        queue_id: session.queue_id,
+        aram,
+        warnings,
+        swap_offers,
+        series_excluded: Vec::new(),
+        raw_session: None,
+    }
+}
+
+/// Where the saved champ-select sessions live in the source tree.
+pub fn draft_fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join("draft")
+}
+
+/// Parse one of the sessions under `fixtures/draft`, by file name without the
+/// extension. Names are restricted so nothing outside that directory is read.
+pub fn load_draft_fixture(name: &str) -> Result<DraftState, String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("Invalid fixture name {}", name));
+    }
+    let path = draft_fixtures_dir().join(format!("{}.json", name));
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let session: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    parse_draft_session(&session)
+}
+
+/// Parse a saved champ-select session by name. Dev helper for iterating on the
+/// parser without a client; the fixtures only exist next to the sources, so
+/// release builds refuse.
+#[tauri::command]
+pub fn parse_draft_fixture(name: String) -> Result<DraftState, String> {
+    if !cfg!(debug_assertions) {
+        return Err("Draft fixtures are only available in development builds".to_string());
+    }
+    load_draft_fixture(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> DraftState {
+        load_draft_fixture(name).unwrap()
+    }
+
+    /// Every session in `fixtures/draft` must parse to its snapshot in
+    /// `fixtures/draft/snapshots`. Run with `UPDATE_SNAPSHOTS=1` to regenerate
+    /// them after an intentional parser change.
+    #[test]
+    fn test_fixture_snapshots() {
+        let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let snapshots_dir = draft_fixtures_dir().join("snapshots");
+        let mut checked = 0;
+
+        for entry in std::fs::read_dir(draft_fixtures_dir()).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let name = path.file_name().unwrap();
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            let state = fixture(&stem);
+            let actual = serde_json::to_value(&state).unwrap();
+            let snapshot_path = snapshots_dir.join(name);
+
+            if update {
+                std::fs::create_dir_all(&snapshots_dir).unwrap();
+                let json = serde_json::to_string_pretty(&actual).unwrap();
+                std::fs::write(&snapshot_path, json + "\n").unwrap();
+            } else {
+                let expected: serde_json::Value = serde_json::from_str(
+                    &std::fs::read_to_string(&snapshot_path)
+                        .unwrap_or_else(|_| panic!("Missing snapshot {}", snapshot_path.display())),
+                )
+                .unwrap();
+                assert_eq!(actual, expected, "Snapshot mismatch for {}", path.display());
+            }
+            checked += 1;
+        }
+
+        assert!(
+            checked > 0,
+            "No fixtures found in {}",
+            draft_fixtures_dir().display()
+        );
+    }
+
+    #[test]
+    fn test_fixture_names_stay_in_the_fixtures_dir() {
+        assert!(load_draft_fixture("../../Cargo").is_err());
+        assert!(load_draft_fixture("/etc/passwd").is_err());
+        assert!(load_draft_fixture("").is_err());
+        assert!(load_draft_fixture("custom_draft").is_ok());
+    }
+
+    #[test]
+    fn test_solo_queue_pick_phase() {
+        let state = fixture("solo_queue_pick_phase");
+        assert_eq!(state.phase, "BAN_PICK");
+        assert_eq!(state.local_player_cell_id, Some(2));
+        let ally = state.teams.iter().find(|t| t.team_id == 100).unwrap();
+        let enemy = state.teams.iter().find(|t| t.team_id == 200).unwrap();
+        assert_eq!(ally.bans.len(), 5);
+        assert_eq!(enemy.bans.len(), 5);
+        assert_eq!(ally.picks.len(), 1);
+        assert_eq!(enemy.picks.len(), 2);
+        assert_eq!(ally.cells[0].champion_id, Some(266));
+        assert_eq!(ally.cells[1].selected_champion_id, Some(64));
+    }
+
+    #[test]
+    fn test_session_identity() {
+        let identity = |name: &str| {
+            let state = fixture(name);
+            (
+                state.local_player_cell_id,
+                state.is_custom_game,
+                state.queue_id,
+            )
+        };
+        assert_eq!(
+            identity("solo_queue_pick_phase"),
+            (Some(2), false, Some(420))
+        );
+        assert_eq!(identity("flex_red_side"), (Some(8), false, Some(440)));
+        assert_eq!(identity("custom_draft"), (Some(1), true, None));
+
+        // The local cell is what places the player on a team and a role
+        let state = fixture("flex_red_side");
+        let local = state.local_cell().unwrap();
+        assert_eq!(local.cell_id, 8);
+        assert_eq!(state.local_team().unwrap().team_id, 200);
+    }
+
+    #[test]
+    fn test_aram_bench() {
+        let state = fixture("aram_bench");
+        let aram = state.aram.as_ref().unwrap();
+        let bench: Vec<i64> = aram.bench.iter().map(|b| b.champion_id).collect();
+        assert_eq!(bench, vec![51, 201, 81]);
+        assert!(aram.bench[2].is_priority);
+        assert_eq!((aram.rerolls_remaining, aram.can_reroll), (1, true));
+        assert_eq!(state.local_cell().unwrap().champion_id, Some(117));
+        assert!(fixture("solo_queue_pick_phase").aram.is_none());
+    }
+
+    #[test]
+    fn test_red_side_bans_follow_team_membership() {
+        let state = fixture("flex_red_side");
+        // myTeam is the red side here
+        assert_eq!(state.local_team().map(|t| t.team_id), Some(200));
+        let ally = state.teams.iter().find(|t| t.team_id == 200).unwrap();
+        let mut ally_bans: Vec<i64> = ally.bans.iter().map(|b| b.champion_id).collect();
+        ally_bans.sort();
+        assert_eq!(ally_bans, vec![24, 266, 268, 412, 523]);
+        assert!(ally.bans.iter().all(|b| b.is_ally_ban));
+        assert!(state.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_side_from_cell_ids() {
+        let session = serde_json::json!({
+            "localPlayerCellId": 7,
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [{ "cellId": 5 }, { "cellId": 7 }],
+            "theirTeam": [{ "cellId": 0 }, { "cellId": 2 }],
+            "actions": [[
+                { "id": 1, "actorCellId": 7, "championId": 157, "completed": true, "type": "ban" },
+                { "id": 2, "actorCellId": 2, "championId": 238, "completed": true, "type": "ban" }
+            ]]
+        });
+        let state = parse_draft_session(&session).unwrap();
+
+        let ally = state.local_team().unwrap();
+        assert_eq!(ally.team_id, 200);
+        assert_eq!(ally.bans[0].champion_id, 157);
+        let enemy = state.teams.iter().find(|t| t.team_id == 100).unwrap();
+        assert_eq!(enemy.bans[0].champion_id, 238);
+    }
+
+    #[test]
+    fn test_pick_order_swaps_and_offers() {
+        // Cells 0 and 1 swapped pick turns: cell 1 picked first
+        let session = serde_json::json!({
+            "localPlayerCellId": 0,
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [
+                { "cellId": 0, "championId": 64, "assignedPosition": "jungle" },
+                { "cellId": 1, "championId": 157, "assignedPosition": "middle" },
+                { "cellId": 2, "championId": 0, "assignedPosition": "bottom" }
+            ],
+            "theirTeam": [{ "cellId": 5 }],
+            "actions": [
+                [{ "id": 1, "actorCellId": 1, "championId": 157, "completed": true, "type": "pick" }],
+                [{ "id": 2, "actorCellId": 5, "championId": 0, "completed": true, "type": "pick" }],
+                [
+                    { "id": 3, "actorCellId": 0, "championId": 64, "completed": true, "type": "pick" },
+                    { "id": 4, "actorCellId": 2, "championId": 0, "isInProgress": true, "type": "pick" }
+                ]
+            ],
+            "trades": [{ "cellId": 1, "id": 10, "state": "RECEIVED" }],
+            "pickOrderSwaps": [{ "cellId": 2, "id": 20, "state": "AVAILABLE" }],
+            "positionSwaps": [
+                { "cellId": 1, "id": 30, "state": "SENT" },
+                { "cellId": 2, "id": 31, "state": "DECLINED" }
+            ]
+        });
+        let state = parse_draft_session(&session).unwrap();
+
+        let ally = state.local_team().unwrap();
+        let turns: Vec<Option<usize>> = ally.cells.iter().map(|c| c.pick_turn).collect();
+        assert_eq!(turns, vec![Some(2), Some(1), Some(3)]);
+        let picks: Vec<(i64, Option<&str>)> = ally
+            .picks
+            .iter()
+            .map(|p| (p.champion_id, p.position.as_deref()))
+            .collect();
+        assert_eq!(picks, vec![(157, Some("middle")), (64, Some("jungle"))]);
+        assert!(ally.cells[2].pick_swap_available);
+        assert!(!ally.cells[1].pick_swap_available);
+
+        assert_eq!(
+            state.swap_offers,
+            vec![
+                SwapOffer {
+                    id: Some(10),
+                    kind: SwapKind::Champion,
+                    cell_id: 1,
+                    incoming: true
+                },
+                SwapOffer {
+                    id: Some(30),
+                    kind: SwapKind::Position,
+                    cell_id: 1,
+                    incoming: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spectator_cells_and_unknown_actors() {
+        let session = serde_json::json!({
+            "localPlayerCellId": 11,
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [{ "cellId": 11, "championId": 0 }, { "cellId": 12, "championId": 0 }],
+            "theirTeam": [{ "cellId": 14, "championId": 0 }, { "cellId": 11, "championId": 7 }],
+            "actions": [[
+                { "id": 1, "actorCellId": 14, "championId": 157, "completed": true, "type": "ban" },
+                { "id": 2, "actorCellId": 3, "championId": 238, "completed": true, "isAllyAction": true, "type": "ban" },
+                { "id": 3, "actorCellId": 4, "championId": 64, "completed": true, "type": "ban" }
+            ]]
+        });
+        let state = parse_draft_session(&session).unwrap();
+
+        // Cell 14 is an enemy even though it is above 9
+        assert_eq!(state.teams[1].bans[0].champion_id, 157);
+        assert!(!state.teams[1].bans[0].is_ally_ban);
+        // Unknown actor falls back to isAllyAction, and is skipped without it
+        assert_eq!(state.teams[0].bans.len(), 1);
+        assert_eq!(state.teams[0].bans[0].champion_id, 238);
+        // Duplicate cell 11 in theirTeam is dropped
+        assert_eq!(state.teams[1].cells.len(), 1);
+        assert_eq!(state.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_intents() {
+        let session = serde_json::json!({
+            "timer": { "phase": "BAN_PICK" },
+            "myTeam": [
+                { "cellId": 0, "championId": 103 },
+                { "cellId": 1, "championPickIntent": 103 },
+                { "cellId": 2, "championPickIntent": 64 },
+                { "cellId": 3, "championPickIntent": 64 }
+            ],
+            "theirTeam": [],
+            "actions": []
+        });
+        let state = parse_draft_session(&session).unwrap();
+        let cells = &state.teams[0].cells;
+
+        assert_eq!(cells[1].selected_champion_id, None);
+        assert_eq!(cells[2].selected_champion_id, Some(64));
+        assert_eq!(cells[3].selected_champion_id, Some(64));
+        assert_eq!(state.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_hover_only_planning() {
+        let state = fixture("hover_only_planning");
+        assert_eq!(state.phase, "PLANNING");
+        let ally = &state.teams[0];
+        assert!(ally.picks.is_empty());
+        assert!(ally.bans.is_empty());
+        assert_eq!(ally.cells[0].selected_champion_id, Some(86));
+        // selectedChampionId is used when championPickIntent is empty
+        assert_eq!(ally.cells[3].selected_champion_id, Some(81));
+        assert!(state.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_string_champion_ids() {
+        let state = fixture("custom_draft");
+        let ally = &state.teams[0];
+        assert_eq!(ally.cells[0].champion_id, Some(24));
+        assert_eq!(ally.cells[1].selected_champion_id, Some(104));
+    }
 }
@@ -0,0 +1,517 @@
+//! Typed shapes of the LCU responses the app reads. Only the fields the app
+//! uses are declared, and nearly all of them are optional: the client leaves
+//! fields out or sends `null` freely between versions, while a field of the
+//! wrong type fails deserialization.
+//!
+//! Fields known to vary between client versions go through the lenient
+//! helpers below. A value none of the known variations match is logged and
+//! counted in the request stats before falling back to the field's default,
+//! so a new client shape shows up instead of reading as zeros.
+//! `fixtures/lcu` holds one response per known variation.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cell::Cell;
+use std::fmt::Debug;
+
+thread_local! {
+    /// Unknown shapes met on this thread; see `count_unknown_shapes`.
+    static UNKNOWN_SHAPES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Run a deserialization, also returning how many values in it matched none
+/// of the known shapes. Deserializing never crosses threads, so the tally
+/// covers exactly this call.
+pub fn count_unknown_shapes<R>(decode: impl FnOnce() -> R) -> (R, u64) {
+    let before = UNKNOWN_SHAPES.with(Cell::get);
+    let result = decode();
+    (result, UNKNOWN_SHAPES.with(Cell::get) - before)
+}
+
+/// Numbers that some endpoints send as numeric strings.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Lenient {
+    Number(i64),
+    Bool(bool),
+    Text(String),
+    #[allow(dead_code)] // Only read through Debug, for the log
+    Other(serde_json::Value),
+}
+
+/// Report a value that matches none of the shapes a field is known to take.
+fn unknown_shape(expected: &str, value: &impl Debug) {
+    eprintln!(
+        "Unexpected LCU value where {} was expected: {:?}",
+        expected, value
+    );
+    UNKNOWN_SHAPES.with(|count| count.set(count.get() + 1));
+}
+
+/// Treat `null` like a missing field.
+fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// A number, or a numeric string as chat presence sends everything. Empty
+/// strings stand for no value.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Ok(match Option::<Lenient>::deserialize(deserializer)? {
+        None => None,
+        Some(Lenient::Number(n)) => Some(n),
+        Some(Lenient::Text(text)) if text.is_empty() => None,
+        Some(Lenient::Text(text)) => text
+            .parse()
+            .inspect_err(|_| unknown_shape("a number", &text))
+            .ok(),
+        Some(other) => {
+            unknown_shape("a number", &other);
+            None
+        }
+    })
+}
+
+/// Champion ids arrive as numbers or numeric strings depending on the endpoint
+/// version; 0 means "no champion".
+fn champion_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    Ok(number(deserializer)?.filter(|&id| id > 0))
+}
+
+/// Ids are numbers in current clients and strings in older ones.
+fn id_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match Option::<Lenient>::deserialize(deserializer)? {
+        None => String::new(),
+        Some(Lenient::Number(id)) => id.to_string(),
+        Some(Lenient::Text(text)) => text,
+        Some(other) => {
+            unknown_shape("an id", &other);
+            String::new()
+        }
+    })
+}
+
+/// Win is a boolean in current clients and "Win"/"Fail" in older ones.
+fn win<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    Ok(match Option::<Lenient>::deserialize(deserializer)? {
+        None => false,
+        Some(Lenient::Bool(win)) => win,
+        Some(Lenient::Text(text)) if text == "Win" || text == "Fail" => text == "Win",
+        Some(other) => {
+            unknown_shape("a win flag", &other);
+            false
+        }
+    })
+}
+
+/// The games list is nested one level deeper (`games.games`) in most client
+/// versions and flat in others.
+fn history_games<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<HistoryGame>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Shape {
+        Nested { games: Vec<HistoryGame> },
+        Flat(Vec<HistoryGame>),
+        Other(serde_json::Value),
+    }
+    Ok(match Option::<Shape>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(Shape::Nested { games }) | Some(Shape::Flat(games)) => games,
+        Some(Shape::Other(value)) => {
+            unknown_shape("a games list", &value);
+            Vec::new()
+        }
+    })
+}
+
+/// `/lol-champ-select/v1/session`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectSession {
+    pub game_id: Option<i64>,
+    pub local_player_cell_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub is_custom_game: bool,
+    pub queue_id: Option<i32>, // Missing in older clients
+    #[serde(deserialize_with = "nullable")]
+    pub timer: SessionTimer,
+    pub my_team: Option<Vec<SessionMember>>, // None when the client left the side out
+    pub their_team: Option<Vec<SessionMember>>,
+    #[serde(deserialize_with = "nullable")]
+    pub actions: Vec<Vec<SessionAction>>, // Grouped by turn
+    #[serde(deserialize_with = "nullable")]
+    pub trades: Vec<SessionTrade>,
+    #[serde(deserialize_with = "nullable")]
+    pub pick_order_swaps: Vec<SessionTrade>, // Same shape as trades
+    #[serde(deserialize_with = "nullable")]
+    pub position_swaps: Vec<SessionTrade>,
+    #[serde(deserialize_with = "nullable")]
+    pub bench_enabled: bool, // ARAM and other modes with a shared bench
+    #[serde(deserialize_with = "nullable")]
+    pub bench_champions: Vec<SessionBenchChampion>,
+    #[serde(deserialize_with = "nullable")]
+    pub allow_rerolling: bool,
+    pub rerolls_remaining: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionTimer {
+    pub adjusted_time_left_in_phase: Option<f64>, // Milliseconds in current clients
+    pub time_left_in_phase: Option<f64>,
+    pub phase: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionMember {
+    pub cell_id: Option<i64>,
+    pub team: Option<i64>,     // 1 for blue side, 2 for red
+    pub puuid: Option<String>, // Empty when the client hides the player
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_id: Option<i64>, // Locked
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_pick_intent: Option<i64>, // Hovered
+    #[serde(deserialize_with = "champion_id")]
+    pub selected_champion_id: Option<i64>, // Hovered, in some client versions
+    pub assigned_position: Option<String>, // Lowercase, empty outside role queues
+    pub spell1_id: Option<i64>,
+    pub spell2_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionAction {
+    pub id: Option<i64>,
+    pub actor_cell_id: Option<i64>,
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_id: Option<i64>,
+    #[serde(deserialize_with = "champion_id")]
+    pub selected_champion_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub completed: bool,
+    #[serde(deserialize_with = "nullable")]
+    pub is_in_progress: bool,
+    pub is_ally_action: Option<bool>,
+    #[serde(rename = "type")]
+    pub action_type: Option<String>, // "ban", "pick", "ten_bans_reveal"...
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionBenchChampion {
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub is_priority: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionTrade {
+    pub id: Option<i64>,
+    pub cell_id: Option<i64>,
+    pub state: Option<String>, // "AVAILABLE" when it can be offered, "SENT" or "RECEIVED" while pending
+}
+
+/// `/lol-gameflow/v1/session`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowSession {
+    #[serde(deserialize_with = "nullable")]
+    pub game_data: GameflowGameData,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowGameData {
+    #[serde(deserialize_with = "nullable")]
+    pub queue: GameflowQueue,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowQueue {
+    pub id: Option<i64>, // -1 or 0 outside a queue
+}
+
+/// `/lol-summoner/v1/current-summoner` and `/lol-summoner/v2/summoners/puuid/{puuid}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Summoner {
+    #[serde(deserialize_with = "id_string")]
+    pub summoner_id: String,
+    #[serde(deserialize_with = "id_string")]
+    pub account_id: String,
+    pub puuid: Option<String>,
+    pub display_name: Option<String>,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub summoner_level: Option<i64>,
+    pub profile_icon_id: Option<i64>,
+    pub xp_since_last_level: Option<i64>,
+    pub xp_until_next_level: Option<i64>,
+}
+
+/// `/lol-summoner/v1/alias/lookup`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Alias {
+    pub puuid: Option<String>,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+}
+
+/// `/lol-ranked/v1/current-ranked-stats` and `/lol-ranked/v1/ranked-stats/{puuid}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RankedStatsResponse {
+    #[serde(deserialize_with = "nullable")]
+    pub queues: Vec<RankedQueue>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RankedQueue {
+    pub queue_type: Option<String>,
+    pub tier: Option<String>, // "NONE" or empty when unranked
+    pub division: Option<String>,
+    pub league_points: Option<i64>,
+    pub wins: Option<i64>,
+    pub losses: Option<i64>,
+    pub rated_tier: Option<String>, // Rated ladders such as Hyper Roll
+    pub rated_rating: Option<i64>,
+    pub mini_series_progress: Option<String>, // e.g. "WLN"
+    pub mini_series_target: Option<i64>,
+    pub highest_tier: Option<String>,
+    pub highest_division: Option<String>,
+    pub previous_season_end_tier: Option<String>,
+    pub previous_season_end_division: Option<String>,
+    #[serde(deserialize_with = "nullable")]
+    pub is_provisional: bool,
+    pub provisional_games_remaining: Option<i64>,
+}
+
+/// `/lol-match-history/v1/products/lol/{puuid}/matches`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryResponse {
+    #[serde(deserialize_with = "history_games")]
+    pub games: Vec<HistoryGame>,
+}
+
+impl MatchHistoryResponse {
+    pub fn games(&self) -> &[HistoryGame] {
+        &self.games
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryGame {
+    pub game_id: Option<i64>,
+    pub game_mode: Option<String>,
+    pub game_creation: Option<i64>, // Epoch millis
+    pub game_duration: Option<i64>, // Seconds
+    pub queue_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub participant_identities: Vec<ParticipantIdentity>,
+    #[serde(deserialize_with = "nullable")]
+    pub participants: Vec<HistoryParticipant>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantIdentity {
+    pub participant_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub player: HistoryPlayer,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryPlayer {
+    pub puuid: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryParticipant {
+    pub participant_id: Option<i64>,
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_id: Option<i64>,
+    #[serde(deserialize_with = "nullable")]
+    pub stats: HistoryStats,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryStats {
+    #[serde(deserialize_with = "win")]
+    pub win: bool,
+    pub kills: Option<i64>,
+    pub deaths: Option<i64>,
+    pub assists: Option<i64>,
+}
+
+/// `/lol-chat/v1/friends`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Friend {
+    pub puuid: Option<String>,
+    pub game_name: Option<String>,
+    pub game_tag: Option<String>,
+    pub availability: Option<String>, // "chat", "away", "dnd", "mobile" or "offline"
+    pub product: Option<String>,      // "league_of_legends" while in League
+    #[serde(deserialize_with = "nullable")]
+    pub lol: FriendPresence,
+}
+
+/// League presence of a friend. Every value arrives as a string.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FriendPresence {
+    pub game_status: Option<String>, // "inGame", "championSelect", "outOfGame"...
+    pub game_queue_type: Option<String>,
+    #[serde(deserialize_with = "champion_id")]
+    pub champion_id: Option<i64>,
+    #[serde(deserialize_with = "number")]
+    pub time_stamp: Option<i64>, // When the game started, epoch millis
+    pub is_observable: Option<String>, // "ALL" when anyone may spectate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::path::Path;
+
+    /// Whether every field in `expected` appears in `actual` with the same
+    /// value. Arrays must match element by element.
+    fn contains(actual: &Value, expected: &Value) -> bool {
+        match (actual, expected) {
+            (Value::Object(actual), Value::Object(expected)) => expected
+                .iter()
+                .all(|(key, value)| contains(actual.get(key).unwrap_or(&Value::Null), value)),
+            (Value::Array(actual), Value::Array(expected)) => {
+                actual.len() == expected.len()
+                    && actual.iter().zip(expected).all(|(a, e)| contains(a, e))
+            }
+            _ => actual == expected,
+        }
+    }
+
+    /// Parse `response` as the DTO for `shape` and serialize it back.
+    fn parse_shape(shape: &str, response: Value) -> serde_json::Result<Value> {
+        fn parse<T: serde::de::DeserializeOwned + Serialize>(
+            response: Value,
+        ) -> serde_json::Result<Value> {
+            serde_json::to_value(serde_json::from_value::<T>(response)?)
+        }
+        match shape {
+            "champ_select_session" => parse::<ChampSelectSession>(response),
+            "match_history" => parse::<MatchHistoryResponse>(response).map(|r| r["games"].clone()),
+            "summoner" => parse::<Summoner>(response),
+            "ranked_stats" => parse::<RankedStatsResponse>(response),
+            "friends" => parse::<Vec<Friend>>(response),
+            "gameflow_session" => parse::<GameflowSession>(response),
+            _ => panic!("Unknown shape {}", shape),
+        }
+    }
+
+    /// Every response in `fixtures/lcu` must parse, without unknown shapes,
+    /// to a DTO containing its `expected` fields.
+    #[test]
+    fn test_response_contracts() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("lcu");
+        let mut checked = 0;
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let case: Value =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            let shape = case["shape"].as_str().unwrap();
+            let actual = parse_shape(shape, case["response"].clone())
+                .unwrap_or_else(|e| panic!("{} failed to parse: {}", path.display(), e));
+            assert!(
+                contains(&actual, &case["expected"]),
+                "{} ({}) parsed to {}",
+                path.display(),
+                case["variation"],
+                actual
+            );
+            checked += 1;
+        }
+        assert!(checked > 0, "No fixtures found in {}", dir.display());
+    }
+
+    #[test]
+    fn test_unknown_shapes_are_counted() {
+        let (member, unknown) = count_unknown_shapes(|| {
+            serde_json::from_value::<SessionMember>(
+                json!({ "championId": [157], "championPickIntent": "64" }),
+            )
+        });
+        let member = member.unwrap();
+        assert_eq!(
+            (member.champion_id, member.champion_pick_intent),
+            (None, Some(64))
+        );
+        assert_eq!(unknown, 1);
+        let (stats, unknown) = count_unknown_shapes(|| {
+            serde_json::from_value::<HistoryStats>(json!({ "win": "Victory" }))
+        });
+        assert!(!stats.unwrap().win);
+        let (history, more) = count_unknown_shapes(|| {
+            serde_json::from_value::<MatchHistoryResponse>(json!({ "games": { "list": [] } }))
+        });
+        assert!(history.unwrap().games().is_empty());
+        assert_eq!(unknown + more, 2);
+    }
+
+    #[test]
+    fn test_lenient_fields() {
+        let session: ChampSelectSession = serde_json::from_value(json!({
+            "timer": null,
+            "myTeam": [
+                { "cellId": 0, "championId": "157", "championPickIntent": 0 },
+                { "cellId": 1, "championId": null, "championPickIntent": "abc" }
+            ],
+            "actions": [[{ "id": 1, "type": "ban", "championId": 64, "completed": null }]]
+        }))
+        .unwrap();
+        let team = session.my_team.unwrap();
+        assert_eq!(team[0].champion_id, Some(157));
+        assert_eq!(team[0].champion_pick_intent, None);
+        assert_eq!(
+            (team[1].champion_id, team[1].champion_pick_intent),
+            (None, None)
+        );
+        assert!(session.their_team.is_none());
+        assert!(!session.actions[0][0].completed);
+
+        let summoner: Summoner =
+            serde_json::from_value(json!({ "summonerId": 42, "accountId": "7" })).unwrap();
+        assert_eq!(
+            (summoner.summoner_id.as_str(), summoner.account_id.as_str()),
+            ("42", "7")
+        );
+
+        let history: MatchHistoryResponse = serde_json::from_value(json!({
+            "games": { "games": [{ "gameId": 1, "participants": [{ "stats": { "win": "Win" } }] }] }
+        }))
+        .unwrap();
+        assert!(history.games()[0].participants[0].stats.win);
+        let flat: MatchHistoryResponse =
+            serde_json::from_value(json!({ "games": [{ "gameId": 2 }] })).unwrap();
+        assert_eq!(flat.games()[0].game_id, Some(2));
+
+        assert!(
+            serde_json::from_value::<SessionMember>(json!({ "cellId": "one" })).is_err(),
+            "a wrong type is an error, not a silent default"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Why a call to the League client failed, for the frontend to branch on
+/// instead of matching message text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LcuError {
+    ClientNotRunning(String), // No lockfile, or the client stopped answering
+    NotInChampSelect,
+    Unauthorized, // Stale credentials, usually after a client restart
+    AuthExpired,  // Still rejected after re-reading the lockfile
+    Timeout,
+    Deserialize(String),
+    Http(u16),
+    Other(String),
+}
+
+impl LcuError {
+    /// Error for a non-success response from `path`.
+    pub fn from_status(path: &str, status: reqwest::StatusCode) -> Self {
+        match status.as_u16() {
+            401 | 403 => LcuError::Unauthorized,
+            404 if path.starts_with("/lol-champ-select/") => LcuError::NotInChampSelect,
+            code => LcuError::Http(code),
+        }
+    }
+
+    /// Whether trying again shortly could succeed. Anything else, like a 404
+    /// outside champ select or a payload that doesn't parse, fails the same way
+    /// on every attempt.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            LcuError::Timeout | LcuError::ClientNotRunning(_) => true,
+            LcuError::Http(status) => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Stable discriminant sent to the frontend.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LcuError::ClientNotRunning(_) => "client_not_running",
+            LcuError::NotInChampSelect => "not_in_champ_select",
+            LcuError::Unauthorized => "unauthorized",
+            LcuError::AuthExpired => "auth_expired",
+            LcuError::Timeout => "timeout",
+            LcuError::Deserialize(_) => "deserialize",
+            LcuError::Http(_) => "http",
+            LcuError::Other(_) => "other",
+        }
+    }
+}
+
+impl fmt::Display for LcuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LcuError::ClientNotRunning(reason) if reason.is_empty() => {
+                write!(f, "League client is not running")
+            }
+            LcuError::ClientNotRunning(reason) => {
+                write!(f, "League client is not running: {}", reason)
+            }
+            LcuError::NotInChampSelect => write!(f, "Not in champion select"),
+            LcuError::Unauthorized => write!(f, "League client rejected the credentials"),
+            LcuError::AuthExpired => {
+                write!(
+                    f,
+                    "League client rejected the credentials from its lockfile"
+                )
+            }
+            LcuError::Timeout => write!(f, "League client did not answer in time"),
+            LcuError::Deserialize(e) => write!(f, "Unexpected response: {}", e),
+            LcuError::Http(status) => write!(f, "HTTP error: {}", status),
+            LcuError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for LcuError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            LcuError::Timeout
+        } else if e.is_connect() {
+            LcuError::ClientNotRunning(e.to_string())
+        } else if e.is_decode() {
+            LcuError::Deserialize(e.to_string())
+        } else if let Some(status) = e.status() {
+            LcuError::Http(status.as_u16())
+        } else {
+            LcuError::Other(format!("Request failed: {}", e))
+        }
+    }
+}
+
+impl From<serde_json::Error> for LcuError {
+    fn from(e: serde_json::Error) -> Self {
+        LcuError::Deserialize(e.to_string())
+    }
+}
+
+/// For callers outside the LCU layer that still report errors as text.
+impl From<LcuError> for String {
+    fn from(e: LcuError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Sent as `{ kind, message, status? }`.
+impl Serialize for LcuError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Payload {
+            kind: &'static str,
+            message: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            status: Option<u16>,
+        }
+        Payload {
+            kind: self.kind(),
+            message: self.to_string(),
+            status: match self {
+                LcuError::Http(status) => Some(*status),
+                _ => None,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_mapping_and_payload() {
+        assert_eq!(
+            LcuError::from_status("/lol-champ-select/v1/session", StatusCode::NOT_FOUND),
+            LcuError::NotInChampSelect
+        );
+        assert_eq!(
+            LcuError::from_status("/lol-gameflow/v1/session", StatusCode::NOT_FOUND),
+            LcuError::Http(404)
+        );
+        assert_eq!(
+            LcuError::from_status("/lol-gameflow/v1/session", StatusCode::UNAUTHORIZED),
+            LcuError::Unauthorized
+        );
+
+        assert!(LcuError::Http(503).is_transient());
+        assert!(!LcuError::Http(404).is_transient());
+        assert!(!LcuError::NotInChampSelect.is_transient());
+
+        assert_eq!(
+            serde_json::to_value(LcuError::Http(500)).unwrap(),
+            json!({ "kind": "http", "message": "HTTP error: 500", "status": 500 })
+        );
+        assert_eq!(
+            serde_json::to_value(LcuError::NotInChampSelect).unwrap()["kind"],
+            "not_in_champ_select"
+        );
+        assert_eq!(
+            serde_json::to_value(LcuError::AuthExpired).unwrap()["kind"],
+            "auth_expired"
+        );
+    }
+}
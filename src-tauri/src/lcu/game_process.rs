@@ -0,0 +1,114 @@
+use std::sync::Mutex;
+
+/// The game itself, as opposed to the client (`LeagueClientUx.exe`).
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+pub const GAME_PROCESS: &str = "League of Legends.exe";
+
+/// Whether `tasklist /FO CSV /NH` output lists the game process.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn tasklist_has_game(output: &str) -> bool {
+    output.lines().any(|line| {
+        line.split(',').next().is_some_and(|name| {
+            name.trim()
+                .trim_matches('"')
+                .eq_ignore_ascii_case(GAME_PROCESS)
+        })
+    })
+}
+
+/// Whether the game process is running, or `None` where it can't be checked.
+pub fn is_game_running() -> Option<bool> {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        use std::process::Command;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+        let output = Command::new("tasklist")
+            .args([
+                "/FI",
+                &format!("IMAGENAME eq {}", GAME_PROCESS),
+                "/FO",
+                "CSV",
+                "/NH",
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        Some(tasklist_has_game(&String::from_utf8_lossy(&output.stdout)))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let status = std::process::Command::new("pgrep")
+            .args(["-x", GAME_PROCESS.trim_end_matches(".exe")])
+            .status()
+            .ok()?;
+        Some(status.success())
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        None
+    }
+}
+
+/// The game process is the source of truth when it can be checked, so a
+/// lagging or restarted client doesn't end the game early. Otherwise the
+/// gameflow phase decides.
+pub fn is_in_game(phase: Option<&str>, game_running: Option<bool>) -> bool {
+    game_running.unwrap_or(phase == Some("InProgress"))
+}
+
+/// Remembers whether the player was in game and reports changes. Updated by
+/// the gameflow watcher.
+#[derive(Default)]
+pub struct InGameTracker {
+    in_game: bool,
+}
+
+impl InGameTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn in_game(&self) -> bool {
+        self.in_game
+    }
+
+    pub fn update(&mut self, in_game: bool) -> Option<bool> {
+        if in_game == self.in_game {
+            return None;
+        }
+        self.in_game = in_game;
+        Some(in_game)
+    }
+}
+
+#[tauri::command]
+pub async fn get_in_game(tracker: tauri::State<'_, Mutex<InGameTracker>>) -> Result<bool, String> {
+    let tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(tracker_guard.in_game())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_detection() {
+        let output = "\"League of Legends.exe\",\"20412\",\"Console\",\"1\",\"1,204,332 K\"\r\n";
+        assert!(tasklist_has_game(output));
+        assert!(!tasklist_has_game(
+            "INFO: No tasks are running which match the specified criteria.\r\n"
+        ));
+
+        // The client restarting mid-game doesn't end it
+        assert!(is_in_game(None, Some(true)));
+        assert!(!is_in_game(Some("InProgress"), Some(false)));
+        assert!(is_in_game(Some("InProgress"), None));
+
+        let mut tracker = InGameTracker::new();
+        assert_eq!(tracker.update(false), None);
+        assert_eq!(tracker.update(true), Some(true));
+        assert_eq!(tracker.update(true), None);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::analytics::meta_shift::check_meta_shift;
+use crate::analytics::rank_changes::check_rank_changes;
+use crate::analytics::spells::check_spells;
+use crate::analytics::vision::check_vision;
+use crate::goals::check_goals;
+use crate::lcu::cancel::enter_phase;
+use crate::lcu::game_process::{is_game_running, is_in_game, InGameTracker};
+use crate::lcu::honor::suggest_honor;
+use crate::lcu::missions::{check_missions, refresh_missions};
+use crate::lcu::monitor::{auto_accept, start_monitor, stop_monitor, ReadyCheckWatcher};
+use crate::lcu::requeue::auto_requeue;
+use crate::lcu::{api::LcuApi, client::LcuClient, lockfile::ClientProfile};
+use crate::metrics::{record_error, ErrorCategory};
+use crate::obs::switch_scene_for_phase;
+use crate::scripts::{run_hook, to_dynamic, Hook};
+use crate::series::commit_series_game;
+use crate::state::{self, StateKey};
+use crate::storage::{sync_recent_matches, timelines::MatchTimelineStore, MatchStore};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{interval, Duration};
+
+const CHAMP_SELECT: &str = "ChampSelect";
+
+/// Payload of the `gameflow-phase-changed` event. `None` means the client isn't reachable.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTransition {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl PhaseTransition {
+    /// The stats screen just appeared: ranked standings and match history are final.
+    pub fn game_ended(&self) -> bool {
+        self.to.as_deref() == Some("EndOfGame")
+    }
+
+    /// The honor vote comes up before the stats screen.
+    pub fn honor_vote_started(&self) -> bool {
+        self.to.as_deref() == Some("PreEndOfGame")
+    }
+
+    pub fn entered_champ_select(&self) -> bool {
+        self.to.as_deref() == Some(CHAMP_SELECT)
+    }
+
+    /// Left for any reason: the game loaded, someone dodged, or the client closed.
+    pub fn left_champ_select(&self) -> bool {
+        self.from.as_deref() == Some(CHAMP_SELECT)
+    }
+}
+
+/// Remembers the last gameflow phase and reports transitions.
+#[derive(Default)]
+pub struct PhaseTracker {
+    last_phase: Option<String>,
+}
+
+impl PhaseTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, phase: Option<String>) -> Option<PhaseTransition> {
+        if phase == self.last_phase {
+            return None;
+        }
+        let from = std::mem::replace(&mut self.last_phase, phase.clone());
+        Some(PhaseTransition { from, to: phase })
+    }
+}
+
+/// Polls the gameflow phase and the game process for the lifetime of the app,
+/// running the game start hooks once per game and the post-game hooks once per
+/// finished game. The draft monitor runs while the phase is champ select.
+pub struct GameflowWatcher<C: LcuApi = LcuClient> {
+    client: Arc<tokio::sync::Mutex<C>>,
+    app_handle: AppHandle,
+    polling_interval_ms: u64,
+}
+
+impl<C: LcuApi> GameflowWatcher<C> {
+    pub fn new(
+        client: Arc<tokio::sync::Mutex<C>>,
+        app_handle: AppHandle,
+        polling_interval_ms: u64,
+    ) -> Self {
+        Self {
+            client,
+            app_handle,
+            polling_interval_ms,
+        }
+    }
+
+    pub async fn start_watching(&self) {
+        let mut interval_timer = interval(Duration::from_millis(self.polling_interval_ms));
+        let mut tracker = PhaseTracker::new();
+        let mut ready_check = ReadyCheckWatcher::new();
+
+        loop {
+            interval_timer.tick().await;
+
+            let phase = {
+                let mut client_guard = self.client.lock().await;
+                client_guard.get_gameflow_phase().await.ok()
+            };
+            match &phase {
+                Some(phase) => state::publish(&self.app_handle, StateKey::GameflowPhase, phase),
+                None => state::invalidate(&self.app_handle, StateKey::GameflowPhase),
+            }
+            auto_accept(
+                &self.app_handle,
+                &self.client,
+                &mut ready_check,
+                phase.as_deref(),
+            )
+            .await;
+            let game_running = tokio::task::spawn_blocking(is_game_running)
+                .await
+                .ok()
+                .flatten();
+
+            // Game start follows the game process, so a client restarted
+            // mid-game doesn't start the same game twice
+            let in_game_changed = self
+                .app_handle
+                .state::<std::sync::Mutex<InGameTracker>>()
+                .lock()
+                .ok()
+                .and_then(|mut t| t.update(is_in_game(phase.as_deref(), game_running)));
+            if let Some(in_game) = in_game_changed {
+                let _ = self.app_handle.emit("in-game-changed", in_game);
+                if in_game {
+                    let app_handle = self.app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        run_game_start_hooks(&app_handle).await;
+                    });
+                }
+            }
+
+            if let Some(transition) = tracker.update(phase) {
+                enter_phase(&self.app_handle, transition.to.clone());
+                self.follow_champ_select(&transition);
+                let _ = self.app_handle.emit("gameflow-phase-changed", &transition);
+                run_hook(
+                    &self.app_handle,
+                    Hook::PhaseChanged,
+                    &[to_dynamic(&transition.from), to_dynamic(&transition.to)],
+                );
+
+                let app_handle = self.app_handle.clone();
+                let phase = transition.to.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = switch_scene_for_phase(&app_handle, phase.as_deref()).await {
+                        eprintln!("OBS scene switch failed: {}", e);
+                    }
+                });
+
+                if transition.honor_vote_started() {
+                    let app_handle = self.app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = suggest_honor(&app_handle).await {
+                            eprintln!("Honor suggestion failed: {}", e);
+                        }
+                    });
+                }
+
+                if transition.game_ended() {
+                    // Standings moved with the game
+                    state::invalidate(&self.app_handle, StateKey::RankedStats);
+                    state::invalidate(&self.app_handle, StateKey::RankedStatsAllQueues);
+                    let app_handle = self.app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        run_post_game_hooks(&app_handle).await;
+                    });
+                }
+            }
+        }
+    }
+
+    /// Start the draft monitor on entering champ select and stop it on leaving.
+    fn follow_champ_select(&self, transition: &PhaseTransition) {
+        let result = if transition.entered_champ_select() {
+            start_monitor(&self.app_handle)
+        } else if transition.left_champ_select() {
+            stop_monitor(&self.app_handle)
+        } else {
+            return;
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to follow champ select: {}", e);
+        }
+    }
+}
+
+/// Baselines taken at game start so post-game hooks can tell what the game changed.
+async fn run_game_start_hooks(app: &AppHandle) {
+    if let Err(e) = commit_series_game(app) {
+        eprintln!("Failed to add the game to the series: {}", e);
+    }
+    if let Err(e) = refresh_missions(app).await {
+        eprintln!("Game start mission refresh failed: {}", e);
+    }
+}
+
+/// Everything that should happen once a game is over. Hooks are independent:
+/// one failing doesn't stop the others.
+async fn run_post_game_hooks(app: &AppHandle) {
+    // PBE standings and games stay out of the live account's history
+    let profile = {
+        let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+        let mut client_guard = client.lock().await;
+        client_guard.profile()
+    };
+    let mut last_match = None;
+    let mut history_synced = false;
+    if profile != Ok(ClientProfile::Pbe) {
+        if let Err(e) = check_rank_changes(app).await {
+            eprintln!("Post-game rank check failed: {}", e);
+        }
+
+        let synced = {
+            let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+            let store = app.state::<std::sync::Mutex<MatchStore>>();
+            let timelines = app.state::<std::sync::Mutex<MatchTimelineStore>>();
+            let mut client_guard = client.lock().await;
+            sync_recent_matches(&mut *client_guard, &store, &timelines, 5).await
+        };
+        match synced {
+            Ok(_) => {
+                history_synced = true;
+                let store = app.state::<std::sync::Mutex<MatchStore>>();
+                last_match = store
+                    .lock()
+                    .ok()
+                    .and_then(|s| s.matches().next_back().cloned());
+            }
+            Err(e) => {
+                record_error(app, ErrorCategory::MatchSync);
+                eprintln!("Post-game match sync failed: {}", e);
+            }
+        }
+    }
+
+    if let Some(game) = &last_match {
+        if let Err(e) = check_vision(app, game.game_id) {
+            eprintln!("Post-game vision check failed: {}", e);
+        }
+        if let Err(e) = check_spells(app, game.game_id) {
+            eprintln!("Post-game spell check failed: {}", e);
+        }
+    }
+
+    if let Err(e) = check_meta_shift(app) {
+        eprintln!("Post-game meta shift check failed: {}", e);
+    }
+
+    if let Err(e) = check_missions(app).await {
+        eprintln!("Post-game mission check failed: {}", e);
+    }
+
+    // Goals read ranked and match data synced above, so they go last
+    if let Err(e) = check_goals(app) {
+        eprintln!("Post-game goal check failed: {}", e);
+    }
+
+    run_hook(app, Hook::GameEnded, &[to_dynamic(&last_match)]);
+
+    // PBE games and failed syncs leave the loss streak without the game just played
+    if let Err(e) = auto_requeue(app, history_synced).await {
+        eprintln!("Auto-requeue failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_tracker_reports_transitions_once() {
+        let mut tracker = PhaseTracker::new();
+        let phase = |p: &str| Some(p.to_string());
+
+        let first = tracker.update(phase("Lobby")).unwrap();
+        assert_eq!(first.from, None);
+        assert!(tracker.update(phase("Lobby")).is_none());
+        assert!(!tracker.update(phase("InProgress")).unwrap().game_ended());
+        assert!(tracker.update(phase("EndOfGame")).unwrap().game_ended());
+        assert_eq!(
+            tracker.update(None).unwrap().from.as_deref(),
+            Some("EndOfGame")
+        );
+    }
+
+    #[test]
+    fn test_champ_select_entry_and_exit() {
+        let mut tracker = PhaseTracker::new();
+        let phase = |p: &str| Some(p.to_string());
+
+        // Already in champ select when the app starts
+        assert!(tracker
+            .update(phase("ChampSelect"))
+            .unwrap()
+            .entered_champ_select());
+        let dodge = tracker.update(phase("Lobby")).unwrap();
+        assert!(dodge.left_champ_select() && !dodge.entered_champ_select());
+        assert!(!tracker
+            .update(phase("Matchmaking"))
+            .unwrap()
+            .left_champ_select());
+        tracker.update(phase("ChampSelect"));
+        assert!(tracker.update(None).unwrap().left_champ_select());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::lcu::{api::LcuApi, client::LcuClient};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// A teammate's supportive numbers from the end-of-game stats block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeammateStats {
+    pub puuid: String,
+    pub name: String,
+    pub champion_id: i64,
+    pub vision_score: f64,
+    pub cc_seconds: f64,
+    pub deaths: f64,
+    pub assists: f64,
+    pub ally_protection: f64, // Healing and shielding on teammates
+}
+
+/// Payload of the `honor-suggestion` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct HonorSuggestion {
+    pub game_id: i64,
+    pub puuid: String,
+    pub name: String,
+    pub champion_id: i64,
+    pub score: f64,
+    pub reasons: Vec<String>, // e.g. "Most vision"
+}
+
+fn stat(stats: &serde_json::Value, key: &str) -> f64 {
+    stats[key].as_f64().unwrap_or(0.0)
+}
+
+/// The local player's teammates, without the local player.
+pub fn parse_teammates(block: &serde_json::Value) -> Vec<TeammateStats> {
+    let local_puuid = block["localPlayer"]["puuid"].as_str().unwrap_or_default();
+    let Some(teams) = block["teams"].as_array() else {
+        return Vec::new();
+    };
+    teams
+        .iter()
+        .filter(|t| t["isPlayerTeam"].as_bool() == Some(true))
+        .flat_map(|t| t["players"].as_array().cloned().unwrap_or_default())
+        .filter(|p| p["puuid"].as_str() != Some(local_puuid))
+        .map(|p| {
+            let stats = &p["stats"];
+            let name = p["riotIdGameName"]
+                .as_str()
+                .or_else(|| p["summonerName"].as_str())
+                .unwrap_or_default();
+            TeammateStats {
+                puuid: p["puuid"].as_str().unwrap_or_default().to_string(),
+                name: name.to_string(),
+                champion_id: p["championId"].as_i64().unwrap_or(0),
+                vision_score: stat(stats, "VISION_SCORE"),
+                cc_seconds: stat(stats, "TIME_CCING_OTHERS"),
+                deaths: stat(stats, "NUM_DEATHS"),
+                assists: stat(stats, "ASSISTS"),
+                ally_protection: stat(stats, "TOTAL_HEAL_ON_TEAMMATES")
+                    + stat(stats, "TOTAL_DAMAGE_SHIELDED_ON_TEAMMATES"),
+            }
+        })
+        .collect()
+}
+
+/// Score each teammate against the others on vision, crowd control, assists,
+/// protecting allies and staying alive, each scaled to the team's best, and
+/// suggest the highest. Kills and damage don't count: honor is for the
+/// teammate who made the game easier, not the one who carried it.
+pub fn suggest(teammates: &[TeammateStats]) -> Option<(&TeammateStats, f64, Vec<String>)> {
+    let best = |f: fn(&TeammateStats) -> f64| teammates.iter().map(f).fold(0.0_f64, f64::max);
+    let share = |value: f64, max: f64| if max > 0.0 { value / max } else { 0.0 };
+    let max_vision = best(|t| t.vision_score);
+    let max_cc = best(|t| t.cc_seconds);
+    let max_assists = best(|t| t.assists);
+    let max_protection = best(|t| t.ally_protection);
+    let max_deaths = best(|t| t.deaths);
+    let min_deaths = teammates.iter().map(|t| t.deaths).fold(f64::MAX, f64::min);
+
+    teammates
+        .iter()
+        .map(|t| {
+            let score = share(t.vision_score, max_vision)
+                + share(t.cc_seconds, max_cc)
+                + share(t.assists, max_assists)
+                + share(t.ally_protection, max_protection)
+                + (1.0 - share(t.deaths, max_deaths));
+            let mut reasons = Vec::new();
+            let leads = |value: f64, max: f64| max > 0.0 && value == max;
+            if leads(t.vision_score, max_vision) {
+                reasons.push("Most vision".to_string());
+            }
+            if leads(t.cc_seconds, max_cc) {
+                reasons.push("Most crowd control".to_string());
+            }
+            if leads(t.ally_protection, max_protection) {
+                reasons.push("Most healing and shielding on allies".to_string());
+            }
+            if t.deaths == min_deaths {
+                reasons.push("Fewest deaths".to_string());
+            }
+            (t, score, reasons)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Emit `honor-suggestion` for the game that just ended.
+pub async fn suggest_honor(app: &AppHandle) -> Result<(), String> {
+    let block = {
+        let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+        let mut client_guard = client.lock().await;
+        client_guard.get_eog_stats_block().await?
+    };
+    let teammates = parse_teammates(&block);
+    let Some((teammate, score, reasons)) = suggest(&teammates) else {
+        return Ok(());
+    };
+
+    let suggestion = HonorSuggestion {
+        game_id: block["gameId"].as_i64().unwrap_or(0),
+        puuid: teammate.puuid.clone(),
+        name: teammate.name.clone(),
+        champion_id: teammate.champion_id,
+        score,
+        reasons,
+    };
+    let _ = app.emit("honor-suggestion", &suggestion);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn player(puuid: &str, vision: i64, cc: i64, deaths: i64, assists: i64) -> serde_json::Value {
+        json!({
+            "puuid": puuid,
+            "riotIdGameName": puuid,
+            "championId": 1,
+            "stats": {
+                "VISION_SCORE": vision,
+                "TIME_CCING_OTHERS": cc,
+                "NUM_DEATHS": deaths,
+                "ASSISTS": assists,
+                "CHAMPIONS_KILLED": 20
+            }
+        })
+    }
+
+    #[test]
+    fn test_suggests_most_supportive_teammate() {
+        let block = json!({
+            "gameId": 42,
+            "localPlayer": { "puuid": "me" },
+            "teams": [
+                {
+                    "isPlayerTeam": true,
+                    "players": [
+                        player("me", 90, 60, 0, 30),
+                        player("carry", 10, 5, 2, 4),
+                        player("support", 70, 55, 3, 18),
+                        player("feeder", 20, 50, 12, 6)
+                    ]
+                },
+                { "isPlayerTeam": false, "players": [player("enemy", 99, 99, 0, 30)] }
+            ]
+        });
+
+        let teammates = parse_teammates(&block);
+        assert_eq!(teammates.len(), 3, "local player and enemies are left out");
+
+        let (best, _, reasons) = suggest(&teammates).unwrap();
+        assert_eq!(best.puuid, "support");
+        assert_eq!(reasons, vec!["Most vision", "Most crowd control"]);
+        assert!(suggest(&[]).is_none());
+    }
+}
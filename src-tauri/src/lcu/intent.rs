@@ -0,0 +1,255 @@
+use crate::analytics::bans::ban_counts;
+use crate::lcu::draft::DraftState;
+use crate::lcu::pool::{local_position, suggest_alternatives, LossReason, PoolAlternative};
+use crate::lcu::timeline::DraftTimelineStore;
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Intended champion per position ("TOP", "JUNGLE", ...), keyed by queue id, as
+/// stored in `AppSettings::pick_intents`.
+pub type PickIntents = BTreeMap<i32, BTreeMap<String, i64>>;
+
+/// Payload of the `pick-intent-lost` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct PickIntentLost {
+    pub champion_id: i64,
+    pub queue_id: i32,
+    pub position: String,
+    pub reason: LossReason,
+    pub alternatives: Vec<PoolAlternative>,
+}
+
+/// How likely a planned pick is to be banned before the player gets to it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntentRisk {
+    pub queue_id: i32,
+    pub position: String,
+    pub champion_id: i64,
+    pub ban_rate: Option<f32>, // Share of recorded drafts it was banned in; None before any
+}
+
+/// Risk for every planned pick, from per-champion ban counts over `drafts`
+/// recorded drafts.
+pub fn intent_risks(
+    intents: &PickIntents,
+    drafts: u32,
+    ban_counts: &BTreeMap<i64, u32>,
+) -> Vec<IntentRisk> {
+    intents
+        .iter()
+        .flat_map(|(&queue_id, positions)| {
+            positions
+                .iter()
+                .map(move |(position, &champion_id)| IntentRisk {
+                    queue_id,
+                    position: position.clone(),
+                    champion_id,
+                    ban_rate: (drafts > 0).then(|| {
+                        ban_counts.get(&champion_id).copied().unwrap_or(0) as f32 / drafts as f32
+                    }),
+                })
+        })
+        .collect()
+}
+
+/// Follows the local player's intended pick through one draft.
+#[derive(Default)]
+pub struct IntentWatcher {
+    queue_id: Option<Option<i32>>, // Looked up once per draft; the inner None means unknown
+    settled: bool,                 // Lost and reported, or locked in by the player
+}
+
+impl IntentWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn needs_queue(&self) -> bool {
+        self.queue_id.is_none()
+    }
+
+    pub fn set_queue(&mut self, queue_id: Option<i32>) {
+        self.queue_id = Some(queue_id);
+    }
+
+    /// The intended champion for this queue and the player's position, with the
+    /// reason it became unavailable, the first time that happens.
+    pub fn check(
+        &mut self,
+        state: &DraftState,
+        intents: &PickIntents,
+    ) -> Option<(i32, String, i64, LossReason)> {
+        if self.settled {
+            return None;
+        }
+        let queue_id = self.queue_id.flatten()?;
+        let position = local_position(state)?;
+        let champion_id = *intents.get(&queue_id)?.get(&position)?;
+
+        let local_cell = state.local_player_cell_id;
+        let local_team = state.local_team().map(|t| t.team_id);
+        let mut reason = None;
+        for team in &state.teams {
+            if team
+                .bans
+                .iter()
+                .any(|b| b.completed && b.champion_id == champion_id)
+            {
+                reason = Some(LossReason::Banned);
+            }
+            for cell in team
+                .cells
+                .iter()
+                .filter(|c| c.champion_id == Some(champion_id))
+            {
+                if Some(cell.cell_id) == local_cell {
+                    // The plan worked out
+                    self.settled = true;
+                    return None;
+                }
+                reason = Some(if Some(team.team_id) == local_team {
+                    LossReason::PickedByAlly
+                } else {
+                    LossReason::PickedByEnemy
+                });
+            }
+        }
+
+        let reason = reason?;
+        self.settled = true;
+        Some((queue_id, position, champion_id, reason))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Emit `pick-intent-lost` with fallback suggestions once the player's
+/// intended pick is banned or taken.
+pub fn announce_intent_loss(app: &AppHandle, watcher: &mut IntentWatcher, state: &DraftState) {
+    let intents = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().pick_intents.clone()))
+        .unwrap_or_default();
+    let Some((queue_id, position, champion_id, reason)) = watcher.check(state, &intents) else {
+        return;
+    };
+
+    let lost = PickIntentLost {
+        champion_id,
+        queue_id,
+        alternatives: suggest_alternatives(app, state, champion_id, Some(&position)),
+        position,
+        reason,
+    };
+    let _ = app.emit("pick-intent-lost", &lost);
+}
+
+/// Set (or with `None`, clear) the intended pick for a queue and position.
+#[tauri::command]
+pub async fn set_pick_intent(
+    queue_id: i32,
+    position: String,
+    champion_id: Option<i64>,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<(), String> {
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut updated = settings_guard.get().clone();
+    let queue = updated.pick_intents.entry(queue_id).or_default();
+    match champion_id {
+        Some(id) => {
+            queue.insert(position.to_uppercase(), id);
+        }
+        None => {
+            queue.remove(&position.to_uppercase());
+        }
+    }
+    updated.pick_intents.retain(|_, queue| !queue.is_empty());
+    settings_guard.set(updated)
+}
+
+#[tauri::command]
+pub async fn get_pick_intent_risks(
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    drafts: tauri::State<'_, Mutex<DraftTimelineStore>>,
+) -> Result<Vec<IntentRisk>, String> {
+    let intents = settings
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .get()
+        .pick_intents
+        .clone();
+    let drafts_guard = drafts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let (draft_count, counts) = ban_counts(drafts_guard.timelines());
+    Ok(intent_risks(&intents, draft_count, &counts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+
+    fn draft(ally_lock: i64, enemy_lock: i64) -> DraftState {
+        SessionBuilder::new()
+            .ally(0, 0, 0)
+            .position("jungle")
+            .ally(1, ally_lock, 0)
+            .position("top")
+            .enemy(5, enemy_lock, 0)
+            .state()
+    }
+
+    #[test]
+    fn test_intent_loss_is_reported_once() {
+        let intents: PickIntents =
+            BTreeMap::from([(420, BTreeMap::from([("JUNGLE".to_string(), 64)]))]);
+
+        let mut watcher = IntentWatcher::new();
+        let state = draft(64, 0);
+        assert!(
+            watcher.check(&state, &intents).is_none(),
+            "queue not known yet"
+        );
+
+        watcher.set_queue(Some(420));
+        assert_eq!(
+            watcher.check(&state, &intents),
+            Some((420, "JUNGLE".to_string(), 64, LossReason::PickedByAlly))
+        );
+        assert!(watcher.check(&state, &intents).is_none());
+
+        watcher.reset();
+        watcher.set_queue(Some(420));
+        let state = draft(0, 0);
+        assert!(watcher.check(&state, &intents).is_none());
+        let state = draft(0, 64);
+        assert_eq!(
+            watcher.check(&state, &intents).map(|(.., reason)| reason),
+            Some(LossReason::PickedByEnemy)
+        );
+
+        // Other queues have their own intents
+        watcher.reset();
+        watcher.set_queue(Some(400));
+        assert!(watcher.check(&state, &intents).is_none());
+    }
+
+    #[test]
+    fn test_intent_risk_is_the_ban_rate() {
+        let intents: PickIntents = BTreeMap::from([(
+            420,
+            BTreeMap::from([("JUNGLE".to_string(), 64), ("TOP".to_string(), 86)]),
+        )]);
+        let counts = BTreeMap::from([(64, 3)]);
+
+        let risks = intent_risks(&intents, 4, &counts);
+        assert_eq!(risks[0].position, "JUNGLE");
+        assert_eq!(risks[0].ban_rate, Some(0.75));
+        assert_eq!(risks[1].ban_rate, Some(0.0));
+        assert_eq!(intent_risks(&intents, 0, &counts)[0].ban_rate, None);
+    }
+}
@@ -1,7 +1,21 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
+/// Launcher folders League gets installed under across regions. Every folder
+/// inside is checked, so localized install names and Garena's numbered game
+/// folders are found too.
+const INSTALL_ROOTS: [&str; 8] = [
+    "C:\\Riot Games",
+    "D:\\Riot Games",
+    "C:\\Program Files\\Riot Games",
+    "C:\\Program Files (x86)\\Riot Games",
+    "C:\\Garena\\Games",
+    "C:\\Program Files\\Garena\\Games",
+    "C:\\Program Files (x86)\\Garena\\Games",
+    "C:\\WeGameApps",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LockfileData {
     pub process_name: String,
@@ -11,7 +25,9 @@ pub struct LockfileData {
     pub protocol: String,
 }
 
-pub fn get_lockfile_paths() -> Vec<PathBuf> {
+/// Where lockfiles can be. `wine_prefixes` are the extra prefixes from
+/// settings, searched before the default ones.
+pub fn get_lockfile_paths(wine_prefixes: &[PathBuf]) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     
     // Primary location: %LOCALAPPDATA%\Riot Games\League of Legends\lockfile
@@ -37,17 +53,142 @@ pub fn get_lockfile_paths() -> Vec<PathBuf> {
         "C:\\Riot Games\\League of Legends\\lockfile",
         "C:\\Program Files\\Riot Games\\League of Legends\\lockfile",
         "C:\\Program Files (x86)\\Riot Games\\League of Legends\\lockfile",
+        "C:\\Riot Games\\League of Legends (PBE)\\lockfile",
     ];
     
     for path_str in program_files_paths {
         paths.push(PathBuf::from(path_str));
     }
+
+    for root in INSTALL_ROOTS {
+        for path in install_lockfile_paths(Path::new(root)) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+
+    // Linux: League runs under wine, so look inside the usual prefixes
+    if cfg!(target_os = "linux") {
+        let mut prefixes = wine_prefixes.to_vec();
+        prefixes.extend(WINE_PREFIXES.iter().map(|p| expand_home(p)));
+        paths.extend(wine_lockfile_paths(&prefixes));
+    }
     
     paths
 }
 
-pub fn read_lockfile() -> Result<LockfileData, String> {
-    let paths = get_lockfile_paths();
+/// Lockfile locations for each install folder under `root`: the folder itself
+/// and, for Garena layouts, its `LeagueClient` subfolder.
+fn install_lockfile_paths(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && !p.ends_with("Riot Client"))
+        .collect();
+    dirs.sort();
+    dirs.into_iter()
+        .flat_map(|dir| {
+            [
+                dir.join("lockfile"),
+                dir.join("LeagueClient").join("lockfile"),
+            ]
+        })
+        .collect()
+}
+
+/// Where Linux launchers keep wine prefixes. Each entry is either a prefix
+/// or a folder of prefixes (Lutris games, Bottles).
+const WINE_PREFIXES: [&str; 6] = [
+    "~/.wine",
+    "~/Games",
+    "~/.local/share/lutris/prefixes",
+    "~/.var/app/net.lutris.Lutris/data/lutris/prefixes",
+    "~/.var/app/com.usebottles.bottles/data/bottles/bottles",
+    "~/snap/leagueoflegends/common/.wine",
+];
+
+/// Launcher folders inside a prefix's `drive_c`.
+const WINE_INSTALL_ROOTS: [&str; 3] = [
+    "Riot Games",
+    "Program Files/Riot Games",
+    "Program Files (x86)/Riot Games",
+];
+
+/// Expand a leading `~` to the user's home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    let path = path.trim();
+    let rest = match path.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with('/') || rest.starts_with('\\') => &rest[1..],
+        _ => return PathBuf::from(path),
+    };
+    match dirs::home_dir() {
+        Some(home) if rest.is_empty() => home,
+        Some(home) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Lockfile locations inside wine prefixes. An entry without a `drive_c`
+/// is searched one level down for prefixes.
+fn wine_lockfile_paths(entries: &[PathBuf]) -> Vec<PathBuf> {
+    let is_prefix = |dir: &Path| dir.join("drive_c").is_dir();
+    let mut paths = Vec::new();
+    for entry in entries {
+        let prefixes: Vec<PathBuf> = if is_prefix(entry) {
+            vec![entry.clone()]
+        } else {
+            let mut children: Vec<PathBuf> = fs::read_dir(entry)
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| is_prefix(p))
+                .collect();
+            children.sort();
+            children
+        };
+        for prefix in prefixes {
+            for root in WINE_INSTALL_ROOTS {
+                let root = prefix.join("drive_c").join(root);
+                paths.push(root.join("League of Legends").join("lockfile"));
+                paths.extend(install_lockfile_paths(&root));
+            }
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    paths.retain(|p| seen.insert(p.clone()));
+    paths
+}
+
+/// Which account realm a client plays on. PBE games are kept out of the
+/// live account's stored history and analytics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientProfile {
+    #[default]
+    Live,
+    Pbe,
+}
+
+impl ClientProfile {
+    /// From a lockfile path or client command line, which name the PBE
+    /// install folder or region.
+    pub fn detect(location: &str) -> Self {
+        if location.to_uppercase().contains("PBE") {
+            ClientProfile::Pbe
+        } else {
+            ClientProfile::Live
+        }
+    }
+}
+
+pub fn read_lockfile(wine_prefixes: &[PathBuf]) -> Result<LockfileData, String> {
+    let paths = get_lockfile_paths(wine_prefixes);
     let mut errors = Vec::new();
     
     for lockfile_path in paths {
@@ -69,6 +210,130 @@ pub fn read_lockfile() -> Result<LockfileData, String> {
     ))
 }
 
+/// A running client found through a lockfile or its process, with where it
+/// was found.
+#[derive(Debug, Clone)]
+pub struct ClientCandidate {
+    pub credentials: LockfileData,
+    pub source: String,
+    pub profile: ClientProfile,
+}
+
+/// Clients with a readable lockfile, in `get_lockfile_paths` order.
+pub fn lockfile_clients(wine_prefixes: &[PathBuf]) -> Vec<ClientCandidate> {
+    get_lockfile_paths(wine_prefixes)
+        .into_iter()
+        .filter_map(|path| {
+            let contents = fs::read_to_string(&path).ok()?;
+            let source = path.display().to_string();
+            Some(ClientCandidate {
+                credentials: parse_lockfile(&contents).ok()?,
+                profile: ClientProfile::detect(&source),
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Credentials from a `LeagueClientUx` command line. Every running client has
+/// one, including a second client sharing an install (and lockfile) with the
+/// first.
+pub fn parse_client_command_line(line: &str) -> Option<LockfileData> {
+    let arg = |name: &str| {
+        line.split_whitespace()
+            .map(|part| part.trim_matches('"'))
+            .find_map(|part| part.strip_prefix(name)?.strip_prefix('='))
+    };
+    Some(LockfileData {
+        process_name: "LeagueClientUx".to_string(),
+        process_id: arg("--app-pid")?.parse().ok()?,
+        port: arg("--app-port")?.parse().ok()?,
+        password: arg("--remoting-auth-token")?.to_string(),
+        protocol: "https".to_string(),
+    })
+}
+
+fn client_command_lines() -> Vec<String> {
+    #[cfg(windows)]
+    let output = {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                "Get-CimInstance Win32_Process -Filter \"Name='LeagueClientUx.exe'\" | ForEach-Object { $_.CommandLine }",
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+    };
+    #[cfg(not(windows))]
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "args="])
+        .output();
+
+    output
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter(|line| line.contains("LeagueClientUx"))
+                .map(|line| line.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Every running client: lockfiles first, then any other `LeagueClientUx`
+/// process. Spawns a process listing, so only call it when asked to.
+pub fn detect_clients(wine_prefixes: &[PathBuf]) -> Vec<ClientCandidate> {
+    let mut clients = lockfile_clients(wine_prefixes);
+    for line in client_command_lines() {
+        if let Some(credentials) = parse_client_command_line(&line) {
+            if !clients
+                .iter()
+                .any(|c| c.credentials.port == credentials.port)
+            {
+                clients.push(ClientCandidate {
+                    source: format!("LeagueClientUx process {}", credentials.process_id),
+                    profile: ClientProfile::detect(&line),
+                    credentials,
+                });
+            }
+        }
+    }
+    clients
+}
+
+/// The client to bind to. Without a preference that's the first lockfile
+/// found. A preferred port that's gone only falls back when exactly one
+/// client is left, since that's the selected client after a restart.
+pub fn find_client(
+    preferred_port: Option<u16>,
+    wine_prefixes: &[PathBuf],
+) -> Result<ClientCandidate, String> {
+    let Some(port) = preferred_port else {
+        return match lockfile_clients(wine_prefixes).into_iter().next() {
+            Some(client) => Ok(client),
+            None => Err(read_lockfile(wine_prefixes).err().unwrap_or_default()),
+        };
+    };
+    let mut clients = lockfile_clients(wine_prefixes);
+    if !clients.iter().any(|c| c.credentials.port == port) {
+        clients = detect_clients(wine_prefixes);
+    }
+    if let Some(i) = clients.iter().position(|c| c.credentials.port == port) {
+        return Ok(clients.remove(i));
+    }
+    match clients.len() {
+        0 => Err(read_lockfile(wine_prefixes).err().unwrap_or_default()),
+        1 => Ok(clients.remove(0)),
+        n => Err(format!(
+            "The selected client on port {} is no longer running; {} other clients were found",
+            port, n
+        )),
+    }
+}
+
 pub fn parse_lockfile(contents: &str) -> Result<LockfileData, String> {
     // Lockfile format: "PROCESS_NAME:PROCESS_ID:PORT:PASSWORD:PROTOCOL"
     let parts: Vec<&str> = contents.trim().split(':').collect();
@@ -113,4 +378,64 @@ mod tests {
         assert_eq!(result.password, "password");
         assert_eq!(result.protocol, "https");
     }
+
+    #[test]
+    fn test_parse_client_command_line() {
+        let line = "\"C:/Riot Games/League of Legends (PBE)/LeagueClientUx.exe\" \"--riotclient-auth-token=abc\" \"--app-port=62143\" \"--remoting-auth-token=Xy_z-12\" \"--app-pid=9120\"";
+        let result = parse_client_command_line(line).unwrap();
+
+        assert_eq!(result.port, 62143);
+        assert_eq!(result.password, "Xy_z-12");
+        assert_eq!(result.process_id, 9120);
+        assert!(parse_client_command_line("LeagueClientUx.exe --app-port=1").is_none());
+        assert_eq!(ClientProfile::detect(line), ClientProfile::Pbe);
+    }
+
+    #[test]
+    fn test_install_lockfile_paths() {
+        let root = std::env::temp_dir().join("trackimo-install-roots-test");
+        let _ = fs::remove_dir_all(&root);
+        for dir in ["32771", "리그 오브 레전드", "Riot Client"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        let paths = install_lockfile_paths(&root);
+        assert_eq!(paths.len(), 4);
+        assert!(paths.contains(&root.join("32771").join("LeagueClient").join("lockfile")));
+        assert!(paths.contains(&root.join("리그 오브 레전드").join("lockfile")));
+        assert!(install_lockfile_paths(&root.join("missing")).is_empty());
+        assert_eq!(
+            ClientProfile::detect("C:\\Riot Games\\League of Legends\\lockfile"),
+            ClientProfile::Live
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_wine_prefixes() {
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(expand_home("~/Games"), home.join("Games"));
+            assert_eq!(expand_home("~"), home);
+        }
+        assert_eq!(expand_home("/opt/wine"), PathBuf::from("/opt/wine"));
+        assert_eq!(expand_home("~other/x"), PathBuf::from("~other/x"));
+
+        let bottles = std::env::temp_dir().join("trackimo-wine-prefix-test");
+        let _ = fs::remove_dir_all(&bottles);
+        let install = bottles
+            .join("league")
+            .join("drive_c")
+            .join("Riot Games")
+            .join("League of Legends");
+        fs::create_dir_all(&install).unwrap();
+        fs::create_dir_all(bottles.join("not-a-prefix")).unwrap();
+
+        let paths = wine_lockfile_paths(std::slice::from_ref(&bottles));
+        assert_eq!(paths[0], install.join("lockfile"));
+        assert!(paths.iter().all(|p| p.starts_with(bottles.join("league"))));
+        assert_eq!(wine_lockfile_paths(&[bottles.join("league")]), paths);
+
+        fs::remove_dir_all(&bottles).unwrap();
+    }
 }
@@ -0,0 +1,185 @@
+use crate::lcu::{api::LcuApi, client::LcuClient, error::LcuError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionObjective {
+    pub description: String,
+    pub current: i64,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mission {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub series_name: Option<String>, // Event pass or questline the mission belongs to
+    pub completed: bool,
+    pub expires_at: Option<i64>, // Epoch millis
+    pub objectives: Vec<MissionObjective>,
+    pub rewards: Vec<String>, // Reward descriptions, e.g. "200 Tokens"
+}
+
+fn non_empty(value: &serde_json::Value) -> Option<String> {
+    value
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// Parse `/lol-missions/v1/missions`. Hidden and expired missions are dropped.
+pub fn parse_missions(json_value: &serde_json::Value, now: i64) -> Vec<Mission> {
+    let Some(missions) = json_value.as_array() else {
+        return Vec::new();
+    };
+
+    missions
+        .iter()
+        .filter(|m| {
+            m["display"]["locations"]
+                .as_array()
+                .is_none_or(|l| !l.is_empty())
+        })
+        .filter_map(|m| {
+            let expires_at = m["endTime"].as_i64().filter(|t| *t > 0);
+            if expires_at.is_some_and(|t| t < now) {
+                return None;
+            }
+
+            let objectives = m["objectives"]
+                .as_array()
+                .map(|objectives| {
+                    objectives
+                        .iter()
+                        .map(|o| MissionObjective {
+                            description: o["description"].as_str().unwrap_or("").to_string(),
+                            current: o["progress"]["currentProgress"].as_i64().unwrap_or(0),
+                            total: o["progress"]["totalCount"].as_i64().unwrap_or(0),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let rewards = m["rewards"]
+                .as_array()
+                .map(|rewards| {
+                    rewards
+                        .iter()
+                        .filter_map(|r| non_empty(&r["description"]))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(Mission {
+                id: non_empty(&m["id"])?,
+                title: m["title"].as_str().unwrap_or("").to_string(),
+                description: m["description"].as_str().unwrap_or("").to_string(),
+                series_name: non_empty(&m["seriesName"]),
+                completed: m["status"].as_str() == Some("COMPLETED"),
+                expires_at,
+                objectives,
+                rewards,
+            })
+        })
+        .collect()
+}
+
+/// Remembers which missions were already complete so only fresh completions are announced.
+#[derive(Default)]
+pub struct MissionTracker {
+    completed: Option<HashSet<String>>, // None until the first fetch
+}
+
+impl MissionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Missions that completed since the previous update. The first update only
+    /// records the baseline.
+    pub fn update(&mut self, missions: &[Mission]) -> Vec<Mission> {
+        let now_completed: HashSet<String> = missions
+            .iter()
+            .filter(|m| m.completed)
+            .map(|m| m.id.clone())
+            .collect();
+
+        let newly_completed = match &self.completed {
+            Some(previous) => missions
+                .iter()
+                .filter(|m| m.completed && !previous.contains(&m.id))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.completed = Some(now_completed);
+        newly_completed
+    }
+}
+
+/// Fetch missions and let the tracker see them. Returns the ones that just completed.
+pub async fn refresh_missions(app: &AppHandle) -> Result<Vec<Mission>, String> {
+    let missions = {
+        let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+        let mut client_guard = client.lock().await;
+        client_guard.get_missions().await?
+    };
+
+    let tracker = app.state::<Mutex<MissionTracker>>();
+    let mut tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(tracker_guard.update(&missions))
+}
+
+/// Post-game hook: emit `mission-completed` for every mission finished by the game.
+pub async fn check_missions(app: &AppHandle) -> Result<(), String> {
+    for mission in refresh_missions(app).await? {
+        let _ = app.emit("mission-completed", &mission);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_missions(
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<Vec<Mission>, LcuError> {
+    let mut client_guard = client.lock().await;
+    client_guard.get_missions().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_missions_and_track_completion() {
+        let payload = |status: &str| {
+            json!([
+                {
+                    "id": "m1", "title": "Play games", "status": status, "endTime": 5000,
+                    "seriesName": "Event_Pass",
+                    "objectives": [{ "description": "Play 3 games", "progress": { "currentProgress": 1, "totalCount": 3 } }],
+                    "rewards": [{ "description": "200 Tokens" }]
+                },
+                { "id": "old", "title": "Expired", "status": "PENDING", "endTime": 10 },
+                { "id": "hidden", "status": "PENDING", "display": { "locations": [] } }
+            ])
+        };
+
+        let missions = parse_missions(&payload("PENDING"), 1000);
+        assert_eq!(missions.len(), 1);
+        assert_eq!(missions[0].objectives[0].total, 3);
+        assert_eq!(missions[0].rewards, vec!["200 Tokens".to_string()]);
+
+        let mut tracker = MissionTracker::new();
+        assert!(tracker.update(&missions).is_empty());
+        let done = tracker.update(&parse_missions(&payload("COMPLETED"), 1000));
+        assert_eq!(done[0].id, "m1");
+        assert!(tracker
+            .update(&parse_missions(&payload("COMPLETED"), 1000))
+            .is_empty());
+    }
+}
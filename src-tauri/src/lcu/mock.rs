@@ -0,0 +1,551 @@
+use super::api::LcuApi;
+use super::client::{
+    is_summoners_rift_queue, with_retries, ChampionMastery, EndpointClass, MatchHistoryGame,
+    RankedSplit, RankedStats, RetryPolicies, SummonerInfo,
+};
+use super::draft::{parse_draft_session, DraftState};
+use super::dto::{ChampSelectSession, Friend};
+use super::error::LcuError;
+use super::missions::Mission;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Misbehaviour injected into the polled endpoints (gameflow phase and champ
+/// select session), so retries, credential refresh and the monitor's recovery
+/// can be exercised deterministically.
+#[derive(Debug, Clone, Default)]
+pub struct MockFaults {
+    pub latency: Duration,                     // Added to every attempt
+    pub fail_every: Option<u32>,               // Every nth attempt answers 500
+    pub expire_credentials_after: Option<u32>, // Attempts served per set of credentials; 401 after until they're refreshed
+}
+
+/// In-memory stand-in for the League client, used to drive the monitor and
+/// other `LcuApi` consumers without network access.
+///
+/// Draft sessions are queued and consumed one per call; once the queue is empty
+/// the mock behaves like a client that is not in champion select. With `faults`
+/// set, polled endpoints go through the real client's retry loop.
+#[derive(Default)]
+pub struct MockLcuClient {
+    pub gameflow_phase: Option<String>,
+    pub draft_sessions: VecDeque<serde_json::Value>,
+    pub last_draft_session: Option<serde_json::Value>, // The one served last
+    pub summoner: Option<SummonerInfo>,
+    pub ranked_stats: Vec<RankedStats>,
+    pub match_history: Vec<MatchHistoryGame>,
+    pub owned_champion_ids: Vec<i64>,
+    pub free_rotation: Vec<i64>,
+    pub masteries: Vec<ChampionMastery>,
+    pub game_details: HashMap<i64, serde_json::Value>,
+    pub game_timelines: HashMap<i64, serde_json::Value>,
+    pub eog_stats_block: Option<serde_json::Value>,
+    pub ranked_split: RankedSplit,
+    pub chest_eligible_ids: Vec<i64>,
+    pub first_win_available: bool,
+    pub queue_id: Option<i32>,
+    pub position_preferences: Vec<String>,
+    pub missions: Vec<Mission>,
+    pub patched_actions: Vec<(i64, i64, bool)>, // (action id, champion id, complete) in call order
+    pub friends: Vec<Friend>,
+    pub spectated: Vec<String>,         // Puuids in call order
+    pub lobby_calls: Vec<&'static str>, // "play_again" / "start_matchmaking" / "accept_ready_check" in call order
+    pub faults: Option<MockFaults>,
+    pub policies: RetryPolicies,
+    pub attempts: u32, // Polled requests, including failed ones and retries
+    pub credential_refreshes: u32, // Times the retry loop dropped credentials
+    served_since_refresh: u32,
+}
+
+impl MockLcuClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_draft_session(&mut self, session: serde_json::Value) {
+        self.draft_sessions.push_back(session);
+    }
+
+    /// Stands in for `LcuClient::clear_credentials` in the retry loop.
+    pub fn clear_credentials(&mut self) {
+        self.credential_refreshes += 1;
+        self.served_since_refresh = 0;
+    }
+
+    /// Run `read` as a polled request: once without faults, and otherwise
+    /// behind them and under the polling retry policy.
+    async fn polled<T>(
+        &mut self,
+        read: fn(&mut Self) -> Result<T, LcuError>,
+    ) -> Result<T, LcuError> {
+        if self.faults.is_none() {
+            return read(self);
+        }
+        with_retries!(self, EndpointClass::Polling, self.attempt(read).await)
+    }
+
+    async fn attempt<T>(
+        &mut self,
+        read: fn(&mut Self) -> Result<T, LcuError>,
+    ) -> Result<T, LcuError> {
+        let faults = self.faults.clone().unwrap_or_default();
+        tokio::time::sleep(faults.latency).await;
+        self.attempts += 1;
+        if faults
+            .fail_every
+            .is_some_and(|n| n > 0 && self.attempts.is_multiple_of(n))
+        {
+            return Err(LcuError::Http(500));
+        }
+        if faults
+            .expire_credentials_after
+            .is_some_and(|n| self.served_since_refresh >= n)
+        {
+            return Err(LcuError::Unauthorized);
+        }
+        self.served_since_refresh += 1;
+        read(self)
+    }
+}
+
+#[async_trait]
+impl LcuApi for MockLcuClient {
+    async fn get_gameflow_phase(&mut self) -> Result<String, LcuError> {
+        self.polled(|mock| {
+            mock.gameflow_phase
+                .clone()
+                .ok_or_else(|| LcuError::ClientNotRunning("Mock LCU".to_string()))
+        })
+        .await
+    }
+
+    async fn get_draft_session(&mut self) -> Result<ChampSelectSession, LcuError> {
+        let session = self
+            .polled(|mock| {
+                mock.draft_sessions
+                    .pop_front()
+                    .ok_or(LcuError::NotInChampSelect)
+            })
+            .await?;
+        self.last_draft_session = Some(session.clone());
+        Ok(serde_json::from_value(session)?)
+    }
+
+    async fn get_draft_session_json(&mut self) -> Result<serde_json::Value, LcuError> {
+        self.last_draft_session
+            .clone()
+            .ok_or(LcuError::NotInChampSelect)
+    }
+
+    async fn get_current_summoner(&mut self) -> Result<SummonerInfo, LcuError> {
+        self.summoner
+            .clone()
+            .ok_or_else(|| LcuError::Other("Mock LCU: no summoner configured".to_string()))
+    }
+
+    async fn get_ranked_stats(
+        &mut self,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError> {
+        Ok(self
+            .ranked_stats
+            .iter()
+            .filter(|s| include_all_queues || is_summoners_rift_queue(&s.queue_type))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_match_history(&mut self) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        self.get_match_history_paginated(0, 10).await
+    }
+
+    async fn get_match_history_paginated(
+        &mut self,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        Ok(self
+            .match_history
+            .iter()
+            .skip(beg_index)
+            .take(end_index.saturating_sub(beg_index))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_owned_champion_ids(&mut self) -> Result<Vec<i64>, LcuError> {
+        Ok(self.owned_champion_ids.clone())
+    }
+
+    async fn get_free_rotation(&mut self) -> Result<Vec<i64>, LcuError> {
+        Ok(self.free_rotation.clone())
+    }
+
+    // The mock serves a single player: every lookup resolves to the configured summoner
+
+    async fn lookup_summoner(
+        &mut self,
+        _game_name: &str,
+        _tag_line: &str,
+    ) -> Result<SummonerInfo, LcuError> {
+        self.get_current_summoner().await
+    }
+
+    async fn get_ranked_stats_for(
+        &mut self,
+        _puuid: &str,
+        include_all_queues: bool,
+    ) -> Result<Vec<RankedStats>, LcuError> {
+        self.get_ranked_stats(include_all_queues).await
+    }
+
+    async fn get_top_masteries(
+        &mut self,
+        _summoner_id: &str,
+        limit: usize,
+    ) -> Result<Vec<ChampionMastery>, LcuError> {
+        Ok(self.masteries.iter().take(limit).cloned().collect())
+    }
+
+    async fn get_match_history_for(
+        &mut self,
+        _puuid: &str,
+        beg_index: usize,
+        end_index: usize,
+    ) -> Result<Vec<MatchHistoryGame>, LcuError> {
+        self.get_match_history_paginated(beg_index, end_index).await
+    }
+
+    async fn get_game_details(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError> {
+        self.game_details
+            .get(&game_id)
+            .cloned()
+            .ok_or(LcuError::Http(404))
+    }
+
+    async fn get_game_timeline(&mut self, game_id: i64) -> Result<serde_json::Value, LcuError> {
+        self.game_timelines
+            .get(&game_id)
+            .cloned()
+            .ok_or(LcuError::Http(404))
+    }
+
+    async fn get_ranked_split(&mut self) -> Result<RankedSplit, LcuError> {
+        Ok(self.ranked_split.clone())
+    }
+
+    async fn get_chest_eligible_champion_ids(&mut self) -> Result<Vec<i64>, LcuError> {
+        Ok(self.chest_eligible_ids.clone())
+    }
+
+    async fn get_eog_stats_block(&mut self) -> Result<serde_json::Value, LcuError> {
+        self.eog_stats_block.clone().ok_or(LcuError::Http(404))
+    }
+
+    async fn get_first_win_available(&mut self) -> Result<bool, LcuError> {
+        Ok(self.first_win_available)
+    }
+
+    async fn get_missions(&mut self) -> Result<Vec<Mission>, LcuError> {
+        Ok(self.missions.clone())
+    }
+
+    async fn get_current_queue_id(&mut self) -> Result<Option<i32>, LcuError> {
+        Ok(self.queue_id)
+    }
+
+    async fn get_position_preferences(&mut self) -> Result<Vec<String>, LcuError> {
+        Ok(self.position_preferences.clone())
+    }
+
+    async fn patch_draft_action(
+        &mut self,
+        action_id: i64,
+        champion_id: i64,
+        complete: bool,
+    ) -> Result<(), LcuError> {
+        self.patched_actions
+            .push((action_id, champion_id, complete));
+        Ok(())
+    }
+
+    async fn play_again(&mut self) -> Result<(), LcuError> {
+        self.lobby_calls.push("play_again");
+        Ok(())
+    }
+
+    async fn start_matchmaking(&mut self) -> Result<(), LcuError> {
+        self.lobby_calls.push("start_matchmaking");
+        Ok(())
+    }
+
+    async fn accept_ready_check(&mut self) -> Result<(), LcuError> {
+        self.lobby_calls.push("accept_ready_check");
+        Ok(())
+    }
+
+    async fn get_friends(&mut self) -> Result<Vec<Friend>, LcuError> {
+        Ok(self.friends.clone())
+    }
+
+    async fn spectate(&mut self, puuid: &str) -> Result<(), LcuError> {
+        self.spectated.push(puuid.to_string());
+        Ok(())
+    }
+}
+
+/// Builds champ-select sessions for tests, in the shape the client sends. Cells
+/// and actions carry the fields the parser reads; an action's `isAllyAction`
+/// follows whether its actor is on `myTeam`.
+#[derive(Debug, Clone)]
+pub struct SessionBuilder {
+    session: serde_json::Value,
+    last_team: Option<&'static str>, // Team of the cell added last
+}
+
+impl Default for SessionBuilder {
+    fn default() -> Self {
+        Self {
+            session: json!({
+                "gameId": 1,
+                "localPlayerCellId": 0,
+                "timer": { "phase": "BAN_PICK" },
+                "myTeam": [],
+                "theirTeam": [],
+                "actions": []
+            }),
+            last_team: None,
+        }
+    }
+}
+
+impl SessionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn game_id(mut self, game_id: i64) -> Self {
+        self.session["gameId"] = json!(game_id);
+        self
+    }
+
+    pub fn local_cell(mut self, cell_id: i64) -> Self {
+        self.session["localPlayerCellId"] = json!(cell_id);
+        self
+    }
+
+    pub fn phase(mut self, phase: &str) -> Self {
+        self.session["timer"]["phase"] = json!(phase);
+        self
+    }
+
+    pub fn timer_ms(mut self, ms: f64) -> Self {
+        self.session["timer"]["adjustedTimeLeftInPhase"] = json!(ms);
+        self
+    }
+
+    fn cell(mut self, team: &'static str, cell_id: i64, champion_id: i64, hover: i64) -> Self {
+        self.session[team].as_array_mut().unwrap().push(json!({
+            "cellId": cell_id,
+            "championId": champion_id,
+            "championPickIntent": hover
+        }));
+        self.last_team = Some(team);
+        self
+    }
+
+    /// A cell on the local player's team, with its locked and hovered champions.
+    pub fn ally(self, cell_id: i64, champion_id: i64, hover: i64) -> Self {
+        self.cell("myTeam", cell_id, champion_id, hover)
+    }
+
+    pub fn enemy(self, cell_id: i64, champion_id: i64, hover: i64) -> Self {
+        self.cell("theirTeam", cell_id, champion_id, hover)
+    }
+
+    /// Assign a position to the cell added last.
+    pub fn position(mut self, position: &str) -> Self {
+        let team = self.last_team.expect("no cell added yet");
+        if let Some(cell) = self.session[team].as_array_mut().unwrap().last_mut() {
+            cell["assignedPosition"] = json!(position);
+        }
+        self
+    }
+
+    /// Start a new group of actions, like the client does for each turn.
+    pub fn turn(mut self) -> Self {
+        self.session["actions"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!([]));
+        self
+    }
+
+    fn action(mut self, id: i64, actor_cell_id: i64, kind: &str, champion_id: i64) -> Self {
+        if self.session["actions"].as_array().unwrap().is_empty() {
+            self = self.turn();
+        }
+        let turn = self.session["actions"]
+            .as_array_mut()
+            .and_then(|groups| groups.last_mut())
+            .and_then(|group| group.as_array_mut())
+            .unwrap();
+        turn.push(json!({
+            "id": id,
+            "actorCellId": actor_cell_id,
+            "championId": champion_id,
+            "completed": true,
+            "isInProgress": false,
+            "type": kind
+        }));
+        self
+    }
+
+    /// A completed ban in the current turn.
+    pub fn ban(self, id: i64, actor_cell_id: i64, champion_id: i64) -> Self {
+        self.action(id, actor_cell_id, "ban", champion_id)
+    }
+
+    /// A completed pick in the current turn.
+    pub fn pick(self, id: i64, actor_cell_id: i64, champion_id: i64) -> Self {
+        self.action(id, actor_cell_id, "pick", champion_id)
+    }
+
+    fn last_action(&mut self) -> &mut serde_json::Value {
+        self.session["actions"]
+            .as_array_mut()
+            .and_then(|groups| groups.last_mut())
+            .and_then(|group| group.as_array_mut())
+            .and_then(|group| group.last_mut())
+            .expect("no action added yet")
+    }
+
+    /// Mark the action added last as not completed.
+    pub fn pending(mut self) -> Self {
+        self.last_action()["completed"] = json!(false);
+        self
+    }
+
+    /// Mark the action added last as the one being acted on now.
+    pub fn in_progress(mut self) -> Self {
+        let action = self.last_action();
+        action["completed"] = json!(false);
+        action["isInProgress"] = json!(true);
+        self
+    }
+
+    pub fn build(mut self) -> serde_json::Value {
+        let allies: Vec<serde_json::Value> = self.session["myTeam"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["cellId"].clone())
+            .collect();
+        for group in self.session["actions"].as_array_mut().unwrap() {
+            for action in group.as_array_mut().unwrap() {
+                action["isAllyAction"] = json!(allies.contains(&action["actorCellId"]));
+            }
+        }
+        self.session
+    }
+
+    pub fn state(self) -> DraftState {
+        parse_draft_session(&self.build()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::monitor::{poll_once, DraftDiffer, MonitorEvent};
+    use std::time::Instant;
+
+    fn faulty(faults: MockFaults) -> MockLcuClient {
+        let mut mock = MockLcuClient::new();
+        mock.faults = Some(faults);
+        mock.policies.polling.backoff = Duration::ZERO;
+        mock.gameflow_phase = Some("ChampSelect".to_string());
+        mock
+    }
+
+    #[tokio::test]
+    async fn intermittent_errors_are_retried_away() {
+        let mut mock = faulty(MockFaults {
+            fail_every: Some(2),
+            ..Default::default()
+        });
+        for _ in 0..3 {
+            assert!(mock.get_gameflow_phase().await.is_ok());
+        }
+        // Every second attempt failed and was retried; server errors keep the
+        // credentials
+        assert_eq!((mock.attempts, mock.credential_refreshes), (5, 0));
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let mut mock = faulty(MockFaults::default());
+        assert_eq!(
+            mock.get_draft_session().await.err(),
+            Some(LcuError::NotInChampSelect)
+        );
+        assert_eq!(mock.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn expired_credentials_are_refreshed() {
+        let mut mock = faulty(MockFaults {
+            expire_credentials_after: Some(2),
+            ..Default::default()
+        });
+        for _ in 0..5 {
+            assert!(mock.get_gameflow_phase().await.is_ok());
+        }
+        assert_eq!(mock.credential_refreshes, 2);
+
+        // The refresh doesn't spend a retry
+        mock.policies.polling.retries = 0;
+        mock.get_gameflow_phase().await.unwrap();
+        assert!(mock.get_gameflow_phase().await.is_ok());
+        assert_eq!(mock.credential_refreshes, 3);
+
+        // Rejected with fresh credentials too: refreshed once, then given up
+        mock.faults = Some(MockFaults {
+            expire_credentials_after: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(mock.get_gameflow_phase().await, Err(LcuError::AuthExpired));
+        assert_eq!(mock.credential_refreshes, 4);
+    }
+
+    #[tokio::test]
+    async fn monitor_recovers_once_the_client_answers_again() {
+        let mut mock = faulty(MockFaults {
+            fail_every: Some(2),
+            latency: Duration::from_millis(5),
+            ..Default::default()
+        });
+        mock.policies.polling.retries = 0;
+        mock.push_draft_session(SessionBuilder::new().ally(0, 0, 0).build());
+        mock.push_draft_session(SessionBuilder::new().ally(0, 0, 157).build());
+        let client = tokio::sync::Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+
+        let started = Instant::now();
+        assert!(matches!(
+            poll_once(&client, &mut differ).await,
+            Some(MonitorEvent::StateChanged(_))
+        ));
+        assert!(matches!(
+            poll_once(&client, &mut differ).await,
+            Some(MonitorEvent::Error(LcuError::Http(500)))
+        ));
+        assert!(matches!(
+            poll_once(&client, &mut differ).await,
+            Some(MonitorEvent::StateChanged(_))
+        ));
+        assert!(started.elapsed() >= Duration::from_millis(15));
+    }
+}
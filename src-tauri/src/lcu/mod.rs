@@ -1,4 +1,29 @@
+pub mod actions;
+pub mod afk;
+pub mod api;
+pub mod auto_ban;
+pub mod auto_draft;
+pub mod cancel;
 pub mod client;
+pub mod coach;
+pub mod composition;
 pub mod draft;
+pub mod dto;
+pub mod error;
+pub mod game_process;
+pub mod gameflow;
+pub mod honor;
+pub mod intent;
 pub mod lockfile;
+pub mod missions;
+#[cfg(test)]
+pub mod mock;
 pub mod monitor;
+pub mod pool;
+pub mod request_stats;
+pub mod requeue;
+pub mod role_prompt;
+pub mod roles;
+pub mod share;
+pub mod spectate;
+pub mod timeline;
@@ -1,17 +1,389 @@
-use crate::lcu::{client::LcuClient, draft::DraftState};
+use crate::analytics::now_millis;
+use crate::champions::assets::{fetch_draft_assets, DraftAssets};
+use crate::champions::cache::ChampionCache;
+use crate::flags::{is_enabled, Flag};
+use crate::lcu::afk::{announce_nudge, auto_hover, top_picks, AfkStep, AfkWatcher};
+use crate::lcu::auto_ban::{
+    ban_candidates, execute_auto_ban, AutoBanner, DEFAULT_AUTO_BAN_DELAY_MS,
+};
+use crate::lcu::auto_draft::{apply_step, AutoDrafter};
+use crate::lcu::coach::{announce_commentary, CoachWatcher};
+use crate::lcu::composition::{announce_comp_gaps, CompGapWatcher};
+use crate::lcu::draft::{DraftState, SwapKind};
+use crate::lcu::intent::{announce_intent_loss, IntentWatcher};
+use crate::lcu::pool::{announce_pool_losses, PoolWatcher};
+use crate::lcu::role_prompt::{announce_role_prompt, RolePromptWatcher};
+use crate::lcu::roles::{announce_role_conflicts, RoleConflictWatcher};
+use crate::lcu::share::{publish_draft, DraftPublisher};
+use crate::lcu::timeline::{store_timeline, TimelineRecorder};
+use crate::lcu::{api::LcuApi, client::LcuClient, error::LcuError};
+use crate::metrics::{record_error, record_feature, ErrorCategory, Feature};
+use crate::performance::current_profile;
+use crate::scripts::{has_hook, run_hook, to_dynamic, Hook};
+use crate::series::follow_series;
+use crate::settings::SettingsStore;
+use crate::startup::ensure_model_loaded;
+use crate::state::{self, StateKey};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
 
-pub struct DraftMonitor {
-    client: Arc<tokio::sync::Mutex<LcuClient>>,
+/// How much the monitor sends to the frontend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventVerbosity {
+    Minimal, // Granular draft events only; the UI follows the state store
+    #[default]
+    Normal, // Plus the full state whenever more than the timer changed
+    Debug,   // Plus the raw LCU session behind each full state
+}
+
+impl EventVerbosity {
+    pub fn sends_full_state(self) -> bool {
+        self != EventVerbosity::Minimal
+    }
+
+    pub fn sends_raw_session(self) -> bool {
+        self == EventVerbosity::Debug
+    }
+}
+
+/// The running draft monitor, if any. One monitor serves every window, so
+/// starting it again while it runs is a no-op.
+#[derive(Default)]
+pub struct MonitorManager {
+    running: Option<(CancellationToken, JoinHandle<()>)>,
+}
+
+impl MonitorManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+            .as_ref()
+            .is_some_and(|(_, handle)| !handle.is_finished())
+    }
+
+    /// Start a monitor through `spawn` unless one is running. Returns whether
+    /// it was started.
+    pub fn start(&mut self, spawn: impl FnOnce(CancellationToken) -> JoinHandle<()>) -> bool {
+        if self.is_running() {
+            return false;
+        }
+        let token = CancellationToken::new();
+        self.running = Some((token.clone(), spawn(token)));
+        true
+    }
+
+    /// Stop the running monitor after its current poll. Returns whether one was
+    /// running.
+    pub fn stop(&mut self) -> bool {
+        let was_running = self.is_running();
+        if let Some((token, _)) = self.running.take() {
+            token.cancel();
+        }
+        was_running
+    }
+}
+
+/// What the monitor should tell the frontend after a poll.
+#[derive(Debug, Clone)]
+pub enum MonitorEvent {
+    StateChanged(Box<DraftState>),
+    Error(LcuError),
+}
+
+/// One specific change between polls, emitted under its own event name with
+/// just the fields it concerns.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum DraftEvent {
+    PickLocked {
+        action_id: i64,
+        cell_id: Option<i64>,
+        champion_id: i64,
+        is_ally: bool,
+    },
+    BanLocked {
+        action_id: i64,
+        cell_id: Option<i64>,
+        champion_id: i64,
+        is_ally: bool,
+    },
+    HoverChanged {
+        cell_id: i64,
+        champion_id: Option<i64>, // None when the hover was cleared
+    },
+    TurnStarted {
+        action_id: i64,
+        cell_id: Option<i64>,
+        action_type: String,
+        is_local_player: bool,
+    },
+    TimerTick {
+        seconds_left: Option<f64>,
+    },
+    PhaseChanged {
+        phase: String,
+        previous: Option<String>,
+    },
+    SwapOccurred {
+        kind: SwapKind,
+        cell_id: i64, // The local player
+        other_cell_id: i64,
+    },
+}
+
+impl DraftEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DraftEvent::PickLocked { .. } => "pick-locked",
+            DraftEvent::BanLocked { .. } => "ban-locked",
+            DraftEvent::HoverChanged { .. } => "hover-changed",
+            DraftEvent::TurnStarted { .. } => "turn-started",
+            DraftEvent::TimerTick { .. } => "timer-tick",
+            DraftEvent::PhaseChanged { .. } => "phase-changed",
+            DraftEvent::SwapOccurred { .. } => "swap-occurred",
+        }
+    }
+}
+
+/// The changes from `previous` to `next`. For the first state of a draft only
+/// the phase, timer and running turns are reported; locks and hovers so far
+/// arrive with the full state.
+pub fn draft_events(previous: Option<&DraftState>, next: &DraftState) -> Vec<DraftEvent> {
+    let mut events = Vec::new();
+    if previous.map(|p| &p.phase) != Some(&next.phase) {
+        events.push(DraftEvent::PhaseChanged {
+            phase: next.phase.clone(),
+            previous: previous.map(|p| p.phase.clone()),
+        });
+    }
+
+    let ally_cells: Vec<i64> = next
+        .local_team()
+        .map(|t| t.cells.iter().map(|c| c.cell_id).collect())
+        .unwrap_or_default();
+    for action in &next.actions {
+        let before = previous.and_then(|p| p.actions.iter().find(|a| a.id == action.id));
+        let is_ally = action
+            .actor_cell_id
+            .map(|cell| ally_cells.contains(&cell))
+            .or(action.is_ally_action)
+            .unwrap_or(false);
+        if previous.is_some() && action.completed && !before.is_some_and(|b| b.completed) {
+            if let Some(champion_id) = action.champion_id {
+                let (action_id, cell_id) = (action.id, action.actor_cell_id);
+                events.push(match action.action_type.as_str() {
+                    "ban" => DraftEvent::BanLocked {
+                        action_id,
+                        cell_id,
+                        champion_id,
+                        is_ally,
+                    },
+                    _ => DraftEvent::PickLocked {
+                        action_id,
+                        cell_id,
+                        champion_id,
+                        is_ally,
+                    },
+                });
+            }
+        }
+        if action.is_in_progress && !action.completed && !before.is_some_and(|b| b.is_in_progress) {
+            events.push(DraftEvent::TurnStarted {
+                action_id: action.id,
+                cell_id: action.actor_cell_id,
+                action_type: action.action_type.clone(),
+                is_local_player: action.actor_cell_id.is_some()
+                    && action.actor_cell_id == next.local_player_cell_id,
+            });
+        }
+    }
+
+    if let Some(previous) = previous {
+        let hovers = |state: &DraftState| -> Vec<(i64, Option<i64>)> {
+            state
+                .teams
+                .iter()
+                .flat_map(|t| t.cells.iter().map(|c| (c.cell_id, c.selected_champion_id)))
+                .collect()
+        };
+        let before = hovers(previous);
+        for (cell_id, champion_id) in hovers(next) {
+            let was = before
+                .iter()
+                .find(|(id, _)| *id == cell_id)
+                .and_then(|(_, h)| *h);
+            if was != champion_id {
+                events.push(DraftEvent::HoverChanged {
+                    cell_id,
+                    champion_id,
+                });
+            }
+        }
+        events.extend(completed_swaps(previous, next));
+    }
+
+    let timer_changed = match (next.timer, previous.and_then(|p| p.timer)) {
+        (Some(t), Some(lt)) => (t - lt).abs() > 0.01,
+        (Some(_), None) => true,
+        (None, Some(_)) => true,
+        (None, None) => false,
+    };
+    if timer_changed {
+        events.push(DraftEvent::TimerTick {
+            seconds_left: next.timer,
+        });
+    }
+    events
+}
+
+/// Whether two set values traded places, as a completed swap leaves them.
+fn exchanged<T: PartialEq>(
+    before: (&Option<T>, &Option<T>),
+    after: (&Option<T>, &Option<T>),
+) -> bool {
+    before.0.is_some()
+        && before.1.is_some()
+        && before.0 != before.1
+        && after == (before.1, before.0)
+}
+
+/// Trades and swaps between the local player and an ally since `previous`.
+/// The offer is gone by the time the cells change, so the cells tell.
+fn completed_swaps(previous: &DraftState, next: &DraftState) -> Vec<DraftEvent> {
+    let (Some(local_before), Some(local), Some(team)) =
+        (previous.local_cell(), next.local_cell(), next.local_team())
+    else {
+        return Vec::new();
+    };
+    let mut events = Vec::new();
+    for cell in team.cells.iter().filter(|c| c.cell_id != local.cell_id) {
+        let Some(before) = previous
+            .teams
+            .iter()
+            .flat_map(|t| t.cells.iter())
+            .find(|c| c.cell_id == cell.cell_id)
+        else {
+            continue;
+        };
+        let swaps = [
+            (
+                SwapKind::Champion,
+                exchanged(
+                    (&local_before.champion_id, &before.champion_id),
+                    (&local.champion_id, &cell.champion_id),
+                ),
+            ),
+            (
+                SwapKind::PickOrder,
+                exchanged(
+                    (&local_before.pick_turn, &before.pick_turn),
+                    (&local.pick_turn, &cell.pick_turn),
+                ),
+            ),
+            (
+                SwapKind::Position,
+                exchanged(
+                    (&local_before.assigned_position, &before.assigned_position),
+                    (&local.assigned_position, &cell.assigned_position),
+                ),
+            ),
+        ];
+        for (kind, _) in swaps.into_iter().filter(|(_, swapped)| *swapped) {
+            events.push(DraftEvent::SwapOccurred {
+                kind,
+                cell_id: local.cell_id,
+                other_cell_id: cell.cell_id,
+            });
+        }
+    }
+    events
+}
+
+/// Everything but the timer, which changes on nearly every poll.
+fn same_content(a: &DraftState, b: &DraftState) -> bool {
+    a.game_id == b.game_id
+        && a.phase == b.phase
+        && a.teams == b.teams
+        && a.actions == b.actions
+        && a.local_player_cell_id == b.local_player_cell_id
+        && a.warnings == b.warnings
+        && a.swap_offers == b.swap_offers
+}
+
+/// Change detection between consecutive polls, independent of how the state is
+/// fetched or where events go.
+#[derive(Default)]
+pub struct DraftDiffer {
+    last_state: Option<DraftState>,
+    events: Vec<DraftEvent>, // From the last update
+    content_changed: bool,   // Whether the last update changed more than the timer
+}
+
+impl DraftDiffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, result: Result<DraftState, LcuError>) -> Option<MonitorEvent> {
+        self.events.clear();
+        self.content_changed = false;
+        match result {
+            Ok(state) => {
+                let events = draft_events(self.last_state.as_ref(), &state);
+                let content_changed = self
+                    .last_state
+                    .as_ref()
+                    .is_none_or(|last| !same_content(last, &state));
+                let timer_changed = events
+                    .iter()
+                    .any(|e| matches!(e, DraftEvent::TimerTick { .. }));
+
+                // Timer changes alone still go to the watchers, for smooth countdowns
+                if content_changed || timer_changed {
+                    self.last_state = Some(state.clone());
+                    self.events = events;
+                    self.content_changed = content_changed;
+                    return Some(MonitorEvent::StateChanged(Box::new(state)));
+                }
+                None
+            }
+            Err(e) => {
+                // Only emit error if we had a previous state (to avoid spam when not in draft)
+                let had_state = self.last_state.take().is_some();
+                had_state.then_some(MonitorEvent::Error(e))
+            }
+        }
+    }
+
+    /// The granular events of the last update.
+    pub fn take_events(&mut self) -> Vec<DraftEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Whether the last update changed anything besides the timer, and so
+    /// needs the full state sent.
+    pub fn content_changed(&self) -> bool {
+        self.content_changed
+    }
+}
+
+pub struct DraftMonitor<C: LcuApi = LcuClient> {
+    client: Arc<tokio::sync::Mutex<C>>,
     app_handle: AppHandle,
     polling_interval_ms: u64,
 }
 
-impl DraftMonitor {
+impl<C: LcuApi + 'static> DraftMonitor<C> {
     pub fn new(
-        client: Arc<tokio::sync::Mutex<LcuClient>>,
+        client: Arc<tokio::sync::Mutex<C>>,
         app_handle: AppHandle,
         polling_interval_ms: u64,
     ) -> Self {
@@ -22,81 +394,793 @@ impl DraftMonitor {
         }
     }
 
-    pub async fn start_monitoring(&self) {
+    /// Poll until `cancel` fires.
+    pub async fn start_monitoring(&self, cancel: CancellationToken) {
         let mut interval_timer = interval(Duration::from_millis(self.polling_interval_ms));
-        let mut last_state: Option<String> = None;
-        let mut last_timer: Option<f64> = None;
-        let mut last_phase: Option<String> = None;
+        let mut differ = DraftDiffer::new();
+        let mut recorder = TimelineRecorder::new();
+        let mut auto_banner = AutoBanner::new();
+        let mut auto_drafter = AutoDrafter::new();
+        let mut afk_watcher = AfkWatcher::new();
+        let mut draft_assets = DraftAssets::new();
+        let mut pool_watcher = PoolWatcher::new();
+        let mut intent_watcher = IntentWatcher::new();
+        let mut comp_watcher = CompGapWatcher::new();
+        let mut role_watcher = RoleConflictWatcher::new();
+        let mut role_prompt_watcher = RolePromptWatcher::new();
+        let mut coach = CoachWatcher::new();
+        let mut publisher = DraftPublisher::new();
         let mut is_first_poll = true;
 
         loop {
             // On first iteration, check immediately; subsequent iterations wait for the interval
             if !is_first_poll {
-                interval_timer.tick().await;
+                tokio::select! {
+                    _ = cancel.cancelled() => return self.finish(&mut recorder),
+                    _ = interval_timer.tick() => {}
+                }
             } else {
                 is_first_poll = false;
             }
 
-            match self.get_current_state().await {
-                Ok(state) => {
-                    // Check if timer changed (even slightly)
-                    let timer_changed = match (state.timer, last_timer) {
-                        (Some(t), Some(lt)) => (t - lt).abs() > 0.01,
-                        (Some(_), None) | (None, Some(_)) => true,
-                        (None, None) => false,
-                    };
-                    
-                    // Check if phase changed
-                    let phase_changed = last_phase.as_ref() != Some(&state.phase);
-                    
-                    // Serialize state to compare
-                    if let Ok(state_json) = serde_json::to_string(&state) {
-                        let state_changed = last_state.as_ref() != Some(&state_json);
-                        
-                        // Emit if state changed OR timer changed OR phase changed (for smooth updates)
-                        if state_changed || timer_changed || phase_changed {
-                            if let Some(window) = self.app_handle.get_webview_window("main") {
-                                let _ = window.emit("draft-state-changed", &state);
-                            }
-                            last_state = Some(state_json);
-                            last_timer = state.timer;
-                            last_phase = Some(state.phase.clone());
-                        }
-                    }
+            let event = poll_once(&self.client, &mut differ).await;
+            // Stopped during the poll; its result would land after the stop
+            if cancel.is_cancelled() {
+                return self.finish(&mut recorder);
+            }
+            if let Some(mut event) = event {
+                if let MonitorEvent::StateChanged(state) = &mut event {
+                    ensure_model_loaded(&self.app_handle);
+                    // Before anything reads the state, so fearless exclusions apply everywhere
+                    follow_series(&self.app_handle, state);
                 }
+                self.record(&mut recorder, &mut event);
+                self.auto_ban(&mut auto_banner, &event);
+                self.auto_draft(&mut auto_drafter, &event);
+                self.watch_afk(&mut afk_watcher, &event);
+                self.fetch_assets(&mut draft_assets, &event);
+                self.watch_pool(&mut pool_watcher, &event);
+                self.watch_intent(&mut intent_watcher, &event).await;
+                self.watch_role_prompt(&mut role_prompt_watcher, &event)
+                    .await;
+                self.watch_composition(&mut comp_watcher, &event);
+                self.watch_roles(&mut role_watcher, &event);
+                self.coach(&mut coach, &event);
+                self.share(&mut publisher, &event);
+                self.run_scripts(&event);
+                for draft_event in differ.take_events() {
+                    self.emit_to_targets(draft_event.name(), &draft_event);
+                }
+                let verbosity = self.verbosity();
+                let content_changed = differ.content_changed();
+                if content_changed {
+                    self.raw_session(&mut event, verbosity).await;
+                }
+                self.emit(event, content_changed && verbosity.sends_full_state());
+            }
+        }
+    }
+
+    /// Feed the timeline recorder and attach enemy hover history to the state.
+    /// A timeline is stored once its draft is over.
+    fn record(&self, recorder: &mut TimelineRecorder, event: &mut MonitorEvent) {
+        let finished = match event {
+            MonitorEvent::StateChanged(state) => {
+                let finished = recorder.observe(state, now_millis());
+                recorder.annotate_hover_history(state);
+                finished
+            }
+            MonitorEvent::Error(_) => recorder.finish(),
+        };
+        if let Some((game_id, events)) = finished {
+            if let Err(e) = store_timeline(&self.app_handle, game_id, events) {
+                eprintln!("Failed to store draft timeline: {}", e);
+            }
+        }
+    }
+
+    /// Store the timeline of a draft cut short by a stop, since the error that
+    /// normally ends it is never polled.
+    fn finish(&self, recorder: &mut TimelineRecorder) {
+        self.record(
+            recorder,
+            &mut MonitorEvent::Error(LcuError::NotInChampSelect),
+        );
+    }
+
+    /// Schedule an auto-ban when the local player's ban turn starts, from the
+    /// auto-ban list and the auto-draft ban rules. The ban is locked after the
+    /// configured delay, re-checking protections first.
+    fn auto_ban(&self, auto_banner: &mut AutoBanner, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return auto_banner.reset(),
+        };
+        let Some(settings) = self
+            .app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+        else {
+            return;
+        };
+        let Some(action_id) = auto_banner.should_schedule(state, &settings) else {
+            return;
+        };
+        let candidates = ban_candidates(state, &settings);
+        let lock_confirmation = settings.lock_confirmation;
+
+        let client = self.client.clone();
+        let app = self.app_handle.clone();
+        let delay = settings
+            .auto_ban_delay_ms
+            .unwrap_or(DEFAULT_AUTO_BAN_DELAY_MS);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            match execute_auto_ban(&client, action_id, &candidates, lock_confirmation).await {
+                Ok(Some(_)) => record_feature(&app, Feature::AutoBan),
+                Ok(None) => {}
                 Err(e) => {
-                    // Only emit error if we had a previous state (to avoid spam when not in draft)
-                    if last_state.is_some() {
-                        if let Some(window) = self.app_handle.get_webview_window("main") {
-                            let _ = window.emit("draft-error", &e);
+                    record_error(&app, ErrorCategory::AutoBan);
+                    eprintln!("Auto-ban failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Apply the pick rules on the local player's turn: hover the first
+    /// available champion, and lock it once the timer reaches the threshold.
+    fn auto_draft(&self, auto_drafter: &mut AutoDrafter, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return auto_drafter.reset(),
+        };
+        let Some(settings) = self
+            .app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+        else {
+            return;
+        };
+        let Some(step) = auto_drafter.update(state, &settings) else {
+            return;
+        };
+
+        let client = self.client.clone();
+        let app = self.app_handle.clone();
+        tokio::spawn(async move {
+            match apply_step(&client, &step).await {
+                Ok(()) => {
+                    if step.locked {
+                        record_feature(&app, Feature::AutoDraft);
+                    }
+                    let _ = app.emit("auto-draft-applied", &step);
+                }
+                Err(e) => eprintln!("Auto-draft failed: {}", e),
+            }
+        });
+    }
+
+    /// Nudge, louder each time, when the player's turn is running out with
+    /// nothing hovered, and hover the top recommendation as a last resort.
+    fn watch_afk(&self, afk_watcher: &mut AfkWatcher, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return afk_watcher.reset(),
+        };
+        let Some(settings) = self
+            .app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+        else {
+            return;
+        };
+
+        for step in afk_watcher.update(state, &settings) {
+            match step {
+                AfkStep::Nudge(nudge) => announce_nudge(&self.app_handle, &nudge),
+                // Still experimental, so it stays off until the flag is on
+                AfkStep::AutoHover(_) if !is_enabled(&self.app_handle, Flag::AutoHover) => {}
+                AfkStep::AutoHover(action_id) => {
+                    let candidates = top_picks(&self.app_handle, state);
+                    let client = self.client.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = auto_hover(&client, action_id, &candidates).await {
+                            eprintln!("Auto-hover failed: {}", e);
                         }
+                    });
+                }
+            }
+        }
+    }
+
+    /// Fetch loading screen art for champions as they appear in the draft.
+    fn fetch_assets(&self, draft_assets: &mut DraftAssets, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return draft_assets.reset(),
+        };
+        if !current_profile(&self.app_handle).prefetch_assets {
+            return;
+        }
+        let champion_ids = draft_assets.new_champions(state);
+        if champion_ids.is_empty() {
+            return;
+        }
+
+        let app = self.app_handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = fetch_draft_assets(app, champion_ids).await {
+                eprintln!("Fetching draft art failed: {}", e);
+            }
+        });
+    }
+
+    /// Suggest alternatives as soon as a pool champion is banned or taken.
+    fn watch_pool(&self, pool_watcher: &mut PoolWatcher, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::StateChanged(state) => {
+                announce_pool_losses(&self.app_handle, pool_watcher, state)
+            }
+            MonitorEvent::Error(_) => pool_watcher.reset(),
+        }
+    }
+
+    /// Alert once the player's planned pick for this queue and position is gone.
+    async fn watch_intent(&self, intent_watcher: &mut IntentWatcher, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return intent_watcher.reset(),
+        };
+        if intent_watcher.needs_queue() {
+            let mut client_guard = self.client.lock().await;
+            let queue_id = client_guard.get_current_queue_id().await.ok().flatten();
+            intent_watcher.set_queue(queue_id);
+        }
+        announce_intent_loss(&self.app_handle, intent_watcher, state);
+    }
+
+    /// Show how the player does in their secondary or autofilled role as soon
+    /// as positions are assigned.
+    async fn watch_role_prompt(&self, watcher: &mut RolePromptWatcher, event: &MonitorEvent) {
+        let state = match event {
+            MonitorEvent::StateChanged(state) => state,
+            MonitorEvent::Error(_) => return watcher.reset(),
+        };
+        if watcher.needs_preferences() {
+            let mut client_guard = self.client.lock().await;
+            let preferences = client_guard
+                .get_position_preferences()
+                .await
+                .unwrap_or_default();
+            watcher.set_preferences(preferences);
+        }
+        announce_role_prompt(&self.app_handle, watcher, state);
+    }
+
+    /// Warn about allied composition gaps as locks come in.
+    fn watch_composition(&self, comp_watcher: &mut CompGapWatcher, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::StateChanged(state) => {
+                announce_comp_gaps(&self.app_handle, comp_watcher, state)
+            }
+            MonitorEvent::Error(_) => comp_watcher.reset(),
+        }
+    }
+
+    /// Point out allies heading for the same role when positions aren't assigned.
+    fn watch_roles(&self, role_watcher: &mut RoleConflictWatcher, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::StateChanged(state) => {
+                announce_role_conflicts(&self.app_handle, role_watcher, state)
+            }
+            MonitorEvent::Error(_) => role_watcher.reset(),
+        }
+    }
+
+    /// Comment on each draft step in coach mode.
+    fn coach(&self, coach: &mut CoachWatcher, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::StateChanged(state) => {
+                announce_commentary(&self.app_handle, coach, state)
+            }
+            MonitorEvent::Error(_) => coach.reset(),
+        }
+    }
+
+    /// Publish the draft for viewers when sharing is on.
+    fn share(&self, publisher: &mut DraftPublisher, event: &MonitorEvent) {
+        match event {
+            MonitorEvent::StateChanged(state) => publish_draft(&self.app_handle, publisher, state),
+            MonitorEvent::Error(_) => publisher.reset(&self.app_handle),
+        }
+    }
+
+    /// Hand each draft update to user scripts that watch the draft.
+    fn run_scripts(&self, event: &MonitorEvent) {
+        if let MonitorEvent::StateChanged(state) = event {
+            if has_hook(&self.app_handle, Hook::DraftChanged) {
+                run_hook(&self.app_handle, Hook::DraftChanged, &[to_dynamic(state)]);
+            }
+        }
+    }
+
+    /// Publish the state and tell the frontend. The full state is only sent
+    /// when more than the timer changed; `timer-tick` covers the rest.
+    fn emit(&self, event: MonitorEvent, send_state: bool) {
+        match event {
+            MonitorEvent::StateChanged(mut state) => {
+                if let Some(cache) = self
+                    .app_handle
+                    .try_state::<std::sync::Mutex<ChampionCache>>()
+                {
+                    if let Ok(cache_guard) = cache.lock() {
+                        state.resolve_spell_names(&cache_guard);
+                        state.resolve_hover_names(&cache_guard);
                     }
-                    last_state = None;
-                    last_timer = None;
-                    last_phase = None;
                 }
+                state::publish(&self.app_handle, StateKey::DraftState, &state);
+                if send_state {
+                    self.emit_to_targets("draft-state-changed", &state);
+                }
+            }
+            MonitorEvent::Error(e) => {
+                state::invalidate(&self.app_handle, StateKey::DraftState);
+                self.emit_to_targets("draft-error", &e)
             }
         }
     }
 
-    async fn get_current_state(&self) -> Result<DraftState, String> {
-        let mut client_guard = self.client.lock().await;
-        client_guard.get_draft_state().await
+    fn verbosity(&self) -> EventVerbosity {
+        self.app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| s.lock().ok().map(|g| g.get().event_verbosity))
+            .unwrap_or_default()
+    }
+
+    /// Pass on the session the state was built from, as the client sent it:
+    /// as its own event in debug verbosity, and in the state itself with
+    /// `draft_include_raw`. Only read when one of them wants it.
+    async fn raw_session(&self, event: &mut MonitorEvent, verbosity: EventVerbosity) {
+        let include_raw = self
+            .app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| s.lock().ok().map(|g| g.get().draft_include_raw))
+            .unwrap_or(false);
+        if !include_raw && !verbosity.sends_raw_session() {
+            return;
+        }
+        let session = {
+            let mut client_guard = self.client.lock().await;
+            client_guard.get_draft_session_json().await
+        };
+        let session = match session {
+            Ok(session) => session,
+            Err(e) => return eprintln!("Failed to read the raw draft session: {}", e),
+        };
+        if verbosity.sends_raw_session() {
+            self.emit_to_targets("draft-session-raw", &session);
+        }
+        if let (true, MonitorEvent::StateChanged(state)) = (include_raw, event) {
+            state.raw_session = Some(session);
+        }
+    }
+
+    /// Send to the windows configured in settings, or to everyone listening.
+    /// Read on every emit so a settings change applies without restarting the monitor.
+    fn emit_to_targets<S: Serialize + Clone>(&self, event: &str, payload: S) {
+        let windows = self
+            .app_handle
+            .try_state::<std::sync::Mutex<SettingsStore>>()
+            .and_then(|s| {
+                s.lock()
+                    .ok()
+                    .and_then(|g| g.get().draft_event_windows.clone())
+            });
+
+        match windows {
+            Some(labels) => {
+                for label in labels {
+                    let _ = self
+                        .app_handle
+                        .emit_to(label.as_str(), event, payload.clone());
+                }
+            }
+            None => {
+                let _ = self.app_handle.emit(event, payload);
+            }
+        }
     }
 }
 
-#[tauri::command]
-pub async fn start_draft_monitoring(
-    app: tauri::AppHandle,
-    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
-) -> Result<(), String> {
-    let polling_interval = 250; // Poll every 250ms for smoother timer updates
-    let monitor = DraftMonitor::new(client.inner().clone(), app, polling_interval);
-
-    // Spawn the monitoring task
-    tokio::spawn(async move {
-        monitor.start_monitoring().await;
+/// Fetch the current draft state once and run it through the differ.
+pub async fn poll_once<C: LcuApi>(
+    client: &tokio::sync::Mutex<C>,
+    differ: &mut DraftDiffer,
+) -> Option<MonitorEvent> {
+    let result = {
+        let mut client_guard = client.lock().await;
+        client_guard.get_draft_state().await
+    };
+    differ.update(result)
+}
+
+/// Start the draft monitor unless it's already running. Returns whether it
+/// was started.
+pub fn start_monitor(app: &AppHandle) -> Result<bool, String> {
+    let manager = app.state::<std::sync::Mutex<MonitorManager>>();
+    let mut manager_guard = manager.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let client = app
+        .state::<Arc<tokio::sync::Mutex<LcuClient>>>()
+        .inner()
+        .clone();
+    let polling_interval = current_profile(app).draft_polling_ms;
+    let monitor = DraftMonitor::new(client, app.clone(), polling_interval);
+
+    let started = manager_guard.start(|cancel| {
+        tokio::spawn(async move {
+            monitor.start_monitoring(cancel).await;
+        })
     });
+    if started {
+        record_feature(app, Feature::DraftMonitor);
+    }
+    Ok(started)
+}
 
+/// Stop the draft monitor. The last published draft state is dropped so
+/// nothing reads it as current. Returns whether it was running.
+pub fn stop_monitor(app: &AppHandle) -> Result<bool, String> {
+    let stopped = app
+        .state::<std::sync::Mutex<MonitorManager>>()
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .stop();
+    if stopped {
+        state::invalidate(app, StateKey::DraftState);
+    }
+    Ok(stopped)
+}
+
+/// Remembers whether the current ready check was accepted, so it's accepted
+/// once even though the phase is seen on every poll.
+#[derive(Default)]
+pub struct ReadyCheckWatcher {
+    accepted: bool,
+}
+
+impl ReadyCheckWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True once per ready check, the first time its phase is seen.
+    pub fn should_accept(&mut self, phase: Option<&str>) -> bool {
+        if phase != Some("ReadyCheck") {
+            self.accepted = false;
+            return false;
+        }
+        !std::mem::replace(&mut self.accepted, true)
+    }
+
+    pub fn reset(&mut self) {
+        self.accepted = false;
+    }
+
+    /// Accept the ready check once it pops, when `enabled`. True when it was
+    /// accepted just now.
+    pub async fn accept<C: LcuApi>(
+        &mut self,
+        client: &tokio::sync::Mutex<C>,
+        enabled: bool,
+        phase: Option<&str>,
+    ) -> Result<bool, LcuError> {
+        if !enabled {
+            self.reset();
+            return Ok(false);
+        }
+        if !self.should_accept(phase) {
+            return Ok(false);
+        }
+        client.lock().await.accept_ready_check().await?;
+        Ok(true)
+    }
+}
+
+/// Auto-accept, run by the gameflow watcher on every poll since a ready check
+/// pops before champ select, while the monitor isn't running yet. Follows the
+/// setting `set_auto_accept` controls.
+pub async fn auto_accept<C: LcuApi>(
+    app: &AppHandle,
+    client: &tokio::sync::Mutex<C>,
+    ready_check: &mut ReadyCheckWatcher,
+    phase: Option<&str>,
+) {
+    let enabled = app
+        .try_state::<std::sync::Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().auto_accept_enabled))
+        .unwrap_or(false);
+    match ready_check.accept(client, enabled, phase).await {
+        Ok(true) => record_feature(app, Feature::AutoAccept),
+        Ok(false) => {}
+        Err(e) => eprintln!("Auto-accept failed: {}", e),
+    }
+}
+
+/// Monitoring normally follows the gameflow phase; this starts it early.
+#[tauri::command]
+pub async fn start_draft_monitoring(app: tauri::AppHandle) -> Result<(), LcuError> {
+    start_monitor(&app).map_err(LcuError::Other)?;
     Ok(())
 }
+
+#[tauri::command]
+pub async fn stop_draft_monitoring(app: tauri::AppHandle) -> Result<bool, LcuError> {
+    stop_monitor(&app).map_err(LcuError::Other)
+}
+
+#[tauri::command]
+pub async fn is_monitoring(
+    manager: tauri::State<'_, std::sync::Mutex<MonitorManager>>,
+) -> Result<bool, LcuError> {
+    let manager_guard = manager
+        .lock()
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+    Ok(manager_guard.is_running())
+}
+
+#[tauri::command]
+pub async fn set_auto_accept(
+    enabled: bool,
+    settings: tauri::State<'_, std::sync::Mutex<SettingsStore>>,
+) -> Result<(), LcuError> {
+    let mut settings_guard = settings
+        .lock()
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+    let mut updated = settings_guard.get().clone();
+    updated.auto_accept_enabled = enabled;
+    settings_guard.set(updated).map_err(LcuError::Other)
+}
+
+#[tauri::command]
+pub async fn get_auto_accept(
+    settings: tauri::State<'_, std::sync::Mutex<SettingsStore>>,
+) -> Result<bool, LcuError> {
+    let settings_guard = settings
+        .lock()
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+    Ok(settings_guard.get().auto_accept_enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::draft::parse_draft_session;
+    use crate::lcu::mock::{MockLcuClient, SessionBuilder};
+    use serde_json::json;
+    use tokio::sync::Mutex;
+
+    fn session(timer_ms: f64, phase: &str, hovered: i64) -> serde_json::Value {
+        SessionBuilder::new()
+            .timer_ms(timer_ms)
+            .phase(phase)
+            .ally(0, 0, hovered)
+            .enemy(5, 0, 0)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn emits_only_when_something_changes() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(30000.0, "BAN_PICK", 0));
+        mock.push_draft_session(session(30000.0, "BAN_PICK", 0));
+        mock.push_draft_session(session(30000.0, "BAN_PICK", 157));
+        let client = Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+
+        assert!(matches!(
+            poll_once(&client, &mut differ).await,
+            Some(MonitorEvent::StateChanged(_))
+        ));
+        assert!(poll_once(&client, &mut differ).await.is_none());
+        match poll_once(&client, &mut differ).await {
+            Some(MonitorEvent::StateChanged(state)) => {
+                assert_eq!(state.teams[0].cells[0].selected_champion_id, Some(157));
+            }
+            other => panic!("expected state change, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn timer_and_phase_changes_are_emitted() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(30000.0, "PLANNING", 0));
+        mock.push_draft_session(session(29000.0, "PLANNING", 0));
+        mock.push_draft_session(session(29000.0, "BAN_PICK", 0));
+        let client = Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+
+        for _ in 0..3 {
+            assert!(poll_once(&client, &mut differ).await.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn granular_events_describe_each_change() {
+        let turn = |champion_id: i64| {
+            SessionBuilder::new()
+                .timer_ms(20000.0)
+                .ally(0, 0, 0)
+                .enemy(5, 0, 0)
+                .pick(7, 0, champion_id)
+        };
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(turn(0).pending().build());
+        mock.push_draft_session(session(19000.0, "BAN_PICK", 157));
+        mock.push_draft_session(turn(0).in_progress().build());
+        mock.push_draft_session(turn(157).build());
+        let client = Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+        let mut names = Vec::new();
+        for _ in 0..4 {
+            poll_once(&client, &mut differ).await;
+            names.push(
+                differ
+                    .take_events()
+                    .iter()
+                    .map(|e| e.name())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        assert_eq!(names[0], vec!["phase-changed", "timer-tick"]);
+        // A timer change and a hover; the action list emptied in between
+        assert_eq!(names[1], vec!["hover-changed", "timer-tick"]);
+        assert_eq!(
+            names[2],
+            vec!["turn-started", "hover-changed", "timer-tick"]
+        );
+        assert_eq!(names[3], vec!["pick-locked"]);
+        assert!(differ.content_changed());
+
+        // Only the timer moved: no full state for the frontend
+        let mut differ = DraftDiffer::new();
+        for timer_ms in [20000.0, 19000.0] {
+            let state = parse_draft_session(&session(timer_ms, "BAN_PICK", 0)).unwrap();
+            differ.update(Ok(state));
+        }
+        assert!(!differ.content_changed());
+        assert_eq!(
+            differ.take_events(),
+            vec![DraftEvent::TimerTick {
+                seconds_left: Some(19.0)
+            }]
+        );
+    }
+
+    #[test]
+    fn completed_swaps_are_reported() {
+        // Cells 0 and 1 with their champion and position, picking in `order`
+        let state = |first: (i64, &str), second: (i64, &str), order: [i64; 2]| {
+            let champion = |cell_id: i64| if cell_id == 0 { first.0 } else { second.0 };
+            SessionBuilder::new()
+                .phase("FINALIZATION")
+                .ally(0, first.0, 0)
+                .position(first.1)
+                .ally(1, second.0, 0)
+                .position(second.1)
+                .pick(1, order[0], champion(order[0]))
+                .pick(2, order[1], champion(order[1]))
+                .state()
+        };
+        let before = state((64, "jungle"), (157, "middle"), [0, 1]);
+        let kinds = |next: &DraftState| -> Vec<SwapKind> {
+            draft_events(Some(&before), next)
+                .into_iter()
+                .filter_map(|e| match e {
+                    DraftEvent::SwapOccurred {
+                        kind,
+                        cell_id: 0,
+                        other_cell_id: 1,
+                    } => Some(kind),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        assert!(kinds(&before).is_empty());
+        assert_eq!(
+            kinds(&state((157, "jungle"), (64, "middle"), [0, 1])),
+            vec![SwapKind::Champion]
+        );
+        assert_eq!(
+            kinds(&state((64, "middle"), (157, "jungle"), [0, 1])),
+            vec![SwapKind::Position]
+        );
+        // The client moved the pick actions between the two cells
+        let reordered = state((64, "jungle"), (157, "middle"), [1, 0]);
+        assert_eq!(reordered.teams[0].cells[0].pick_turn, Some(2));
+        assert_eq!(kinds(&reordered), vec![SwapKind::PickOrder]);
+    }
+
+    #[tokio::test]
+    async fn errors_are_reported_once_after_leaving_draft() {
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(session(30000.0, "BAN_PICK", 0));
+        let client = Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+
+        // Not in draft yet: errors are silent
+        let mut idle = DraftDiffer::new();
+        assert!(poll_once(&Mutex::new(MockLcuClient::new()), &mut idle)
+            .await
+            .is_none());
+
+        assert!(poll_once(&client, &mut differ).await.is_some());
+        assert!(matches!(
+            poll_once(&client, &mut differ).await,
+            Some(MonitorEvent::Error(_))
+        ));
+        assert!(poll_once(&client, &mut differ).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn only_one_monitor_runs_and_it_can_be_stopped() {
+        let mut manager = MonitorManager::new();
+        let spawn =
+            |cancel: CancellationToken| tokio::spawn(async move { cancel.cancelled().await });
+
+        assert!(manager.start(spawn));
+        assert!(!manager.start(spawn), "already running");
+        assert!(manager.is_running());
+
+        assert!(manager.stop());
+        assert!(!manager.is_running());
+        assert!(!manager.stop());
+        assert!(manager.start(spawn));
+    }
+
+    #[test]
+    fn ready_checks_are_accepted_once_each() {
+        let mut watcher = ReadyCheckWatcher::new();
+        assert!(!watcher.should_accept(Some("Matchmaking")));
+        assert!(watcher.should_accept(Some("ReadyCheck")));
+        assert!(!watcher.should_accept(Some("ReadyCheck")));
+
+        // Someone declined: back to matchmaking, then a new ready check
+        assert!(!watcher.should_accept(Some("Matchmaking")));
+        assert!(watcher.should_accept(Some("ReadyCheck")));
+        assert!(!watcher.should_accept(None));
+    }
+
+    #[tokio::test]
+    async fn auto_accept_does_nothing_while_off() {
+        let client = Mutex::new(MockLcuClient::new());
+        let mut ready_check = ReadyCheckWatcher::new();
+        let ready = Some("ReadyCheck");
+
+        assert_eq!(ready_check.accept(&client, false, ready).await, Ok(false));
+        assert!(client.lock().await.lobby_calls.is_empty());
+
+        // Turned on mid ready check: still accepted
+        assert_eq!(ready_check.accept(&client, true, ready).await, Ok(true));
+        assert_eq!(ready_check.accept(&client, true, ready).await, Ok(false));
+        assert_eq!(client.lock().await.lobby_calls, vec!["accept_ready_check"]);
+    }
+
+    #[tokio::test]
+    async fn debug_verbosity_has_the_raw_session() {
+        let settings: crate::settings::AppSettings =
+            serde_json::from_value(json!({ "event_verbosity": "debug" })).unwrap();
+        assert!(settings.event_verbosity.sends_raw_session());
+        assert!(EventVerbosity::default().sends_full_state());
+        assert!(!EventVerbosity::Minimal.sends_full_state());
+
+        let raw = session(30000.0, "PLANNING", 157);
+        let mut mock = MockLcuClient::new();
+        mock.push_draft_session(raw.clone());
+        let client = Mutex::new(mock);
+        let mut differ = DraftDiffer::new();
+        poll_once(&client, &mut differ).await;
+        assert_eq!(client.lock().await.get_draft_session_json().await, Ok(raw));
+    }
+}
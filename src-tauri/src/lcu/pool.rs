@@ -0,0 +1,242 @@
+use crate::champions::similar::{find_similar, SimilarChampion};
+use crate::lcu::draft::DraftState;
+use crate::model::DraftRecommendationModel;
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Alternatives offered per lost champion.
+const ALTERNATIVES: usize = 3;
+
+/// Similar champions considered before the model re-scores them.
+const SIMILAR_CANDIDATES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LossReason {
+    Banned,
+    PickedByEnemy,
+    PickedByAlly,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolAlternative {
+    pub champion_id: i64,
+    pub name: String,
+    pub similarity: f32,
+    pub model_score: Option<f32>, // None when the model isn't loaded
+}
+
+/// Payload of the `pool-champion-lost` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolChampionLost {
+    pub champion_id: i64,
+    pub reason: LossReason,
+    pub position: Option<String>, // The local player's assigned position, used for model scores
+    pub alternatives: Vec<PoolAlternative>,
+}
+
+/// Remembers which pool champions were already reported lost this draft.
+#[derive(Default)]
+pub struct PoolWatcher {
+    reported: HashSet<i64>,
+}
+
+impl PoolWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pool champions that became unavailable since the last call: banned by
+    /// anyone, or locked by the enemy team.
+    pub fn new_losses(&mut self, state: &DraftState, pool: &[i64]) -> Vec<(i64, LossReason)> {
+        let local_team = state.local_team().map(|t| t.team_id);
+        let mut losses = Vec::new();
+
+        for team in &state.teams {
+            let banned = team
+                .bans
+                .iter()
+                .filter(|b| b.completed)
+                .map(|b| (b.champion_id, LossReason::Banned));
+            losses.extend(banned);
+
+            if local_team.is_some_and(|id| id != team.team_id) {
+                let picked = team
+                    .cells
+                    .iter()
+                    .filter_map(|c| c.champion_id)
+                    .map(|id| (id, LossReason::PickedByEnemy));
+                losses.extend(picked);
+            }
+        }
+
+        losses
+            .into_iter()
+            .filter(|(id, _)| pool.contains(id) && self.reported.insert(*id))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.reported.clear();
+    }
+}
+
+/// Order similar champions by similarity blended with the model's score for the
+/// player's role, when there is one, and keep the best few.
+pub fn rank_alternatives(
+    similar: Vec<SimilarChampion>,
+    model_scores: &HashMap<i64, f32>,
+) -> Vec<PoolAlternative> {
+    let best_score = model_scores.values().copied().fold(0.0, f32::max);
+    let blended = |alt: &PoolAlternative| match alt.model_score {
+        Some(score) if best_score > 0.0 => (alt.similarity + score / best_score) / 2.0,
+        _ => alt.similarity,
+    };
+
+    let mut alternatives: Vec<PoolAlternative> = similar
+        .into_iter()
+        .map(|s| PoolAlternative {
+            model_score: model_scores.get(&s.champion_id).copied(),
+            champion_id: s.champion_id,
+            name: s.name,
+            similarity: s.similarity,
+        })
+        .collect();
+    alternatives.sort_by(|a, b| blended(b).total_cmp(&blended(a)));
+    alternatives.truncate(ALTERNATIVES);
+    alternatives
+}
+
+/// Model scores of every available champion for `position`.
+pub(crate) fn model_scores(
+    app: &AppHandle,
+    state: &DraftState,
+    position: Option<&str>,
+) -> HashMap<i64, f32> {
+    let model = app
+        .try_state::<Mutex<Option<Arc<DraftRecommendationModel>>>>()
+        .and_then(|m| m.lock().ok().and_then(|guard| guard.clone()));
+    let Some(model) = model else {
+        return HashMap::new();
+    };
+
+    match model.get_recommendations(state, usize::MAX, position) {
+        Ok(result) => result
+            .recommendations
+            .iter()
+            .map(|rec| (rec.champion_id as i64, rec.score))
+            .collect(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// The local player's assigned position, in the upper-case form the model uses.
+pub fn local_position(state: &DraftState) -> Option<String> {
+    state
+        .local_cell()
+        .and_then(|c| c.assigned_position.as_deref())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_uppercase())
+}
+
+/// Champions still available in `state` that could stand in for `champion_id`.
+pub fn suggest_alternatives(
+    app: &AppHandle,
+    state: &DraftState,
+    champion_id: i64,
+    position: Option<&str>,
+) -> Vec<PoolAlternative> {
+    let excluded = state.unavailable_champions();
+    match find_similar(app, champion_id, &excluded, SIMILAR_CANDIDATES) {
+        Ok(similar) => rank_alternatives(similar, &model_scores(app, state, position)),
+        Err(e) => {
+            eprintln!("Finding alternatives for {} failed: {}", champion_id, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Emit `pool-champion-lost` for every pool champion that was just banned or
+/// taken by the enemy, with alternatives the player can still pick.
+pub fn announce_pool_losses(app: &AppHandle, watcher: &mut PoolWatcher, state: &DraftState) {
+    let pool = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().champion_pool.clone()))
+        .unwrap_or_default();
+    if pool.is_empty() {
+        return;
+    }
+    let losses = watcher.new_losses(state, &pool);
+    if losses.is_empty() {
+        return;
+    }
+
+    let position = local_position(state);
+    for (champion_id, reason) in losses {
+        let lost = PoolChampionLost {
+            champion_id,
+            reason,
+            position: position.clone(),
+            alternatives: suggest_alternatives(app, state, champion_id, position.as_deref()),
+        };
+        let _ = app.emit("pool-champion-lost", &lost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+
+    fn draft(enemy_lock: i64, ban_completed: bool) -> DraftState {
+        let session = SessionBuilder::new()
+            .ally(0, 0, 0)
+            .enemy(5, enemy_lock, 0)
+            .ban(1, 5, 157);
+        if ban_completed {
+            session.state()
+        } else {
+            session.pending().state()
+        }
+    }
+
+    #[test]
+    fn test_pool_losses_are_reported_once() {
+        let mut watcher = PoolWatcher::new();
+        let pool = [157, 92, 266];
+
+        // A ban hover isn't a loss yet
+        let state = draft(0, false);
+        assert!(watcher.new_losses(&state, &pool).is_empty());
+
+        let state = draft(92, true);
+        let mut losses = watcher.new_losses(&state, &pool);
+        losses.sort_by_key(|(id, _)| *id);
+        assert_eq!(
+            losses,
+            vec![(92, LossReason::PickedByEnemy), (157, LossReason::Banned)]
+        );
+        assert!(watcher.new_losses(&state, &pool).is_empty());
+
+        let similar = vec![
+            SimilarChampion {
+                champion_id: 39,
+                name: "Irelia".to_string(),
+                similarity: 0.9,
+            },
+            SimilarChampion {
+                champion_id: 266,
+                name: "Aatrox".to_string(),
+                similarity: 0.7,
+            },
+        ];
+        // Aatrox is far stronger in this draft, which outweighs the similarity gap
+        let scores = HashMap::from([(39, 0.1), (266, 0.4)]);
+        let alternatives = rank_alternatives(similar, &scores);
+        assert_eq!(alternatives[0].champion_id, 266);
+        assert_eq!(alternatives[1].model_score, Some(0.1));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::lcu::client::LcuClient;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Latencies kept for the average and the maximum.
+const RECENT_REQUESTS: usize = 100;
+
+/// Requests slower than the draft polling interval show up as stutter.
+const SLOW_REQUEST: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RequestStats {
+    pub requests: u64,             // Sent to the client, coalesced ones excluded
+    pub failures: u64,             // Connection errors and non-success statuses
+    pub coalesced: u64,            // Served from a response fetched moments before
+    pub credential_refreshes: u64, // Lockfile reads after credentials were dropped
+    pub unknown_shapes: u64,       // Response values none of the known variations matched
+    pub slow_requests: u64,
+    pub average_ms: Option<f64>, // Over the most recent requests
+    pub max_ms: Option<u64>,
+}
+
+/// How requests to the client have been going since the app started. Kept by
+/// the client itself.
+#[derive(Default)]
+pub struct RequestCounters {
+    stats: RequestStats,
+    recent: VecDeque<Duration>,
+}
+
+impl RequestCounters {
+    pub fn record_request(&mut self, latency: Duration, ok: bool) {
+        self.stats.requests += 1;
+        self.stats.failures += !ok as u64;
+        self.stats.slow_requests += (latency > SLOW_REQUEST) as u64;
+        if self.recent.len() == RECENT_REQUESTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(latency);
+    }
+
+    pub fn record_coalesced(&mut self) {
+        self.stats.coalesced += 1;
+    }
+
+    pub fn record_credential_refresh(&mut self) {
+        self.stats.credential_refreshes += 1;
+    }
+
+    pub fn record_unknown_shapes(&mut self, count: u64) {
+        self.stats.unknown_shapes += count;
+    }
+
+    pub fn report(&self) -> RequestStats {
+        let millis = || self.recent.iter().map(|d| d.as_millis() as u64);
+        RequestStats {
+            average_ms: (!self.recent.is_empty())
+                .then(|| millis().sum::<u64>() as f64 / self.recent.len() as f64),
+            max_ms: millis().max(),
+            ..self.stats.clone()
+        }
+    }
+}
+
+/// How requests to the client have been going since the app started.
+#[tauri::command]
+pub async fn get_request_stats(
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<RequestStats, String> {
+    Ok(client.lock().await.request_stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_averages_recent_requests() {
+        let mut counters = RequestCounters::default();
+        assert_eq!(counters.report().average_ms, None);
+
+        counters.record_request(Duration::from_millis(400), false);
+        for _ in 0..RECENT_REQUESTS {
+            counters.record_request(Duration::from_millis(10), true);
+        }
+        let report = counters.report();
+        assert_eq!(report.requests, RECENT_REQUESTS as u64 + 1);
+        assert_eq!((report.failures, report.slow_requests), (1, 1));
+        assert_eq!(report.average_ms, Some(10.0), "the slow one aged out");
+        assert_eq!(report.max_ms, Some(10));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::analytics::tilt::current_loss_streak;
+use crate::lcu::{api::LcuApi, client::LcuClient};
+use crate::settings::{AppSettings, SettingsStore};
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::{sleep, Duration};
+
+pub const DEFAULT_MAX_GAMES: u32 = 3;
+pub const DEFAULT_MAX_LOSS_STREAK: u32 = 2;
+
+/// Why auto-requeue didn't queue again. Payload of `auto-requeue-stopped`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum RequeueStop {
+    SessionLimit { games: u32 },
+    LossStreak { losses: u32 },
+    MatchSyncFailed, // The ended game isn't in the history, so the streak can't be trusted
+}
+
+/// Requeues done since the app started.
+#[derive(Default)]
+pub struct RequeueSession {
+    requeued: u32,
+}
+
+impl RequeueSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a requeue if the session limit and loss streak allow another game.
+    pub fn try_requeue(
+        &mut self,
+        settings: &AppSettings,
+        loss_streak: u32,
+    ) -> Result<u32, RequeueStop> {
+        let max_losses = settings
+            .auto_requeue_max_loss_streak
+            .unwrap_or(DEFAULT_MAX_LOSS_STREAK);
+        if loss_streak >= max_losses {
+            return Err(RequeueStop::LossStreak {
+                losses: loss_streak,
+            });
+        }
+
+        let max_games = settings.auto_requeue_max_games.unwrap_or(DEFAULT_MAX_GAMES);
+        if self.requeued >= max_games {
+            return Err(RequeueStop::SessionLimit {
+                games: self.requeued,
+            });
+        }
+
+        self.requeued += 1;
+        Ok(self.requeued)
+    }
+}
+
+/// Post-game hook: once the honor screen is over, go back to the lobby and
+/// search again if auto-requeue is on. Runs after match sync so the loss
+/// streak includes the game that just ended; without a successful sync
+/// (`synced` false) it doesn't requeue at all.
+pub async fn auto_requeue(app: &AppHandle, synced: bool) -> Result<(), String> {
+    let settings = {
+        let store = app.state::<Mutex<SettingsStore>>();
+        let guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        guard.get().clone()
+    };
+    if !settings.auto_requeue_enabled {
+        return Ok(());
+    }
+    if !synced {
+        let _ = app.emit("auto-requeue-stopped", &RequeueStop::MatchSyncFailed);
+        return Ok(());
+    }
+
+    let loss_streak = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let matches: Vec<&StoredMatch> = guard.matches().collect();
+        current_loss_streak(&matches)
+    };
+
+    let decision = {
+        let session = app.state::<Mutex<RequeueSession>>();
+        let mut guard = session.lock().map_err(|e| format!("Lock error: {}", e))?;
+        guard.try_requeue(&settings, loss_streak)
+    };
+    let games = match decision {
+        Ok(games) => games,
+        Err(stop) => {
+            let _ = app.emit("auto-requeue-stopped", &stop);
+            return Ok(());
+        }
+    };
+
+    let client = app.state::<Arc<tokio::sync::Mutex<LcuClient>>>();
+    client.lock().await.play_again().await?;
+    // The lobby needs a moment to exist again before matchmaking accepts a search
+    sleep(Duration::from_millis(1500)).await;
+    client.lock().await.start_matchmaking().await?;
+
+    let _ = app.emit("auto-requeued", games);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requeue_stops_on_limits() {
+        let settings = AppSettings {
+            auto_requeue_enabled: true,
+            auto_requeue_max_games: Some(2),
+            ..Default::default()
+        };
+        let mut session = RequeueSession::new();
+
+        assert_eq!(session.try_requeue(&settings, 0), Ok(1));
+        assert_eq!(
+            session.try_requeue(&settings, DEFAULT_MAX_LOSS_STREAK),
+            Err(RequeueStop::LossStreak { losses: 2 })
+        );
+        assert_eq!(session.try_requeue(&settings, 1), Ok(2));
+        assert_eq!(
+            session.try_requeue(&settings, 0),
+            Err(RequeueStop::SessionLimit { games: 2 })
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::lcu::draft::DraftState;
+use crate::lcu::pool::local_position;
+use crate::model::autofill::{autofilled_position, safe_picks};
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Champions offered in the shortlist.
+const SHORTLIST: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleAssignment {
+    Secondary,
+    Autofill,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoleShortlistEntry {
+    pub champion_id: i64,
+    pub games: u32, // The owner's games on it in this position; 0 for safe picks never played there
+    pub wins: u32,
+    pub safe_pick: bool, // From the autofill list rather than the owner's history
+}
+
+/// Payload of the `role-prompt` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct RolePrompt {
+    pub position: String,
+    pub assignment: RoleAssignment,
+    pub games: u32, // The owner's stored games in this position
+    pub wins: u32,
+    pub win_rate: Option<f32>, // None without games in the position
+    pub average_kda: Option<f32>,
+    pub shortlist: Vec<RoleShortlistEntry>, // Most played first, then safe picks
+}
+
+/// Notices once per draft that the local player got their secondary role or
+/// was autofilled.
+#[derive(Default)]
+pub struct RolePromptWatcher {
+    preferences: Option<Vec<String>>, // Looked up once per draft
+    announced: bool,
+}
+
+impl RolePromptWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn needs_preferences(&self) -> bool {
+        self.preferences.is_none()
+    }
+
+    pub fn set_preferences(&mut self, preferences: Vec<String>) {
+        self.preferences = Some(preferences);
+    }
+
+    /// The assigned position and how it relates to the lobby picks, the first
+    /// time it's off the player's first pick.
+    pub fn check(&mut self, state: &DraftState) -> Option<(String, RoleAssignment)> {
+        if self.announced {
+            return None;
+        }
+        let preferences = self.preferences.as_ref()?;
+        let position = local_position(state)?;
+        self.announced = true;
+
+        if let Some(position) = autofilled_position(Some(&position), preferences) {
+            return Some((position, RoleAssignment::Autofill));
+        }
+        let first = preferences.first()?;
+        (!first.eq_ignore_ascii_case(&position) && first != "FILL")
+            .then_some((position, RoleAssignment::Secondary))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The owner's record in `position`, with the champions they play there and,
+/// when autofilled, safe picks to round out the shortlist.
+pub fn role_prompt<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+    position: &str,
+    assignment: RoleAssignment,
+) -> RolePrompt {
+    let (mut games, mut wins, mut kda_sum) = (0, 0, 0.0);
+    let mut champions: BTreeMap<i64, (u32, u32)> = BTreeMap::new();
+    for owner in matches.into_iter().filter_map(|m| m.owner()) {
+        if owner.position.as_deref() != Some(position) {
+            continue;
+        }
+        games += 1;
+        wins += owner.win as u32;
+        kda_sum += (owner.kills + owner.assists) as f32 / owner.deaths.max(1) as f32;
+        let record = champions.entry(owner.champion_id).or_default();
+        record.0 += 1;
+        record.1 += owner.win as u32;
+    }
+
+    let mut shortlist: Vec<RoleShortlistEntry> = champions
+        .iter()
+        .map(|(&champion_id, &(games, wins))| RoleShortlistEntry {
+            champion_id,
+            games,
+            wins,
+            safe_pick: false,
+        })
+        .collect();
+    shortlist.sort_by_key(|e| std::cmp::Reverse(e.games));
+    shortlist.truncate(SHORTLIST);
+    if assignment == RoleAssignment::Autofill {
+        for &champion_id in safe_picks(position) {
+            if shortlist.len() >= SHORTLIST {
+                break;
+            }
+            if !champions.contains_key(&champion_id) {
+                shortlist.push(RoleShortlistEntry {
+                    champion_id,
+                    games: 0,
+                    wins: 0,
+                    safe_pick: true,
+                });
+            }
+        }
+    }
+
+    RolePrompt {
+        position: position.to_string(),
+        assignment,
+        games,
+        wins,
+        win_rate: (games > 0).then(|| wins as f32 / games as f32),
+        average_kda: (games > 0).then(|| kda_sum / games as f32),
+        shortlist,
+    }
+}
+
+/// Emit `role-prompt` as soon as the draft shows the player off their first
+/// role, ahead of the ban phase.
+pub fn announce_role_prompt(app: &AppHandle, watcher: &mut RolePromptWatcher, state: &DraftState) {
+    let Some((position, assignment)) = watcher.check(state) else {
+        return;
+    };
+    let store = app.state::<Mutex<MatchStore>>();
+    let Ok(store_guard) = store.lock() else {
+        return;
+    };
+    let prompt = role_prompt(store_guard.matches(), &position, assignment);
+    let _ = app.emit("role-prompt", &prompt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+    use crate::storage::parse_game_details;
+    use crate::storage::tests::game_details;
+
+    fn state(position: &str) -> DraftState {
+        SessionBuilder::new()
+            .phase("PLANNING")
+            .ally(0, 0, 0)
+            .position(position)
+            .state()
+    }
+
+    fn preferences(positions: &[&str]) -> Vec<String> {
+        positions.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detects_secondary_and_autofill_once() {
+        let mut watcher = RolePromptWatcher::new();
+        assert!(
+            watcher.check(&state("top")).is_none(),
+            "preferences not known yet"
+        );
+
+        watcher.set_preferences(preferences(&["MIDDLE", "TOP"]));
+        assert_eq!(
+            watcher.check(&state("top")),
+            Some(("TOP".to_string(), RoleAssignment::Secondary))
+        );
+        assert!(watcher.check(&state("top")).is_none());
+
+        watcher.reset();
+        watcher.set_preferences(preferences(&["MIDDLE", "TOP"]));
+        assert_eq!(
+            watcher.check(&state("utility")),
+            Some(("UTILITY".to_string(), RoleAssignment::Autofill))
+        );
+
+        watcher.reset();
+        watcher.set_preferences(preferences(&["MIDDLE", "TOP"]));
+        assert!(watcher.check(&state("middle")).is_none());
+    }
+
+    #[test]
+    fn test_role_prompt_pads_autofill_with_safe_picks() {
+        // (champion, my team); team 100 wins
+        let games = [(89, 100), (89, 200), (412, 100)];
+        let mut matches: Vec<StoredMatch> = games
+            .iter()
+            .enumerate()
+            .map(|(i, (champion, team))| {
+                parse_game_details(
+                    &game_details(
+                        i as i64,
+                        420,
+                        &[("me", *champion, *team, "BOTTOM", "DUO_SUPPORT")],
+                    ),
+                    "me",
+                )
+                .unwrap()
+            })
+            .collect();
+        // Another position
+        matches.push(
+            parse_game_details(
+                &game_details(9, 420, &[("me", 1, 100, "MIDDLE", "SOLO")]),
+                "me",
+            )
+            .unwrap(),
+        );
+
+        let prompt = role_prompt(&matches, "UTILITY", RoleAssignment::Autofill);
+        assert_eq!((prompt.games, prompt.wins), (3, 2));
+        let ids: Vec<i64> = prompt.shortlist.iter().map(|e| e.champion_id).collect();
+        assert_eq!(ids, vec![89, 412, 16, 117, 111]);
+        assert!(!prompt.shortlist[1].safe_pick && prompt.shortlist[2].safe_pick);
+
+        let secondary = role_prompt(&matches, "UTILITY", RoleAssignment::Secondary);
+        assert_eq!(secondary.shortlist.len(), 2);
+    }
+}
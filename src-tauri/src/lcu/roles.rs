@@ -0,0 +1,189 @@
+use crate::champions::cache::ChampionCache;
+use crate::champions::similar::position_shares;
+use crate::lcu::draft::DraftState;
+use crate::storage::{MatchStore, POSITIONS};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Share of a champion's stored games needed for a position to count as its role.
+const MAIN_ROLE_SHARE: f32 = 0.5;
+
+/// The position a champion is played in most, if it clearly has one.
+pub fn main_role(shares: &[f32; 5]) -> Option<&'static str> {
+    let (slot, share) = shares
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))?;
+    (*share >= MAIN_ROLE_SHARE).then_some(POSITIONS[slot])
+}
+
+/// Payload of the `role-conflict` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoleConflict {
+    pub position: String,
+    pub cell_ids: Vec<i64>,
+    pub champion_ids: Vec<i64>,
+    pub champion_names: Vec<String>,
+}
+
+/// Allied cells whose locked or hovered champions share a main role. Only
+/// applies when the queue doesn't assign positions.
+pub fn find_conflicts(
+    state: &DraftState,
+    role: impl Fn(i64) -> Option<&'static str>,
+) -> Vec<(&'static str, Vec<(i64, i64)>)> {
+    let Some(team) = state.local_team() else {
+        return Vec::new();
+    };
+    if team.cells.iter().any(|c| c.assigned_position.is_some()) {
+        return Vec::new();
+    }
+
+    let mut by_role: BTreeMap<&'static str, Vec<(i64, i64)>> = BTreeMap::new();
+    for cell in &team.cells {
+        let Some(champion_id) = cell.champion_id.or(cell.selected_champion_id) else {
+            continue;
+        };
+        if let Some(role) = role(champion_id) {
+            by_role
+                .entry(role)
+                .or_default()
+                .push((cell.cell_id, champion_id));
+        }
+    }
+    by_role
+        .into_iter()
+        .filter(|(_, cells)| cells.len() > 1)
+        .collect()
+}
+
+/// Reports each role clash once per draft.
+#[derive(Default)]
+pub struct RoleConflictWatcher {
+    roles: Option<HashMap<i64, [f32; 5]>>, // Position shares, computed once per draft
+    reported: HashSet<(&'static str, Vec<i64>)>,
+}
+
+impl RoleConflictWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn needs_roles(&self) -> bool {
+        self.roles.is_none()
+    }
+
+    pub fn set_roles(&mut self, shares: HashMap<i64, [f32; 5]>) {
+        self.roles = Some(shares);
+    }
+
+    /// Conflicts as `(position, [(cell id, champion id)])` not yet reported.
+    pub fn new_conflicts(&mut self, state: &DraftState) -> Vec<(&'static str, Vec<(i64, i64)>)> {
+        let Some(roles) = &self.roles else {
+            return Vec::new();
+        };
+        let conflicts = find_conflicts(state, |id| roles.get(&id).and_then(main_role));
+        conflicts
+            .into_iter()
+            .filter(|(role, cells)| {
+                let champions = cells.iter().map(|(_, champion)| *champion).collect();
+                self.reported.insert((role, champions))
+            })
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Emit `role-conflict` when allies without assigned positions go for
+/// champions that usually play the same role.
+pub fn announce_role_conflicts(
+    app: &AppHandle,
+    watcher: &mut RoleConflictWatcher,
+    state: &DraftState,
+) {
+    if watcher.needs_roles() {
+        let Some(store) = app.try_state::<Mutex<MatchStore>>() else {
+            return;
+        };
+        let Ok(store_guard) = store.lock() else {
+            return;
+        };
+        watcher.set_roles(position_shares(&store_guard));
+    }
+
+    let conflicts = watcher.new_conflicts(state);
+    if conflicts.is_empty() {
+        return;
+    }
+    let cache = app.try_state::<Mutex<ChampionCache>>();
+    let cache_guard = cache.as_ref().and_then(|c| c.lock().ok());
+    for (position, cells) in conflicts {
+        let conflict = RoleConflict {
+            position: position.to_string(),
+            cell_ids: cells.iter().map(|(cell, _)| *cell).collect(),
+            champion_ids: cells.iter().map(|(_, champion)| *champion).collect(),
+            champion_names: cells
+                .iter()
+                .map(|(_, champion)| {
+                    cache_guard
+                        .as_ref()
+                        .and_then(|c| c.get_champion_by_id(*champion))
+                        .map(|c| c.name)
+                        .unwrap_or_default()
+                })
+                .collect(),
+        };
+        let _ = app.emit("role-conflict", &conflict);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+
+    fn draft(position: &str, third_hover: i64) -> DraftState {
+        SessionBuilder::new()
+            .ally(0, 0, 222)
+            .position(position)
+            .ally(1, 51, 0)
+            .position(position)
+            .ally(2, 0, third_hover)
+            .position(position)
+            .enemy(5, 21, 0)
+            .state()
+    }
+
+    #[test]
+    fn test_role_conflicts_in_blind_queues() {
+        let shares = HashMap::from([
+            (222, [0.0, 0.0, 0.0, 1.0, 0.0]), // Jinx
+            (51, [0.0, 0.0, 0.1, 0.9, 0.0]),  // Caitlyn
+            (21, [0.0, 0.0, 0.0, 1.0, 0.0]),  // Miss Fortune, on the enemy team
+            (99, [0.0, 0.0, 0.6, 0.0, 0.4]),  // Lux
+        ]);
+        assert_eq!(main_role(&shares[&99]), Some("MIDDLE"));
+        assert_eq!(main_role(&[0.3, 0.3, 0.4, 0.0, 0.0]), None);
+
+        let mut watcher = RoleConflictWatcher::new();
+        watcher.set_roles(shares);
+
+        let state = draft("", 99);
+        assert_eq!(
+            watcher.new_conflicts(&state),
+            vec![("BOTTOM", vec![(0, 222), (1, 51)])]
+        );
+        assert!(watcher.new_conflicts(&state).is_empty(), "reported once");
+
+        // Assigned positions settle roles already
+        watcher.reset();
+        watcher.set_roles(HashMap::from([(222, [0.0, 0.0, 0.0, 1.0, 0.0])]));
+        let state = draft("bottom", 222);
+        assert!(watcher.new_conflicts(&state).is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+use crate::analytics::now_millis;
+use crate::champions::cache::ChampionCache;
+use crate::lcu::draft::DraftState;
+use crate::settings::SettingsStore;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::Duration;
+
+/// Least time between two published snapshots. The monitor sees timer ticks
+/// four times a second; viewers don't need that.
+const PUBLISH_INTERVAL_MS: i64 = 1000;
+
+/// How often a viewer asks the relay for the latest snapshot.
+const VIEW_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// What is stored on the relay under a share token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedDraft {
+    pub published_at: i64,
+    pub state: DraftState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionAction {
+    Pick,
+    Ban,
+}
+
+/// A pick or ban a viewer sends back to the player sharing their draft.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoachSuggestion {
+    pub champion_id: i64,
+    pub action: SuggestionAction,
+    pub note: Option<String>,
+    pub sent_at: i64,
+}
+
+/// The viewer watching someone else's draft and the coach's suggestion for
+/// our own, for the session.
+#[derive(Default)]
+pub struct ShareSession {
+    viewer_stop: Option<Arc<AtomicBool>>, // Set to stop the running viewer
+    coach_suggestion: Option<CoachSuggestion>, // Latest one for the draft being shared
+}
+
+impl ShareSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn viewer_running(&self) -> bool {
+        self.viewer_stop.is_some()
+    }
+
+    /// Stop the running viewer, if any, and keep `stop` for the next one.
+    fn replace_viewer(&mut self, stop: Option<Arc<AtomicBool>>) {
+        if let Some(previous) = std::mem::replace(&mut self.viewer_stop, stop) {
+            previous.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// The coach's suggestion for the draft being shared, if one arrived.
+pub fn current_coach_suggestion(app: &AppHandle) -> Option<CoachSuggestion> {
+    let session = app.try_state::<Mutex<ShareSession>>()?;
+    let session_guard = session.lock().ok()?;
+    session_guard.coach_suggestion.clone()
+}
+
+/// Keep `incoming` when it's newer than the suggestion already held.
+fn accept_suggestion(current: &mut Option<CoachSuggestion>, incoming: CoachSuggestion) -> bool {
+    if current
+        .as_ref()
+        .is_some_and(|held| held.sent_at >= incoming.sent_at)
+    {
+        return false;
+    }
+    *current = Some(incoming);
+    true
+}
+
+/// Whether `host` is this machine, where plain HTTP doesn't expose the token.
+fn is_loopback(host: &str) -> bool {
+    host == "localhost"
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// The relay URL for a token. The token is sent as a bearer credential, so
+/// the relay has to use HTTPS unless it runs on this machine. Tokens end up in
+/// the path, so only letters, digits and dashes are accepted.
+pub fn share_url(relay_url: &str, token: &str) -> Result<String, String> {
+    let relay_url = relay_url.trim().trim_end_matches('/');
+    let parsed =
+        reqwest::Url::parse(relay_url).map_err(|_| format!("Invalid relay URL {}", relay_url))?;
+    let loopback = parsed.host_str().is_some_and(is_loopback);
+    match parsed.scheme() {
+        "https" => {}
+        "http" if loopback => {}
+        "http" => return Err("The relay has to use https:// unless it runs locally".to_string()),
+        _ => return Err(format!("Invalid relay URL {}", relay_url)),
+    }
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err("Invalid share token".to_string());
+    }
+    Ok(format!("{}/drafts/{}", relay_url, token))
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Pushes draft snapshots to the relay while sharing is on, at most once per
+/// `PUBLISH_INTERVAL_MS` and never with two uploads in flight.
+#[derive(Default)]
+pub struct DraftPublisher {
+    last_published: Option<i64>,
+    in_flight: Arc<AtomicBool>,
+}
+
+impl DraftPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn should_publish(&mut self, now: i64) -> bool {
+        if self.in_flight.load(Ordering::SeqCst)
+            || self
+                .last_published
+                .is_some_and(|last| now - last < PUBLISH_INTERVAL_MS)
+        {
+            return false;
+        }
+        self.last_published = Some(now);
+        true
+    }
+
+    /// A draft ended; suggestions for it no longer apply.
+    pub fn reset(&mut self, app: &AppHandle) {
+        self.last_published = None;
+        if let Some(session) = app.try_state::<Mutex<ShareSession>>() {
+            if let Ok(mut session_guard) = session.lock() {
+                session_guard.coach_suggestion = None;
+            }
+        }
+    }
+}
+
+/// Upload the state when "share my draft" is on and a relay and token are set,
+/// then pick up the coach's latest suggestion, emitted as `coach-suggestion`.
+pub fn publish_draft(app: &AppHandle, publisher: &mut DraftPublisher, state: &DraftState) {
+    let Some(settings) = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+    else {
+        return;
+    };
+    let (true, Some(relay_url), Some(token)) = (
+        settings.share_draft,
+        settings.share_relay_url,
+        settings.share_token,
+    ) else {
+        return;
+    };
+    let now = now_millis();
+    if !publisher.should_publish(now) {
+        return;
+    }
+
+    let snapshot = SharedDraft {
+        published_at: now,
+        state: state.clone(),
+    };
+    let in_flight = publisher.in_flight.clone();
+    in_flight.store(true, Ordering::SeqCst);
+    let app = app.clone();
+    tokio::spawn(async move {
+        let result = async {
+            let url = share_url(&relay_url, &token)?;
+            let client = http_client()?;
+            client
+                .put(&url)
+                .bearer_auth(&token)
+                .json(&snapshot)
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?
+                .error_for_status()
+                .map_err(|e| format!("HTTP error: {}", e))?;
+
+            // The relay answers 204 until a coach has sent something
+            let response = client
+                .get(format!("{}/suggestions/latest", url))
+                .bearer_auth(&token)
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?
+                .error_for_status()
+                .map_err(|e| format!("HTTP error: {}", e))?;
+            if response.status() == reqwest::StatusCode::NO_CONTENT {
+                return Ok(());
+            }
+            let incoming: CoachSuggestion = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse coach suggestion: {}", e))?;
+            let accepted = app
+                .state::<Mutex<ShareSession>>()
+                .lock()
+                .map(|mut session| {
+                    accept_suggestion(&mut session.coach_suggestion, incoming.clone())
+                })
+                .unwrap_or(false);
+            if accepted {
+                let _ = app.emit("coach-suggestion", &incoming);
+            }
+            Ok::<(), String>(())
+        }
+        .await;
+        if let Err(e) = result {
+            eprintln!("Sharing draft failed: {}", e);
+        }
+        in_flight.store(false, Ordering::SeqCst);
+    });
+}
+
+async fn fetch_shared(
+    client: &reqwest::Client,
+    url: &str,
+    token: &str,
+) -> Result<SharedDraft, String> {
+    client
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse shared draft: {}", e))
+}
+
+/// 32 bytes from the OS CSPRNG, hex encoded. Anyone holding the token can
+/// read the draft and send suggestions, so it mustn't be guessable.
+fn random_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Failed to generate a share token".to_string())?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Create a new random share token and save it in settings.
+#[tauri::command]
+pub async fn create_share_token(
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<String, String> {
+    let token = random_token()?;
+
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut updated = settings_guard.get().clone();
+    updated.share_token = Some(token.clone());
+    settings_guard.set(updated)?;
+    Ok(token)
+}
+
+/// Watch someone else's shared draft. Snapshots are emitted as
+/// `shared-draft-state` and relay failures as `shared-draft-error`, once per
+/// outage. Starting a viewer stops the previous one.
+#[tauri::command]
+pub async fn start_draft_viewer(
+    app: AppHandle,
+    relay_url: String,
+    token: String,
+    session: tauri::State<'_, Mutex<ShareSession>>,
+) -> Result<(), String> {
+    let url = share_url(&relay_url, &token)?;
+    let client = http_client()?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    session
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .replace_viewer(Some(stop.clone()));
+
+    tokio::spawn(async move {
+        let mut last_seen = None;
+        let mut failing = false;
+        while !stop.load(Ordering::SeqCst) {
+            match fetch_shared(&client, &url, &token).await {
+                Ok(mut shared) => {
+                    failing = false;
+                    if last_seen != Some(shared.published_at) {
+                        last_seen = Some(shared.published_at);
+                        if let Some(cache) = app.try_state::<Mutex<ChampionCache>>() {
+                            if let Ok(cache_guard) = cache.lock() {
+                                shared.state.resolve_spell_names(&cache_guard);
+                                shared.state.resolve_hover_names(&cache_guard);
+                            }
+                        }
+                        let _ = app.emit("shared-draft-state", &shared);
+                    }
+                }
+                Err(e) if !failing => {
+                    failing = true;
+                    let _ = app.emit("shared-draft-error", &e);
+                }
+                Err(_) => {}
+            }
+            tokio::time::sleep(VIEW_INTERVAL).await;
+        }
+    });
+    Ok(())
+}
+
+/// Send a pick or ban suggestion to the player whose draft is being watched.
+/// The share token doubles as the credential the relay checks.
+#[tauri::command]
+pub async fn send_coach_suggestion(
+    relay_url: String,
+    token: String,
+    champion_id: i64,
+    action: SuggestionAction,
+    note: Option<String>,
+) -> Result<(), String> {
+    let url = share_url(&relay_url, &token)?;
+    let suggestion = CoachSuggestion {
+        champion_id,
+        action,
+        note: note.filter(|n| !n.trim().is_empty()),
+        sent_at: now_millis(),
+    };
+    http_client()?
+        .post(format!("{}/suggestions", url))
+        .bearer_auth(&token)
+        .json(&suggestion)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_draft_viewer(
+    session: tauri::State<'_, Mutex<ShareSession>>,
+) -> Result<(), String> {
+    session
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .replace_viewer(None);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_url_and_throttle() {
+        assert_eq!(
+            share_url("https://relay.example.com/ ", "ab12-cd").unwrap(),
+            "https://relay.example.com/drafts/ab12-cd"
+        );
+        assert!(share_url("relay.example.com", "ab12").is_err());
+        assert!(share_url("http://relay.example.com", "ab12").is_err());
+        assert!(share_url("http://localhost:8080", "ab12").is_ok());
+        assert!(share_url("http://127.0.0.1:8080", "ab12").is_ok());
+        assert!(share_url("http://[::1]:8080", "ab12").is_ok());
+        assert!(share_url("ftp://relay.example.com", "ab12").is_err());
+        assert!(share_url("https://relay.example.com", "../admin").is_err());
+
+        let mut publisher = DraftPublisher::new();
+        assert!(publisher.should_publish(10_000));
+        assert!(!publisher.should_publish(10_500));
+        assert!(publisher.should_publish(11_000));
+        publisher.in_flight.store(true, Ordering::SeqCst);
+        let suggestion = |sent_at| CoachSuggestion {
+            champion_id: 64,
+            action: SuggestionAction::Pick,
+            note: None,
+            sent_at,
+        };
+        let mut held = None;
+        assert!(accept_suggestion(&mut held, suggestion(5)));
+        assert!(!accept_suggestion(&mut held, suggestion(5)), "already seen");
+        assert!(accept_suggestion(&mut held, suggestion(6)));
+
+        assert!(
+            !publisher.should_publish(20_000),
+            "previous upload still running"
+        );
+    }
+
+    #[test]
+    fn test_random_tokens() {
+        let token = random_token().unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(share_url("https://relay.example.com", &token).is_ok());
+        assert_ne!(token, random_token().unwrap());
+    }
+}
@@ -0,0 +1,135 @@
+use super::api::LcuApi;
+use super::client::LcuClient;
+use super::dto::Friend;
+use super::error::LcuError;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// A friend currently in a game, from their chat presence.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FriendLiveGame {
+    pub puuid: String,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub champion_id: Option<i64>,
+    pub queue_type: Option<String>, // e.g. "RANKED_SOLO_5x5"
+    pub started_at: Option<i64>,    // Epoch millis
+    pub observable: bool,           // False when the friend turned spectating off
+}
+
+/// Friends in a League game, longest running first.
+pub fn live_games(friends: &[Friend]) -> Vec<FriendLiveGame> {
+    let mut games: Vec<FriendLiveGame> = friends
+        .iter()
+        .filter(|f| f.product.as_deref() == Some("league_of_legends"))
+        .filter(|f| f.lol.game_status.as_deref() == Some("inGame"))
+        .filter_map(|f| {
+            Some(FriendLiveGame {
+                puuid: f.puuid.clone().filter(|p| !p.is_empty())?,
+                game_name: f.game_name.clone(),
+                tag_line: f.game_tag.clone(),
+                champion_id: f.lol.champion_id,
+                queue_type: f.lol.game_queue_type.clone().filter(|q| !q.is_empty()),
+                started_at: f.lol.time_stamp.filter(|t| *t > 0),
+                observable: f.lol.is_observable.as_deref() != Some("NONE"),
+            })
+        })
+        .collect();
+    games.sort_by_key(|g| g.started_at.unwrap_or(i64::MAX));
+    games
+}
+
+/// Split a Riot ID written as "Name#TAG"; None for anything else, such as a puuid.
+fn split_riot_id(target: &str) -> Option<(&str, &str)> {
+    let (game_name, tag_line) = target.rsplit_once('#')?;
+    (!game_name.is_empty() && !tag_line.is_empty()).then_some((game_name, tag_line))
+}
+
+/// Resolve `target` to a puuid, from the friends list when it's a friend's
+/// Riot ID and through a summoner lookup otherwise.
+pub async fn resolve_puuid<C: LcuApi>(client: &mut C, target: &str) -> Result<String, LcuError> {
+    let target = target.trim();
+    let Some((game_name, tag_line)) = split_riot_id(target) else {
+        return Ok(target.to_string());
+    };
+    let friend = client.get_friends().await?.into_iter().find(|f| {
+        f.game_name
+            .as_deref()
+            .is_some_and(|n| n.eq_ignore_ascii_case(game_name))
+            && f.game_tag
+                .as_deref()
+                .is_some_and(|t| t.eq_ignore_ascii_case(tag_line))
+    });
+    match friend.and_then(|f| f.puuid).filter(|p| !p.is_empty()) {
+        Some(puuid) => Ok(puuid),
+        None => Ok(client.lookup_summoner(game_name, tag_line).await?.puuid),
+    }
+}
+
+#[tauri::command]
+pub async fn get_friend_live_games(
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<Vec<FriendLiveGame>, LcuError> {
+    let mut client_guard = client.lock().await;
+    Ok(live_games(&client_guard.get_friends().await?))
+}
+
+/// Spectate the live game of `target`, a puuid or a Riot ID.
+#[tauri::command]
+pub async fn spectate(
+    target: String,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<(), LcuError> {
+    let mut client_guard = client.lock().await;
+    let puuid = resolve_puuid(&mut *client_guard, &target).await?;
+    client_guard.spectate(&puuid).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcuClient;
+    use serde_json::json;
+
+    fn friends() -> Vec<Friend> {
+        serde_json::from_value(json!([
+            {
+                "puuid": "late", "gameName": "Late", "gameTag": "EUW", "product": "league_of_legends",
+                "lol": { "gameStatus": "inGame", "championId": "157", "timeStamp": "2000", "gameQueueType": "RANKED_SOLO_5x5" }
+            },
+            {
+                "puuid": "early", "gameName": "Early", "gameTag": "EUW", "product": "league_of_legends",
+                "lol": { "gameStatus": "inGame", "timeStamp": "1000", "isObservable": "NONE" }
+            },
+            {
+                "puuid": "select", "gameName": "Select", "gameTag": "EUW", "product": "league_of_legends",
+                "lol": { "gameStatus": "championSelect" }
+            },
+            { "puuid": "valorant", "product": "valorant", "lol": null }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_live_games_from_presence() {
+        let games = live_games(&friends());
+        let puuids: Vec<&str> = games.iter().map(|g| g.puuid.as_str()).collect();
+        assert_eq!(puuids, vec!["early", "late"]);
+        assert!(!games[0].observable);
+        assert_eq!(games[1].champion_id, Some(157));
+        assert_eq!(games[1].started_at, Some(2000));
+    }
+
+    #[tokio::test]
+    async fn riot_ids_resolve_through_friends_first() {
+        let mut mock = MockLcuClient::new();
+        mock.friends = friends();
+        assert_eq!(resolve_puuid(&mut mock, "late#euw").await.unwrap(), "late");
+        assert_eq!(
+            resolve_puuid(&mut mock, " abc-123 ").await.unwrap(),
+            "abc-123"
+        );
+        // Not a friend and no summoner to look up
+        assert!(resolve_puuid(&mut mock, "Stranger#NA1").await.is_err());
+    }
+}
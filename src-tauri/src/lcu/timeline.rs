@@ -0,0 +1,336 @@
+use crate::lcu::draft::DraftState;
+use crate::storage::usage::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Hover,
+    BanHover,
+    Lock,
+    Ban,
+}
+
+/// One thing that happened in champion select, relative to when recording started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub elapsed_ms: i64,
+    pub kind: TimelineEventKind,
+    pub cell_id: Option<i64>,
+    pub team_id: Option<i64>,
+    pub champion_id: i64,
+    pub is_local_player: bool,
+    pub phase: String,
+}
+
+/// Turns successive draft states into hover/lock/ban events.
+#[derive(Default)]
+pub struct TimelineRecorder {
+    game_id: Option<i64>,
+    started_at_ms: Option<i64>,
+    events: Vec<TimelineEvent>,
+    cell_hovers: HashMap<i64, Option<i64>>,
+    ban_hovers: HashMap<i64, Option<i64>>, // Keyed by action id
+    completed_actions: HashSet<i64>,
+}
+
+impl TimelineRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hovered champions per cell in the order they were first hovered.
+    pub fn hover_history(&self) -> BTreeMap<i64, Vec<i64>> {
+        let mut history: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+        for event in self
+            .events
+            .iter()
+            .filter(|e| e.kind == TimelineEventKind::Hover)
+        {
+            if let Some(cell_id) = event.cell_id {
+                let hovers = history.entry(cell_id).or_default();
+                if !hovers.contains(&event.champion_id) {
+                    hovers.push(event.champion_id);
+                }
+            }
+        }
+        history
+    }
+
+    /// Fill `previous_hovers` on enemy cells: everything they hovered this draft
+    /// except what they're hovering or have locked right now.
+    pub fn annotate_hover_history(&self, state: &mut DraftState) {
+        let history = self.hover_history();
+        let local_cell = state.local_player_cell_id;
+
+        for team in &mut state.teams {
+            if team.cells.iter().any(|c| Some(c.cell_id) == local_cell) {
+                continue;
+            }
+            for cell in &mut team.cells {
+                let current = cell.champion_id.or(cell.selected_champion_id);
+                cell.previous_hovers = history
+                    .get(&cell.cell_id)
+                    .map(|hovers| {
+                        hovers
+                            .iter()
+                            .copied()
+                            .filter(|id| Some(*id) != current)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+        }
+    }
+
+    /// Record whatever changed since the previous state. A different game id
+    /// starts a new timeline; the finished one is returned.
+    pub fn observe(
+        &mut self,
+        state: &DraftState,
+        now_ms: i64,
+    ) -> Option<(i64, Vec<TimelineEvent>)> {
+        let mut finished = None;
+        if self.started_at_ms.is_some() && state.game_id != self.game_id {
+            finished = self.finish();
+        }
+
+        let started_at = *self.started_at_ms.get_or_insert(now_ms);
+        self.game_id = state.game_id;
+        let elapsed_ms = now_ms - started_at;
+
+        let team_of: HashMap<i64, i64> = state
+            .teams
+            .iter()
+            .flat_map(|t| t.cells.iter().map(move |c| (c.cell_id, t.team_id)))
+            .collect();
+        let mut push = |kind, cell_id: Option<i64>, champion_id| {
+            self.events.push(TimelineEvent {
+                elapsed_ms,
+                kind,
+                cell_id,
+                team_id: cell_id.and_then(|c| team_of.get(&c).copied()),
+                champion_id,
+                is_local_player: cell_id.is_some() && cell_id == state.local_player_cell_id,
+                phase: state.phase.clone(),
+            });
+        };
+
+        for cell in state.teams.iter().flat_map(|t| t.cells.iter()) {
+            let hover = cell
+                .selected_champion_id
+                .filter(|_| cell.champion_id.is_none());
+            let previous = self.cell_hovers.insert(cell.cell_id, hover);
+            if let Some(champion_id) = hover {
+                if previous != Some(hover) {
+                    push(TimelineEventKind::Hover, Some(cell.cell_id), champion_id);
+                }
+            }
+        }
+
+        for action in &state.actions {
+            if action.completed {
+                if self.completed_actions.insert(action.id) {
+                    if let Some(champion_id) = action.champion_id {
+                        let kind = if action.action_type == "ban" {
+                            TimelineEventKind::Ban
+                        } else {
+                            TimelineEventKind::Lock
+                        };
+                        push(kind, action.actor_cell_id, champion_id);
+                    }
+                }
+            } else if action.action_type == "ban" && action.is_in_progress {
+                let previous = self.ban_hovers.insert(action.id, action.champion_id);
+                if let Some(champion_id) = action.champion_id {
+                    if previous != Some(action.champion_id) {
+                        push(
+                            TimelineEventKind::BanHover,
+                            action.actor_cell_id,
+                            champion_id,
+                        );
+                    }
+                }
+            }
+        }
+
+        finished
+    }
+
+    /// End the current timeline, returning it if it belongs to a known game.
+    pub fn finish(&mut self) -> Option<(i64, Vec<TimelineEvent>)> {
+        let game_id = self.game_id;
+        let events = std::mem::take(&mut self.events);
+        *self = Self::default();
+        game_id
+            .filter(|_| !events.is_empty())
+            .map(|id| (id, events))
+    }
+}
+
+/// Recorded timelines keyed by game id.
+pub struct DraftTimelineStore {
+    timelines: BTreeMap<i64, Vec<TimelineEvent>>,
+    store_path: PathBuf,
+}
+
+impl DraftTimelineStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("draft_timelines.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            timelines: BTreeMap::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read draft timelines: {}", e))?;
+        self.timelines = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse draft timelines: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.timelines)
+            .map_err(|e| format!("Failed to serialize draft timelines: {}", e))?;
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write draft timelines: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn insert(&mut self, game_id: i64, events: Vec<TimelineEvent>) {
+        self.timelines.insert(game_id, events);
+    }
+
+    pub fn get(&self, game_id: i64) -> Option<&Vec<TimelineEvent>> {
+        self.timelines.get(&game_id)
+    }
+
+    /// Every recorded draft, oldest first.
+    pub fn game_ids(&self) -> impl Iterator<Item = i64> + '_ {
+        self.timelines.keys().copied()
+    }
+
+    pub fn timelines(&self) -> impl Iterator<Item = &Vec<TimelineEvent>> + '_ {
+        self.timelines.values()
+    }
+}
+
+/// Persist a finished timeline. Drafts that were dodged are kept too.
+pub fn store_timeline(
+    app: &AppHandle,
+    game_id: i64,
+    events: Vec<TimelineEvent>,
+) -> Result<(), String> {
+    let timelines = app.state::<Mutex<DraftTimelineStore>>();
+    let mut timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+    timelines_guard.insert(game_id, events);
+    timelines_guard.save()
+}
+
+#[tauri::command]
+pub async fn get_draft_timeline_events(
+    game_id: i64,
+    timelines: tauri::State<'_, Mutex<DraftTimelineStore>>,
+) -> Result<Vec<TimelineEvent>, String> {
+    let timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(timelines_guard.get(game_id).cloned().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::SessionBuilder;
+
+    fn state(
+        my_hover: i64,
+        enemy_hover: i64,
+        ban: Option<(i64, bool)>,
+        locked: bool,
+    ) -> DraftState {
+        let mut session = SessionBuilder::new()
+            .game_id(42)
+            .ally(0, if locked { my_hover } else { 0 }, my_hover)
+            .enemy(5, 0, enemy_hover);
+        if let Some((champion, completed)) = ban {
+            session = session.ban(1, 5, champion);
+            if !completed {
+                session = session.in_progress();
+            }
+        }
+        if locked {
+            session = session.pick(2, 0, my_hover);
+        }
+        session.state()
+    }
+
+    #[test]
+    fn test_recorder_emits_each_change_once() {
+        let mut recorder = TimelineRecorder::new();
+        recorder.observe(&state(64, 238, None, false), 1_000);
+        recorder.observe(&state(64, 238, Some((157, false)), false), 2_000);
+        recorder.observe(&state(64, 91, Some((157, true)), false), 3_000);
+        recorder.observe(&state(64, 91, Some((157, true)), true), 4_000);
+        recorder.observe(&state(64, 91, Some((157, true)), true), 5_000);
+
+        let (game_id, events) = recorder.finish().unwrap();
+        assert_eq!(game_id, 42);
+        let kinds: Vec<(TimelineEventKind, i64, i64)> = events
+            .iter()
+            .map(|e| (e.kind, e.champion_id, e.elapsed_ms))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TimelineEventKind::Hover, 64, 0),
+                (TimelineEventKind::Hover, 238, 0),
+                (TimelineEventKind::BanHover, 157, 1_000),
+                (TimelineEventKind::Hover, 91, 2_000),
+                (TimelineEventKind::Ban, 157, 2_000),
+                (TimelineEventKind::Lock, 64, 3_000),
+            ]
+        );
+        assert!(events[0].is_local_player);
+        assert_eq!(events[3].cell_id, Some(5));
+        assert!(recorder.finish().is_none());
+    }
+
+    #[test]
+    fn test_enemy_hover_history_survives_switches() {
+        let mut recorder = TimelineRecorder::new();
+        recorder.observe(&state(64, 238, None, false), 0);
+        recorder.observe(&state(64, 91, None, false), 1_000);
+        let mut current = state(64, 238, None, false);
+        recorder.observe(&current, 2_000);
+        recorder.annotate_hover_history(&mut current);
+
+        let cell = |state: &DraftState, id: i64| {
+            state
+                .teams
+                .iter()
+                .flat_map(|t| t.cells.iter())
+                .find(|c| c.cell_id == id)
+                .unwrap()
+                .previous_hovers
+                .clone()
+        };
+        assert_eq!(cell(&current, 5), vec![91]);
+        // The local player's own team is not annotated
+        assert!(cell(&current, 0).is_empty());
+    }
+}
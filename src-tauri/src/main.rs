@@ -1,19 +1,78 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod analytics;
+mod audio;
 mod champions;
+mod cli;
+mod downloads;
+mod flags;
+mod goals;
 mod lcu;
+mod metrics;
 mod model;
+mod obs;
+mod patch_notes;
+mod performance;
+mod platform;
+mod profile;
+mod scripts;
+mod series;
+mod settings;
+mod startup;
+mod state;
+mod status;
+mod storage;
+mod teams;
 
-use champions::cache::ChampionCache;
-use lcu::client::LcuClient;
+use analytics::meta_shift::MetaShiftStore;
+use champions::{assets::PriorityDownloads, cache::ChampionCache};
+use downloads::DownloadRegistry;
+use flags::RemoteFlags;
+use goals::GoalStore;
+use lcu::{
+    actions::PendingLock, cancel::PhaseTokens, client::LcuClient, game_process::InGameTracker,
+    missions::MissionTracker, monitor::MonitorManager, requeue::RequeueSession,
+    share::ShareSession, timeline::DraftTimelineStore,
+};
+use metrics::MetricsStore;
+use obs::SceneSwitcher;
+use patch_notes::PatchNotesStore;
+use scripts::ScriptHost;
+use series::SeriesTracker;
+use settings::SettingsStore;
+use startup::StartupState;
+use state::AppState;
+use status::PendingUpdate;
 use std::sync::Arc;
-use tauri::Manager;
+use storage::{snapshots::RankSnapshotStore, timelines::MatchTimelineStore, MatchStore};
+use tauri::{Manager, RunEvent};
+use teams::TeamStore;
 use tokio::sync::Mutex as TokioMutex;
 
 fn main() {
+    // `recommend` and `export` run without opening the window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parse_args(&args) {
+        Ok(Some(command)) => {
+            cli::attach_console();
+            if let Err(e) = cli::run(command) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            cli::attach_console();
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             #[cfg(windows)]
             let _ = app.handle().plugin(tauri_plugin_updater::Builder::new().build());
@@ -23,32 +82,64 @@ fn main() {
                 window.open_devtools();
             }
 
-            // Try to load champion data from cache on startup
-            if let Ok(cache_guard) = app.state::<std::sync::Mutex<ChampionCache>>().try_lock() {
-                let _ = cache_guard.load_from_cache();
-            }
-
-            // Initialize the draft recommendation model
-            let model = match model::initialize_model(app.handle()) {
-                Ok(model) => {
-                    println!("Draft recommendation model loaded successfully");
-                    Some(model)
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to load draft recommendation model: {}", e);
-                    eprintln!("Model recommendations will not be available");
-                    None
-                }
-            };
-            // Always manage the model state (even if None) so the command can access it
-            app.manage(std::sync::Mutex::new(model));
+            startup::run(app.handle());
 
             Ok(())
         })
         .manage(Arc::new(TokioMutex::new(LcuClient::new())))
+        // Filled in by the model startup stage; commands treat None as not loaded yet
+        .manage(std::sync::Mutex::new(None::<Arc<model::DraftRecommendationModel>>))
         .manage(std::sync::Mutex::new(
             ChampionCache::new().expect("Failed to initialize champion cache"),
         ))
+        .manage(std::sync::Mutex::new(
+            SettingsStore::new().expect("Failed to initialize settings"),
+        ))
+        .manage(std::sync::Mutex::new(
+            MatchStore::new().expect("Failed to initialize match store"),
+        ))
+        .manage(std::sync::Mutex::new(
+            RankSnapshotStore::new().expect("Failed to initialize rank snapshots"),
+        ))
+        .manage(std::sync::Mutex::new(MissionTracker::new()))
+        .manage(std::sync::Mutex::new(RequeueSession::new()))
+        .manage(std::sync::Mutex::new(DownloadRegistry::new()))
+        .manage(std::sync::Mutex::new(PriorityDownloads::new()))
+        .manage(std::sync::Mutex::new(
+            GoalStore::new().expect("Failed to initialize goals"),
+        ))
+        .manage(std::sync::Mutex::new(
+            DraftTimelineStore::new().expect("Failed to initialize draft timelines"),
+        ))
+        .manage(std::sync::Mutex::new(
+            MatchTimelineStore::new().expect("Failed to initialize match timelines"),
+        ))
+        .manage(std::sync::Mutex::new(
+            MetaShiftStore::new().expect("Failed to initialize meta shifts"),
+        ))
+        .manage(std::sync::Mutex::new(
+            PatchNotesStore::new().expect("Failed to initialize patch notes"),
+        ))
+        .manage(std::sync::Mutex::new(
+            TeamStore::new().expect("Failed to initialize teams"),
+        ))
+        .manage(std::sync::Mutex::new(
+            ScriptHost::new().expect("Failed to initialize scripts"),
+        ))
+        .manage(std::sync::Mutex::new(
+            MetricsStore::new().expect("Failed to initialize metrics"),
+        ))
+        .manage(std::sync::Mutex::new(SeriesTracker::new()))
+        .manage(std::sync::Mutex::new(RemoteFlags::new()))
+        .manage(std::sync::Mutex::new(ShareSession::new()))
+        .manage(std::sync::Mutex::new(PendingLock::new()))
+        .manage(std::sync::Mutex::new(PhaseTokens::new()))
+        .manage(std::sync::Mutex::new(PendingUpdate::new()))
+        .manage(std::sync::Mutex::new(InGameTracker::new()))
+        .manage(std::sync::Mutex::new(StartupState::new()))
+        .manage(TokioMutex::new(SceneSwitcher::new()))
+        .manage(std::sync::Mutex::new(AppState::new()))
+        .manage(std::sync::Mutex::new(MonitorManager::new()))
         .invoke_handler(tauri::generate_handler![
             lcu::client::get_gameflow_phase,
             lcu::client::get_draft_session,
@@ -57,13 +148,107 @@ fn main() {
             lcu::client::get_ranked_stats,
             lcu::client::get_match_history,
             lcu::client::get_match_history_paginated,
+            lcu::client::get_free_rotation,
+            lcu::client::get_chest_eligible_champions,
+            lcu::client::get_first_win_available,
+            lcu::client::list_detected_clients,
+            lcu::client::select_client,
+            lcu::client::get_client_profile,
+            lcu::request_stats::get_request_stats,
+            lcu::game_process::get_in_game,
+            lcu::missions::get_missions,
+            lcu::draft::parse_draft_fixture,
+            lcu::actions::hover_champion,
+            lcu::actions::lock_champion,
+            lcu::actions::ban_champion,
+            lcu::auto_draft::set_auto_draft,
+            lcu::auto_draft::get_auto_draft,
             lcu::monitor::start_draft_monitoring,
+            lcu::monitor::stop_draft_monitoring,
+            lcu::monitor::is_monitoring,
+            lcu::monitor::set_auto_accept,
+            lcu::monitor::get_auto_accept,
+            lcu::timeline::get_draft_timeline_events,
+            lcu::intent::set_pick_intent,
+            lcu::intent::get_pick_intent_risks,
+            lcu::share::create_share_token,
+            lcu::share::start_draft_viewer,
+            lcu::share::stop_draft_viewer,
+            lcu::share::send_coach_suggestion,
+            lcu::spectate::get_friend_live_games,
+            lcu::spectate::spectate,
             champions::client::fetch_champion_data,
+            downloads::cancel_download,
             champions::cache::get_champion_by_id,
             champions::cache::get_all_champions,
             champions::cache::get_champion_version,
+            champions::cache::get_champion_data_for_version,
+            champions::grid::get_champion_grid,
+            champions::similar::get_similar_champions,
+            champions::assets::prefetch_assets,
+            champions::static_data::get_static_data,
+            patch_notes::get_patch_changes,
+            obs::get_obs_scenes,
+            platform::get_platform_capabilities,
+            audio::list_audio_devices,
+            performance::get_performance_profile,
             model::get_draft_recommendations,
+            model::aram::get_aram_bench_recommendations,
+            profile::get_profile,
+            storage::sync_match_history,
+            storage::timelines::get_match_timeline,
+            storage::usage::get_storage_usage,
+            storage::usage::trim_cache,
+            analytics::encounters::get_recent_encounters,
+            analytics::encounters::get_lobby_tendencies,
+            analytics::champion_pool::get_inferred_pool,
+            analytics::split::get_split_progress,
+            analytics::meta_shift::get_unseen_meta_shift,
+            analytics::trends::get_performance_trends,
+            analytics::schedule::get_schedule_insights,
+            analytics::pool_health::get_pool_health,
+            analytics::vision::get_vision_report,
+            analytics::gold_graph::get_gold_graph,
+            analytics::matchups::get_matchup_stats,
+            analytics::runes::get_rune_stats,
+            analytics::spells::get_spell_stats,
+            analytics::queues::get_queue_insights,
+            analytics::draft_review::get_review_summary,
+            analytics::bans::get_ban_stats,
+            settings::get_settings,
+            settings::update_settings,
+            settings::patch_settings,
+            flags::get_feature_flags,
+            flags::set_feature_flag,
+            flags::refresh_feature_flags,
+            scripts::list_scripts,
+            scripts::set_script_enabled,
+            scripts::reload_scripts,
+            metrics::get_metrics_report,
+            metrics::submit_metrics,
+            metrics::clear_metrics,
+            status::get_app_status,
+            startup::get_startup_progress,
+            startup::reload_model,
+            goals::add_goal,
+            goals::remove_goal,
+            goals::get_goal_progress,
+            teams::get_rosters,
+            teams::save_roster,
+            teams::remove_roster,
+            teams::set_active_roster,
+            teams::get_active_roster,
+            teams::scouting::scout_opponents,
+            teams::scouting::export_scouting_report,
+            series::start_series,
+            series::end_series,
+            series::get_series_state,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                metrics::flush_metrics(app);
+            }
+        });
 }
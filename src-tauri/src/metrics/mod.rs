@@ -0,0 +1,345 @@
+use crate::analytics::now_millis;
+use crate::settings::SettingsStore;
+use crate::storage::usage::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::time::{interval, Duration};
+
+/// How often counts recorded since the last write are saved. Features are
+/// recorded on every recommendation refresh, too often to write each one.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Features whose use is counted. Only these fixed names are ever recorded,
+/// never champion, summoner or match data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    DraftRecommendations,
+    DraftMonitor,
+    AutoBan,
+    AutoAccept,
+    AutoDraft,
+    Scouting,
+    ObsSceneSwitch,
+    AramAdvisor,
+    FearlessSeries,
+}
+
+impl Feature {
+    fn key(self) -> &'static str {
+        match self {
+            Feature::DraftRecommendations => "draft_recommendations",
+            Feature::DraftMonitor => "draft_monitor",
+            Feature::AutoBan => "auto_ban",
+            Feature::AutoAccept => "auto_accept",
+            Feature::AutoDraft => "auto_draft",
+            Feature::Scouting => "scouting",
+            Feature::ObsSceneSwitch => "obs_scene_switch",
+            Feature::AramAdvisor => "aram_advisor",
+            Feature::FearlessSeries => "fearless_series",
+        }
+    }
+}
+
+/// Kinds of failure that are counted, without their messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Startup,
+    Recommendation,
+    MatchSync,
+    AutoBan,
+}
+
+impl ErrorCategory {
+    fn key(self) -> &'static str {
+        match self {
+            ErrorCategory::Startup => "startup",
+            ErrorCategory::Recommendation => "recommendation",
+            ErrorCategory::MatchSync => "match_sync",
+            ErrorCategory::AutoBan => "auto_ban",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MetricsFile {
+    since: Option<i64>, // When counting started, or restarted after a submit
+    features: BTreeMap<String, u64>,
+    errors: BTreeMap<String, u64>,
+    last_submitted: Option<i64>,
+}
+
+/// Exactly what `submit_metrics` sends.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsReport {
+    pub app_version: String,
+    pub os: String,
+    pub since: Option<i64>,
+    pub until: i64,
+    pub features: BTreeMap<String, u64>,
+    pub errors: BTreeMap<String, u64>,
+}
+
+/// Local usage counters. Nothing is recorded unless metrics are enabled in
+/// settings, and nothing leaves the machine until `submit_metrics` is called.
+pub struct MetricsStore {
+    data: MetricsFile,
+    store_path: PathBuf,
+    loaded: bool,
+    dirty: bool, // Counted since the last write
+}
+
+impl MetricsStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("metrics.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            data: MetricsFile::default(),
+            store_path,
+            loaded: false,
+            dirty: false,
+        }
+    }
+
+    /// Counts recorded before loading, such as a failed startup stage, are
+    /// added to the stored ones.
+    pub fn load(&mut self) -> Result<(), String> {
+        self.loaded = true;
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read metrics: {}", e))?;
+        let mut stored: MetricsFile = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse metrics: {}", e))?;
+
+        let recorded = std::mem::take(&mut self.data);
+        stored.since = stored.since.or(recorded.since);
+        for (key, n) in recorded.features {
+            *stored.features.entry(key).or_insert(0) += n;
+        }
+        for (key, n) in recorded.errors {
+            *stored.errors.entry(key).or_insert(0) += n;
+        }
+        self.data = stored;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.data)
+            .map_err(|e| format!("Failed to serialize metrics: {}", e))?;
+
+        fs::write(&self.store_path, json).map_err(|e| format!("Failed to write metrics: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Write the counts if any were recorded since the last write. Saving
+    /// before the file is loaded would overwrite it.
+    pub fn flush(&mut self) -> Result<(), String> {
+        if self.loaded && self.dirty {
+            self.save()?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn count(&mut self, errors: bool, key: &str, now: i64) {
+        self.dirty = true;
+        self.data.since.get_or_insert(now);
+        let counts = if errors {
+            &mut self.data.errors
+        } else {
+            &mut self.data.features
+        };
+        *counts.entry(key.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn report(&self, app_version: &str, now: i64) -> MetricsReport {
+        MetricsReport {
+            app_version: app_version.to_string(),
+            os: std::env::consts::OS.to_string(),
+            since: self.data.since,
+            until: now,
+            features: self.data.features.clone(),
+            errors: self.data.errors.clone(),
+        }
+    }
+
+    /// Drop the counts, keeping when they were last sent.
+    pub fn reset(&mut self, submitted_at: Option<i64>) {
+        self.data = MetricsFile {
+            last_submitted: submitted_at.or(self.data.last_submitted),
+            ..MetricsFile::default()
+        };
+    }
+}
+
+fn metrics_enabled(app: &AppHandle) -> bool {
+    app.try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().metrics_enabled))
+        .unwrap_or(false)
+}
+
+fn count(app: &AppHandle, errors: bool, key: &str) {
+    if !metrics_enabled(app) {
+        return;
+    }
+    let Some(store) = app.try_state::<Mutex<MetricsStore>>() else {
+        return;
+    };
+    let Ok(mut store_guard) = store.lock() else {
+        return;
+    };
+    store_guard.count(errors, key, now_millis());
+}
+
+/// Save the counts recorded since the last write, as on exit.
+pub fn flush_metrics(app: &AppHandle) {
+    let Some(store) = app.try_state::<Mutex<MetricsStore>>() else {
+        return;
+    };
+    let Ok(mut store_guard) = store.lock() else {
+        return;
+    };
+    if let Err(e) = store_guard.flush() {
+        eprintln!("Failed to save metrics: {}", e);
+    }
+}
+
+/// Save recorded counts every `FLUSH_INTERVAL` for the rest of the session.
+pub fn start_metrics_flush(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval_timer = interval(FLUSH_INTERVAL);
+        loop {
+            interval_timer.tick().await;
+            flush_metrics(&app);
+        }
+    });
+}
+
+pub fn record_feature(app: &AppHandle, feature: Feature) {
+    count(app, false, feature.key());
+}
+
+pub fn record_error(app: &AppHandle, category: ErrorCategory) {
+    count(app, true, category.key());
+}
+
+fn app_version(app: &AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+/// The report `submit_metrics` would send right now.
+#[tauri::command]
+pub async fn get_metrics_report(
+    app: AppHandle,
+    store: tauri::State<'_, Mutex<MetricsStore>>,
+) -> Result<MetricsReport, String> {
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(store_guard.report(&app_version(&app), now_millis()))
+}
+
+/// Post the report to the configured endpoint and start counting afresh.
+#[tauri::command]
+pub async fn submit_metrics(
+    app: AppHandle,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    store: tauri::State<'_, Mutex<MetricsStore>>,
+) -> Result<MetricsReport, String> {
+    let url = {
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if !settings_guard.get().metrics_enabled {
+            return Err("Usage metrics are turned off".to_string());
+        }
+        settings_guard.get().metrics_url.clone()
+    };
+    let url = url
+        .filter(|u| !u.trim().is_empty())
+        .ok_or_else(|| "No metrics endpoint configured".to_string())?;
+
+    let report = {
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        store_guard.report(&app_version(&app), now_millis())
+    };
+
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .post(url.trim())
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?;
+
+    let mut store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    store_guard.reset(Some(report.until));
+    store_guard.save()?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn clear_metrics(store: tauri::State<'_, Mutex<MetricsStore>>) -> Result<(), String> {
+    let mut store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    store_guard.reset(None);
+    store_guard.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_reset() {
+        let path =
+            std::env::temp_dir().join(format!("trackimo-metrics-{}.json", std::process::id()));
+        let mut store = MetricsStore::with_path(path.clone());
+        store.count(false, Feature::Scouting.key(), 100);
+        store.count(true, ErrorCategory::MatchSync.key(), 300);
+        store.save().unwrap();
+
+        // Counted before loading, as a startup failure would be
+        let mut reloaded = MetricsStore::with_path(path.clone());
+        reloaded.count(false, Feature::Scouting.key(), 200);
+        reloaded.load().unwrap();
+        let report = reloaded.report("1.0.5", 400);
+        assert_eq!(report.since, Some(100));
+        assert_eq!(report.features.get("scouting"), Some(&2));
+        assert_eq!(report.errors.get("match_sync"), Some(&1));
+
+        reloaded.reset(Some(400));
+        let report = reloaded.report("1.0.5", 500);
+        assert!(report.features.is_empty() && report.since.is_none());
+        assert_eq!(reloaded.data.last_submitted, Some(400));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_counts_are_written_on_flush() {
+        let path = std::env::temp_dir().join(format!(
+            "trackimo-metrics-flush-{}.json",
+            std::process::id()
+        ));
+        let mut store = MetricsStore::with_path(path.clone());
+        store.load().unwrap();
+        store.count(false, Feature::DraftRecommendations.key(), 100);
+        assert!(!path.exists(), "counting alone doesn't write");
+
+        store.flush().unwrap();
+        let mut reloaded = MetricsStore::with_path(path.clone());
+        reloaded.load().unwrap();
+        let report = reloaded.report("1.0.5", 200);
+        assert_eq!(report.features.get("draft_recommendations"), Some(&1));
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,252 @@
+use super::DraftRecommendationModel;
+use crate::lcu::draft::DraftState;
+use crate::metrics::{record_feature, Feature};
+use crate::startup::ensure_model_loaded;
+use crate::storage::{MatchStore, StoredMatch};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// ARAM on Howling Abyss.
+pub const ARAM_QUEUE_ID: i32 = 450;
+
+/// A bench champion has to score this much better than the current one to be
+/// worth the swap.
+const SWAP_MARGIN: f32 = 1.1;
+
+/// Win rate assumed for champions the player hasn't played in ARAM, and what
+/// a reroll is expected to land on.
+const UNPLAYED_WIN_RATE: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AramScoreSource {
+    Model, // The draft model, aggregated over roles
+    Stats, // The player's own ARAM win rates
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AramOption {
+    pub champion_id: i64,
+    pub score: f32,
+    pub on_bench: bool, // False for the champion the player holds
+    pub games: u32,     // The player's stored ARAM games on it
+    pub wins: u32,
+}
+
+/// Whether to swap with the bench or reroll. Payload of
+/// `get_aram_bench_recommendations`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AramAdvice {
+    pub options: Vec<AramOption>, // Current champion and the bench, best first
+    pub source: AramScoreSource,
+    pub swap_to: Option<i64>, // Best bench champion, when clearly better than the current one
+    pub reroll: bool,         // Nothing worth swapping for and the current champion is below par
+}
+
+/// The owner's ARAM games and wins per champion.
+pub fn aram_record<'a>(
+    matches: impl IntoIterator<Item = &'a StoredMatch>,
+) -> HashMap<i64, (u32, u32)> {
+    let mut record = HashMap::new();
+    for owner in matches
+        .into_iter()
+        .filter(|m| m.queue_id == ARAM_QUEUE_ID)
+        .filter_map(|m| m.owner())
+    {
+        let (games, wins) = record.entry(owner.champion_id).or_insert((0, 0));
+        *games += 1;
+        *wins += owner.win as u32;
+    }
+    record
+}
+
+/// Win rate pulled towards even, so one lucky game doesn't top the table.
+fn smoothed_win_rate(games: u32, wins: u32) -> f32 {
+    (wins as f32 + 2.0 * UNPLAYED_WIN_RATE) / (games as f32 + 2.0)
+}
+
+/// Rank the current champion and the bench by `score`. `par` is what a
+/// reroll is expected to be worth.
+pub fn advise(
+    current: Option<i64>,
+    bench: &[i64],
+    score: impl Fn(i64) -> f32,
+    par: f32,
+    can_reroll: bool,
+    record: &HashMap<i64, (u32, u32)>,
+    source: AramScoreSource,
+) -> AramAdvice {
+    let option = |champion_id: i64, on_bench: bool| {
+        let (games, wins) = record.get(&champion_id).copied().unwrap_or((0, 0));
+        AramOption {
+            champion_id,
+            score: score(champion_id),
+            on_bench,
+            games,
+            wins,
+        }
+    };
+    let current = current.map(|id| option(id, false));
+    let mut options: Vec<AramOption> = bench.iter().map(|&id| option(id, true)).collect();
+    options.extend(current.clone());
+    options.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let current_score = current.as_ref().map_or(0.0, |c| c.score);
+    let swap_to = options
+        .iter()
+        .find(|o| o.on_bench)
+        .filter(|o| o.score > current_score * SWAP_MARGIN)
+        .map(|o| o.champion_id);
+    let reroll = can_reroll && swap_to.is_none() && current_score < par;
+
+    AramAdvice {
+        options,
+        source,
+        swap_to,
+        reroll,
+    }
+}
+
+/// Model scores for every champion, with the player's own champion put back in
+/// the pool so it can be scored against the bench. Par is the average score.
+fn model_scores(
+    model: &DraftRecommendationModel,
+    state: &DraftState,
+) -> Result<(HashMap<i64, f32>, f32), String> {
+    let mut state = state.clone();
+    let local_cell = state.local_player_cell_id;
+    for team in &mut state.teams {
+        team.picks.retain(|p| p.cell_id != local_cell);
+        for cell in team
+            .cells
+            .iter_mut()
+            .filter(|c| Some(c.cell_id) == local_cell)
+        {
+            cell.champion_id = None;
+        }
+    }
+    let result = model
+        .get_recommendations(&state, usize::MAX, None)
+        .map_err(|e| e.to_string())?;
+    let scores: HashMap<i64, f32> = result
+        .recommendations
+        .iter()
+        .map(|r| (r.champion_id as i64, r.score))
+        .collect();
+    let par = scores.values().sum::<f32>() / scores.len().max(1) as f32;
+    Ok((scores, par))
+}
+
+/// Rank the bench and the current champion in an ARAM champion select, with
+/// the draft model when it's loaded and the player's ARAM win rates otherwise.
+#[tauri::command]
+pub async fn get_aram_bench_recommendations(
+    app: tauri::AppHandle,
+    draft_state: DraftState,
+    model: tauri::State<'_, Mutex<Option<Arc<DraftRecommendationModel>>>>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+) -> Result<AramAdvice, String> {
+    let aram = draft_state
+        .aram
+        .as_ref()
+        .ok_or("This champion select has no bench")?;
+    let bench: Vec<i64> = aram.bench.iter().map(|b| b.champion_id).collect();
+    let current = draft_state.local_cell().and_then(|c| c.champion_id);
+
+    let record = {
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        aram_record(store_guard.matches())
+    };
+
+    ensure_model_loaded(&app);
+    let model = model
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .clone();
+    record_feature(&app, Feature::AramAdvisor);
+    let scored = model.map(|m| model_scores(&m, &draft_state)).transpose();
+    let advice = match scored {
+        Ok(Some((scores, par))) => advise(
+            current,
+            &bench,
+            |id| scores.get(&id).copied().unwrap_or(0.0),
+            par,
+            aram.can_reroll,
+            &record,
+            AramScoreSource::Model,
+        ),
+        result => {
+            if let Err(e) = result {
+                eprintln!("ARAM model scoring failed, using win rates: {}", e);
+            }
+            advise(
+                current,
+                &bench,
+                |id| {
+                    let (games, wins) = record.get(&id).copied().unwrap_or((0, 0));
+                    smoothed_win_rate(games, wins)
+                },
+                UNPLAYED_WIN_RATE,
+                aram.can_reroll,
+                &record,
+                AramScoreSource::Stats,
+            )
+        }
+    };
+    Ok(advice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_advice(
+        current: i64,
+        bench: &[i64],
+        can_reroll: bool,
+        record: &HashMap<i64, (u32, u32)>,
+    ) -> AramAdvice {
+        let score = |id: i64| {
+            let (games, wins) = record.get(&id).copied().unwrap_or((0, 0));
+            smoothed_win_rate(games, wins)
+        };
+        advise(
+            Some(current),
+            bench,
+            score,
+            UNPLAYED_WIN_RATE,
+            can_reroll,
+            record,
+            AramScoreSource::Stats,
+        )
+    }
+
+    #[test]
+    fn test_swap_for_a_clearly_better_bench_champion() {
+        let record = HashMap::from([(51, (10, 8)), (86, (10, 5)), (201, (4, 1))]);
+        let advice = stats_advice(86, &[201, 51], true, &record);
+        assert_eq!(advice.swap_to, Some(51));
+        assert!(!advice.reroll);
+        let ranked: Vec<i64> = advice.options.iter().map(|o| o.champion_id).collect();
+        assert_eq!(ranked, vec![51, 86, 201]);
+        assert_eq!((advice.options[0].games, advice.options[0].wins), (10, 8));
+    }
+
+    #[test]
+    fn test_reroll_only_without_a_better_swap() {
+        let record = HashMap::from([(86, (6, 1)), (201, (2, 1))]);
+        // An even bench champion beats a losing one
+        assert_eq!(stats_advice(86, &[201], true, &record).swap_to, Some(201));
+
+        let record = HashMap::from([(86, (6, 2)), (201, (6, 2))]);
+        let advice = stats_advice(86, &[201], true, &record);
+        assert_eq!(advice.swap_to, None);
+        assert!(advice.reroll);
+        assert!(!stats_advice(86, &[201], false, &record).reroll);
+
+        // Close enough to even isn't worth the reroll
+        let record = HashMap::from([(86, (4, 2))]);
+        assert!(!stats_advice(86, &[], true, &record).reroll);
+    }
+}
@@ -0,0 +1,127 @@
+use super::comfort::hardness;
+use super::ChampionRecommendation;
+use crate::champions::client::Champion;
+use std::collections::HashMap;
+
+/// Safe picks per position for games on a role the player didn't queue for:
+/// simple kits that stay useful when played badly.
+const AUTOFILL_PICKS: [(&str, &[i64]); 5] = [
+    ("TOP", &[54, 86, 516, 14, 75, 82]), // Malphite, Garen, Ornn, Sion, Nasus, Mordekaiser
+    ("JUNGLE", &[19, 32, 11, 106, 254, 20]), // Warwick, Amumu, Master Yi, Volibear, Vi, Nunu
+    ("MIDDLE", &[1, 90, 99, 45, 3, 711]), // Annie, Malzahar, Lux, Veigar, Galio, Vex
+    ("BOTTOM", &[21, 22, 15, 51, 222, 18]), // Miss Fortune, Ashe, Sivir, Caitlyn, Jinx, Tristana
+    ("UTILITY", &[89, 16, 117, 111, 40, 37]), // Leona, Soraka, Lulu, Nautilus, Janna, Sona
+];
+
+/// Share of the blended score that comes from the model; the rest rewards
+/// the easiest picks on the list.
+const MODEL_WEIGHT: f32 = 0.6;
+
+/// The curated safe picks for `position`.
+pub fn safe_picks(position: &str) -> &'static [i64] {
+    AUTOFILL_PICKS
+        .iter()
+        .find(|(p, _)| p.eq_ignore_ascii_case(position))
+        .map_or(&[], |(_, picks)| *picks)
+}
+
+/// The position the player was assigned when it's neither of their lobby
+/// picks. Filling, or not having picked at all, never counts as autofill.
+pub fn autofilled_position(assigned: Option<&str>, preferences: &[String]) -> Option<String> {
+    let assigned = assigned?.to_uppercase();
+    if preferences.is_empty()
+        || preferences
+            .iter()
+            .any(|p| p == "FILL" || p.eq_ignore_ascii_case(&assigned))
+    {
+        return None;
+    }
+    Some(assigned)
+}
+
+/// Keep only the safe picks for `position`, ranked by the model's score
+/// blended with how easy each one is, and keep `top_k`.
+pub fn apply_autofill(
+    recommendations: &mut Vec<ChampionRecommendation>,
+    position: &str,
+    champions: &[Champion],
+    top_k: usize,
+) {
+    let picks = safe_picks(position);
+    let hardness_of: HashMap<i64, f32> = champions
+        .iter()
+        .map(|champion| (champion.key, hardness(champion)))
+        .collect();
+
+    recommendations.retain(|rec| rec.score > 0.0 && picks.contains(&(rec.champion_id as i64)));
+    let best = recommendations
+        .iter()
+        .map(|rec| rec.score)
+        .fold(0.0, f32::max);
+    for rec in recommendations.iter_mut() {
+        let ease = 1.0
+            - hardness_of
+                .get(&(rec.champion_id as i64))
+                .copied()
+                .unwrap_or(0.0);
+        rec.score = MODEL_WEIGHT * rec.score / best + (1.0 - MODEL_WEIGHT) * ease;
+    }
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    recommendations.truncate(top_k);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::client::ChampionInfo;
+
+    fn preferences(positions: &[&str]) -> Vec<String> {
+        positions.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_autofilled_position() {
+        let mid_top = preferences(&["MIDDLE", "TOP"]);
+        assert_eq!(
+            autofilled_position(Some("utility"), &mid_top).as_deref(),
+            Some("UTILITY")
+        );
+        assert!(autofilled_position(Some("top"), &mid_top).is_none());
+        assert!(autofilled_position(Some("utility"), &preferences(&["MIDDLE", "FILL"])).is_none());
+        assert!(autofilled_position(Some("utility"), &[]).is_none());
+        assert!(autofilled_position(None, &mid_top).is_none());
+    }
+
+    #[test]
+    fn test_apply_autofill_keeps_safe_picks() {
+        let champion = |key: i64, difficulty: u8| Champion {
+            id: key.to_string(),
+            key,
+            name: key.to_string(),
+            title: String::new(),
+            tags: Vec::new(),
+            info: Some(ChampionInfo {
+                attack: 5,
+                defense: 5,
+                magic: 5,
+                difficulty,
+            }),
+        };
+        let champions = vec![champion(89, 4), champion(117, 7), champion(412, 7)];
+        let rec = |champion_id: u32, score: f32| ChampionRecommendation {
+            champion_id,
+            score,
+            coach: None,
+        };
+        let mut recommendations = vec![
+            rec(412, 0.5), // Thresh, not on the list
+            rec(117, 0.4),
+            rec(89, 0.3),
+            rec(111, 0.0), // Unavailable
+        ];
+
+        apply_autofill(&mut recommendations, "utility", &champions, 5);
+        let ids: Vec<u32> = recommendations.iter().map(|r| r.champion_id).collect();
+        assert_eq!(ids, vec![89, 117], "the easier pick wins a close call");
+    }
+}
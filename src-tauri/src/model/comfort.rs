@@ -0,0 +1,191 @@
+use super::ChampionRecommendation;
+use crate::champions::cache::ChampionCache;
+use crate::champions::client::Champion;
+use crate::storage::MatchStore;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Champions whose Data Dragon difficulty undersells how hard they are to play well.
+const CURATED_DEMANDING: [i64; 16] = [
+    268, // Azir
+    92,  // Riven
+    64,  // Lee Sin
+    157, // Yasuo
+    777, // Yone
+    39,  // Irelia
+    246, // Qiyana
+    84,  // Akali
+    523, // Aphelios
+    429, // Kalista
+    142, // Zoe
+    55,  // Katarina
+    76,  // Nidalee
+    164, // Camille
+    126, // Jayce
+    41,  // Gangplank
+];
+
+/// Data Dragon difficulty at or below this isn't penalised.
+const EASY_DIFFICULTY: u8 = 4;
+
+/// Share of the score the hardest champions lose.
+const DIFFICULTY_PENALTY: f32 = 0.4;
+
+/// Score gain for a champion whose class is all the player plays.
+const SIMILARITY_BOOST: f32 = 0.3;
+
+/// Most recent games of the player's used to find what they already play.
+const POOL_GAMES: usize = 50;
+
+/// How demanding a champion is, from 0 (easy) to 1 (hardest).
+pub fn hardness(champion: &Champion) -> f32 {
+    if CURATED_DEMANDING.contains(&champion.key) {
+        return 1.0;
+    }
+    let difficulty = champion.info.as_ref().map_or(0, |info| info.difficulty);
+    difficulty.saturating_sub(EASY_DIFFICULTY) as f32 / (10 - EASY_DIFFICULTY) as f32
+}
+
+/// Share of the player's games per champion class (Data Dragon tag). A champion
+/// with several tags splits its games between them.
+fn tag_affinity(champions: &[Champion], played: &HashMap<i64, u32>) -> HashMap<String, f32> {
+    let total: u32 = played.values().sum();
+    let mut affinity = HashMap::new();
+    if total == 0 {
+        return affinity;
+    }
+
+    for champion in champions {
+        let Some(games) = played.get(&champion.key) else {
+            continue;
+        };
+        for tag in &champion.tags {
+            *affinity.entry(tag.clone()).or_insert(0.0) +=
+                *games as f32 / (champion.tags.len() as f32 * total as f32);
+        }
+    }
+    affinity
+}
+
+/// Score multiplier per champion: demanding champions are pushed down, ones
+/// sharing a class with the player's pool are pushed up.
+pub fn comfort_factors(champions: &[Champion], played: &HashMap<i64, u32>) -> HashMap<i64, f32> {
+    let affinity = tag_affinity(champions, played);
+    champions
+        .iter()
+        .map(|champion| {
+            let similarity = champion
+                .tags
+                .iter()
+                .filter_map(|tag| affinity.get(tag))
+                .fold(0.0, |best: f32, share| best.max(*share));
+            let factor = (1.0 - DIFFICULTY_PENALTY * hardness(champion))
+                * (1.0 + SIMILARITY_BOOST * similarity);
+            (champion.key, factor)
+        })
+        .collect()
+}
+
+/// Comfort factors for the local player, from their recently played champions.
+pub fn load_comfort_factors(app: &AppHandle) -> Result<HashMap<i64, f32>, String> {
+    let champions = {
+        let cache = app.state::<Mutex<ChampionCache>>();
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        cache_guard.get_all_champions()
+    };
+
+    let mut played = HashMap::new();
+    let store = app.state::<Mutex<MatchStore>>();
+    let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    for owner in store_guard
+        .matches()
+        .rev()
+        .take(POOL_GAMES)
+        .filter_map(|m| m.owner())
+    {
+        *played.entry(owner.champion_id).or_insert(0) += 1;
+    }
+
+    Ok(comfort_factors(&champions, &played))
+}
+
+/// Apply comfort factors to the scores, re-rank, and keep `top_k`.
+pub fn apply_comfort(
+    recommendations: &mut Vec<ChampionRecommendation>,
+    factors: &HashMap<i64, f32>,
+    top_k: usize,
+) {
+    for rec in recommendations.iter_mut() {
+        if let Some(factor) = factors.get(&(rec.champion_id as i64)) {
+            rec.score *= factor;
+        }
+    }
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    recommendations.truncate(top_k);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::champions::client::ChampionInfo;
+
+    fn champion(key: i64, tags: &[&str], difficulty: u8) -> Champion {
+        Champion {
+            id: key.to_string(),
+            key,
+            name: key.to_string(),
+            title: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            info: Some(ChampionInfo {
+                attack: 5,
+                defense: 5,
+                magic: 5,
+                difficulty,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_comfort_prefers_easy_familiar_champions() {
+        let champions = vec![
+            champion(268, &["Mage", "Marksman"], 8), // Azir, curated
+            champion(1, &["Mage"], 2),
+            champion(2, &["Mage"], 10),
+            champion(3, &["Tank"], 3),
+            champion(4, &["Mage", "Support"], 4),
+        ];
+        // The player only plays champion 4, half mage
+        let played = HashMap::from([(4, 10)]);
+        let factors = comfort_factors(&champions, &played);
+
+        assert_eq!(factors[&3], 1.0);
+        assert!(factors[&1] > factors[&3], "a familiar class is boosted");
+        assert!(factors[&2] < factors[&1], "difficulty 10 is penalised");
+        assert!(
+            (factors[&268] - factors[&2]).abs() < 1e-6,
+            "curated counts as hardest"
+        );
+
+        let mut recommendations = vec![
+            ChampionRecommendation {
+                champion_id: 268,
+                score: 0.5,
+                coach: None,
+            },
+            ChampionRecommendation {
+                champion_id: 1,
+                score: 0.4,
+                coach: None,
+            },
+            ChampionRecommendation {
+                champion_id: 3,
+                score: 0.3,
+                coach: None,
+            },
+        ];
+        apply_comfort(&mut recommendations, &factors, 2);
+        let ids: Vec<u32> = recommendations.iter().map(|r| r.champion_id).collect();
+        assert_eq!(ids, vec![1, 268]);
+    }
+}
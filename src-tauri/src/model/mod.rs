@@ -1,12 +1,33 @@
-use crate::lcu::draft::DraftState;
+pub mod aram;
+pub mod autofill;
+pub mod comfort;
+
+use crate::champions::assets::fetch_draft_assets;
+use crate::champions::cache::ChampionCache;
+use crate::lcu::{api::LcuApi, client::LcuClient, draft::DraftState};
+use crate::lcu::share::{current_coach_suggestion, CoachSuggestion, SuggestionAction};
+use crate::metrics::{record_error, record_feature, ErrorCategory, Feature};
+use crate::performance::{current_profile, ONNX_THREADS};
+use crate::scripts::{has_hook, rerank, Hook};
+use crate::settings::SettingsStore;
+use crate::startup::ensure_model_loaded;
+use autofill::{apply_autofill, autofilled_position};
+use comfort::{apply_comfort, load_comfort_factors};
 use ndarray::{Array, CowArray, IxDyn};
-use ort::{Environment, GraphOptimizationLevel, LoggingLevel, Session, SessionBuilder, Value};
+use ort::{Environment, ExecutionProvider, GraphOptimizationLevel, LoggingLevel, Session, SessionBuilder, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::Manager;
 
+/// Unranked Summoner's Rift queues: draft pick, blind pick and quickplay.
+const NORMAL_QUEUE_IDS: [i32; 3] = [400, 430, 490];
+
+/// Score multiplier for chest-eligible champions. Small enough to reorder close
+/// candidates without pushing weak picks to the top.
+const CHEST_BOOST: f32 = 1.1;
+
 #[derive(Debug, Deserialize)]
 struct Metadata {
     feature_dim: usize,
@@ -16,6 +37,8 @@ struct Metadata {
     model_config: ModelConfig,
     feature_config: FeatureConfig,
     roles: HashMap<String, u8>,
+    #[serde(default)]
+    champion_embeddings: HashMap<String, Vec<f32>>, // By champion id; only in newer exports
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,21 +70,82 @@ struct FeatureConfig {
 pub struct ChampionRecommendation {
     pub champion_id: u32,
     pub score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coach: Option<CoachSuggestion>, // Set on the entry sent by someone watching the shared draft
 }
 
 #[derive(Debug, Serialize)]
 pub struct Recommendations {
     pub recommendations: Vec<ChampionRecommendation>,
     pub win_probability: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autofilled_position: Option<String>, // Set when only safe picks for an autofilled role are recommended
+}
+
+/// How much ONNX Runtime rewrites the graph before running it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimizationLevel {
+    Disable,
+    Basic,
+    Extended,
+    #[default]
+    All,
+}
+
+impl OptimizationLevel {
+    fn ort_level(self) -> GraphOptimizationLevel {
+        match self {
+            OptimizationLevel::Disable => GraphOptimizationLevel::Disable,
+            OptimizationLevel::Basic => GraphOptimizationLevel::Level1,
+            OptimizationLevel::Extended => GraphOptimizationLevel::Level2,
+            OptimizationLevel::All => GraphOptimizationLevel::Level3,
+        }
+    }
+}
+
+/// How the inference session is built.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionOptions {
+    pub use_gpu: bool,
+    pub intra_threads: i16,
+    pub inter_threads: Option<i16>, // None leaves ONNX Runtime's default
+    pub optimization_level: OptimizationLevel,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        Self {
+            use_gpu: false,
+            intra_threads: ONNX_THREADS,
+            inter_threads: None,
+            optimization_level: OptimizationLevel::default(),
+        }
+    }
+}
+
+/// Model output for one feature vector, reused while the features don't change.
+struct CachedInference {
+    features: Vec<f32>,
+    available_mask: Vec<f32>,
+    probabilities: Vec<f32>,
+    win_probability: f32,
 }
 
 pub struct DraftRecommendationModel {
     session: std::sync::Mutex<Session>,
     metadata: Metadata,
+    inference_cache: std::sync::Mutex<HashMap<String, CachedInference>>, // Keyed by role
 }
 
 impl DraftRecommendationModel {
-    pub fn new(model_path: &str, metadata_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    /// With `use_gpu`, CUDA or DirectML is tried first; ONNX Runtime falls
+    /// back to the CPU when neither is available.
+    pub fn new(
+        model_path: &str,
+        metadata_path: &str,
+        options: &SessionOptions,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         // Create ONNX environment
         let environment = Environment::builder()
             .with_name("draft_recommender")
@@ -70,10 +154,20 @@ impl DraftRecommendationModel {
             .into_arc();
 
         // Load ONNX model
-        let session = SessionBuilder::new(&environment)?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_intra_threads(4)?
-            .with_model_from_file(model_path)?;
+        let mut builder = SessionBuilder::new(&environment)?;
+        if options.use_gpu {
+            builder = builder.with_execution_providers([
+                ExecutionProvider::cuda(),
+                ExecutionProvider::directml(),
+            ])?;
+        }
+        builder = builder
+            .with_optimization_level(options.optimization_level.ort_level())?
+            .with_intra_threads(options.intra_threads)?;
+        if let Some(inter_threads) = options.inter_threads {
+            builder = builder.with_inter_threads(inter_threads)?;
+        }
+        let session = builder.with_model_from_file(model_path)?;
 
         // Load metadata
         let metadata_json = std::fs::read_to_string(metadata_path)?;
@@ -81,10 +175,19 @@ impl DraftRecommendationModel {
 
         Ok(Self { 
             session: std::sync::Mutex::new(session), 
-            metadata 
+            metadata,
+            inference_cache: std::sync::Mutex::new(HashMap::new()),
         })
     }
 
+    /// The champion's learned embedding, if the model export includes embeddings.
+    pub fn champion_embedding(&self, champion_id: i64) -> Option<&[f32]> {
+        self.metadata
+            .champion_embeddings
+            .get(&champion_id.to_string())
+            .map(|e| e.as_slice())
+    }
+
     pub fn get_recommendations(
         &self,
         draft_state: &DraftState,
@@ -124,12 +227,14 @@ impl DraftRecommendationModel {
             .map(|(champion_id, score)| ChampionRecommendation {
                 champion_id,
                 score,
+                coach: None,
             })
             .collect();
         
         Ok(Recommendations {
             recommendations,
             win_probability: total_win_prob,
+            autofilled_position: None,
         })
     }
     
@@ -145,17 +250,98 @@ impl DraftRecommendationModel {
         // Get available champions mask
         let available_mask = self.get_available_champions_mask(draft_state);
 
+        let role_key = player_role.map(|r| r.to_uppercase()).unwrap_or_default();
+        let (probabilities, win_prob) = self.run_inference_cached(&role_key, features, available_mask)?;
+
+        // Get top-k recommendations
+        let mut indexed_probs: Vec<(usize, f32)> =
+            probabilities.iter().enumerate().map(|(i, &p)| (i, p)).collect();
+        indexed_probs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        let recommendations: Vec<ChampionRecommendation> = indexed_probs
+            .iter()
+            .take(top_k)
+            .filter_map(|(idx, prob)| {
+                let champion_id_str = idx.to_string();
+                let champion_id = self.metadata.champion_mapping.idx_to_champion
+                    .get(&champion_id_str)
+                    .copied()?;
+                Some(ChampionRecommendation {
+                    champion_id,
+                    score: *prob,
+                    coach: None,
+                })
+            })
+            .collect();
+
+        // Determine player's team (not the team currently picking!)
+        let player_team = self.get_player_team(draft_state);
+        let win_prob_adjusted = if player_team == 200 {
+            1.0 - win_prob // Red team - invert blue team prediction
+        } else {
+            win_prob
+        };
+
+        Ok(Recommendations {
+            recommendations,
+            win_probability: win_prob_adjusted,
+            autofilled_position: None,
+        })
+    }
+
+    /// Run the model, reusing the previous output for this role when the features are
+    /// identical. Draft updates the model can't see (timer ticks, hovers of champions
+    /// outside the model's mapping) then cost a feature extraction, not an inference.
+    fn run_inference_cached(
+        &self,
+        role_key: &str,
+        features: Vec<f32>,
+        available_mask: Vec<f32>,
+    ) -> Result<(Vec<f32>, f32), Box<dyn std::error::Error>> {
+        {
+            let cache = self.inference_cache.lock()
+                .map_err(|e| format!("Failed to lock inference cache: {:?}", e))?;
+            if let Some(cached) = cache.get(role_key) {
+                if cached.features == features && cached.available_mask == available_mask {
+                    return Ok((cached.probabilities.clone(), cached.win_probability));
+                }
+            }
+        }
+
+        let (probabilities, win_probability) = self.run_inference(&features, &available_mask)?;
+
+        let mut cache = self.inference_cache.lock()
+            .map_err(|e| format!("Failed to lock inference cache: {:?}", e))?;
+        cache.insert(
+            role_key.to_string(),
+            CachedInference {
+                features,
+                available_mask,
+                probabilities: probabilities.clone(),
+                win_probability,
+            },
+        );
+
+        Ok((probabilities, win_probability))
+    }
+
+    /// Softmaxed champion probabilities and the raw (blue side) win probability.
+    fn run_inference(
+        &self,
+        features: &[f32],
+        available_mask: &[f32],
+    ) -> Result<(Vec<f32>, f32), Box<dyn std::error::Error>> {
         // Prepare inputs as ndarray arrays
         // features: [1, 1, feature_dim]
         let features_array = Array::from_shape_vec(
             IxDyn(&[1, 1, self.metadata.feature_dim]),
-            features,
+            features.to_vec(),
         )?;
 
         // available_champions: [1, num_champions]
         let available_array = Array::from_shape_vec(
             IxDyn(&[1, self.metadata.num_champions]),
-            available_mask,
+            available_mask.to_vec(),
         )?;
 
         // Run inference
@@ -193,42 +379,11 @@ impl DraftRecommendationModel {
         let sum_exp: f32 = exp_logits.iter().sum();
         let probabilities: Vec<f32> = exp_logits.iter().map(|&x| x / sum_exp).collect();
 
-        // Get top-k recommendations
-        let mut indexed_probs: Vec<(usize, f32)> =
-            probabilities.iter().enumerate().map(|(i, &p)| (i, p)).collect();
-        indexed_probs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-        let recommendations: Vec<ChampionRecommendation> = indexed_probs
-            .iter()
-            .take(top_k)
-            .filter_map(|(idx, prob)| {
-                let champion_id_str = idx.to_string();
-                let champion_id = self.metadata.champion_mapping.idx_to_champion
-                    .get(&champion_id_str)
-                    .copied()?;
-                Some(ChampionRecommendation {
-                    champion_id,
-                    score: *prob,
-                })
-            })
-            .collect();
-
         // Get win probability
         let win_prob_slice = win_probability.as_slice().ok_or("Failed to get win_probability slice")?;
         let win_prob = win_prob_slice[0];
-        
-        // Determine player's team (not the team currently picking!)
-        let player_team = self.get_player_team(draft_state);
-        let win_prob_adjusted = if player_team == 200 {
-            1.0 - win_prob // Red team - invert blue team prediction
-        } else {
-            win_prob
-        };
 
-        Ok(Recommendations {
-            recommendations,
-            win_probability: win_prob_adjusted,
-        })
+        Ok((probabilities, win_prob))
     }
 
     fn extract_features(&self, draft_state: &DraftState, player_role: Option<&str>) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
@@ -570,6 +725,9 @@ impl DraftRecommendationModel {
                     .chain(t.bans.iter().map(|b| b.champion_id as u32))
             })
             .collect();
+
+        // Champions already played in a fearless series
+        unavailable.extend(draft_state.series_excluded.iter().map(|&id| id as u32));
         
         // Also exclude pre-selected champions (hovered but not locked)
         // NOTE: This includes ALL prelocks (including the player's own)
@@ -653,13 +811,106 @@ impl DraftRecommendationModel {
     }
 }
 
+/// Multiply the scores of `boosted` champions by `factor`, re-rank, and keep `top_k`.
+pub fn boost_recommendations(
+    recommendations: &mut Vec<ChampionRecommendation>,
+    boosted: &HashSet<i64>,
+    factor: f32,
+    top_k: usize,
+) {
+    for rec in recommendations.iter_mut() {
+        if boosted.contains(&(rec.champion_id as i64)) {
+            rec.score *= factor;
+        }
+    }
+    recommendations.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+    recommendations.truncate(top_k);
+}
+
+/// List the coach's suggestion first, as its own entry. A pick the model also
+/// recommends is listed once, keeping the model's score.
+pub fn add_coach_entry(recommendations: &mut Vec<ChampionRecommendation>, suggestion: CoachSuggestion) {
+    let champion_id = suggestion.champion_id as u32;
+    let mut score = 0.0;
+    if suggestion.action == SuggestionAction::Pick {
+        if let Some(idx) = recommendations.iter().position(|r| r.champion_id == champion_id) {
+            score = recommendations.remove(idx).score;
+        }
+    }
+    recommendations.insert(0, ChampionRecommendation {
+        champion_id,
+        score,
+        coach: Some(suggestion),
+    });
+}
+
 #[tauri::command]
 pub async fn get_draft_recommendations(
+    app: tauri::AppHandle,
     draft_state: DraftState,
     top_k: Option<usize>,
     player_role: Option<String>,
     model: tauri::State<'_, std::sync::Mutex<Option<Arc<DraftRecommendationModel>>>>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    settings: tauri::State<'_, std::sync::Mutex<SettingsStore>>,
 ) -> Result<Recommendations, String> {
+    let (boost_chests, comfort_mode, autofill_mode) = settings
+        .lock()
+        .map(|s| (s.get().boost_chest_eligible, s.get().comfort_mode, s.get().autofill_mode))
+        .unwrap_or((false, false, false));
+
+    let autofilled = if autofill_mode {
+        let assigned = draft_state.local_player_cell_id.and_then(|id| {
+            draft_state
+                .teams
+                .iter()
+                .flat_map(|t| t.cells.iter())
+                .find(|c| c.cell_id == id)
+                .and_then(|c| c.assigned_position.clone())
+        });
+        let preferences = client
+            .lock()
+            .await
+            .get_position_preferences()
+            .await
+            .unwrap_or_default();
+        autofilled_position(assigned.as_deref(), &preferences)
+    } else {
+        None
+    };
+    let autofill_champions = match &autofilled {
+        Some(_) => {
+            let cache = app.state::<std::sync::Mutex<ChampionCache>>();
+            let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+            cache_guard.get_all_champions()
+        }
+        None => Vec::new(),
+    };
+
+    // Only in normals: ranked recommendations stay purely about winning
+    let chest_eligible: Option<HashSet<i64>> = if boost_chests {
+        let mut client_guard = client.lock().await;
+        let queue_id = client_guard.get_current_queue_id().await.ok().flatten();
+        if queue_id.is_some_and(|q| NORMAL_QUEUE_IDS.contains(&q)) {
+            client_guard
+                .get_chest_eligible_champion_ids()
+                .await
+                .ok()
+                .map(|ids| ids.into_iter().collect())
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let comfort = if comfort_mode {
+        load_comfort_factors(&app).ok()
+    } else {
+        None
+    };
+
+    ensure_model_loaded(&app);
     let model_guard = model.lock()
         .map_err(|e| format!("Failed to lock model state: {:?}", e))?;
     
@@ -667,9 +918,79 @@ pub async fn get_draft_recommendations(
         .ok_or_else(|| "Draft recommendation model is not available. Model files may be missing.".to_string())?;
     
     let top_k = top_k.unwrap_or(5);
-    model
-        .get_recommendations(&draft_state, top_k, player_role.as_deref())
-        .map_err(|e| e.to_string())
+    // Look a little deeper when re-ranking so a boosted champion just outside the top-k can move in
+    let script_rerank = has_hook(&app, Hook::Rerank);
+    // Autofill keeps only a handful of safe picks, so it needs every champion's score
+    let depth = if autofilled.is_some() {
+        usize::MAX
+    } else if chest_eligible.is_some() || comfort.is_some() || script_rerank {
+        top_k * 2
+    } else {
+        top_k
+    };
+    record_feature(&app, Feature::DraftRecommendations);
+    let mut result = model
+        .get_recommendations(
+            &draft_state,
+            depth,
+            player_role.as_deref().or(autofilled.as_deref()),
+        )
+        .map_err(|e| {
+            record_error(&app, ErrorCategory::Recommendation);
+            e.to_string()
+        })?;
+    if let Some(position) = &autofilled {
+        apply_autofill(&mut result.recommendations, position, &autofill_champions, depth);
+    }
+    if let Some(eligible) = chest_eligible {
+        boost_recommendations(&mut result.recommendations, &eligible, CHEST_BOOST, depth);
+    }
+    if let Some(factors) = comfort {
+        apply_comfort(&mut result.recommendations, &factors, depth);
+    }
+    if script_rerank {
+        rerank(&app, &mut result.recommendations, &draft_state);
+    }
+    result.recommendations.truncate(top_k);
+    if let Some(suggestion) = current_coach_suggestion(&app) {
+        add_coach_entry(&mut result.recommendations, suggestion);
+    }
+    result.autofilled_position = autofilled;
+
+    // Recommended champions get their art fetched like the ones already in the draft
+    if current_profile(&app).prefetch_assets {
+        let champion_ids = result
+            .recommendations
+            .iter()
+            .map(|rec| rec.champion_id as i64)
+            .collect();
+        tokio::spawn(async move {
+            if let Err(e) = fetch_draft_assets(app, champion_ids).await {
+                eprintln!("Fetching recommendation art failed: {}", e);
+            }
+        });
+    }
+    Ok(result)
+}
+
+/// Session options from the GPU flag and the ONNX settings. Intra-op threads
+/// not set explicitly follow the performance profile.
+pub fn session_options(app_handle: &tauri::AppHandle) -> SessionOptions {
+    let (intra_threads, inter_threads, optimization_level) = app_handle
+        .try_state::<std::sync::Mutex<SettingsStore>>()
+        .and_then(|s| {
+            s.lock().ok().map(|g| {
+                let s = g.get();
+                (s.onnx_intra_threads, s.onnx_inter_threads, s.onnx_optimization_level)
+            })
+        })
+        .unwrap_or_default();
+    SessionOptions {
+        use_gpu: crate::flags::is_enabled(app_handle, crate::flags::Flag::GpuInference),
+        intra_threads: intra_threads.unwrap_or_else(|| current_profile(app_handle).onnx_threads),
+        inter_threads,
+        optimization_level: optimization_level.unwrap_or_default(),
+    }
 }
 
 pub fn initialize_model(app_handle: &tauri::AppHandle) -> Result<Arc<DraftRecommendationModel>, Box<dyn std::error::Error>> {
@@ -734,9 +1055,19 @@ pub fn initialize_model(app_handle: &tauri::AppHandle) -> Result<Arc<DraftRecomm
         ).into());
     };
 
+    // Low-spec machines use the quantized export when it ships alongside the model
+    let profile = current_profile(app_handle);
+    let quantized = model_path.with_file_name("model_quantized.onnx");
+    let model_path = if profile.quantized_model && quantized.exists() {
+        quantized
+    } else {
+        model_path
+    };
+
     let model = DraftRecommendationModel::new(
         model_path.to_str().ok_or("Invalid model path")?,
         metadata_path.to_str().ok_or("Invalid metadata path")?,
+        &session_options(app_handle),
     )?;
 
     Ok(Arc::new(model))
@@ -0,0 +1,234 @@
+pub mod websocket;
+
+use crate::metrics::{record_feature, Feature};
+use crate::settings::SettingsStore;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::digest::{digest, SHA256};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::time::{timeout, Duration};
+use websocket::WebSocket;
+
+/// Where obs-websocket listens out of the box.
+pub const DEFAULT_URL: &str = "ws://127.0.0.1:4455";
+
+/// obs-websocket 5 protocol: subprotocol, RPC version and message opcodes.
+const PROTOCOL: &str = "obswebsocket.json";
+const RPC_VERSION: u32 = 1;
+const OP_HELLO: u64 = 0;
+const OP_IDENTIFY: u64 = 1;
+const OP_IDENTIFIED: u64 = 2;
+const OP_REQUEST: u64 = 6;
+const OP_REQUEST_RESPONSE: u64 = 7;
+
+/// Longest a whole scene switch may take, connection included.
+const SWITCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Held for the length of a scene switch, so quick phase changes land in order.
+#[derive(Default)]
+pub struct SceneSwitcher;
+
+impl SceneSwitcher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// The `authentication` value for Identify, from the password and the
+/// challenge and salt sent in Hello.
+pub fn auth_string(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(digest(&SHA256, format!("{}{}", password, salt).as_bytes()));
+    BASE64.encode(digest(
+        &SHA256,
+        format!("{}{}", secret, challenge).as_bytes(),
+    ))
+}
+
+/// The scene configured for a gameflow phase. Blank names count as unset.
+pub fn scene_for_phase<'a>(
+    scenes: &'a BTreeMap<String, String>,
+    phase: Option<&str>,
+) -> Option<&'a str> {
+    let scene = scenes.get(phase?)?.trim();
+    (!scene.is_empty()).then_some(scene)
+}
+
+pub struct ObsClient {
+    socket: WebSocket,
+    next_request: u64,
+}
+
+impl ObsClient {
+    /// Connect and identify, authenticating when OBS asks for it.
+    pub async fn connect(url: &str, password: Option<&str>) -> Result<Self, String> {
+        let mut socket = WebSocket::connect(url, PROTOCOL).await?;
+
+        let hello = read_message(&mut socket).await?;
+        if hello["op"].as_u64() != Some(OP_HELLO) {
+            return Err("OBS did not say hello".to_string());
+        }
+        let mut identify = json!({ "rpcVersion": RPC_VERSION, "eventSubscriptions": 0 });
+        if let Some(auth) = hello["d"].get("authentication") {
+            let password = password
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "OBS requires a password".to_string())?;
+            identify["authentication"] = json!(auth_string(
+                password,
+                auth["salt"].as_str().unwrap_or_default(),
+                auth["challenge"].as_str().unwrap_or_default()
+            ));
+        }
+        let message = json!({ "op": OP_IDENTIFY, "d": identify });
+        socket.send_text(&message.to_string()).await?;
+
+        // A wrong password closes the connection instead of answering
+        let identified = read_message(&mut socket).await?;
+        if identified["op"].as_u64() != Some(OP_IDENTIFIED) {
+            return Err("OBS did not accept the connection".to_string());
+        }
+
+        Ok(Self {
+            socket,
+            next_request: 0,
+        })
+    }
+
+    async fn request(&mut self, request_type: &str, data: Value) -> Result<Value, String> {
+        self.next_request += 1;
+        let request_id = self.next_request.to_string();
+        let message = json!({
+            "op": OP_REQUEST,
+            "d": {
+                "requestType": request_type,
+                "requestId": request_id,
+                "requestData": data,
+            }
+        });
+        self.socket.send_text(&message.to_string()).await?;
+
+        loop {
+            let response = read_message(&mut self.socket).await?;
+            let d = &response["d"];
+            if response["op"].as_u64() != Some(OP_REQUEST_RESPONSE)
+                || d["requestId"].as_str() != Some(request_id.as_str())
+            {
+                continue;
+            }
+            let status = &d["requestStatus"];
+            if status["result"].as_bool() != Some(true) {
+                return Err(format!(
+                    "{} failed: {}",
+                    request_type,
+                    status["comment"].as_str().unwrap_or("no reason given")
+                ));
+            }
+            return Ok(d["responseData"].clone());
+        }
+    }
+
+    pub async fn set_scene(&mut self, scene: &str) -> Result<(), String> {
+        self.request("SetCurrentProgramScene", json!({ "sceneName": scene }))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn scene_names(&mut self) -> Result<Vec<String>, String> {
+        let data = self.request("GetSceneList", json!({})).await?;
+        Ok(data["scenes"]
+            .as_array()
+            .map(|scenes| {
+                scenes
+                    .iter()
+                    .filter_map(|s| s["sceneName"].as_str().map(|n| n.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn close(self) {
+        self.socket.close().await;
+    }
+}
+
+async fn read_message(socket: &mut WebSocket) -> Result<Value, String> {
+    let text = socket.read_text().await?;
+    serde_json::from_str(&text).map_err(|e| format!("Failed to parse OBS message: {}", e))
+}
+
+/// Switch OBS to the scene mapped to `phase`, when OBS switching is on.
+pub async fn switch_scene_for_phase(app: &AppHandle, phase: Option<&str>) -> Result<(), String> {
+    let Some(settings) = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().clone()))
+    else {
+        return Ok(());
+    };
+    if !settings.obs_enabled {
+        return Ok(());
+    }
+    let Some(scene) = scene_for_phase(&settings.obs_scenes, phase) else {
+        return Ok(());
+    };
+    let url = settings.obs_url.as_deref().unwrap_or(DEFAULT_URL);
+
+    let switcher = app.state::<tokio::sync::Mutex<SceneSwitcher>>();
+    let _switching = switcher.lock().await;
+    timeout(SWITCH_TIMEOUT, async {
+        let mut client = ObsClient::connect(url, settings.obs_password.as_deref()).await?;
+        let result = client.set_scene(scene).await;
+        client.close().await;
+        result
+    })
+    .await
+    .map_err(|_| "OBS did not answer in time".to_string())??;
+    record_feature(app, Feature::ObsSceneSwitch);
+    Ok(())
+}
+
+/// Scene names in OBS, which also checks the connection settings.
+#[tauri::command]
+pub async fn get_obs_scenes(
+    url: Option<String>,
+    password: Option<String>,
+) -> Result<Vec<String>, String> {
+    let url = url.filter(|u| !u.trim().is_empty());
+    timeout(SWITCH_TIMEOUT, async {
+        let mut client =
+            ObsClient::connect(url.as_deref().unwrap_or(DEFAULT_URL), password.as_deref()).await?;
+        let scenes = client.scene_names().await;
+        client.close().await;
+        scenes
+    })
+    .await
+    .map_err(|_| "OBS did not answer in time".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_string_and_scene_mapping() {
+        // Example from the obs-websocket protocol documentation
+        assert_eq!(
+            auth_string(
+                "supersecretpassword",
+                "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=",
+                "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY="
+            ),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+
+        let scenes = BTreeMap::from([
+            ("ChampSelect".to_string(), "Draft".to_string()),
+            ("InProgress".to_string(), " ".to_string()),
+        ]);
+        assert_eq!(scene_for_phase(&scenes, Some("ChampSelect")), Some("Draft"));
+        assert_eq!(scene_for_phase(&scenes, Some("InProgress")), None);
+        assert_eq!(scene_for_phase(&scenes, Some("Lobby")), None);
+        assert_eq!(scene_for_phase(&scenes, None), None);
+    }
+}
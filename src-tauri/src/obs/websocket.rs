@@ -0,0 +1,231 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ring::digest::{digest, SHA1_FOR_LEGACY_USE_ONLY};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Appended to the client key to build the accept key (RFC 6455, 1.3).
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest message accepted from the server. OBS replies are a few KB.
+const MAX_MESSAGE: usize = 1 << 20;
+
+/// Largest handshake response accepted.
+const MAX_HANDSHAKE: usize = 8 * 1024;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+        let value = RandomState::new().hash_one(i).to_le_bytes();
+        chunk.copy_from_slice(&value[..chunk.len()]);
+    }
+    bytes
+}
+
+/// What the server must answer in `Sec-WebSocket-Accept` for `key`.
+pub fn accept_key(key: &str) -> String {
+    let hash = digest(
+        &SHA1_FOR_LEGACY_USE_ONLY,
+        format!("{}{}", key, ACCEPT_GUID).as_bytes(),
+    );
+    BASE64.encode(hash.as_ref())
+}
+
+/// A single unfragmented client frame. Clients always mask their payload.
+pub fn encode_frame(opcode: u8, payload: &[u8], mask: [u8; 4]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len if len < 126 => frame.push(0x80 | len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+/// Just enough of a WebSocket client to exchange text messages with a local
+/// server over plain `ws://`.
+pub struct WebSocket {
+    stream: TcpStream,
+}
+
+impl WebSocket {
+    /// Connect and upgrade, asking for `protocol` as the subprotocol.
+    pub async fn connect(url: &str, protocol: &str) -> Result<Self, String> {
+        let rest = url
+            .trim()
+            .strip_prefix("ws://")
+            .ok_or_else(|| format!("Only ws:// URLs are supported, got {}", url))?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        if host.is_empty() {
+            return Err(format!("Invalid WebSocket URL {}", url));
+        }
+
+        let mut stream = TcpStream::connect(host)
+            .await
+            .map_err(|e| format!("Failed to connect to {}: {}", host, e))?;
+
+        let key = BASE64.encode(random_bytes::<16>());
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Protocol: {}\r\n\r\n",
+            path, host, key, protocol
+        );
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to send handshake: {}", e))?;
+
+        // Read byte by byte so nothing after the headers is consumed
+        let mut response = Vec::new();
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() > MAX_HANDSHAKE {
+                return Err("Handshake response too large".to_string());
+            }
+            let byte = stream
+                .read_u8()
+                .await
+                .map_err(|e| format!("Failed to read handshake: {}", e))?;
+            response.push(byte);
+        }
+        let response = String::from_utf8_lossy(&response);
+        let mut lines = response.lines();
+        let status = lines.next().unwrap_or_default();
+        if status.split_whitespace().nth(1) != Some("101") {
+            return Err(format!("WebSocket upgrade refused: {}", status));
+        }
+        let expected = accept_key(&key);
+        let accepted = lines.any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("sec-websocket-accept") && value.trim() == expected
+            })
+        });
+        if !accepted {
+            return Err("WebSocket upgrade had a wrong accept key".to_string());
+        }
+
+        Ok(Self { stream })
+    }
+
+    async fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<(), String> {
+        let frame = encode_frame(opcode, payload, random_bytes::<4>());
+        self.stream
+            .write_all(&frame)
+            .await
+            .map_err(|e| format!("Failed to send message: {}", e))
+    }
+
+    pub async fn send_text(&mut self, text: &str) -> Result<(), String> {
+        self.write_frame(OP_TEXT, text.as_bytes()).await
+    }
+
+    /// The next text message. Pings are answered along the way.
+    pub async fn read_text(&mut self) -> Result<String, String> {
+        let read_err = |e: std::io::Error| format!("Failed to read message: {}", e);
+        let mut message = Vec::new();
+        loop {
+            let mut header = [0u8; 2];
+            self.stream
+                .read_exact(&mut header)
+                .await
+                .map_err(read_err)?;
+            let fin = header[0] & 0x80 != 0;
+            let opcode = header[0] & 0x0F;
+            let len = match header[1] & 0x7F {
+                126 => self.stream.read_u16().await.map_err(read_err)? as usize,
+                127 => {
+                    let len = self.stream.read_u64().await.map_err(read_err)?;
+                    usize::try_from(len).unwrap_or(usize::MAX)
+                }
+                len => len as usize,
+            };
+            // `message` never exceeds MAX_MESSAGE, so this can't underflow
+            if len > MAX_MESSAGE - message.len() {
+                return Err("Message too large".to_string());
+            }
+            let mask = if header[1] & 0x80 != 0 {
+                let mut mask = [0u8; 4];
+                self.stream.read_exact(&mut mask).await.map_err(read_err)?;
+                Some(mask)
+            } else {
+                None
+            };
+            let mut payload = vec![0u8; len];
+            self.stream
+                .read_exact(&mut payload)
+                .await
+                .map_err(read_err)?;
+            if let Some(mask) = mask {
+                payload
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, b)| *b ^= mask[i % 4]);
+            }
+
+            match opcode {
+                OP_TEXT | OP_CONTINUATION => {
+                    message.extend_from_slice(&payload);
+                    if fin {
+                        return String::from_utf8(message)
+                            .map_err(|_| "Message is not valid UTF-8".to_string());
+                    }
+                }
+                OP_PING => self.write_frame(OP_PONG, &payload).await?,
+                OP_PONG => {}
+                OP_CLOSE => {
+                    let reason = payload.get(2..).map(String::from_utf8_lossy);
+                    return Err(format!(
+                        "Connection closed: {}",
+                        reason.as_deref().unwrap_or("no reason")
+                    ));
+                }
+                other => return Err(format!("Unsupported frame opcode {}", other)),
+            }
+        }
+    }
+
+    pub async fn close(mut self) {
+        let _ = self.write_frame(OP_CLOSE, &1000u16.to_be_bytes()).await;
+        let _ = self.stream.shutdown().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_key_and_framing() {
+        // Example from RFC 6455
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        let frame = encode_frame(OP_TEXT, b"Hello", [0x37, 0xfa, 0x21, 0x3d]);
+        assert_eq!(
+            frame,
+            vec![0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58]
+        );
+        let long = encode_frame(OP_TEXT, &[0u8; 300], [0; 4]);
+        assert_eq!(&long[..4], &[0x81, 0x80 | 126, 0x01, 0x2c]);
+        assert_eq!(long.len(), 4 + 4 + 300);
+    }
+}
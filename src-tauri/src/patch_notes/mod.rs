@@ -0,0 +1,273 @@
+use crate::champions::cache::{same_patch, ChampionCache};
+use crate::champions::client::Champion;
+use crate::settings::SettingsStore;
+use crate::storage::usage::cache_root;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Buff,
+    Nerf,
+    Adjusted, // Mixed or neutral changes, e.g. reworks and bug fixes
+}
+
+impl ChangeKind {
+    fn parse(value: &str) -> Self {
+        match value.to_lowercase().as_str() {
+            "buff" | "buffed" => ChangeKind::Buff,
+            "nerf" | "nerfed" => ChangeKind::Nerf,
+            _ => ChangeKind::Adjusted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChampionChange {
+    pub champion_id: i64,
+    pub kind: ChangeKind,
+    pub summary: Option<String>,
+}
+
+/// Champion changes of one patch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchNotes {
+    pub patch: String, // As given by the source, e.g. "14.3"
+    pub changes: Vec<ChampionChange>,
+}
+
+/// Parse a patch summary of the form
+/// `{ "patch": "14.3", "champions": [{ "championId": 266, "change": "buff", "summary": "..." }] }`.
+///
+/// Champions can also be named by Data Dragon id or display name (`"champion": "Aatrox"`);
+/// ones that don't match any known champion are dropped.
+pub fn parse_patch_notes(
+    json_value: &serde_json::Value,
+    champions: &[Champion],
+) -> Result<PatchNotes, String> {
+    let patch = json_value["patch"]
+        .as_str()
+        .ok_or_else(|| "Patch notes have no patch version".to_string())?;
+    let entries = json_value["champions"]
+        .as_array()
+        .ok_or_else(|| "Patch notes have no champion list".to_string())?;
+
+    let champion_id = |entry: &serde_json::Value| {
+        entry["championId"].as_i64().or_else(|| {
+            let name = entry["champion"].as_str()?;
+            champions
+                .iter()
+                .find(|c| c.id.eq_ignore_ascii_case(name) || c.name.eq_ignore_ascii_case(name))
+                .map(|c| c.key)
+        })
+    };
+
+    let changes = entries
+        .iter()
+        .filter_map(|entry| {
+            Some(ChampionChange {
+                champion_id: champion_id(entry)?,
+                kind: ChangeKind::parse(entry["change"].as_str().unwrap_or("")),
+                summary: entry["summary"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+            })
+        })
+        .collect();
+
+    Ok(PatchNotes {
+        patch: patch.to_string(),
+        changes,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedPatchNotes {
+    version: String, // Champion data version the notes were fetched for
+    notes: PatchNotes,
+}
+
+/// The last fetched patch notes, kept on disk so they're fetched once per patch.
+pub struct PatchNotesStore {
+    cached: Option<CachedPatchNotes>,
+    store_path: PathBuf,
+}
+
+impl PatchNotesStore {
+    pub fn new() -> Result<Self, String> {
+        let cache_dir = cache_root()?;
+
+        fs::create_dir_all(&cache_dir)
+            .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+
+        Ok(Self::with_path(cache_dir.join("patch_notes.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            cached: None,
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read patch notes: {}", e))?;
+        self.cached = Some(
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse patch notes: {}", e))?,
+        );
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.cached)
+            .map_err(|e| format!("Failed to serialize patch notes: {}", e))?;
+
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write patch notes: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Notes fetched while `version` was current, if any.
+    pub fn get(&self, version: &str) -> Option<&PatchNotes> {
+        self.cached
+            .as_ref()
+            .filter(|c| same_patch(&c.version, version))
+            .map(|c| &c.notes)
+    }
+
+    pub fn set(&mut self, version: &str, notes: PatchNotes) -> Result<(), String> {
+        self.cached = Some(CachedPatchNotes {
+            version: version.to_string(),
+            notes,
+        });
+        self.save()
+    }
+}
+
+async fn fetch_patch_notes(url: &str, champions: &[Champion]) -> Result<PatchNotes, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let json_value: serde_json::Value = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse patch notes: {}", e))?;
+
+    parse_patch_notes(&json_value, champions)
+}
+
+/// Patch notes for the current patch, fetched from the configured source the
+/// first time they're needed on a patch.
+async fn current_patch_notes(app: &AppHandle) -> Result<PatchNotes, String> {
+    let (version, champions) = {
+        let cache = app.state::<Mutex<ChampionCache>>();
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        (cache_guard.get_version(), cache_guard.get_all_champions())
+    };
+    let version = version.ok_or_else(|| "Champion data has not been downloaded yet".to_string())?;
+
+    let store = app.state::<Mutex<PatchNotesStore>>();
+    {
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(notes) = store_guard.get(&version) {
+            return Ok(notes.clone());
+        }
+    }
+
+    let url = {
+        let settings = app.state::<Mutex<SettingsStore>>();
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        settings_guard.get().patch_notes_url.clone()
+    }
+    .ok_or_else(|| "No patch notes source is configured".to_string())?;
+
+    let mut notes = fetch_patch_notes(&url, &champions).await?;
+    // A source that hasn't caught up yet has nothing about this patch
+    if !same_patch(&notes.patch, &version) {
+        notes.changes.clear();
+    }
+
+    let mut store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    store_guard.set(&version, notes.clone())?;
+    Ok(notes)
+}
+
+/// How `champion_id` changed in the current patch, or `None` if it didn't.
+#[tauri::command]
+pub async fn get_patch_changes(
+    app: AppHandle,
+    champion_id: i64,
+) -> Result<Option<ChampionChange>, String> {
+    let notes = current_patch_notes(&app).await?;
+    Ok(notes
+        .changes
+        .into_iter()
+        .find(|c| c.champion_id == champion_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_patch_notes_resolves_names() {
+        let champions = vec![Champion {
+            id: "MonkeyKing".to_string(),
+            key: 62,
+            name: "Wukong".to_string(),
+            title: String::new(),
+            tags: vec![],
+            info: None,
+        }];
+        let notes = parse_patch_notes(
+            &json!({
+                "patch": "14.3",
+                "champions": [
+                    { "championId": 266, "change": "nerf", "summary": "Q damage down" },
+                    { "champion": "wukong", "change": "Buffed" },
+                    { "champion": "MonkeyKing", "change": "rework" },
+                    { "champion": "Unknown", "change": "buff" }
+                ]
+            }),
+            &champions,
+        )
+        .unwrap();
+
+        let kinds: Vec<_> = notes
+            .changes
+            .iter()
+            .map(|c| (c.champion_id, c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (266, ChangeKind::Nerf),
+                (62, ChangeKind::Buff),
+                (62, ChangeKind::Adjusted)
+            ]
+        );
+        assert_eq!(notes.changes[0].summary.as_deref(), Some("Q damage down"));
+        assert!(parse_patch_notes(&json!({ "champions": [] }), &champions).is_err());
+    }
+}
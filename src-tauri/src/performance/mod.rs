@@ -0,0 +1,134 @@
+use crate::settings::SettingsStore;
+use serde::Serialize;
+use std::sync::{Mutex, OnceLock};
+use sysinfo::System;
+use tauri::{AppHandle, Manager};
+
+/// Machines with less memory than this get the low-spec profile automatically.
+const LOW_SPEC_MEMORY_MB: u64 = 8 * 1024;
+
+/// Machines with fewer logical cores than this get the low-spec profile automatically.
+const LOW_SPEC_CORES: usize = 4;
+
+/// Draft polling in the regular profile; frequent enough for a smooth timer.
+const DRAFT_POLL_MS: u64 = 250;
+
+/// Gameflow polling in the regular profile.
+const GAMEFLOW_POLL_MS: u64 = 2000;
+
+const LOW_SPEC_DRAFT_POLL_MS: u64 = 1000;
+const LOW_SPEC_GAMEFLOW_POLL_MS: u64 = 5000;
+
+/// ONNX Runtime intra-op threads in the regular profile.
+pub const ONNX_THREADS: i16 = 4;
+const LOW_SPEC_ONNX_THREADS: i16 = 2;
+
+/// Read once; the hardware doesn't change while the app runs.
+static SPECS: OnceLock<SystemSpecs> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SystemSpecs {
+    pub total_memory_mb: u64,
+    pub cpu_cores: usize, // Logical cores
+}
+
+impl SystemSpecs {
+    pub fn is_low_spec(&self) -> bool {
+        self.total_memory_mb < LOW_SPEC_MEMORY_MB || self.cpu_cores < LOW_SPEC_CORES
+    }
+}
+
+/// How hard the app works in the background.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PerformanceProfile {
+    pub low_spec: bool,
+    pub automatic: bool, // Chosen from the machine's specs rather than the setting
+    pub specs: SystemSpecs,
+    pub draft_polling_ms: u64,
+    pub gameflow_polling_ms: u64,
+    pub onnx_threads: i16,
+    pub quantized_model: bool, // Prefer model_quantized.onnx when it's shipped
+    pub prefetch_assets: bool, // Download art ahead of time, in drafts and at startup
+    pub overlay: bool,         // Whether the frontend opens the in-game overlay
+}
+
+pub fn system_specs() -> SystemSpecs {
+    *SPECS.get_or_init(|| {
+        let mut system = System::new();
+        system.refresh_memory();
+        system.refresh_cpu();
+        SystemSpecs {
+            total_memory_mb: system.total_memory() / (1024 * 1024),
+            cpu_cores: system.cpus().len(),
+        }
+    })
+}
+
+/// The profile for `low_spec_mode` (`None` decides from `specs`).
+pub fn profile_for(low_spec_mode: Option<bool>, specs: SystemSpecs) -> PerformanceProfile {
+    let low_spec = low_spec_mode.unwrap_or_else(|| specs.is_low_spec());
+    PerformanceProfile {
+        low_spec,
+        automatic: low_spec_mode.is_none(),
+        specs,
+        draft_polling_ms: if low_spec {
+            LOW_SPEC_DRAFT_POLL_MS
+        } else {
+            DRAFT_POLL_MS
+        },
+        gameflow_polling_ms: if low_spec {
+            LOW_SPEC_GAMEFLOW_POLL_MS
+        } else {
+            GAMEFLOW_POLL_MS
+        },
+        onnx_threads: if low_spec {
+            LOW_SPEC_ONNX_THREADS
+        } else {
+            ONNX_THREADS
+        },
+        quantized_model: low_spec,
+        prefetch_assets: !low_spec,
+        overlay: !low_spec,
+    }
+}
+
+/// The profile in effect, from the `low_spec_mode` setting and this machine.
+pub fn current_profile(app: &AppHandle) -> PerformanceProfile {
+    let low_spec_mode = app
+        .try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().and_then(|g| g.get().low_spec_mode));
+    profile_for(low_spec_mode, system_specs())
+}
+
+#[tauri::command]
+pub async fn get_performance_profile(app: AppHandle) -> Result<PerformanceProfile, String> {
+    Ok(current_profile(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_spec_is_picked_from_specs_unless_set() {
+        let laptop = SystemSpecs {
+            total_memory_mb: 4096,
+            cpu_cores: 8,
+        };
+        let desktop = SystemSpecs {
+            total_memory_mb: 32768,
+            cpu_cores: 16,
+        };
+
+        let auto = profile_for(None, laptop);
+        assert!(auto.low_spec && auto.automatic);
+        assert_eq!(auto.onnx_threads, LOW_SPEC_ONNX_THREADS);
+        assert!(!auto.prefetch_assets && !auto.overlay && auto.quantized_model);
+
+        assert!(!profile_for(None, desktop).low_spec);
+        assert!(profile_for(Some(true), desktop).low_spec);
+        let forced_off = profile_for(Some(false), laptop);
+        assert!(!forced_off.low_spec && !forced_off.automatic);
+        assert_eq!(forced_off.draft_polling_ms, DRAFT_POLL_MS);
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// How an in-game overlay can be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayMode {
+    AlwaysOnTop,
+    WindowWithNotification, // A normal window, with a notification pointing at it
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayServer {
+    Wayland,
+    X11,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformCapabilities {
+    pub os: String,
+    pub display_server: Option<DisplayServer>, // Linux only
+    pub desktop: Option<String>,               // XDG_CURRENT_DESKTOP, e.g. "GNOME" or "KDE"
+    pub always_on_top: bool,
+    pub overlay_mode: OverlayMode,
+}
+
+/// What the windowing system allows, from the OS and session environment.
+///
+/// Wayland leaves stacking to the compositor, so a native Wayland window can't
+/// keep itself above the game. Under XWayland (`GDK_BACKEND=x11`) or X11 it can.
+pub fn detect(os: &str, env: impl Fn(&str) -> Option<String>) -> PlatformCapabilities {
+    let set = |name: &str| env(name).filter(|v| !v.is_empty());
+    let (display_server, always_on_top) = if os == "linux" {
+        let wayland = set("WAYLAND_DISPLAY").is_some()
+            || set("XDG_SESSION_TYPE").is_some_and(|t| t.eq_ignore_ascii_case("wayland"));
+        let forced_x11 = set("GDK_BACKEND").is_some_and(|b| b.starts_with("x11"));
+        if wayland {
+            (
+                Some(DisplayServer::Wayland),
+                forced_x11 && set("DISPLAY").is_some(),
+            )
+        } else if set("DISPLAY").is_some() {
+            (Some(DisplayServer::X11), true)
+        } else {
+            (None, false)
+        }
+    } else {
+        (None, true)
+    };
+
+    PlatformCapabilities {
+        os: os.to_string(),
+        display_server,
+        desktop: set("XDG_CURRENT_DESKTOP").filter(|_| os == "linux"),
+        always_on_top,
+        overlay_mode: if always_on_top {
+            OverlayMode::AlwaysOnTop
+        } else {
+            OverlayMode::WindowWithNotification
+        },
+    }
+}
+
+pub fn capabilities() -> PlatformCapabilities {
+    detect(std::env::consts::OS, |name| std::env::var(name).ok())
+}
+
+#[tauri::command]
+pub async fn get_platform_capabilities() -> Result<PlatformCapabilities, String> {
+    Ok(capabilities())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect_with(os: &str, vars: &[(&str, &str)]) -> PlatformCapabilities {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        detect(os, |name| vars.get(name).map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn test_detect_overlay_mode() {
+        assert_eq!(
+            detect_with("windows", &[]).overlay_mode,
+            OverlayMode::AlwaysOnTop
+        );
+
+        let x11 = detect_with("linux", &[("DISPLAY", ":0"), ("XDG_CURRENT_DESKTOP", "i3")]);
+        assert_eq!(x11.display_server, Some(DisplayServer::X11));
+        assert_eq!(x11.overlay_mode, OverlayMode::AlwaysOnTop);
+
+        let wayland = detect_with(
+            "linux",
+            &[("WAYLAND_DISPLAY", "wayland-0"), ("DISPLAY", ":0")],
+        );
+        assert_eq!(wayland.display_server, Some(DisplayServer::Wayland));
+        assert_eq!(wayland.overlay_mode, OverlayMode::WindowWithNotification);
+
+        let xwayland = detect_with(
+            "linux",
+            &[
+                ("XDG_SESSION_TYPE", "wayland"),
+                ("DISPLAY", ":0"),
+                ("GDK_BACKEND", "x11"),
+            ],
+        );
+        assert!(xwayland.always_on_top);
+    }
+}
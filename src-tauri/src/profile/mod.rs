@@ -0,0 +1,125 @@
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::{ChampionMastery, LcuClient, MatchHistoryGame, RankedStats, SummonerInfo};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const TOP_MASTERIES: usize = 5;
+const RECENT_MATCHES: usize = 20;
+
+/// Everything the profile page shows for one player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub summoner: SummonerInfo,
+    pub ranked_stats: Vec<RankedStats>,
+    pub top_masteries: Vec<ChampionMastery>,
+    pub recent_matches: Vec<MatchHistoryGame>,
+}
+
+/// Split "GameName#TAG" into its two parts. Game names may contain '#', tag lines may not.
+pub fn parse_riot_id(riot_id: &str) -> Result<(String, String), String> {
+    let (game_name, tag_line) = riot_id
+        .trim()
+        .rsplit_once('#')
+        .ok_or_else(|| format!("Invalid Riot ID '{}': expected Name#TAG", riot_id))?;
+    let (game_name, tag_line) = (game_name.trim(), tag_line.trim());
+
+    if game_name.is_empty() || tag_line.is_empty() {
+        return Err(format!("Invalid Riot ID '{}': expected Name#TAG", riot_id));
+    }
+
+    Ok((game_name.to_string(), tag_line.to_string()))
+}
+
+/// Build a profile for any player on the current server.
+///
+/// Only the summoner lookup is required; ranked, mastery and match history are
+/// left empty when the client refuses them (e.g. private or brand-new accounts).
+pub async fn fetch_profile<C: LcuApi>(
+    client: &mut C,
+    riot_id: &str,
+) -> Result<PlayerProfile, String> {
+    let (game_name, tag_line) = parse_riot_id(riot_id)?;
+    let summoner = client.lookup_summoner(&game_name, &tag_line).await?;
+
+    let ranked_stats = client
+        .get_ranked_stats_for(&summoner.puuid, true)
+        .await
+        .unwrap_or_default();
+    let top_masteries = client
+        .get_top_masteries(&summoner.summoner_id, TOP_MASTERIES)
+        .await
+        .unwrap_or_default();
+    let recent_matches = client
+        .get_match_history_for(&summoner.puuid, 0, RECENT_MATCHES)
+        .await
+        .unwrap_or_default();
+
+    Ok(PlayerProfile {
+        summoner,
+        ranked_stats,
+        top_masteries,
+        recent_matches,
+    })
+}
+
+#[tauri::command]
+pub async fn get_profile(
+    riot_id: String,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<PlayerProfile, String> {
+    let mut client_guard = client.lock().await;
+    fetch_profile(&mut *client_guard, &riot_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::mock::MockLcuClient;
+
+    #[test]
+    fn test_parse_riot_id() {
+        assert_eq!(
+            parse_riot_id(" Faker#KR1 ").unwrap(),
+            ("Faker".to_string(), "KR1".to_string())
+        );
+        assert_eq!(
+            parse_riot_id("a#b#EUW").unwrap(),
+            ("a#b".to_string(), "EUW".to_string())
+        );
+        assert!(parse_riot_id("NoTag").is_err());
+        assert!(parse_riot_id("Name#").is_err());
+    }
+
+    #[tokio::test]
+    async fn profile_requires_a_summoner_but_not_the_rest() {
+        let mut mock = MockLcuClient::new();
+        assert!(fetch_profile(&mut mock, "Someone#EUW").await.is_err());
+
+        mock.summoner = Some(SummonerInfo {
+            summoner_id: "1".to_string(),
+            account_id: "2".to_string(),
+            puuid: "abc".to_string(),
+            display_name: "Someone".to_string(),
+            game_name: Some("Someone".to_string()),
+            tag_line: Some("EUW".to_string()),
+            summoner_level: 30,
+            profile_icon_id: 1,
+            xp_since_last_level: 0,
+            xp_until_next_level: 0,
+        });
+        mock.masteries = (1..=8)
+            .map(|i| ChampionMastery {
+                champion_id: i,
+                champion_level: 7,
+                champion_points: 1000 * i,
+                last_play_time: 0,
+                chest_granted: false,
+            })
+            .collect();
+
+        let profile = fetch_profile(&mut mock, "Someone#EUW").await.unwrap();
+        assert_eq!(profile.summoner.puuid, "abc");
+        assert_eq!(profile.top_masteries.len(), TOP_MASTERIES);
+        assert!(profile.recent_matches.is_empty());
+    }
+}
@@ -0,0 +1,552 @@
+use crate::lcu::draft::DraftState;
+use crate::model::ChampionRecommendation;
+use crate::settings::SettingsStore;
+use crate::storage::usage::data_dir;
+use rhai::{CallFnOptions, Dynamic, Engine, Scope, AST, FLOAT, INT};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Points where user scripts are called. A script opts in by defining a
+/// function with the hook's name:
+///
+/// - `on_draft_changed(draft)` after each champ select update
+/// - `on_phase_change(from, to)` on gameflow transitions; `()` while the client is away
+/// - `on_game_end(game)` with the newest stored match, or `()` if it wasn't synced
+/// - `rerank(recommendations, draft)` returning the array it was given, reordered
+///   or with new `score`s
+///
+/// Scripts can call `notify(title, body)` to show a desktop notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    DraftChanged,
+    PhaseChanged,
+    GameEnded,
+    Rerank,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 4] = [
+        Hook::DraftChanged,
+        Hook::PhaseChanged,
+        Hook::GameEnded,
+        Hook::Rerank,
+    ];
+
+    pub fn fn_name(self) -> &'static str {
+        match self {
+            Hook::DraftChanged => "on_draft_changed",
+            Hook::PhaseChanged => "on_phase_change",
+            Hook::GameEnded => "on_game_end",
+            Hook::Rerank => "rerank",
+        }
+    }
+}
+
+/// Operations a single hook call may run before it's stopped, so a runaway
+/// loop can't stall draft updates.
+const MAX_OPERATIONS: u64 = 200_000;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptInfo {
+    pub name: String, // File name without `.rhai`
+    pub enabled: bool,
+    pub hooks: Vec<String>,    // Hook functions the script defines
+    pub error: Option<String>, // Why the script didn't load
+}
+
+/// Payload of the `script-error` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptError {
+    pub script: String,
+    pub hook: String,
+    pub error: String,
+}
+
+struct LoadedScript {
+    name: String,
+    ast: AST,
+}
+
+/// The enabled scripts from the scripts directory, compiled once and run in a
+/// sandboxed engine: no file, network or process access, and bounded
+/// operations, call depth and collection sizes.
+pub struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<LoadedScript>,
+    errors: BTreeMap<String, String>,
+    notifications: Arc<Mutex<Vec<(String, String)>>>, // Queued by `notify` during a call
+    scripts_dir: PathBuf,
+}
+
+impl ScriptHost {
+    pub fn new() -> Result<Self, String> {
+        let scripts_dir = data_dir()?.join("scripts");
+
+        fs::create_dir_all(&scripts_dir)
+            .map_err(|e| format!("Failed to create scripts directory: {}", e))?;
+
+        Ok(Self::with_path(scripts_dir))
+    }
+
+    pub fn with_path(scripts_dir: PathBuf) -> Self {
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(10_000)
+            .set_max_array_size(1_000)
+            .set_max_map_size(1_000)
+            .disable_symbol("eval")
+            .on_print(|text| println!("[script] {}", text))
+            .on_debug(|text, _, _| println!("[script] {}", text));
+
+        let queue = notifications.clone();
+        engine.register_fn("notify", move |title: &str, body: &str| {
+            if let Ok(mut queue) = queue.lock() {
+                queue.push((title.to_string(), body.to_string()));
+            }
+        });
+
+        Self {
+            engine,
+            scripts: Vec::new(),
+            errors: BTreeMap::new(),
+            notifications,
+            scripts_dir,
+        }
+    }
+
+    /// Every `.rhai` file in the scripts directory, by name.
+    pub fn available(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.scripts_dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Compile the enabled scripts, replacing whatever was loaded before. A
+    /// script that doesn't compile is left out and its error kept for
+    /// `list_scripts`; the others still load.
+    pub fn load(&mut self, enabled: &[String]) -> Result<(), String> {
+        self.scripts.clear();
+        self.errors.clear();
+
+        for name in self.available() {
+            if !enabled.contains(&name) {
+                continue;
+            }
+            let path = self.scripts_dir.join(format!("{}.rhai", name));
+            let compiled = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read script: {}", e))
+                .and_then(|source| self.engine.compile(source).map_err(|e| e.to_string()));
+            match compiled {
+                Ok(ast) => self.scripts.push(LoadedScript { name, ast }),
+                Err(e) => {
+                    self.errors.insert(name, e);
+                }
+            }
+        }
+
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self
+                .errors
+                .iter()
+                .map(|(name, e)| format!("{}: {}", name, e))
+                .collect::<Vec<_>>()
+                .join("; "))
+        }
+    }
+
+    pub fn info(&self, enabled: &[String]) -> Vec<ScriptInfo> {
+        self.available()
+            .into_iter()
+            .map(|name| {
+                let hooks = self
+                    .scripts
+                    .iter()
+                    .find(|s| s.name == name)
+                    .map(|s| {
+                        Hook::ALL
+                            .iter()
+                            .filter(|hook| defines(&s.ast, **hook))
+                            .map(|hook| hook.fn_name().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                ScriptInfo {
+                    enabled: enabled.contains(&name),
+                    error: self.errors.get(&name).cloned(),
+                    hooks,
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    pub fn has_hook(&self, hook: Hook) -> bool {
+        self.scripts.iter().any(|s| defines(&s.ast, hook))
+    }
+
+    /// Call `hook` in every loaded script that defines it, in name order. Each
+    /// script gets its own copy of `args`.
+    pub fn call(&self, hook: Hook, args: &[Dynamic]) -> Vec<(String, Result<Dynamic, String>)> {
+        self.scripts
+            .iter()
+            .filter(|s| defines(&s.ast, hook))
+            .map(|s| (s.name.clone(), self.call_script(s, hook, args.to_vec())))
+            .collect()
+    }
+
+    fn call_script(
+        &self,
+        script: &LoadedScript,
+        hook: Hook,
+        args: Vec<Dynamic>,
+    ) -> Result<Dynamic, String> {
+        // Only the hook function runs, not the script's top-level statements
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine
+            .call_fn_with_options(
+                options,
+                &mut Scope::new(),
+                &script.ast,
+                hook.fn_name(),
+                args,
+            )
+            .map_err(|e| e.to_string())
+    }
+
+    /// Pass recommendations through each script's `rerank`. A script whose
+    /// result isn't a list of recommendations is skipped.
+    pub fn rerank(
+        &self,
+        recommendations: Vec<ChampionRecommendation>,
+        draft: &Dynamic,
+    ) -> (Vec<ChampionRecommendation>, Vec<(String, String)>) {
+        let mut recommendations = recommendations;
+        let mut errors = Vec::new();
+        for script in self
+            .scripts
+            .iter()
+            .filter(|s| defines(&s.ast, Hook::Rerank))
+        {
+            let args = vec![recommendations_to_dynamic(&recommendations), draft.clone()];
+            match self
+                .call_script(script, Hook::Rerank, args)
+                .and_then(recommendations_from_dynamic)
+            {
+                Ok(reranked) => recommendations = reranked,
+                Err(e) => errors.push((script.name.clone(), e)),
+            }
+        }
+        (recommendations, errors)
+    }
+
+    /// Notifications scripts asked for since the last call.
+    pub fn take_notifications(&self) -> Vec<(String, String)> {
+        self.notifications
+            .lock()
+            .map(|mut queue| std::mem::take(&mut *queue))
+            .unwrap_or_default()
+    }
+}
+
+fn defines(ast: &AST, hook: Hook) -> bool {
+    ast.iter_functions().any(|f| f.name == hook.fn_name())
+}
+
+fn recommendations_to_dynamic(recommendations: &[ChampionRecommendation]) -> Dynamic {
+    recommendations
+        .iter()
+        .map(|rec| {
+            let mut map = rhai::Map::new();
+            map.insert(
+                "champion_id".into(),
+                Dynamic::from_int(rec.champion_id as INT),
+            );
+            map.insert("score".into(), Dynamic::from_float(rec.score as FLOAT));
+            Dynamic::from_map(map)
+        })
+        .collect::<rhai::Array>()
+        .into()
+}
+
+fn recommendations_from_dynamic(value: Dynamic) -> Result<Vec<ChampionRecommendation>, String> {
+    let invalid = || "rerank must return an array of #{ champion_id, score }".to_string();
+    value
+        .into_array()
+        .map_err(|_| invalid())?
+        .into_iter()
+        .map(|item| {
+            let map = item.try_cast::<rhai::Map>().ok_or_else(invalid)?;
+            let champion_id = map
+                .get("champion_id")
+                .and_then(|v| v.as_int().ok())
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or_else(invalid)?;
+            let score = map
+                .get("score")
+                .and_then(|v| {
+                    v.as_float()
+                        .or_else(|_| v.as_int().map(|i| i as FLOAT))
+                        .ok()
+                })
+                .ok_or_else(invalid)?;
+            Ok(ChampionRecommendation {
+                champion_id,
+                score: score as f32,
+                coach: None,
+            })
+        })
+        .collect()
+}
+
+pub fn to_dynamic<T: Serialize>(value: &T) -> Dynamic {
+    rhai::serde::to_dynamic(value).unwrap_or(Dynamic::UNIT)
+}
+
+fn enabled_scripts(app: &AppHandle) -> Vec<String> {
+    app.try_state::<Mutex<SettingsStore>>()
+        .and_then(|s| s.lock().ok().map(|g| g.get().enabled_scripts.clone()))
+        .unwrap_or_default()
+}
+
+/// Whether any loaded script defines `hook`, to skip building its arguments.
+pub fn has_hook(app: &AppHandle, hook: Hook) -> bool {
+    app.try_state::<Mutex<ScriptHost>>()
+        .and_then(|s| s.lock().ok().map(|host| host.has_hook(hook)))
+        .unwrap_or(false)
+}
+
+fn report_error(app: &AppHandle, script: String, hook: Hook, error: String) {
+    eprintln!("Script {} failed in {}: {}", script, hook.fn_name(), error);
+    let _ = app.emit(
+        "script-error",
+        ScriptError {
+            script,
+            hook: hook.fn_name().to_string(),
+            error,
+        },
+    );
+}
+
+/// Run `hook` in every script that defines it, then show the notifications
+/// they queued. Failures are reported through `script-error` and don't reach
+/// the caller.
+pub fn run_hook(app: &AppHandle, hook: Hook, args: &[Dynamic]) {
+    let Some(host) = app.try_state::<Mutex<ScriptHost>>() else {
+        return;
+    };
+    let (results, notifications) = match host.lock() {
+        Ok(host) => (host.call(hook, args), host.take_notifications()),
+        Err(_) => return,
+    };
+
+    for (title, body) in notifications {
+        let _ = app.notification().builder().title(title).body(body).show();
+    }
+    for (script, result) in results {
+        if let Err(error) = result {
+            report_error(app, script, hook, error);
+        }
+    }
+}
+
+/// Let scripts reorder or rescore recommendations. Each `rerank` sees the
+/// previous script's output.
+pub fn rerank(
+    app: &AppHandle,
+    recommendations: &mut Vec<ChampionRecommendation>,
+    draft: &DraftState,
+) {
+    let Some(host) = app.try_state::<Mutex<ScriptHost>>() else {
+        return;
+    };
+    let (errors, notifications) = {
+        let Ok(host_guard) = host.lock() else {
+            return;
+        };
+        if !host_guard.has_hook(Hook::Rerank) {
+            return;
+        }
+        let (reranked, errors) =
+            host_guard.rerank(std::mem::take(recommendations), &to_dynamic(draft));
+        *recommendations = reranked;
+        (errors, host_guard.take_notifications())
+    };
+    for (title, body) in notifications {
+        let _ = app.notification().builder().title(title).body(body).show();
+    }
+    for (script, error) in errors {
+        report_error(app, script, Hook::Rerank, error);
+    }
+}
+
+fn reload(app: &AppHandle, host: &Mutex<ScriptHost>) -> Result<Vec<ScriptInfo>, String> {
+    let enabled = enabled_scripts(app);
+    let mut host_guard = host.lock().map_err(|e| format!("Lock error: {}", e))?;
+    // Compile errors show up per script in the returned list
+    let _ = host_guard.load(&enabled);
+    Ok(host_guard.info(&enabled))
+}
+
+#[tauri::command]
+pub async fn list_scripts(
+    app: AppHandle,
+    scripts: tauri::State<'_, Mutex<ScriptHost>>,
+) -> Result<Vec<ScriptInfo>, String> {
+    let enabled = enabled_scripts(&app);
+    let host_guard = scripts.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(host_guard.info(&enabled))
+}
+
+#[tauri::command]
+pub async fn set_script_enabled(
+    app: AppHandle,
+    name: String,
+    enabled: bool,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    scripts: tauri::State<'_, Mutex<ScriptHost>>,
+) -> Result<Vec<ScriptInfo>, String> {
+    {
+        let host_guard = scripts.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if !host_guard.available().contains(&name) {
+            return Err(format!("No script named {}", name));
+        }
+    }
+    {
+        let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut updated = settings_guard.get().clone();
+        updated.enabled_scripts.retain(|n| *n != name);
+        if enabled {
+            updated.enabled_scripts.push(name);
+        }
+        settings_guard.set(updated)?;
+    }
+    reload(&app, &scripts)
+}
+
+/// Pick up edits to script files without restarting.
+#[tauri::command]
+pub async fn reload_scripts(
+    app: AppHandle,
+    scripts: tauri::State<'_, Mutex<ScriptHost>>,
+) -> Result<Vec<ScriptInfo>, String> {
+    reload(&app, &scripts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host_with(scripts: &[(&str, &str)]) -> ScriptHost {
+        let dir = std::env::temp_dir().join(format!(
+            "trackimo-scripts-{}-{}",
+            std::process::id(),
+            scripts[0].0
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (name, source) in scripts {
+            fs::write(dir.join(format!("{}.rhai", name)), source).unwrap();
+        }
+        ScriptHost::with_path(dir)
+    }
+
+    #[test]
+    fn test_hooks_notifications_and_limits() {
+        let mut host = host_with(&[
+            (
+                "alerts",
+                r#"fn on_phase_change(from, to) { if to == "ReadyCheck" { notify("Queue", "Game found"); } }"#,
+            ),
+            ("broken", "fn on_game_end(game) {"),
+            ("spin", "fn on_phase_change(from, to) { loop {} }"),
+            ("unused", "fn on_draft_changed(draft) {}"),
+        ]);
+        let enabled: Vec<String> = ["alerts", "broken", "spin"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert!(host.load(&enabled).is_err(), "broken doesn't compile");
+        let info = host.info(&enabled);
+        assert_eq!(info.len(), 4);
+        assert!(info[1].error.is_some());
+        assert!(!info[3].enabled && info[3].hooks.is_empty());
+        assert!(
+            !host.has_hook(Hook::DraftChanged),
+            "disabled scripts don't load"
+        );
+
+        let results = host.call(
+            Hook::PhaseChanged,
+            &[Dynamic::from("Lobby"), Dynamic::from("ReadyCheck")],
+        );
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err(), "runaway loop is stopped");
+        assert_eq!(
+            host.take_notifications(),
+            vec![("Queue".to_string(), "Game found".to_string())]
+        );
+        assert!(host.take_notifications().is_empty());
+    }
+
+    #[test]
+    fn test_rerank_chains_and_skips_bad_results() {
+        let mut host = host_with(&[
+            (
+                "a_boost",
+                "fn rerank(recs, draft) { for i in 0..recs.len() { if recs[i].champion_id == 2 { recs[i].score *= 3.0; } } recs }",
+            ),
+            ("b_bad", "fn rerank(recs, draft) { 42 }"),
+            (
+                "c_sort",
+                "fn rerank(recs, draft) { recs.sort(|a, b| if a.score > b.score { -1 } else { 1 }); recs }",
+            ),
+        ]);
+        let enabled: Vec<String> = ["a_boost", "b_bad", "c_sort"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        host.load(&enabled).unwrap();
+
+        let recs = vec![
+            ChampionRecommendation {
+                champion_id: 1,
+                score: 0.5,
+                coach: None,
+            },
+            ChampionRecommendation {
+                champion_id: 2,
+                score: 0.25,
+                coach: None,
+            },
+        ];
+        let (reranked, errors) = host.rerank(recs, &Dynamic::UNIT);
+        assert_eq!(
+            reranked.iter().map(|r| r.champion_id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!((reranked[0].score - 0.75).abs() < 1e-6);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "b_bad");
+    }
+}
@@ -0,0 +1,234 @@
+use crate::analytics::now_millis;
+use crate::lcu::draft::DraftState;
+use crate::metrics::{record_feature, Feature};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// One finished game of a series.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeriesGame {
+    pub game_id: Option<i64>,
+    pub champion_ids: Vec<i64>, // Locked by either team
+}
+
+/// Payload of `get_series_state` and the `series-changed` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeriesState {
+    pub active: bool,
+    pub started_at: Option<i64>,
+    pub games: Vec<SeriesGame>,
+    pub excluded: Vec<i64>, // Every champion picked so far, sorted
+}
+
+struct Series {
+    started_at: i64,
+    games: Vec<SeriesGame>,
+}
+
+/// Fearless draft across the games of a series: a champion picked in one game,
+/// by either team, can't be picked again until the series ends. Lives for the
+/// app session only.
+#[derive(Default)]
+pub struct SeriesTracker {
+    series: Option<Series>,
+    pending: Option<SeriesGame>, // Picks of the current draft, until its game starts
+}
+
+impl SeriesTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new series, dropping any previous one.
+    pub fn start(&mut self, now: i64) {
+        self.series = Some(Series {
+            started_at: now,
+            games: Vec::new(),
+        });
+        self.pending = None;
+    }
+
+    /// End the series, returning how it went.
+    pub fn end(&mut self) -> SeriesState {
+        let state = self.state();
+        self.series = None;
+        self.pending = None;
+        SeriesState {
+            active: false,
+            ..state
+        }
+    }
+
+    pub fn state(&self) -> SeriesState {
+        SeriesState {
+            active: self.series.is_some(),
+            started_at: self.series.as_ref().map(|s| s.started_at),
+            games: self
+                .series
+                .as_ref()
+                .map(|s| s.games.clone())
+                .unwrap_or_default(),
+            excluded: self.excluded(),
+        }
+    }
+
+    /// Champions picked in earlier games of the running series.
+    pub fn excluded(&self) -> Vec<i64> {
+        let mut excluded: Vec<i64> = self
+            .series
+            .iter()
+            .flat_map(|s| s.games.iter())
+            .flat_map(|g| g.champion_ids.iter().copied())
+            .collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+        excluded
+    }
+
+    /// Mark earlier picks unavailable in `state` and remember its own picks
+    /// for when its game starts.
+    pub fn observe(&mut self, state: &mut DraftState) {
+        if self.series.is_none() {
+            return;
+        }
+        state.series_excluded = self.excluded();
+        self.pending = Some(SeriesGame {
+            game_id: state.game_id,
+            champion_ids: state
+                .teams
+                .iter()
+                .flat_map(|t| t.picks.iter())
+                .filter(|p| p.completed)
+                .map(|p| p.champion_id)
+                .collect(),
+        });
+    }
+
+    /// The last observed draft made it into a game, so its picks count. A
+    /// dodged draft is replaced by the next one before this is called.
+    pub fn commit_game(&mut self) -> bool {
+        let (Some(series), Some(game)) = (self.series.as_mut(), self.pending.take()) else {
+            return false;
+        };
+        if game.champion_ids.is_empty()
+            || series
+                .games
+                .iter()
+                .any(|g| g.game_id.is_some() && g.game_id == game.game_id)
+        {
+            return false;
+        }
+        series.games.push(game);
+        true
+    }
+}
+
+/// Apply the running series, if any, to a draft state coming from the client.
+pub fn follow_series(app: &AppHandle, state: &mut DraftState) {
+    if let Some(tracker) = app.try_state::<Mutex<SeriesTracker>>() {
+        if let Ok(mut tracker_guard) = tracker.lock() {
+            tracker_guard.observe(state);
+        }
+    }
+}
+
+/// Game start hook: the draft that led to it joins the series.
+pub fn commit_series_game(app: &AppHandle) -> Result<(), String> {
+    let tracker = app.state::<Mutex<SeriesTracker>>();
+    let mut tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if tracker_guard.commit_game() {
+        let _ = app.emit("series-changed", tracker_guard.state());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn start_series(
+    app: AppHandle,
+    tracker: tauri::State<'_, Mutex<SeriesTracker>>,
+) -> Result<SeriesState, String> {
+    let mut tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    tracker_guard.start(now_millis());
+    record_feature(&app, Feature::FearlessSeries);
+    let state = tracker_guard.state();
+    let _ = app.emit("series-changed", &state);
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn end_series(
+    app: AppHandle,
+    tracker: tauri::State<'_, Mutex<SeriesTracker>>,
+) -> Result<SeriesState, String> {
+    let mut tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let ended = tracker_guard.end();
+    let _ = app.emit("series-changed", tracker_guard.state());
+    Ok(ended)
+}
+
+#[tauri::command]
+pub async fn get_series_state(
+    tracker: tauri::State<'_, Mutex<SeriesTracker>>,
+) -> Result<SeriesState, String> {
+    let tracker_guard = tracker.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(tracker_guard.state())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::draft::{parse_draft_session, ChampionPick};
+    use serde_json::json;
+
+    fn draft(game_id: i64, picks: &[i64]) -> DraftState {
+        let mut state = parse_draft_session(&json!({
+            "gameId": game_id,
+            "localPlayerCellId": 0,
+            "timer": { "phase": "FINALIZATION" },
+            "myTeam": [{ "cellId": 0, "championId": 0, "championPickIntent": 0 }],
+            "theirTeam": [],
+            "actions": []
+        }))
+        .unwrap();
+        state.teams[0].picks = picks
+            .iter()
+            .map(|&champion_id| ChampionPick {
+                champion_id,
+                cell_id: None,
+                completed: true,
+                is_ally_pick: true,
+                position: None,
+            })
+            .collect();
+        state
+    }
+
+    #[test]
+    fn test_picks_are_excluded_once_their_game_starts() {
+        let mut tracker = SeriesTracker::new();
+        tracker.observe(&mut draft(1, &[86]));
+        assert!(!tracker.commit_game(), "no series running");
+
+        tracker.start(0);
+        let mut first = draft(1, &[86, 51]);
+        tracker.observe(&mut first);
+        assert!(first.series_excluded.is_empty());
+        // A dodged draft is replaced by the next one
+        tracker.observe(&mut draft(2, &[103, 51]));
+        assert!(tracker.commit_game());
+        assert!(!tracker.commit_game(), "each draft counts once");
+
+        let mut second = draft(3, &[86]);
+        tracker.observe(&mut second);
+        assert_eq!(second.series_excluded, vec![51, 103]);
+        assert!(tracker.commit_game());
+        assert_eq!(tracker.state().excluded, vec![51, 86, 103]);
+        assert_eq!(tracker.state().games.len(), 2);
+
+        let ended = tracker.end();
+        assert!(!ended.active);
+        assert_eq!(ended.games.len(), 2);
+        assert!(tracker.excluded().is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::lcu::client::{LcuClient, RetryPolicies};
+use crate::lcu::monitor::EventVerbosity;
+use crate::model::OptimizationLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// User preferences. Every field has a default so settings files written by
+/// older versions keep loading.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub rank_change_notifications: bool,
+    pub boost_chest_eligible: bool, // Nudge champions that can still earn a chest up in normals
+    pub draft_event_windows: Option<Vec<String>>, // Window labels for draft events; None broadcasts app-wide
+    pub auto_ban_enabled: bool,
+    pub auto_ban_champion_ids: Vec<i64>, // In priority order; protected ones are skipped
+    pub auto_ban_delay_ms: Option<u64>,  // Wait after the ban turn starts; None uses the default
+    pub auto_requeue_enabled: bool,
+    pub auto_requeue_max_games: Option<u32>, // Requeues per app session; None uses the default
+    pub auto_requeue_max_loss_streak: Option<u32>, // Stop after this many losses in a row
+    pub auto_accept_enabled: bool,           // Accept ready checks as soon as they pop
+    pub auto_draft_enabled: bool, // Hover and lock picks from the rules below on my turns; bans go through auto-ban
+    pub auto_draft_picks: BTreeMap<String, Vec<i64>>, // Champions by position ("TOP", ...), in priority order; "ANY" is tried after the position's own
+    pub auto_draft_bans: BTreeMap<String, Vec<i64>>, // Same for bans, tried after the auto-ban list
+    pub auto_draft_lock_seconds: Option<u32>, // Lock at this many seconds left; None uses the default
+    pub lock_confirmation: bool, // Lock requests must be repeated within two seconds, and never lock during bans
+    pub patch_notes_url: Option<String>, // JSON summary of the current patch's champion changes
+    pub comfort_mode: bool, // Favour easy champions similar to the player's pool in recommendations
+    pub autofill_mode: bool, // Recommend only safe picks when assigned a role outside my lobby picks
+    pub champion_pool: Vec<i64>, // Champions the player mains; losing one in draft suggests alternatives
+    pub pick_intents: BTreeMap<i32, BTreeMap<String, i64>>, // Planned champion per position, by queue id
+    pub coach_mode: bool,                                   // Comment on each draft step
+    pub share_draft: bool, // Publish draft snapshots to the relay for a friend or coach to watch
+    pub share_relay_url: Option<String>,
+    pub share_token: Option<String>, // Identifies this player's draft on the relay
+    pub obs_enabled: bool,           // Switch OBS scenes on gameflow phase changes
+    pub obs_url: Option<String>,     // obs-websocket address; None uses ws://127.0.0.1:4455
+    pub obs_password: Option<String>,
+    pub obs_scenes: BTreeMap<String, String>, // Scene name per gameflow phase, e.g. "ChampSelect"
+    pub wine_prefixes: Vec<String>, // Linux: extra wine prefixes (or folders of them) to find the client in; `~` allowed
+    pub feature_flags: BTreeMap<String, bool>, // Per-user overrides of experimental features, by flag key
+    pub feature_flags_url: Option<String>, // JSON object of flag key to bool, applied to everyone
+    pub enabled_scripts: Vec<String>, // Scripts from the scripts directory to run, by name; all are off until enabled
+    pub metrics_enabled: bool,        // Count feature use and error categories locally; opt-in
+    pub metrics_url: Option<String>,  // Where `submit_metrics` posts the report
+    pub afk_nudge_enabled: bool,      // Notify when my turn is running out with nothing hovered
+    pub afk_nudge_seconds: Vec<u32>, // Seconds left at which to nudge, each louder; empty uses the default
+    pub afk_auto_hover_seconds: Option<u32>, // With nudges on, hover the top recommendation at this many seconds left (needs the auto_hover flag); never locks
+    pub sound_alerts: bool, // Play alert sounds in the app instead of through notifications
+    pub audio_output_device: Option<String>, // Device id from `list_audio_devices`; None uses the system default
+    pub voice_ducking: bool,                 // Lower alert volume while a voice chat app is running
+    pub voice_ducking_volume: Option<f32>,   // 0 to 1; None uses the default
+    pub voice_apps: Vec<String>, // Process names that count as voice chat; empty uses Discord, TeamSpeak and Mumble
+    pub vision_benchmarks: BTreeMap<String, f64>, // Par vision score per minute by position; missing ones use the defaults
+    pub vision_callouts: bool, // Notify when jungle or support vision keeps falling below par
+    pub riot_api_key: Option<String>, // Used for match timelines the client no longer has
+    pub riot_platform: Option<String>, // Platform the account plays on, e.g. "EUW1"; needed with the API key
+    pub low_spec_mode: Option<bool>, // Slower polling, lighter model, no prefetching or overlay; None decides from RAM and cores
+    pub onnx_intra_threads: Option<i16>, // Threads per model operation; None follows the performance profile
+    pub onnx_inter_threads: Option<i16>, // Threads across independent operations; None leaves ONNX Runtime's default
+    pub onnx_optimization_level: Option<OptimizationLevel>, // None optimizes fully
+    pub lazy_model_loading: bool, // Load the draft model at the first champion select instead of at startup
+    pub lcu_polling_timeout_ms: Option<u64>, // Gameflow, champ select and other polled endpoints; None uses the default
+    pub lcu_polling_retries: Option<u32>,    // Retries after a failed poll, with fresh credentials
+    pub lcu_polling_backoff_ms: Option<u64>, // Wait before the first retry, doubling after
+    pub lcu_history_timeout_ms: Option<u64>, // Match history, game details and timelines
+    pub lcu_history_retries: Option<u32>,
+    pub lcu_history_backoff_ms: Option<u64>,
+    pub event_verbosity: EventVerbosity, // Minimal drops full draft states, debug adds the raw LCU session
+    pub draft_include_raw: bool, // Attach the untouched LCU session to draft-state-changed as raw_session
+}
+
+pub struct SettingsStore {
+    settings: AppSettings,
+    settings_path: PathBuf,
+}
+
+impl SettingsStore {
+    pub fn new() -> Result<Self, String> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| "Failed to get config directory".to_string())?
+            .join("trackimo-desktop");
+
+        fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+        Ok(Self {
+            settings: AppSettings::default(),
+            settings_path: config_dir.join("settings.json"),
+        })
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.settings_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.settings_path)
+            .map_err(|e| format!("Failed to read settings: {}", e))?;
+        self.settings = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse settings: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn get(&self) -> &AppSettings {
+        &self.settings
+    }
+
+    pub fn set(&mut self, settings: AppSettings) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+        fs::write(&self.settings_path, json)
+            .map_err(|e| format!("Failed to write settings: {}", e))?;
+
+        self.settings = settings;
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn get_settings(
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+) -> Result<AppSettings, String> {
+    let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(settings_guard.get().clone())
+}
+
+/// Overlay the fields present in `patch` on `current`, leaving the rest as they
+/// are. Unknown fields are an error, so a typo can't pass for a change.
+pub fn merge_settings(
+    current: &AppSettings,
+    patch: serde_json::Value,
+) -> Result<AppSettings, String> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err("Settings patch must be an object".to_string());
+    };
+    let mut merged = serde_json::to_value(current)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let Some(fields) = merged.as_object_mut() {
+        for (key, value) in patch {
+            if !fields.contains_key(&key) {
+                return Err(format!("Unknown setting {}", key));
+            }
+            fields.insert(key, value);
+        }
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))
+}
+
+/// Replace every setting. Fields missing from `new_settings` fall back to their
+/// defaults, so changing only a few goes through `patch_settings`.
+#[tauri::command]
+pub async fn update_settings(
+    new_settings: AppSettings,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<(), String> {
+    {
+        let mut client_guard = client.lock().await;
+        client_guard.set_policies(RetryPolicies::from_settings(&new_settings));
+        client_guard.set_wine_prefixes(&new_settings.wine_prefixes);
+    }
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    settings_guard.set(new_settings)
+}
+
+/// Change only the settings present in `patch`, keeping every other one.
+#[tauri::command]
+pub async fn patch_settings(
+    patch: serde_json::Value,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<AppSettings, String> {
+    let merged = {
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        merge_settings(settings_guard.get(), patch)?
+    };
+    {
+        let mut client_guard = client.lock().await;
+        client_guard.set_policies(RetryPolicies::from_settings(&merged));
+        client_guard.set_wine_prefixes(&merged.wine_prefixes);
+    }
+    let mut settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+    settings_guard.set(merged.clone())?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_patch_keeps_missing_fields() {
+        let current = AppSettings {
+            lock_confirmation: true,
+            auto_ban_enabled: true,
+            auto_ban_champion_ids: vec![238],
+            pick_intents: BTreeMap::from([(420, BTreeMap::from([("MIDDLE".to_string(), 103)]))]),
+            ..Default::default()
+        };
+        let merged = merge_settings(&current, json!({ "auto_ban_enabled": false })).unwrap();
+        assert!(!merged.auto_ban_enabled);
+        assert!(merged.lock_confirmation);
+        assert_eq!(merged.auto_ban_champion_ids, vec![238]);
+        assert_eq!(merged.pick_intents, current.pick_intents);
+
+        assert!(merge_settings(&current, json!({ "lock_confirmaton": false })).is_err());
+        assert!(merge_settings(&current, json!({ "auto_ban_enabled": "yes" })).is_err());
+        assert!(merge_settings(&current, json!([])).is_err());
+    }
+}
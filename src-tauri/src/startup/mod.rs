@@ -0,0 +1,409 @@
+use crate::analytics::meta_shift::MetaShiftStore;
+use crate::analytics::now_millis;
+use crate::champions::assets::resume_pending_prefetches;
+use crate::champions::cache::ChampionCache;
+use crate::champions::client::RiotApiClient;
+use crate::downloads::DownloadTracker;
+use crate::flags::refresh_remote;
+use crate::goals::GoalStore;
+use crate::lcu::client::{retry_policies, LcuClient};
+use crate::lcu::gameflow::GameflowWatcher;
+use crate::lcu::timeline::DraftTimelineStore;
+use crate::metrics::{record_error, start_metrics_flush, ErrorCategory, MetricsStore};
+use crate::model::{initialize_model, DraftRecommendationModel};
+use crate::patch_notes::PatchNotesStore;
+use crate::performance::current_profile;
+use crate::scripts::ScriptHost;
+use crate::settings::SettingsStore;
+use crate::status::champion_data_stale;
+use crate::storage::{snapshots::RankSnapshotStore, timelines::MatchTimelineStore, MatchStore};
+use crate::teams::TeamStore;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStage {
+    Settings,
+    ChampionData,
+    Stores,
+    BackgroundTasks,
+    Model,
+}
+
+/// Stages in the order they run.
+const STAGES: [StartupStage; 5] = [
+    StartupStage::Settings,
+    StartupStage::ChampionData,
+    StartupStage::Stores,
+    StartupStage::BackgroundTasks,
+    StartupStage::Model,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageStatus {
+    Running,
+    Done,
+    Failed,
+    Deferred, // Left for later, e.g. the model with lazy loading on
+}
+
+/// Payload of the `startup-progress` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupProgress {
+    pub stage: StartupStage,
+    pub status: StageStatus,
+    pub error: Option<String>,
+    pub finished: usize, // Stages done or failed so far
+    pub total: usize,
+}
+
+/// Latest progress per stage, for a window that subscribes after startup began.
+#[derive(Default)]
+pub struct StartupState {
+    progress: Vec<StartupProgress>,
+    model_loading: bool, // So a draft arriving mid-load doesn't start a second one
+    model_deferred: bool, // Startup left the model for the first draft
+}
+
+impl StartupState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Record a stage's new status and return the progress to announce.
+fn record(
+    progress: &mut Vec<StartupProgress>,
+    stage: StartupStage,
+    status: StageStatus,
+    error: Option<String>,
+) -> StartupProgress {
+    progress.retain(|p| p.stage != stage);
+    let finished = progress
+        .iter()
+        .filter(|p| p.status != StageStatus::Running)
+        .count()
+        + (status != StageStatus::Running) as usize;
+    let update = StartupProgress {
+        stage,
+        status,
+        error,
+        finished,
+        total: STAGES.len(),
+    };
+    progress.push(update.clone());
+    progress.sort_by_key(|p| STAGES.iter().position(|s| *s == p.stage));
+    update
+}
+
+fn report(app: &AppHandle, stage: StartupStage, status: StageStatus, error: Option<String>) {
+    let state = app.state::<Mutex<StartupState>>();
+    let Ok(mut state_guard) = state.lock() else {
+        return;
+    };
+    let update = record(&mut state_guard.progress, stage, status, error);
+    drop(state_guard);
+    let _ = app.emit("startup-progress", &update);
+}
+
+fn finish(app: &AppHandle, stage: StartupStage, result: Result<(), String>) {
+    match result {
+        Ok(()) => report(app, stage, StageStatus::Done, None),
+        Err(e) => {
+            eprintln!("Warning: Startup stage {:?} failed: {}", stage, e);
+            record_error(app, ErrorCategory::Startup);
+            report(app, stage, StageStatus::Failed, Some(e));
+        }
+    }
+}
+
+fn run_stage(app: &AppHandle, stage: StartupStage, run: impl FnOnce() -> Result<(), String>) {
+    report(app, stage, StageStatus::Running, None);
+    finish(app, stage, run());
+}
+
+/// Lock a managed store and run `load` on it.
+fn load_store<S: Send + 'static>(
+    app: &AppHandle,
+    name: &str,
+    load: impl FnOnce(&mut S) -> Result<(), String>,
+) -> Result<(), String> {
+    let store = app.state::<Mutex<S>>();
+    let mut guard = store
+        .lock()
+        .map_err(|e| format!("{}: lock error: {}", name, e))?;
+    load(&mut guard).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Every persisted store, loaded independently so one broken file doesn't
+/// keep the others from loading.
+fn load_stores(app: &AppHandle) -> Result<(), String> {
+    let enabled_scripts = app
+        .state::<Mutex<SettingsStore>>()
+        .lock()
+        .map(|s| s.get().enabled_scripts.clone())
+        .unwrap_or_default();
+    let errors: Vec<String> = [
+        load_store::<MatchStore>(app, "matches", |s| s.load()),
+        load_store::<RankSnapshotStore>(app, "rank snapshots", |s| s.load()),
+        load_store::<GoalStore>(app, "goals", |s| s.load()),
+        load_store::<DraftTimelineStore>(app, "draft timelines", |s| s.load()),
+        load_store::<MatchTimelineStore>(app, "match timelines", |s| s.load()),
+        load_store::<MetaShiftStore>(app, "meta shifts", |s| s.load()),
+        load_store::<PatchNotesStore>(app, "patch notes", |s| s.load()),
+        load_store::<TeamStore>(app, "teams", |s| s.load()),
+        load_store::<ScriptHost>(app, "scripts", |s| s.load(&enabled_scripts)),
+        load_store::<MetricsStore>(app, "metrics", |s| s.load()),
+    ]
+    .into_iter()
+    .filter_map(|r| r.err())
+    .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Download the latest champion data and store it, returning its version.
+async fn refresh_champion_data(app: &AppHandle) -> Result<String, String> {
+    let client = RiotApiClient::new(None);
+    let mut tracker = DownloadTracker::start(app, "champion-data", "Champion data");
+    let result = client.fetch_champion_data(&mut tracker).await;
+    tracker.complete(&result);
+    let data = result?;
+
+    let version = data.version.clone();
+    let cache = app.state::<Mutex<ChampionCache>>();
+    let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+    cache_guard.set_data(data)?;
+    Ok(version)
+}
+
+/// Put the cached champion data in memory, refreshing it from Data Dragon when
+/// there is none or it's more than a patch old. A failed refresh keeps the
+/// cached data. Emits `champions-ready` with the version once data is loaded.
+async fn load_champion_data(app: &AppHandle) -> Result<(), String> {
+    let (cached, updated_at) = {
+        let cache = app.state::<Mutex<ChampionCache>>();
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let version = cache_guard.hydrate()?;
+        let updated_at = version.as_deref().and_then(|v| cache_guard.updated_at(v));
+        (version, updated_at)
+    };
+
+    let version = match cached {
+        Some(version) if !champion_data_stale(updated_at, now_millis()) => version,
+        cached => match refresh_champion_data(app).await {
+            Ok(version) => version,
+            Err(e) => match cached {
+                Some(version) => {
+                    eprintln!(
+                        "Warning: Failed to refresh champion data {}: {}",
+                        version, e
+                    );
+                    version
+                }
+                None => return Err(e),
+            },
+        },
+    };
+
+    let _ = app.emit("champions-ready", &version);
+    Ok(())
+}
+
+fn start_background_tasks(app: &AppHandle) -> Result<(), String> {
+    let profile = current_profile(app);
+
+    let client = app
+        .state::<Arc<tokio::sync::Mutex<LcuClient>>>()
+        .inner()
+        .clone();
+    let policies = retry_policies(app);
+    let wine_prefixes = app
+        .state::<Mutex<SettingsStore>>()
+        .lock()
+        .map(|s| s.get().wine_prefixes.clone())
+        .unwrap_or_default();
+
+    // Watch gameflow for the whole session so post-game hooks run without the UI
+    let watcher = GameflowWatcher::new(client.clone(), app.clone(), profile.gameflow_polling_ms);
+    tauri::async_runtime::spawn(async move {
+        // Before the first poll, so every request follows the settings
+        {
+            let mut client_guard = client.lock().await;
+            client_guard.set_policies(policies);
+            client_guard.set_wine_prefixes(&wine_prefixes);
+        }
+        watcher.start_watching().await;
+    });
+
+    start_metrics_flush(app);
+
+    // Finish icon downloads interrupted by the last shutdown
+    if profile.prefetch_assets {
+        let handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            resume_pending_prefetches(&handle).await;
+        });
+    }
+
+    #[cfg(windows)]
+    crate::status::check_for_update(app);
+
+    Ok(())
+}
+
+async fn load_model(app: &AppHandle) -> Result<(), String> {
+    let handle = app.clone();
+    let model = tokio::task::spawn_blocking(move || {
+        initialize_model(&handle).map_err(|e| format!("Failed to load draft model: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Model loading stopped: {}", e))??;
+
+    let state = app.state::<Mutex<Option<Arc<DraftRecommendationModel>>>>();
+    let mut model_guard = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    *model_guard = Some(model);
+    Ok(())
+}
+
+/// Load the model in the background, reporting through the model stage,
+/// unless it's already loading.
+fn spawn_model_load(app: &AppHandle) {
+    let state = app.state::<Mutex<StartupState>>();
+    let Ok(mut state_guard) = state.lock() else {
+        return;
+    };
+    if std::mem::replace(&mut state_guard.model_loading, true) {
+        return;
+    }
+    drop(state_guard);
+    let app = app.clone();
+    report(&app, StartupStage::Model, StageStatus::Running, None);
+    tauri::async_runtime::spawn(async move {
+        // Remote flags decide how the model runs, so fetch them first
+        if let Err(e) = refresh_remote(&app).await {
+            eprintln!("Warning: Failed to fetch feature flags: {}", e);
+        }
+        let result = load_model(&app).await;
+        if let Ok(mut state_guard) = app.state::<Mutex<StartupState>>().lock() {
+            state_guard.model_loading = false;
+        }
+        finish(&app, StartupStage::Model, result);
+    });
+}
+
+/// With lazy loading, load the model when the first draft needs it. Once
+/// tried, a failed load isn't retried on every draft update.
+pub fn ensure_model_loaded(app: &AppHandle) {
+    let deferred = app
+        .try_state::<Mutex<StartupState>>()
+        .and_then(|state| {
+            state
+                .lock()
+                .ok()
+                .map(|mut s| std::mem::take(&mut s.model_deferred))
+        })
+        .unwrap_or(false);
+    if deferred {
+        spawn_model_load(app);
+    }
+}
+
+/// Bring the app up. Settings and the stores load before this returns so the
+/// first commands see them; champion data, background tasks and the model
+/// follow without blocking the window. Each stage reports through
+/// `startup-progress`, and a failed stage doesn't stop the ones after it.
+/// With `lazy_model_loading` the model stage is deferred to the first draft.
+pub fn run(app: &AppHandle) {
+    if let Ok(mut state_guard) = app.state::<Mutex<StartupState>>().lock() {
+        state_guard.progress.clear();
+    }
+
+    run_stage(app, StartupStage::Settings, || {
+        load_store::<SettingsStore>(app, "settings", |s| s.load())
+    });
+    report(app, StartupStage::ChampionData, StageStatus::Running, None);
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = load_champion_data(&handle).await;
+        finish(&handle, StartupStage::ChampionData, result);
+    });
+    run_stage(app, StartupStage::Stores, || load_stores(app));
+    run_stage(app, StartupStage::BackgroundTasks, || {
+        start_background_tasks(app)
+    });
+
+    let lazy = app
+        .state::<Mutex<SettingsStore>>()
+        .lock()
+        .is_ok_and(|s| s.get().lazy_model_loading);
+    if lazy {
+        if let Ok(mut state_guard) = app.state::<Mutex<StartupState>>().lock() {
+            state_guard.model_deferred = true;
+        }
+        report(app, StartupStage::Model, StageStatus::Deferred, None);
+    } else {
+        spawn_model_load(app);
+    }
+}
+
+/// Rebuild the model session, picking up changed ONNX settings.
+#[tauri::command]
+pub async fn reload_model(app: AppHandle) -> Result<(), String> {
+    load_model(&app).await
+}
+
+/// Progress so far, one entry per stage that has started.
+#[tauri::command]
+pub async fn get_startup_progress(
+    state: tauri::State<'_, Mutex<StartupState>>,
+) -> Result<Vec<StartupProgress>, String> {
+    let state_guard = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(state_guard.progress.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_counts_finished_stages() {
+        let mut progress = Vec::new();
+        let first = record(
+            &mut progress,
+            StartupStage::Settings,
+            StageStatus::Running,
+            None,
+        );
+        assert_eq!((first.finished, first.total), (0, 5));
+
+        record(
+            &mut progress,
+            StartupStage::Model,
+            StageStatus::Running,
+            None,
+        );
+        record(
+            &mut progress,
+            StartupStage::Settings,
+            StageStatus::Done,
+            None,
+        );
+        let failed = record(
+            &mut progress,
+            StartupStage::Model,
+            StageStatus::Failed,
+            Some("missing".to_string()),
+        );
+        assert_eq!(failed.finished, 2);
+        assert_eq!(progress.len(), 2, "one entry per stage");
+        assert_eq!(progress[0].stage, StartupStage::Settings);
+    }
+}
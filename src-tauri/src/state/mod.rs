@@ -0,0 +1,317 @@
+use crate::analytics::now_millis;
+use crate::champions::cache::ChampionCache;
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::{LcuClient, RankedStats, SummonerInfo};
+use crate::lcu::draft::DraftState;
+use crate::lcu::dto::ChampSelectSession;
+use crate::lcu::error::LcuError;
+use crate::series::follow_series;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Values the UI reads on most screens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateKey {
+    Summoner,
+    RankedStats,
+    RankedStatsAllQueues, // Including TFT and Arena
+    GameflowPhase,
+    DraftState,
+}
+
+impl StateKey {
+    /// How long a value is served without asking the client again.
+    fn max_age_ms(self) -> i64 {
+        match self {
+            StateKey::Summoner => 5 * 60_000,
+            StateKey::RankedStats | StateKey::RankedStatsAllQueues => 60_000,
+            StateKey::GameflowPhase => 2_000,
+            StateKey::DraftState => 250,
+        }
+    }
+}
+
+pub enum Cached {
+    Fresh(serde_json::Value),
+    Stale(serde_json::Value), // Served as is while a refresh runs
+    Missing,
+}
+
+struct Entry {
+    value: serde_json::Value,
+    fetched_at: i64,
+    refreshing: bool,
+}
+
+/// Payload of the `app-state-changed` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct StateChange {
+    pub key: StateKey,
+    pub value: Option<serde_json::Value>, // None when the value is no longer known
+}
+
+/// The latest summoner, ranked standings, gameflow phase and draft, served
+/// stale-while-revalidate: reads answer from here at once and refresh in the
+/// background once a value is older than its key allows.
+#[derive(Default)]
+pub struct AppState {
+    entries: HashMap<StateKey, Entry>,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: StateKey, now: i64) -> Cached {
+        match self.entries.get(&key) {
+            Some(entry) if now - entry.fetched_at < key.max_age_ms() => {
+                Cached::Fresh(entry.value.clone())
+            }
+            Some(entry) => Cached::Stale(entry.value.clone()),
+            None => Cached::Missing,
+        }
+    }
+
+    /// Claim the refresh of a stale value; false when one is already running.
+    pub fn begin_refresh(&mut self, key: StateKey) -> bool {
+        match self.entries.get_mut(&key) {
+            Some(entry) if !entry.refreshing => {
+                entry.refreshing = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Give up a refresh that failed, keeping the value for the next read to
+    /// try again.
+    pub fn end_refresh(&mut self, key: StateKey) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.refreshing = false;
+        }
+    }
+
+    /// Store a fetched value. True when it differs from the one held.
+    pub fn update(&mut self, key: StateKey, value: serde_json::Value, now: i64) -> bool {
+        let changed = self.entries.get(&key).map(|e| &e.value) != Some(&value);
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                fetched_at: now,
+                refreshing: false,
+            },
+        );
+        changed
+    }
+
+    /// Forget a value that can't be trusted anymore, such as the draft once
+    /// champion select is over. True when there was one.
+    pub fn invalidate(&mut self, key: StateKey) -> bool {
+        self.entries.remove(&key).is_some()
+    }
+}
+
+fn notify(app: &AppHandle, key: StateKey, value: Option<serde_json::Value>) {
+    let _ = app.emit("app-state-changed", StateChange { key, value });
+}
+
+/// Record a value fetched elsewhere, such as by the draft monitor.
+pub fn publish<T: Serialize>(app: &AppHandle, key: StateKey, value: &T) {
+    let Ok(value) = serde_json::to_value(value) else {
+        return;
+    };
+    let Some(state) = app.try_state::<Mutex<AppState>>() else {
+        return;
+    };
+    let changed = match state.lock() {
+        Ok(mut state_guard) => state_guard.update(key, value.clone(), now_millis()),
+        Err(_) => return,
+    };
+    if changed {
+        notify(app, key, Some(value));
+    }
+}
+
+pub fn invalidate(app: &AppHandle, key: StateKey) {
+    let Some(state) = app.try_state::<Mutex<AppState>>() else {
+        return;
+    };
+    let removed = state.lock().map(|mut s| s.invalidate(key)).unwrap_or(false);
+    if removed {
+        notify(app, key, None);
+    }
+}
+
+fn end_refresh(app: &AppHandle, key: StateKey) {
+    if let Some(state) = app.try_state::<Mutex<AppState>>() {
+        if let Ok(mut state_guard) = state.lock() {
+            state_guard.end_refresh(key);
+        }
+    }
+}
+
+/// Answer from the store, refreshing in the background when the value is
+/// stale and fetching in place only when there is none yet.
+pub async fn read<T, F, Fut>(app: &AppHandle, key: StateKey, fetch: F) -> Result<T, LcuError>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    F: FnOnce(AppHandle) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, LcuError>> + Send + 'static,
+{
+    let cached = {
+        let state = app.state::<Mutex<AppState>>();
+        let mut state_guard = state
+            .lock()
+            .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+        let cached = state_guard.get(key, now_millis());
+        if matches!(cached, Cached::Stale(_)) && state_guard.begin_refresh(key) {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                match fetch(app.clone()).await {
+                    Ok(value) => publish(&app, key, &value),
+                    // The client is gone or left the phase the value belonged to
+                    Err(LcuError::ClientNotRunning(_) | LcuError::NotInChampSelect) => {
+                        invalidate(&app, key)
+                    }
+                    // A timeout or server error says nothing about the value
+                    Err(_) => end_refresh(&app, key),
+                }
+            });
+            return decode(cached);
+        }
+        cached
+    };
+
+    match cached {
+        Cached::Missing => {
+            let value = fetch(app.clone()).await?;
+            publish(app, key, &value);
+            Ok(value)
+        }
+        cached => decode(cached),
+    }
+}
+
+fn decode<T: DeserializeOwned>(cached: Cached) -> Result<T, LcuError> {
+    match cached {
+        Cached::Fresh(value) | Cached::Stale(value) => Ok(serde_json::from_value(value)?),
+        Cached::Missing => Err(LcuError::Other("No cached value".to_string())),
+    }
+}
+
+fn client(app: &AppHandle) -> Arc<tokio::sync::Mutex<LcuClient>> {
+    app.state::<Arc<tokio::sync::Mutex<LcuClient>>>()
+        .inner()
+        .clone()
+}
+
+pub async fn current_summoner(app: &AppHandle) -> Result<SummonerInfo, LcuError> {
+    read(app, StateKey::Summoner, |app| async move {
+        client(&app).lock().await.get_current_summoner().await
+    })
+    .await
+}
+
+pub async fn ranked_stats(
+    app: &AppHandle,
+    include_all_queues: bool,
+) -> Result<Vec<RankedStats>, LcuError> {
+    let key = if include_all_queues {
+        StateKey::RankedStatsAllQueues
+    } else {
+        StateKey::RankedStats
+    };
+    read(app, key, move |app| async move {
+        client(&app)
+            .lock()
+            .await
+            .get_ranked_stats(include_all_queues)
+            .await
+    })
+    .await
+}
+
+pub async fn gameflow_phase(app: &AppHandle) -> Result<String, LcuError> {
+    read(app, StateKey::GameflowPhase, |app| async move {
+        client(&app).lock().await.get_gameflow_phase().await
+    })
+    .await
+}
+
+pub async fn draft_state(app: &AppHandle) -> Result<DraftState, LcuError> {
+    read(app, StateKey::DraftState, |app| async move {
+        let session = client(&app).lock().await.get_draft_session().await?;
+        resolve_draft(&app, &session)
+    })
+    .await
+}
+
+/// The draft of `session`, with spell names and the running series applied.
+pub fn resolve_draft(
+    app: &AppHandle,
+    session: &ChampSelectSession,
+) -> Result<DraftState, LcuError> {
+    let mut state = crate::lcu::draft::draft_state(session);
+    let cache = app.state::<Mutex<ChampionCache>>();
+    let cache_guard = cache
+        .lock()
+        .map_err(|e| LcuError::Other(format!("Lock error: {}", e)))?;
+    state.resolve_spell_names(&cache_guard);
+    follow_series(app, &mut state);
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_values_go_stale_and_refresh_once() {
+        let mut state = AppState::new();
+        assert!(matches!(
+            state.get(StateKey::GameflowPhase, 0),
+            Cached::Missing
+        ));
+        assert!(
+            !state.begin_refresh(StateKey::GameflowPhase),
+            "nothing to refresh"
+        );
+
+        assert!(state.update(StateKey::GameflowPhase, json!("Lobby"), 1_000));
+        assert!(matches!(
+            state.get(StateKey::GameflowPhase, 2_000),
+            Cached::Fresh(_)
+        ));
+        assert!(matches!(
+            state.get(StateKey::GameflowPhase, 3_000),
+            Cached::Stale(_)
+        ));
+        assert!(state.begin_refresh(StateKey::GameflowPhase));
+        assert!(
+            !state.begin_refresh(StateKey::GameflowPhase),
+            "already running"
+        );
+        state.end_refresh(StateKey::GameflowPhase);
+        assert!(matches!(
+            state.get(StateKey::GameflowPhase, 3_000),
+            Cached::Stale(_)
+        ));
+        assert!(
+            state.begin_refresh(StateKey::GameflowPhase),
+            "a failed refresh is tried again"
+        );
+
+        assert!(!state.update(StateKey::GameflowPhase, json!("Lobby"), 3_100));
+        assert!(state.update(StateKey::GameflowPhase, json!("Matchmaking"), 3_200));
+        assert!(state.invalidate(StateKey::GameflowPhase));
+        assert!(!state.invalidate(StateKey::GameflowPhase));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::analytics::now_millis;
+use crate::champions::cache::ChampionCache;
+use crate::downloads::DownloadRegistry;
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::LcuClient;
+use crate::lcu::game_process::InGameTracker;
+use crate::lcu::lockfile::ClientProfile;
+use crate::lcu::monitor::MonitorManager;
+use crate::lcu::share::ShareSession;
+use crate::model::DraftRecommendationModel;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// Champion data older than a patch is probably behind the live game.
+const CHAMPION_DATA_MAX_AGE_MS: i64 = 14 * 24 * 60 * 60 * 1000;
+
+/// Newer version found by the startup update check, if any.
+#[derive(Default)]
+pub struct PendingUpdate {
+    version: Option<String>,
+}
+
+impl PendingUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LcuStatus {
+    pub connected: bool,
+    pub phase: Option<String>,
+    pub port: Option<u16>,
+    pub profile: Option<ClientProfile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChampionDataStatus {
+    pub version: Option<String>,
+    pub updated_at: Option<i64>,
+    pub stale: bool,
+}
+
+/// Everything the status bar shows, in one payload.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppStatus {
+    pub lcu: LcuStatus,
+    pub model_loaded: bool,
+    pub champion_data: ChampionDataStatus,
+    pub background_tasks: Vec<String>, // e.g. "draft_monitor", "download:<id>"
+    pub in_game: bool,
+    pub pending_update: Option<String>, // Version available to install
+}
+
+/// No data, or data written more than a patch ago.
+pub fn champion_data_stale(updated_at: Option<i64>, now: i64) -> bool {
+    updated_at.is_none_or(|at| now - at > CHAMPION_DATA_MAX_AGE_MS)
+}
+
+/// Look for an app update once at startup so the status can mention it.
+#[cfg(windows)]
+pub fn check_for_update(app: &AppHandle) {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Ok(updater) = app.updater() else {
+            return;
+        };
+        if let Ok(Some(update)) = updater.check().await {
+            let pending = app.state::<Mutex<PendingUpdate>>();
+            if let Ok(mut pending_guard) = pending.lock() {
+                pending_guard.version = Some(update.version);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_app_status(
+    app: AppHandle,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    model: tauri::State<'_, Mutex<Option<Arc<DraftRecommendationModel>>>>,
+    cache: tauri::State<'_, Mutex<ChampionCache>>,
+    downloads: tauri::State<'_, Mutex<DownloadRegistry>>,
+    monitor: tauri::State<'_, Mutex<MonitorManager>>,
+    share: tauri::State<'_, Mutex<ShareSession>>,
+) -> Result<AppStatus, String> {
+    let lcu = {
+        let mut client_guard = client.lock().await;
+        let phase = client_guard.get_gameflow_phase().await.ok();
+        let (port, profile) = match (phase.is_some(), client_guard.get_lockfile()) {
+            (true, Ok(lockfile)) => (Some(lockfile.port), client_guard.profile().ok()),
+            _ => (None, None),
+        };
+        LcuStatus {
+            connected: phase.is_some(),
+            phase,
+            port,
+            profile,
+        }
+    };
+
+    let model_loaded = model
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .is_some();
+
+    let champion_data = {
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let version = cache_guard.get_version();
+        let updated_at = version.as_deref().and_then(|v| cache_guard.updated_at(v));
+        ChampionDataStatus {
+            stale: champion_data_stale(updated_at, now_millis()),
+            version,
+            updated_at,
+        }
+    };
+
+    let mut background_tasks = vec!["gameflow_watcher".to_string()];
+    if monitor
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .is_running()
+    {
+        background_tasks.push("draft_monitor".to_string());
+    }
+    if share
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .viewer_running()
+    {
+        background_tasks.push("draft_viewer".to_string());
+    }
+    {
+        let downloads_guard = downloads.lock().map_err(|e| format!("Lock error: {}", e))?;
+        background_tasks.extend(
+            downloads_guard
+                .running()
+                .into_iter()
+                .map(|id| format!("download:{}", id)),
+        );
+    }
+
+    Ok(AppStatus {
+        lcu,
+        model_loaded,
+        champion_data,
+        background_tasks,
+        in_game: app
+            .state::<Mutex<InGameTracker>>()
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?
+            .in_game(),
+        pending_update: app
+            .state::<Mutex<PendingUpdate>>()
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?
+            .version
+            .clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_champion_data_staleness() {
+        let day = 24 * 60 * 60 * 1000;
+        let now = 100 * day;
+        assert!(champion_data_stale(None, now));
+        assert!(!champion_data_stale(Some(now - 3 * day), now));
+        assert!(champion_data_stale(Some(now - 15 * day), now));
+    }
+}
@@ -0,0 +1,465 @@
+pub mod snapshots;
+pub mod timelines;
+pub mod usage;
+
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::LcuClient;
+use crate::lcu::lockfile::ClientProfile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use timelines::{fetch_lcu_timeline, MatchTimeline, MatchTimelineStore};
+use usage::data_dir;
+
+/// One of the ten players in a stored game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredParticipant {
+    pub puuid: String,
+    pub game_name: Option<String>,
+    pub tag_line: Option<String>,
+    pub champion_id: i64,
+    pub team_id: i64,
+    pub position: Option<String>, // TOP, JUNGLE, MIDDLE, BOTTOM or UTILITY when the client reports a lane
+    pub win: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    #[serde(default)]
+    pub items: Vec<i64>, // Final build, item0..item6 with empty slots dropped
+    #[serde(default)]
+    pub runes: Vec<i64>, // Primary style, sub style, then the six perks
+    #[serde(default)]
+    pub cs: Option<i32>, // Lane minions plus jungle monsters; None for games stored before it was kept
+    #[serde(default)]
+    pub vision_score: Option<i32>,
+    #[serde(default)]
+    pub damage_to_champions: Option<i64>,
+    #[serde(default)]
+    pub first_blood: Option<bool>, // Got or assisted the first kill of the game
+    #[serde(default)]
+    pub gold_at_10: Option<i32>, // From the game timeline, when the client had one
+    #[serde(default)]
+    pub participant_id: Option<i64>, // Links the player to their timeline frames
+    #[serde(default)]
+    pub spells: Vec<i64>, // Summoner spell ids, D then F; empty for games stored before they were kept
+}
+
+impl StoredParticipant {
+    /// The keystone: the first perk after the style ids. Style ids are the
+    /// only rune ids that are whole hundreds.
+    pub fn keystone(&self) -> Option<i64> {
+        self.runes.iter().copied().find(|id| id % 100 != 0)
+    }
+
+    /// The secondary rune style, when both styles were recorded.
+    pub fn secondary_style(&self) -> Option<i64> {
+        let mut styles = self.runes.iter().copied().filter(|id| id % 100 == 0);
+        styles.nth(1)
+    }
+}
+
+/// A finished game as seen by the local player, with every participant kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMatch {
+    pub game_id: i64,
+    pub queue_id: i32,
+    pub game_mode: String,
+    pub game_creation: i64,
+    pub game_duration: i32,
+    pub owner_puuid: String,
+    pub participants: Vec<StoredParticipant>,
+    #[serde(default)]
+    pub game_version: Option<String>, // Client version, e.g. "14.3.567.1234"
+}
+
+impl StoredMatch {
+    /// The local player's own entry in this game.
+    pub fn owner(&self) -> Option<&StoredParticipant> {
+        self.participants
+            .iter()
+            .find(|p| p.puuid == self.owner_puuid)
+    }
+
+    /// The enemy in the owner's position. `None` when the owner's lane is
+    /// unknown or nobody on the other team was reported in it.
+    pub fn lane_opponent(&self) -> Option<&StoredParticipant> {
+        let owner = self.owner()?;
+        let position = owner.position.as_ref()?;
+        self.participants
+            .iter()
+            .find(|p| p.team_id != owner.team_id && p.position.as_ref() == Some(position))
+    }
+}
+
+/// Stored position names, in lane order.
+pub const POSITIONS: [&str; 5] = ["TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY"];
+
+/// Map the LCU's lane/role pair onto the position names the model uses.
+fn parse_position(timeline: &serde_json::Value) -> Option<String> {
+    let lane = timeline["lane"].as_str().unwrap_or("");
+    let role = timeline["role"].as_str().unwrap_or("");
+
+    let position = match (lane, role) {
+        ("TOP", _) => "TOP",
+        ("JUNGLE", _) => "JUNGLE",
+        ("MIDDLE" | "MID", _) => "MIDDLE",
+        ("BOTTOM" | "BOT", "DUO_SUPPORT") => "UTILITY",
+        ("BOTTOM" | "BOT", _) => "BOTTOM",
+        _ => return None,
+    };
+    Some(position.to_string())
+}
+
+/// Non-zero ids of the numbered stat fields `{prefix}{from}..={prefix}{to}`.
+fn numbered_ids(stats: &serde_json::Value, prefix: &str, from: u32, to: u32) -> Vec<i64> {
+    (from..=to)
+        .filter_map(|i| stats[format!("{}{}", prefix, i)].as_i64())
+        .filter(|id| *id > 0)
+        .collect()
+}
+
+/// Timeline frame nearest to the ten minute mark.
+const TEN_MINUTES_MS: i64 = 10 * 60 * 1000;
+
+/// Build a stored match from `/lol-match-history/v1/games/{gameId}`.
+///
+/// Returns `None` when the owner isn't among the participants, which means the
+/// payload isn't one of their games (or is too incomplete to be useful).
+pub fn parse_game_details(game: &serde_json::Value, owner_puuid: &str) -> Option<StoredMatch> {
+    let identities = game["participantIdentities"].as_array()?;
+    let stats_array = game["participants"].as_array()?;
+
+    let participants: Vec<StoredParticipant> = identities
+        .iter()
+        .filter_map(|identity| {
+            let participant_id = identity["participantId"].as_i64()?;
+            let player = &identity["player"];
+            let participant = stats_array
+                .iter()
+                .find(|p| p["participantId"].as_i64() == Some(participant_id))?;
+            let stats = &participant["stats"];
+            // Win can be boolean or string "Win"/"Fail"
+            let win = stats["win"]
+                .as_bool()
+                .unwrap_or_else(|| stats["win"].as_str().map(|s| s == "Win").unwrap_or(false));
+
+            Some(StoredParticipant {
+                puuid: player["puuid"].as_str().unwrap_or("").to_string(),
+                game_name: player["gameName"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                tag_line: player["tagLine"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                champion_id: participant["championId"].as_i64().unwrap_or(0),
+                team_id: participant["teamId"].as_i64().unwrap_or(0),
+                position: parse_position(&participant["timeline"]),
+                win,
+                kills: stats["kills"].as_i64().unwrap_or(0) as i32,
+                deaths: stats["deaths"].as_i64().unwrap_or(0) as i32,
+                assists: stats["assists"].as_i64().unwrap_or(0) as i32,
+                items: numbered_ids(stats, "item", 0, 6),
+                runes: ["perkPrimaryStyle", "perkSubStyle"]
+                    .iter()
+                    .filter_map(|k| stats[*k].as_i64())
+                    .filter(|id| *id > 0)
+                    .chain(numbered_ids(stats, "perk", 0, 5))
+                    .collect(),
+                cs: stats["totalMinionsKilled"].as_i64().map(|minions| {
+                    (minions + stats["neutralMinionsKilled"].as_i64().unwrap_or(0)) as i32
+                }),
+                vision_score: stats["visionScore"].as_i64().map(|v| v as i32),
+                damage_to_champions: stats["totalDamageDealtToChampions"].as_i64(),
+                first_blood: stats["firstBloodKill"]
+                    .as_bool()
+                    .map(|kill| kill || stats["firstBloodAssist"].as_bool().unwrap_or(false)),
+                gold_at_10: None,
+                participant_id: Some(participant_id),
+                spells: ["spell1Id", "spell2Id"]
+                    .iter()
+                    .filter_map(|k| participant[*k].as_i64())
+                    .filter(|id| *id > 0)
+                    .collect(),
+            })
+        })
+        .collect();
+
+    if !participants.iter().any(|p| p.puuid == owner_puuid) {
+        return None;
+    }
+
+    Some(StoredMatch {
+        game_id: game["gameId"].as_i64()?,
+        queue_id: game["queueId"].as_i64().unwrap_or(0) as i32,
+        game_mode: game["gameMode"].as_str().unwrap_or("").to_string(),
+        game_creation: game["gameCreation"].as_i64().unwrap_or(0),
+        game_duration: game["gameDuration"].as_i64().unwrap_or(0) as i32,
+        owner_puuid: owner_puuid.to_string(),
+        participants,
+        game_version: game["gameVersion"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+    })
+}
+
+/// Local, append-only store of the player's finished games, kept as a JSON file
+/// next to the app's other data.
+pub struct MatchStore {
+    matches: BTreeMap<i64, StoredMatch>,
+    store_path: PathBuf,
+}
+
+impl MatchStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("matches.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            matches: BTreeMap::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read match store: {}", e))?;
+        let matches: Vec<StoredMatch> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse match store: {}", e))?;
+
+        self.matches = matches.into_iter().map(|m| (m.game_id, m)).collect();
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let matches: Vec<&StoredMatch> = self.matches.values().collect();
+        let json = serde_json::to_string(&matches)
+            .map_err(|e| format!("Failed to serialize match store: {}", e))?;
+
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write match store: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn contains(&self, game_id: i64) -> bool {
+        self.matches.contains_key(&game_id)
+    }
+
+    /// Add a match, returning false if it was already stored.
+    pub fn insert(&mut self, stored: StoredMatch) -> bool {
+        if self.contains(stored.game_id) {
+            return false;
+        }
+        self.matches.insert(stored.game_id, stored);
+        true
+    }
+
+    /// All stored matches, oldest first.
+    pub fn matches(&self) -> impl DoubleEndedIterator<Item = &StoredMatch> {
+        self.matches.values()
+    }
+}
+
+/// Fill in each participant's gold at ten minutes from the game's timeline.
+/// Games that ended before ten minutes are left without.
+pub fn apply_timeline(stored: &mut StoredMatch, timeline: &MatchTimeline) {
+    for participant in &mut stored.participants {
+        participant.gold_at_10 = participant
+            .participant_id
+            .and_then(|id| timeline.participant_at(id, TEN_MINUTES_MS))
+            .map(|frame| frame.total_gold);
+    }
+}
+
+/// Pull the most recent games from the client and store any we don't have yet.
+///
+/// Returns how many new matches were stored. Games whose details can't be fetched
+/// are skipped and picked up again on the next sync. Their timelines are kept
+/// in `timelines` when the client still has them.
+pub async fn sync_recent_matches<C: LcuApi>(
+    client: &mut C,
+    store: &Mutex<MatchStore>,
+    timelines: &Mutex<MatchTimelineStore>,
+    count: usize,
+) -> Result<usize, String> {
+    let summoner = client.get_current_summoner().await?;
+    let history = client
+        .get_match_history_for(&summoner.puuid, 0, count)
+        .await?;
+
+    let missing: Vec<i64> = {
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        history
+            .iter()
+            .map(|g| g.game_id)
+            .filter(|id| !store_guard.contains(*id))
+            .collect()
+    };
+
+    let mut fetched = Vec::new();
+    let mut fetched_timelines = Vec::new();
+    for game_id in missing {
+        if let Ok(details) = client.get_game_details(game_id).await {
+            if let Some(mut stored) = parse_game_details(&details, &summoner.puuid) {
+                // Older games may have no timeline left; store them without
+                if let Ok(timeline) = fetch_lcu_timeline(client, game_id).await {
+                    apply_timeline(&mut stored, &timeline);
+                    fetched_timelines.push(timeline);
+                }
+                fetched.push(stored);
+            }
+        }
+    }
+
+    if !fetched_timelines.is_empty() {
+        let mut timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+        for timeline in fetched_timelines {
+            timelines_guard.insert(timeline);
+        }
+        timelines_guard.save()?;
+    }
+
+    let mut store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let added = fetched
+        .into_iter()
+        .filter(|stored| store_guard.insert(stored.clone()))
+        .count();
+    if added > 0 {
+        store_guard.save()?;
+    }
+    Ok(added)
+}
+
+#[tauri::command]
+pub async fn sync_match_history(
+    count: Option<usize>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    store: tauri::State<'_, Mutex<MatchStore>>,
+    timelines: tauri::State<'_, Mutex<MatchTimelineStore>>,
+) -> Result<usize, String> {
+    let mut client_guard = client.lock().await;
+    if client_guard.profile()? == ClientProfile::Pbe {
+        return Err("PBE games are not stored".to_string());
+    }
+    sync_recent_matches(&mut *client_guard, &store, &timelines, count.unwrap_or(20)).await
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Game details payload with the given (puuid, champion, team, lane, role) participants.
+    /// Team 100 wins.
+    pub(crate) fn game_details(
+        game_id: i64,
+        queue_id: i64,
+        players: &[(&str, i64, i64, &str, &str)],
+    ) -> serde_json::Value {
+        let identities: Vec<_> = players
+            .iter()
+            .enumerate()
+            .map(|(i, (puuid, ..))| {
+                json!({ "participantId": i + 1, "player": { "puuid": puuid, "gameName": puuid, "tagLine": "EUW" } })
+            })
+            .collect();
+        let participants: Vec<_> = players
+            .iter()
+            .enumerate()
+            .map(|(i, (_, champion, team, lane, role))| {
+                json!({
+                    "participantId": i + 1,
+                    "championId": champion,
+                    "spell1Id": 4, "spell2Id": 14,
+                    "teamId": team,
+                    "stats": {
+                        "win": *team == 100, "kills": 1, "deaths": 2, "assists": 3,
+                        "item0": 3031, "item1": 0, "item6": 3340,
+                        "perkPrimaryStyle": 8000, "perkSubStyle": 8100, "perk0": 8005,
+                        "totalMinionsKilled": 150, "neutralMinionsKilled": 30,
+                        "visionScore": 24, "totalDamageDealtToChampions": 12000,
+                        "firstBloodKill": false, "firstBloodAssist": i == 0
+                    },
+                    "timeline": { "lane": lane, "role": role }
+                })
+            })
+            .collect();
+        json!({
+            "gameId": game_id,
+            "queueId": queue_id,
+            "gameMode": "CLASSIC",
+            "gameCreation": game_id * 1000,
+            "gameDuration": 1800,
+            "gameVersion": "14.3.567.1234",
+            "participantIdentities": identities,
+            "participants": participants
+        })
+    }
+
+    #[test]
+    fn test_parse_game_details() {
+        let game = game_details(
+            1,
+            420,
+            &[
+                ("me", 266, 100, "TOP", "SOLO"),
+                ("ally", 412, 100, "BOTTOM", "DUO_SUPPORT"),
+                ("enemy", 157, 200, "MIDDLE", "SOLO"),
+            ],
+        );
+
+        let stored = parse_game_details(&game, "me").unwrap();
+        assert_eq!(stored.participants.len(), 3);
+        assert_eq!(stored.owner().unwrap().position.as_deref(), Some("TOP"));
+        assert_eq!(stored.participants[1].position.as_deref(), Some("UTILITY"));
+        assert!(!stored.participants[2].win);
+        assert_eq!(stored.owner().unwrap().items, vec![3031, 3340]);
+        assert_eq!(stored.owner().unwrap().runes, vec![8000, 8100, 8005]);
+        assert_eq!(stored.owner().unwrap().spells, vec![4, 14]);
+        assert_eq!(stored.owner().unwrap().cs, Some(180));
+        assert_eq!(stored.game_version.as_deref(), Some("14.3.567.1234"));
+        assert_eq!(stored.owner().unwrap().first_blood, Some(true));
+        assert_eq!(stored.participants[1].first_blood, Some(false));
+
+        assert!(parse_game_details(&game, "someone-else").is_none());
+    }
+
+    #[test]
+    fn test_apply_timeline_gold_at_10() {
+        let game = game_details(
+            1,
+            420,
+            &[
+                ("me", 266, 100, "TOP", "SOLO"),
+                ("enemy", 157, 200, "TOP", "SOLO"),
+            ],
+        );
+        let json = json!({
+            "frames": [
+                { "timestamp": 540_012, "participantFrames": { "1": { "totalGold": 2900 }, "2": { "totalGold": 3100 } } },
+                { "timestamp": 600_034, "participantFrames": { "1": { "totalGold": 3500 }, "2": { "totalGold": 3300 } } }
+            ]
+        });
+        let mut timeline = timelines::parse_timeline(1, &json).unwrap();
+
+        let mut stored = parse_game_details(&game, "me").unwrap();
+        apply_timeline(&mut stored, &timeline);
+        assert_eq!(stored.owner().unwrap().gold_at_10, Some(3500));
+        assert_eq!(stored.participants[1].gold_at_10, Some(3300));
+
+        timeline.frames.truncate(1);
+        let mut short = parse_game_details(&game, "me").unwrap();
+        apply_timeline(&mut short, &timeline);
+        assert_eq!(short.owner().unwrap().gold_at_10, None);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::lcu::client::RankedStats;
+use crate::storage::usage::data_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RankChangeKind {
+    Promoted,
+    Demoted,
+    EnteredPromos,
+}
+
+/// Ranked standing in one queue at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankSnapshot {
+    pub timestamp: i64, // Epoch millis
+    pub queue_type: String,
+    pub tier: String,
+    pub rank: String,
+    pub league_points: i32,
+    pub wins: i32,
+    pub losses: i32,
+    #[serde(default)]
+    pub in_promos: bool,
+    #[serde(default)]
+    pub change: Option<RankChangeKind>, // Set on the snapshot where a rank change happened
+}
+
+impl RankSnapshot {
+    pub fn from_stats(stats: &RankedStats, timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            queue_type: stats.queue_type.clone(),
+            tier: stats.tier.clone(),
+            rank: stats.rank.clone(),
+            league_points: stats.league_points,
+            wins: stats.wins,
+            losses: stats.losses,
+            in_promos: stats.miniseries.is_some(),
+            change: None,
+        }
+    }
+
+    fn same_standing(&self, other: &RankSnapshot) -> bool {
+        self.queue_type == other.queue_type
+            && self.tier == other.tier
+            && self.rank == other.rank
+            && self.league_points == other.league_points
+            && self.wins == other.wins
+            && self.losses == other.losses
+            && self.in_promos == other.in_promos
+    }
+}
+
+/// History of ranked snapshots, only growing when something actually changed.
+pub struct RankSnapshotStore {
+    snapshots: Vec<RankSnapshot>,
+    store_path: PathBuf,
+}
+
+impl RankSnapshotStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("rank_snapshots.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            snapshots: Vec::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read rank snapshots: {}", e))?;
+        self.snapshots = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse rank snapshots: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.snapshots)
+            .map_err(|e| format!("Failed to serialize rank snapshots: {}", e))?;
+
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write rank snapshots: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn latest(&self, queue_type: &str) -> Option<&RankSnapshot> {
+        self.snapshots
+            .iter()
+            .rev()
+            .find(|s| s.queue_type == queue_type)
+    }
+
+    /// Record the given standings, skipping queues that haven't changed since
+    /// their last snapshot. Returns the snapshots that were added.
+    pub fn record(&mut self, stats: &[RankedStats], timestamp: i64) -> Vec<RankSnapshot> {
+        let mut added = Vec::new();
+
+        for queue in stats {
+            let snapshot = RankSnapshot::from_stats(queue, timestamp);
+            if self
+                .latest(&queue.queue_type)
+                .is_some_and(|last| last.same_standing(&snapshot))
+            {
+                continue;
+            }
+            self.snapshots.push(snapshot.clone());
+            added.push(snapshot);
+        }
+
+        added
+    }
+
+    /// Flag the latest snapshot of a queue as the moment its rank changed.
+    pub fn mark_change(&mut self, queue_type: &str, kind: RankChangeKind) {
+        if let Some(snapshot) = self
+            .snapshots
+            .iter_mut()
+            .rev()
+            .find(|s| s.queue_type == queue_type)
+        {
+            snapshot.change = Some(kind);
+        }
+    }
+
+    /// Snapshots taken within `[start, end]`, oldest first. Open bounds are unbounded.
+    pub fn between(&self, start: Option<i64>, end: Option<i64>) -> Vec<&RankSnapshot> {
+        self.snapshots
+            .iter()
+            .filter(|s| start.is_none_or(|t| s.timestamp >= t))
+            .filter(|s| end.is_none_or(|t| s.timestamp <= t))
+            .collect()
+    }
+}
@@ -0,0 +1,272 @@
+use crate::lcu::api::LcuApi;
+use crate::lcu::client::LcuClient;
+use crate::settings::SettingsStore;
+use crate::storage::usage::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::time::Duration;
+
+/// Timelines kept on disk; the oldest games are dropped past this.
+const MAX_TIMELINES: usize = 100;
+
+/// One participant at one frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticipantFrame {
+    pub participant_id: i64,
+    pub total_gold: i32,
+    pub xp: i32,
+    pub cs: i32, // Lane minions plus jungle monsters
+}
+
+/// Everyone's totals at one point of the game, roughly once a minute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineFrame {
+    pub timestamp: i64, // Millis since the game started
+    pub participants: Vec<ParticipantFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchTimeline {
+    pub game_id: i64,
+    pub frames: Vec<TimelineFrame>, // In game order
+}
+
+impl MatchTimeline {
+    /// The first frame at or after `timestamp`, or `None` if the game ended before.
+    pub fn frame_at(&self, timestamp: i64) -> Option<&TimelineFrame> {
+        self.frames.iter().find(|f| f.timestamp >= timestamp)
+    }
+
+    pub fn participant_at(&self, participant_id: i64, timestamp: i64) -> Option<&ParticipantFrame> {
+        self.frame_at(timestamp)?
+            .participants
+            .iter()
+            .find(|p| p.participant_id == participant_id)
+    }
+}
+
+/// Parse a timeline from the client's `/lol-match-history/v1/game-timelines/{gameId}`
+/// or Riot's match-v5 timeline, which wraps the same frames in `info`.
+pub fn parse_timeline(game_id: i64, json: &serde_json::Value) -> Option<MatchTimeline> {
+    let root = if json["info"].is_object() {
+        &json["info"]
+    } else {
+        json
+    };
+    let frames = root["frames"]
+        .as_array()?
+        .iter()
+        .map(|frame| {
+            let mut participants: Vec<ParticipantFrame> = frame["participantFrames"]
+                .as_object()
+                .map(|by_id| {
+                    by_id
+                        .iter()
+                        .filter_map(|(key, p)| {
+                            let participant_id =
+                                p["participantId"].as_i64().or_else(|| key.parse().ok())?;
+                            let stat = |k: &str| p[k].as_i64().unwrap_or(0) as i32;
+                            Some(ParticipantFrame {
+                                participant_id,
+                                total_gold: stat("totalGold"),
+                                xp: stat("xp"),
+                                cs: stat("minionsKilled") + stat("jungleMinionsKilled"),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            participants.sort_by_key(|p| p.participant_id);
+            TimelineFrame {
+                timestamp: frame["timestamp"].as_i64().unwrap_or(0),
+                participants,
+            }
+        })
+        .collect();
+    Some(MatchTimeline { game_id, frames })
+}
+
+/// Timelines of recent games, keyed by game id.
+pub struct MatchTimelineStore {
+    timelines: BTreeMap<i64, MatchTimeline>,
+    store_path: PathBuf,
+}
+
+impl MatchTimelineStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("match_timelines.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            timelines: BTreeMap::new(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read match timelines: {}", e))?;
+        self.timelines = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse match timelines: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string(&self.timelines)
+            .map_err(|e| format!("Failed to serialize match timelines: {}", e))?;
+        fs::write(&self.store_path, json)
+            .map_err(|e| format!("Failed to write match timelines: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Game ids only grow, so the lowest ones are the oldest games.
+    pub fn insert(&mut self, timeline: MatchTimeline) {
+        self.timelines.insert(timeline.game_id, timeline);
+        while self.timelines.len() > MAX_TIMELINES {
+            self.timelines.pop_first();
+        }
+    }
+
+    pub fn get(&self, game_id: i64) -> Option<&MatchTimeline> {
+        self.timelines.get(&game_id)
+    }
+}
+
+/// Regional routing value match-v5 is served under for a platform.
+fn riot_region(platform: &str) -> &'static str {
+    match platform.to_uppercase().as_str() {
+        "NA1" | "BR1" | "LA1" | "LA2" => "americas",
+        "KR" | "JP1" => "asia",
+        "OC1" | "PH2" | "SG2" | "TH2" | "TW2" | "VN2" => "sea",
+        _ => "europe",
+    }
+}
+
+/// A timeline from the Riot API, for games the client no longer serves.
+pub async fn fetch_riot_timeline(
+    api_key: &str,
+    platform: &str,
+    game_id: i64,
+) -> Result<MatchTimeline, String> {
+    let url = format!(
+        "https://{}.api.riotgames.com/lol/match/v5/matches/{}_{}/timeline",
+        riot_region(platform),
+        platform.to_uppercase(),
+        game_id
+    );
+    let json: serde_json::Value = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        .get(url)
+        .header("X-Riot-Token", api_key)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("HTTP error: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse timeline: {}", e))?;
+    parse_timeline(game_id, &json).ok_or_else(|| "Timeline has no frames".to_string())
+}
+
+pub async fn fetch_lcu_timeline<C: LcuApi>(
+    client: &mut C,
+    game_id: i64,
+) -> Result<MatchTimeline, String> {
+    let json = client.get_game_timeline(game_id).await?;
+    parse_timeline(game_id, &json).ok_or_else(|| "Timeline has no frames".to_string())
+}
+
+/// A game's timeline: stored, else from the Riot API when a key is configured,
+/// else from the client. Fetched timelines are stored.
+#[tauri::command]
+pub async fn get_match_timeline(
+    game_id: i64,
+    settings: tauri::State<'_, Mutex<SettingsStore>>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+    timelines: tauri::State<'_, Mutex<MatchTimelineStore>>,
+) -> Result<MatchTimeline, String> {
+    {
+        let timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(timeline) = timelines_guard.get(game_id) {
+            return Ok(timeline.clone());
+        }
+    }
+
+    let riot = {
+        let settings_guard = settings.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let s = settings_guard.get();
+        s.riot_api_key
+            .clone()
+            .filter(|k| !k.trim().is_empty())
+            .zip(s.riot_platform.clone())
+    };
+    let mut result = Err("No timeline source available".to_string());
+    if let Some((api_key, platform)) = riot {
+        result = fetch_riot_timeline(api_key.trim(), &platform, game_id).await;
+    }
+    if result.is_err() {
+        let mut client_guard = client.lock().await;
+        result = fetch_lcu_timeline(&mut *client_guard, game_id).await;
+    }
+    let timeline = result?;
+
+    let mut timelines_guard = timelines.lock().map_err(|e| format!("Lock error: {}", e))?;
+    timelines_guard.insert(timeline.clone());
+    timelines_guard.save()?;
+    Ok(timeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_lcu_and_riot_timelines() {
+        let frames = json!([
+            { "timestamp": 0, "participantFrames": {
+                "1": { "participantId": 1, "totalGold": 500, "xp": 0, "minionsKilled": 0, "jungleMinionsKilled": 0 }
+            } },
+            { "timestamp": 600_034, "participantFrames": {
+                "2": { "participantId": 2, "totalGold": 3300, "xp": 4200, "minionsKilled": 70, "jungleMinionsKilled": 4 },
+                "1": { "participantId": 1, "totalGold": 3500, "xp": 4800, "minionsKilled": 80, "jungleMinionsKilled": 2 }
+            } }
+        ]);
+        let lcu = parse_timeline(7, &json!({ "frames": frames })).unwrap();
+        let riot =
+            parse_timeline(7, &json!({ "metadata": {}, "info": { "frames": frames } })).unwrap();
+        assert_eq!(lcu, riot);
+
+        let at_10 = lcu.participant_at(1, 10 * 60 * 1000).unwrap();
+        assert_eq!((at_10.total_gold, at_10.xp, at_10.cs), (3500, 4800, 82));
+        assert_eq!(lcu.frames[1].participants[0].participant_id, 1);
+        assert!(lcu.frame_at(20 * 60 * 1000).is_none());
+        assert!(parse_timeline(7, &json!({})).is_none());
+    }
+
+    #[test]
+    fn test_store_keeps_recent_games() {
+        let mut store = MatchTimelineStore::with_path(PathBuf::from("unused.json"));
+        for game_id in 0..(MAX_TIMELINES as i64 + 5) {
+            store.insert(MatchTimeline {
+                game_id,
+                frames: Vec::new(),
+            });
+        }
+        assert!(store.get(4).is_none());
+        assert!(store.get(5).is_some());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::champions::cache::ChampionCache;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+/// Bytes on disk per kind of app data. Missing directories count as zero.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageUsage {
+    pub champion_cache: u64,
+    pub image_cache: u64,
+    pub database: u64, // Matches, ranked history, goals and draft timelines
+    pub logs: u64,
+    pub replays: u64, // The game's own replay folder, which the app reads but doesn't own
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheKind {
+    Images,
+    ChampionData, // Static data of past patches; the current patch is never evicted
+}
+
+fn app_dir(base: Option<PathBuf>, name: &str) -> Result<PathBuf, String> {
+    base.map(|d| d.join("trackimo-desktop"))
+        .ok_or_else(|| format!("Failed to get {} directory", name))
+}
+
+/// Where the stores keep their files, created if missing.
+pub fn data_dir() -> Result<PathBuf, String> {
+    let dir = app_dir(dirs::data_dir(), "data")?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create data directory: {}", e))?;
+    Ok(dir)
+}
+
+pub fn cache_root() -> Result<PathBuf, String> {
+    app_dir(dirs::cache_dir(), "cache")
+}
+
+/// Downloaded champion icons and splash art.
+pub fn image_cache_dir() -> Result<PathBuf, String> {
+    Ok(cache_root()?.join("images"))
+}
+
+/// Per-patch champion static data, one directory per version.
+pub fn champion_data_dir() -> Result<PathBuf, String> {
+    Ok(cache_root()?.join("champions"))
+}
+
+fn replay_dir() -> Option<PathBuf> {
+    dirs::document_dir().map(|d| d.join("League of Legends").join("Replays"))
+}
+
+/// Total size of a file or directory tree. Symlinks are not followed, so a
+/// link out of the tree or back into it is neither counted nor walked.
+pub fn disk_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_symlink() {
+        return 0;
+    }
+    if metadata.is_file() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| disk_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+fn storage_usage(app: &AppHandle) -> Result<StorageUsage, String> {
+    let cache = cache_root()?;
+    let logs = app
+        .path()
+        .app_log_dir()
+        .map(|dir| disk_size(&dir))
+        .unwrap_or(0);
+
+    Ok(StorageUsage {
+        champion_cache: disk_size(&cache.join("champions.json")) + disk_size(&champion_data_dir()?),
+        image_cache: disk_size(&image_cache_dir()?),
+        database: disk_size(&app_dir(dirs::data_dir(), "data")?),
+        logs,
+        replays: replay_dir().map(|dir| disk_size(&dir)).unwrap_or(0),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub last_used: SystemTime,
+}
+
+impl CacheEntry {
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let metadata = fs::metadata(&path).ok()?;
+        // Access times are often disabled; modification time is the fallback
+        let last_used = metadata
+            .accessed()
+            .or_else(|_| metadata.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        Some(Self {
+            size: disk_size(&path),
+            path,
+            last_used,
+        })
+    }
+}
+
+/// Least recently used entries to delete so the rest fits in `max_bytes`.
+/// Entries for which `keep` is true are never chosen.
+pub fn plan_eviction(
+    mut entries: Vec<CacheEntry>,
+    max_bytes: u64,
+    keep: impl Fn(&CacheEntry) -> bool,
+) -> Vec<CacheEntry> {
+    let mut total: u64 = entries.iter().map(|e| e.size).sum();
+    entries.sort_by_key(|e| e.last_used);
+
+    let mut evicted = Vec::new();
+    for entry in entries {
+        if total <= max_bytes {
+            break;
+        }
+        if keep(&entry) {
+            continue;
+        }
+        total -= entry.size;
+        evicted.push(entry);
+    }
+    evicted
+}
+
+fn files_under(path: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            files_under(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn trim(kind: CacheKind, max_bytes: u64, current_version: Option<String>) -> Result<u64, String> {
+    let (entries, current_dir) = match kind {
+        CacheKind::Images => {
+            let mut files = Vec::new();
+            files_under(&image_cache_dir()?, &mut files);
+            (files, None)
+        }
+        CacheKind::ChampionData => {
+            let dir = champion_data_dir()?;
+            let patches = fs::read_dir(&dir)
+                .map(|entries| entries.flatten().map(|e| e.path()).collect())
+                .unwrap_or_default();
+            (patches, current_version.map(|v| dir.join(v)))
+        }
+    };
+
+    let entries = entries
+        .into_iter()
+        .filter_map(CacheEntry::from_path)
+        .collect();
+    let evicted = plan_eviction(entries, max_bytes, |e| {
+        Some(&e.path) == current_dir.as_ref()
+    });
+
+    let mut freed = 0;
+    for entry in evicted {
+        let removed = if entry.path.is_dir() {
+            fs::remove_dir_all(&entry.path)
+        } else {
+            fs::remove_file(&entry.path)
+        };
+        removed.map_err(|e| format!("Failed to remove {}: {}", entry.path.display(), e))?;
+        freed += entry.size;
+    }
+    Ok(freed)
+}
+
+#[tauri::command]
+pub async fn get_storage_usage(app: AppHandle) -> Result<StorageUsage, String> {
+    storage_usage(&app)
+}
+
+/// Evict least recently used cache entries until `kind` takes at most `max_mb`.
+/// Returns the number of bytes freed.
+#[tauri::command]
+pub async fn trim_cache(
+    kind: CacheKind,
+    max_mb: u64,
+    cache: tauri::State<'_, Mutex<ChampionCache>>,
+) -> Result<u64, String> {
+    let current_version = {
+        let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+        cache_guard.get_version()
+    };
+    trim(kind, max_mb * 1024 * 1024, current_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn entry(name: &str, size: u64, age_secs: u64) -> CacheEntry {
+        CacheEntry {
+            path: PathBuf::from(name),
+            size,
+            last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(1000 - age_secs),
+        }
+    }
+
+    #[test]
+    fn test_plan_eviction_drops_oldest_first() {
+        let entries = vec![
+            entry("recent", 40, 1),
+            entry("current-patch", 50, 900),
+            entry("old", 30, 500),
+            entry("older", 20, 600),
+        ];
+
+        let evicted = plan_eviction(entries.clone(), 100, |e| {
+            e.path == Path::new("current-patch")
+        });
+        let names: Vec<_> = evicted.iter().map(|e| e.path.to_str().unwrap()).collect();
+        assert_eq!(names, vec!["older", "old"]);
+
+        assert!(plan_eviction(entries, 200, |_| false).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_size_skips_symlinks() {
+        let dir = std::env::temp_dir().join("trackimo-disk-size-test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("file"), [0u8; 10]).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("loop")).unwrap();
+
+        assert_eq!(disk_size(&dir), 10);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
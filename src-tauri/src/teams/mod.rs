@@ -0,0 +1,248 @@
+pub mod scouting;
+
+use crate::analytics::now_millis;
+use crate::storage::usage::data_dir;
+use crate::storage::POSITIONS;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+pub const ROSTER_SIZE: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RosterPlayer {
+    pub game_name: String,
+    pub tag_line: String,
+    pub position: String, // One of `POSITIONS`
+    #[serde(default)]
+    pub champion_pool: Vec<i64>,
+}
+
+/// A named team of up to five players, one per position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roster {
+    pub id: String,
+    pub name: String,
+    pub players: Vec<RosterPlayer>,
+    pub updated_at: i64,
+}
+
+/// Check a roster before saving and normalise player positions to upper case.
+pub fn validate_roster(roster: &mut Roster) -> Result<(), String> {
+    if roster.name.trim().is_empty() {
+        return Err("Roster name is required".to_string());
+    }
+    if roster.players.len() > ROSTER_SIZE {
+        return Err(format!("A roster has at most {} players", ROSTER_SIZE));
+    }
+    for (i, player) in roster.players.iter_mut().enumerate() {
+        player.position = player.position.to_uppercase();
+        if !POSITIONS.contains(&player.position.as_str()) {
+            return Err(format!("Unknown position {}", player.position));
+        }
+        if player.game_name.trim().is_empty() || player.tag_line.trim().is_empty() {
+            return Err(format!("Player {} needs a Riot ID", i + 1));
+        }
+    }
+    for (i, player) in roster.players.iter().enumerate() {
+        if roster.players[..i]
+            .iter()
+            .any(|other| other.position == player.position)
+        {
+            return Err(format!("Two players are set to {}", player.position));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TeamsFile {
+    rosters: Vec<Roster>,
+    active_id: Option<String>,
+}
+
+pub struct TeamStore {
+    data: TeamsFile,
+    store_path: PathBuf,
+}
+
+impl TeamStore {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self::with_path(data_dir()?.join("teams.json")))
+    }
+
+    pub fn with_path(store_path: PathBuf) -> Self {
+        Self {
+            data: TeamsFile::default(),
+            store_path,
+        }
+    }
+
+    pub fn load(&mut self) -> Result<(), String> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read_to_string(&self.store_path)
+            .map_err(|e| format!("Failed to read teams: {}", e))?;
+        self.data =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse teams: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.data)
+            .map_err(|e| format!("Failed to serialize teams: {}", e))?;
+
+        fs::write(&self.store_path, json).map_err(|e| format!("Failed to write teams: {}", e))?;
+
+        Ok(())
+    }
+
+    pub fn rosters(&self) -> &[Roster] {
+        &self.data.rosters
+    }
+
+    /// Insert a roster, or replace the one with the same id.
+    pub fn upsert(&mut self, roster: Roster) {
+        match self.data.rosters.iter_mut().find(|r| r.id == roster.id) {
+            Some(existing) => *existing = roster,
+            None => self.data.rosters.push(roster),
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.data.rosters.len();
+        self.data.rosters.retain(|r| r.id != id);
+        if self.data.active_id.as_deref() == Some(id) {
+            self.data.active_id = None;
+        }
+        self.data.rosters.len() != before
+    }
+
+    pub fn active(&self) -> Option<&Roster> {
+        let id = self.data.active_id.as_deref()?;
+        self.data.rosters.iter().find(|r| r.id == id)
+    }
+
+    pub fn set_active(&mut self, id: Option<String>) -> Result<(), String> {
+        if let Some(id) = &id {
+            if !self.data.rosters.iter().any(|r| &r.id == id) {
+                return Err(format!("Unknown roster {}", id));
+            }
+        }
+        self.data.active_id = id;
+        Ok(())
+    }
+}
+
+/// The roster scrim features work on, if the user picked one.
+pub fn active_roster(app: &AppHandle) -> Option<Roster> {
+    let teams = app.try_state::<Mutex<TeamStore>>()?;
+    let teams_guard = teams.lock().ok()?;
+    teams_guard.active().cloned()
+}
+
+#[tauri::command]
+pub async fn get_rosters(teams: tauri::State<'_, Mutex<TeamStore>>) -> Result<Vec<Roster>, String> {
+    let teams_guard = teams.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(teams_guard.rosters().to_vec())
+}
+
+/// Create a roster (without an id) or update an existing one.
+#[tauri::command]
+pub async fn save_roster(
+    id: Option<String>,
+    name: String,
+    players: Vec<RosterPlayer>,
+    teams: tauri::State<'_, Mutex<TeamStore>>,
+) -> Result<Roster, String> {
+    let now = now_millis();
+    let mut roster = Roster {
+        id: id.unwrap_or_else(|| format!("roster-{}", now)),
+        name,
+        players,
+        updated_at: now,
+    };
+    validate_roster(&mut roster)?;
+
+    let mut teams_guard = teams.lock().map_err(|e| format!("Lock error: {}", e))?;
+    teams_guard.upsert(roster.clone());
+    teams_guard.save()?;
+    Ok(roster)
+}
+
+#[tauri::command]
+pub async fn remove_roster(
+    id: String,
+    teams: tauri::State<'_, Mutex<TeamStore>>,
+) -> Result<bool, String> {
+    let mut teams_guard = teams.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let removed = teams_guard.remove(&id);
+    if removed {
+        teams_guard.save()?;
+    }
+    Ok(removed)
+}
+
+/// Make a roster the active one, or with `None` leave team mode.
+#[tauri::command]
+pub async fn set_active_roster(
+    id: Option<String>,
+    teams: tauri::State<'_, Mutex<TeamStore>>,
+) -> Result<(), String> {
+    let mut teams_guard = teams.lock().map_err(|e| format!("Lock error: {}", e))?;
+    teams_guard.set_active(id)?;
+    teams_guard.save()
+}
+
+#[tauri::command]
+pub async fn get_active_roster(app: AppHandle) -> Result<Option<Roster>, String> {
+    Ok(active_roster(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(position: &str) -> RosterPlayer {
+        RosterPlayer {
+            game_name: "Player".to_string(),
+            tag_line: "EUW".to_string(),
+            position: position.to_string(),
+            champion_pool: vec![],
+        }
+    }
+
+    fn roster(id: &str, positions: &[&str]) -> Roster {
+        Roster {
+            id: id.to_string(),
+            name: "Scrim team".to_string(),
+            players: positions.iter().map(|p| player(p)).collect(),
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_rosters_and_active_selection() {
+        let mut valid = roster("a", &["top", "JUNGLE"]);
+        assert!(validate_roster(&mut valid).is_ok());
+        assert_eq!(valid.players[0].position, "TOP");
+        assert!(validate_roster(&mut roster("b", &["TOP", "top"])).is_err());
+        assert!(validate_roster(&mut roster("b", &["SUPPORT"])).is_err());
+
+        let mut store = TeamStore::with_path(PathBuf::from("unused.json"));
+        assert!(store.set_active(Some("a".to_string())).is_err());
+        store.upsert(valid);
+        store.set_active(Some("a".to_string())).unwrap();
+        store.upsert(roster("a", &["MIDDLE"]));
+        assert_eq!(store.rosters().len(), 1);
+        assert_eq!(store.active().unwrap().players[0].position, "MIDDLE");
+
+        assert!(store.remove("a"));
+        assert!(store.active().is_none());
+    }
+}
@@ -0,0 +1,502 @@
+use super::{active_roster, Roster};
+use crate::analytics::champion_pool::{
+    fetched_games, infer_pool, stored_games, PoolGame, RolePool,
+};
+use crate::analytics::now_millis;
+use crate::champions::cache::ChampionCache;
+use crate::lcu::api::LcuApi;
+use crate::lcu::cancel::{phase_token, until_cancelled};
+use crate::lcu::client::LcuClient;
+use crate::metrics::{record_feature, Feature};
+use crate::profile::{fetch_profile, PlayerProfile};
+use crate::storage::MatchStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// Champions listed per scouted player.
+const POOL_SIZE: usize = 5;
+
+/// Most recent games shown as picks.
+const RECENT_PICKS: usize = 10;
+
+/// Bans suggested per report.
+const BAN_SUGGESTIONS: usize = 5;
+
+/// One opponent as entered by the captain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpponentPlayer {
+    pub riot_id: String,          // "Name#TAG"
+    pub position: Option<String>, // If the captain knows it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoutedChampion {
+    pub champion_id: i64,
+    pub name: String,
+    pub games: u32, // In recent match history
+    pub wins: u32,
+    pub mastery_points: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentPick {
+    pub champion_id: i64,
+    pub name: String,
+    pub win: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoutedPlayer {
+    pub riot_id: String,
+    pub position: Option<String>,
+    pub solo_rank: Option<String>, // e.g. "GOLD II"
+    pub champion_pool: Vec<ScoutedChampion>,
+    pub recent_picks: Vec<RecentPick>, // Newest first
+    #[serde(default)]
+    pub inferred_pool: Vec<RolePool>, // From stored matches and the fetched history
+    pub error: Option<String>,         // Set when the player couldn't be looked up
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BanSuggestion {
+    pub champion_id: i64,
+    pub name: String,
+    pub riot_id: String, // The opponent who plays it
+    pub games: u32,
+    pub win_rate: f32,
+}
+
+/// A champion in both an opponent's pool and one of ours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContestedChampion {
+    pub champion_id: i64,
+    pub name: String,
+    pub theirs: Vec<String>, // Riot IDs
+    pub ours: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoutingReport {
+    pub opponent: String,
+    pub team: Option<String>, // The active roster's name
+    pub generated_at: i64,
+    pub players: Vec<ScoutedPlayer>,
+    pub contested: Vec<ContestedChampion>,
+    #[serde(default)]
+    pub ban_suggestions: Vec<BanSuggestion>,
+}
+
+/// Pool and recent picks from a fetched profile. Champions are ranked by recent
+/// games, then mastery. `stored` are the player's games in the match store,
+/// used with the fetched history to infer their pool per role.
+pub fn scout_player(
+    opponent: &OpponentPlayer,
+    profile: &PlayerProfile,
+    mut stored: Vec<PoolGame>,
+    name: impl Fn(i64) -> String,
+) -> ScoutedPlayer {
+    let mut pool: HashMap<i64, ScoutedChampion> = HashMap::new();
+    let new_entry = |champion_id: i64| ScoutedChampion {
+        champion_id,
+        name: name(champion_id),
+        games: 0,
+        wins: 0,
+        mastery_points: 0,
+    };
+    for game in &profile.recent_matches {
+        let champion_id = game.champion_id as i64;
+        let champion = pool
+            .entry(champion_id)
+            .or_insert_with(|| new_entry(champion_id));
+        champion.games += 1;
+        champion.wins += game.win as u32;
+    }
+    for mastery in &profile.top_masteries {
+        pool.entry(mastery.champion_id)
+            .or_insert_with(|| new_entry(mastery.champion_id))
+            .mastery_points = mastery.champion_points;
+    }
+
+    let mut champion_pool: Vec<ScoutedChampion> = pool.into_values().collect();
+    champion_pool.sort_by(|a, b| {
+        b.games
+            .cmp(&a.games)
+            .then(b.mastery_points.cmp(&a.mastery_points))
+    });
+    champion_pool.truncate(POOL_SIZE);
+
+    let mut recent: Vec<_> = profile.recent_matches.iter().collect();
+    recent.sort_by_key(|g| std::cmp::Reverse(g.game_creation));
+    let recent_picks = recent
+        .into_iter()
+        .take(RECENT_PICKS)
+        .map(|g| RecentPick {
+            champion_id: g.champion_id as i64,
+            name: name(g.champion_id as i64),
+            win: g.win,
+        })
+        .collect();
+
+    ScoutedPlayer {
+        riot_id: opponent.riot_id.clone(),
+        position: opponent.position.as_ref().map(|p| p.to_uppercase()),
+        solo_rank: profile
+            .ranked_stats
+            .iter()
+            .find(|s| s.queue_type == "RANKED_SOLO_5x5" && !s.tier.is_empty())
+            .map(|s| format!("{} {}", s.tier, s.rank).trim().to_string()),
+        champion_pool,
+        recent_picks,
+        inferred_pool: {
+            stored.extend(fetched_games(&profile.recent_matches));
+            infer_pool(stored)
+        },
+        error: None,
+    }
+}
+
+/// Champions worth banning: the opponents' inferred pool for their position (or
+/// every role when it isn't known), weighted by games and win rate.
+pub fn suggest_bans(players: &[ScoutedPlayer], name: impl Fn(i64) -> String) -> Vec<BanSuggestion> {
+    let mut suggestions: Vec<BanSuggestion> = Vec::new();
+    for player in players {
+        let pools = player
+            .inferred_pool
+            .iter()
+            .filter(|pool| player.position.as_ref().is_none_or(|p| *p == pool.position));
+        for champion in pools.flat_map(|pool| &pool.champions) {
+            if suggestions
+                .iter()
+                .any(|s| s.champion_id == champion.champion_id)
+            {
+                continue;
+            }
+            suggestions.push(BanSuggestion {
+                champion_id: champion.champion_id,
+                name: name(champion.champion_id),
+                riot_id: player.riot_id.clone(),
+                games: champion.games,
+                win_rate: champion.win_rate,
+            });
+        }
+    }
+    let weight = |s: &BanSuggestion| s.games as f32 * s.win_rate;
+    suggestions.sort_by(|a, b| weight(b).total_cmp(&weight(a)));
+    suggestions.truncate(BAN_SUGGESTIONS);
+    suggestions
+}
+
+/// Champions the opponents play that someone on our roster also plays.
+pub fn contested_champions(players: &[ScoutedPlayer], roster: &Roster) -> Vec<ContestedChampion> {
+    let mut contested: Vec<ContestedChampion> = Vec::new();
+    for player in players {
+        for champion in &player.champion_pool {
+            let ours: Vec<String> = roster
+                .players
+                .iter()
+                .filter(|p| p.champion_pool.contains(&champion.champion_id))
+                .map(|p| format!("{}#{}", p.game_name, p.tag_line))
+                .collect();
+            if ours.is_empty() {
+                continue;
+            }
+            match contested
+                .iter_mut()
+                .find(|c| c.champion_id == champion.champion_id)
+            {
+                Some(existing) => existing.theirs.push(player.riot_id.clone()),
+                None => contested.push(ContestedChampion {
+                    champion_id: champion.champion_id,
+                    name: champion.name.clone(),
+                    theirs: vec![player.riot_id.clone()],
+                    ours,
+                }),
+            }
+        }
+    }
+    contested
+}
+
+/// The report as a Markdown document for sharing before the scrim.
+pub fn render_markdown(report: &ScoutingReport) -> String {
+    let mut doc = format!("# Scouting: {}\n", report.opponent);
+    if let Some(team) = &report.team {
+        doc += &format!("\nPrepared for {}.\n", team);
+    }
+
+    for player in &report.players {
+        doc += &format!("\n## {}", player.riot_id);
+        if let Some(position) = &player.position {
+            doc += &format!(" ({})", position);
+        }
+        doc += "\n\n";
+        if let Some(error) = &player.error {
+            doc += &format!("_Lookup failed: {}_\n", error);
+            continue;
+        }
+        doc += &format!(
+            "- Rank: {}\n",
+            player.solo_rank.as_deref().unwrap_or("Unranked")
+        );
+
+        let pool: Vec<String> = player
+            .champion_pool
+            .iter()
+            .map(|c| match c.games {
+                0 => format!("{} ({} mastery)", c.name, c.mastery_points),
+                games => format!("{} ({}W {}L)", c.name, c.wins, games - c.wins),
+            })
+            .collect();
+        doc += &format!("- Champion pool: {}\n", pool.join(", "));
+
+        let picks: Vec<String> = player
+            .recent_picks
+            .iter()
+            .map(|p| format!("{} {}", p.name, if p.win { "W" } else { "L" }))
+            .collect();
+        doc += &format!("- Recent picks: {}\n", picks.join(", "));
+
+        for pool in &player.inferred_pool {
+            let champions: Vec<String> = pool
+                .champions
+                .iter()
+                .map(|c| {
+                    let name = player
+                        .champion_pool
+                        .iter()
+                        .find(|known| known.champion_id == c.champion_id)
+                        .map_or_else(|| format!("Champion {}", c.champion_id), |k| k.name.clone());
+                    format!("{} ({} games, {:.0}%)", name, c.games, c.win_rate * 100.0)
+                })
+                .collect();
+            doc += &format!("- Reliable {}: {}\n", pool.position, champions.join(", "));
+        }
+    }
+
+    if !report.ban_suggestions.is_empty() {
+        doc += "\n## Ban suggestions\n\n";
+        for ban in &report.ban_suggestions {
+            doc += &format!(
+                "- {} ({}: {} games, {:.0}%)\n",
+                ban.name,
+                ban.riot_id,
+                ban.games,
+                ban.win_rate * 100.0
+            );
+        }
+    }
+
+    if !report.contested.is_empty() {
+        doc += "\n## Contested champions\n\n";
+        for champion in &report.contested {
+            doc += &format!(
+                "- {}: {} vs {}\n",
+                champion.name,
+                champion.theirs.join(", "),
+                champion.ours.join(", ")
+            );
+        }
+    }
+    doc
+}
+
+/// Look up every opponent and gather their pools and recent picks. Players that
+/// can't be resolved are kept with the error so the captain can fix the ID.
+pub async fn scout<C: LcuApi>(
+    client: &mut C,
+    opponents: &[OpponentPlayer],
+) -> Vec<(OpponentPlayer, Result<PlayerProfile, String>)> {
+    let mut profiles = Vec::new();
+    for opponent in opponents {
+        let profile = fetch_profile(client, &opponent.riot_id).await;
+        profiles.push((opponent.clone(), profile));
+    }
+    profiles
+}
+
+/// Build a pre-scrim scouting report for an opponent team, in the context of
+/// the active roster when there is one.
+#[tauri::command]
+pub async fn scout_opponents(
+    app: AppHandle,
+    opponent: String,
+    players: Vec<OpponentPlayer>,
+    client: tauri::State<'_, Arc<tokio::sync::Mutex<LcuClient>>>,
+) -> Result<ScoutingReport, String> {
+    record_feature(&app, Feature::Scouting);
+    let token = phase_token(&app);
+    let profiles = until_cancelled(&token, async {
+        let mut client_guard = client.lock().await;
+        Ok(scout(&mut *client_guard, &players).await)
+    })
+    .await?;
+
+    let stored: Vec<Vec<PoolGame>> = {
+        let store = app.state::<Mutex<MatchStore>>();
+        let store_guard = store.lock().map_err(|e| format!("Lock error: {}", e))?;
+        profiles
+            .iter()
+            .map(|(_, profile)| match profile {
+                Ok(profile) => stored_games(store_guard.matches(), &profile.summoner.puuid),
+                Err(_) => Vec::new(),
+            })
+            .collect()
+    };
+
+    let cache = app.state::<Mutex<ChampionCache>>();
+    let cache_guard = cache.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let name = |id: i64| {
+        cache_guard
+            .get_champion_by_id(id)
+            .map(|c| c.name)
+            .unwrap_or_else(|| format!("Champion {}", id))
+    };
+    let scouted: Vec<ScoutedPlayer> = profiles
+        .iter()
+        .zip(stored)
+        .map(|((opponent, profile), stored)| match profile {
+            Ok(profile) => scout_player(opponent, profile, stored, name),
+            Err(e) => ScoutedPlayer {
+                riot_id: opponent.riot_id.clone(),
+                position: opponent.position.as_ref().map(|p| p.to_uppercase()),
+                solo_rank: None,
+                champion_pool: Vec::new(),
+                recent_picks: Vec::new(),
+                inferred_pool: Vec::new(),
+                error: Some(e.clone()),
+            },
+        })
+        .collect();
+
+    let roster = active_roster(&app);
+    Ok(ScoutingReport {
+        opponent,
+        team: roster.as_ref().map(|r| r.name.clone()),
+        generated_at: now_millis(),
+        contested: roster
+            .map(|r| contested_champions(&scouted, &r))
+            .unwrap_or_default(),
+        ban_suggestions: suggest_bans(&scouted, name),
+        players: scouted,
+    })
+}
+
+/// Render a scouting report as "json" or "markdown" for the frontend to save.
+#[tauri::command]
+pub async fn export_scouting_report(
+    report: ScoutingReport,
+    format: String,
+) -> Result<String, String> {
+    match format.as_str() {
+        "json" => serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize report: {}", e)),
+        "markdown" | "md" => Ok(render_markdown(&report)),
+        other => Err(format!("Unknown export format {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcu::client::{ChampionMastery, MatchHistoryGame, SummonerInfo};
+    use crate::teams::RosterPlayer;
+
+    fn game(game_id: i64, champion_id: i32, win: bool) -> MatchHistoryGame {
+        MatchHistoryGame {
+            game_id,
+            queue_id: 420,
+            champion_id,
+            game_mode: "CLASSIC".to_string(),
+            game_creation: game_id,
+            game_duration: 1800,
+            win,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+        }
+    }
+
+    #[test]
+    fn test_scouting_report() {
+        let profile = PlayerProfile {
+            summoner: SummonerInfo {
+                summoner_id: "1".to_string(),
+                account_id: "2".to_string(),
+                puuid: "abc".to_string(),
+                display_name: "Rival".to_string(),
+                game_name: Some("Rival".to_string()),
+                tag_line: Some("EUW".to_string()),
+                summoner_level: 100,
+                profile_icon_id: 1,
+                xp_since_last_level: 0,
+                xp_until_next_level: 0,
+            },
+            ranked_stats: vec![],
+            top_masteries: vec![ChampionMastery {
+                champion_id: 103,
+                champion_level: 7,
+                champion_points: 250_000,
+                last_play_time: 0,
+                chest_granted: false,
+            }],
+            recent_matches: vec![
+                game(0, 64, true),
+                game(1, 64, true),
+                game(2, 64, false),
+                game(3, 121, true),
+            ],
+        };
+        let opponent = OpponentPlayer {
+            riot_id: "Rival#EUW".to_string(),
+            position: Some("jungle".to_string()),
+        };
+        let stored = vec![PoolGame {
+            game_id: 1, // Also in the fetched history
+            game_creation: 1,
+            champion_id: 64,
+            position: Some("JUNGLE".to_string()),
+            win: true,
+        }];
+        let player = scout_player(&opponent, &profile, stored, |id| format!("C{}", id));
+
+        let pool: Vec<(i64, u32)> = player
+            .champion_pool
+            .iter()
+            .map(|c| (c.champion_id, c.games))
+            .collect();
+        assert_eq!(pool, vec![(64, 3), (121, 1), (103, 0)]);
+        assert_eq!(player.inferred_pool[0].position, "JUNGLE");
+        assert_eq!(player.inferred_pool[0].champions[0].games, 3);
+        assert_eq!(player.recent_picks[0].champion_id, 121, "newest first");
+
+        let roster = Roster {
+            id: "r".to_string(),
+            name: "Us".to_string(),
+            players: vec![RosterPlayer {
+                game_name: "Me".to_string(),
+                tag_line: "EUW".to_string(),
+                position: "JUNGLE".to_string(),
+                champion_pool: vec![64],
+            }],
+            updated_at: 0,
+        };
+        let report = ScoutingReport {
+            opponent: "Rivals".to_string(),
+            team: Some(roster.name.clone()),
+            generated_at: 0,
+            contested: contested_champions(std::slice::from_ref(&player), &roster),
+            ban_suggestions: suggest_bans(std::slice::from_ref(&player), |id| format!("C{}", id)),
+            players: vec![player],
+        };
+        assert_eq!(report.contested[0].ours, vec!["Me#EUW"]);
+        assert_eq!(report.ban_suggestions[0].champion_id, 64);
+
+        let doc = render_markdown(&report);
+        assert!(doc.contains("## Rival#EUW (JUNGLE)"));
+        assert!(doc.contains("- Champion pool: C64 (2W 1L), C121 (1W 0L), C103 (250000 mastery)"));
+        assert!(doc.contains("- Reliable JUNGLE: C64 (3 games, 67%)"));
+        assert!(doc.contains("- C64: Rival#EUW vs Me#EUW"));
+    }
+}
@@ -3,7 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getVersion } from "@tauri-apps/api/app";
 import { check } from "@tauri-apps/plugin-updater";
-import type { DraftState, Champion, SummonerInfo, RankedStats, MatchHistoryGame } from "./types";
+import type { DraftState, TimerTick, PhaseTransition, StateChange, Champion, SummonerInfo, RankedStats, MatchHistoryGame } from "./types";
 import DraftView from "./DraftView";
 import PlayerDashboard from "./components/player/PlayerDashboard";
 import Header from "./components/Header";
@@ -22,7 +22,6 @@ function App() {
   const [isInstallingUpdate, setIsInstallingUpdate] = useState(false);
   const [draftTimer, setDraftTimer] = useState<{ current: number; max: number } | null>(null);
   const draftStateRef = useRef<DraftState | null>(null);
-  const monitoringStartedRef = useRef(false);
   const previousConnectedRef = useRef(false);
 
   useEffect(() => {
@@ -35,7 +34,11 @@ function App() {
         // Check for updates (silently in background)
         checkForUpdatesSilently();
         
-        // Load champions
+        // The backend loads champion data at startup, fetching it when the
+        // cache is empty or stale; pick up whatever is there already
+        const unlistenChampions = await listen<string>("champions-ready", () => {
+          loadChampions();
+        });
         await loadChampions();
         setTauriReady(true);
         
@@ -45,26 +48,41 @@ function App() {
           setDraftState(event.payload);
         });
 
-        // Monitor gameflow phase to detect game end/dodge
-        const phaseCheckInterval = setInterval(async () => {
-          try {
-            const phase: string = await invoke("get_gameflow_phase");
-            
-            if (draftStateRef.current && phase !== "ChampSelect" && phase !== "ChampionSelect") {
-              draftStateRef.current = null;
-              setDraftState(null);
-            }
-          } catch (e) {
-            // Ignore errors - LCU might be disconnected
+        // Minimal event verbosity drops draft-state-changed, but the state
+        // store still publishes every draft update
+        const unlistenStore = await listen<StateChange>("app-state-changed", (event) => {
+          if (event.payload.key === "draft_state" && event.payload.value) {
+            draftStateRef.current = event.payload.value as DraftState;
+            setDraftState(draftStateRef.current);
           }
-        }, 2000);
-        
+        });
+
+        // The full state is only re-sent when more than the timer changes
+        const unlistenTimer = await listen<TimerTick>("timer-tick", (event) => {
+          if (draftStateRef.current) {
+            draftStateRef.current = { ...draftStateRef.current, timer: event.payload.seconds_left ?? undefined };
+            setDraftState(draftStateRef.current);
+          }
+        });
+
+        // The backend starts and stops monitoring with champ select; drop the
+        // draft once it's left (game start, dodge or disconnect)
+        const unlistenPhase = await listen<PhaseTransition>("gameflow-phase-changed", (event) => {
+          if (event.payload.to !== "ChampSelect") {
+            draftStateRef.current = null;
+            setDraftState(null);
+          }
+        });
+
         // Auto-connect to LCU (after listener is set up)
         await autoConnect();
 
         return () => {
+          unlistenChampions();
           unlistenDraft();
-          clearInterval(phaseCheckInterval);
+          unlistenStore();
+          unlistenTimer();
+          unlistenPhase();
         };
       } catch (e) {
         // Retry initialization
@@ -81,20 +99,11 @@ function App() {
       const champMap = new Map<number, Champion>();
       allChampions.forEach(champ => champMap.set(champ.key, champ));
       setChampions(champMap);
-      
-      // Fetch from API if cache is empty
-      if (champMap.size === 0) {
-        const champData = await invoke("fetch_champion_data", { apiKey: null }) as { version: string, champions: Record<string, Champion> };
-        const updated: Champion[] = await invoke("get_all_champions");
-        updated.forEach(champ => champMap.set(champ.key, champ));
-        setChampions(new Map(champMap));
-        setChampionVersion(champData.version);
-      } else {
-        const version = await invoke("get_champion_version") as string | null;
-        if (version) setChampionVersion(version);
-      }
+
+      const version = await invoke("get_champion_version") as string | null;
+      if (version) setChampionVersion(version);
     } catch {
-      // Failed to load champions - will retry on next connection attempt
+      // Failed to load champions - reloaded once the backend reports them ready
     }
   };
 
@@ -135,24 +144,18 @@ function App() {
       const phase = await invoke("get_gameflow_phase") as string;
       const wasConnected = previousConnectedRef.current;
       previousConnectedRef.current = true;
-      
-      // Start monitoring if not already started
-      if (!monitoringStartedRef.current) {
-        await invoke("start_draft_monitoring");
-        monitoringStartedRef.current = true;
-        
-        // Force immediate detection if we're already in champ select
-        if (phase === "ChampSelect" || phase === "ChampionSelect") {
-          try {
-            const currentDraftState: DraftState = await invoke("get_draft_state");
-            draftStateRef.current = currentDraftState;
-            setDraftState(currentDraftState);
-          } catch (e) {
-            // Not in draft or error fetching - monitor will handle it
-          }
+
+      // Monitoring may have started before the listener was set up
+      if (!wasConnected && phase === "ChampSelect") {
+        try {
+          const currentDraftState: DraftState = await invoke("get_draft_state");
+          draftStateRef.current = currentDraftState;
+          setDraftState(currentDraftState);
+        } catch (e) {
+          // Not in draft or error fetching - monitor will handle it
         }
       }
-      
+
       // Fetch player info if we just connected or if we don't have data yet
       if (!wasConnected || !summonerInfo) {
         // Small delay to ensure League API is fully ready
@@ -162,7 +165,6 @@ function App() {
     } catch (error) {
       // LCU not connected
       previousConnectedRef.current = false;
-      monitoringStartedRef.current = false;
       setSummonerInfo(null);
       setRankedStats([]);
       setMatchHistory([]);
@@ -14,6 +14,7 @@ interface PickCardProps {
   isPlayerTeam: boolean;
   takenRoles: string[];
   onRoleSelect?: (role: string) => void;
+  previousHovers?: string[]; // Champions an enemy hovered earlier in this draft
 }
 
 const ROLES = [
@@ -97,6 +98,7 @@ export default function PickCard({
   isPlayerTeam,
   takenRoles,
   onRoleSelect,
+  previousHovers = [],
 }: PickCardProps) {
   const [showRoleSelector, setShowRoleSelector] = useState(false);
   const [dropdownPosition, setDropdownPosition] = useState({ top: 0, left: 0 });
@@ -291,6 +293,11 @@ export default function PickCard({
               } font-semibold drop-shadow-[0_1px_2px_rgba(0,0,0,0.8)]`}>
                 {isLocked ? "LOCKED" : isPreLocked ? "PRE-LOCKED" : isActivelySelecting ? "SELECTING..." : ""}
               </div>
+              {previousHovers.length > 0 && (
+                <div className="text-xs text-gray-300 drop-shadow-[0_1px_2px_rgba(0,0,0,0.8)]">
+                  Previously hovered: {previousHovers.join(", ")}
+                </div>
+              )}
             </div>
           </>
         ) : (
@@ -1,6 +1,6 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { DraftState, Champion } from "../../types";
+import type { DraftState, Champion, ChampionChange, CoachSuggestion } from "../../types";
 
 interface RecommendationsPanelProps {
   draftState: DraftState;
@@ -12,6 +12,7 @@ interface RecommendationsPanelProps {
 interface Recommendation {
   champion_id: number;
   score: number;
+  coach?: CoachSuggestion; // Set on the entry sent by whoever watches the shared draft
 }
 
 interface RecommendationsResult {
@@ -28,6 +29,7 @@ export default function RecommendationsPanel({
   const [recommendations, setRecommendations] = useState<RecommendationsResult | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [patchChanges, setPatchChanges] = useState<Map<number, ChampionChange>>(new Map());
 
   // Get the current player's cell ID from LCU
   const currentPlayerCellId = draftState.local_player_cell_id ?? null;
@@ -148,6 +150,20 @@ export default function RecommendationsPanel({
     return () => clearTimeout(timeoutId);
   }, [draftState, currentPlayerRole, currentPlayerCellId, teammateSelectionsKey]);
 
+  // Flag recommendations that were buffed or nerfed this patch
+  useEffect(() => {
+    const ids = recommendations?.recommendations.map((rec) => rec.champion_id) ?? [];
+    Promise.all(
+      ids.map((championId) =>
+        invoke<ChampionChange | null>("get_patch_changes", { championId }).catch(() => null)
+      )
+    ).then((changes) => {
+      const byChampion = new Map<number, ChampionChange>();
+      changes.forEach((change) => change && byChampion.set(change.champion_id, change));
+      setPatchChanges(byChampion);
+    });
+  }, [recommendations]);
+
   // Hide panel only if the current player has locked their champion or no current player
   if (currentPlayerCellId == null || hasPlayerLockedChampion()) {
     return null;
@@ -171,7 +187,10 @@ export default function RecommendationsPanel({
   }
 
   // Show panel if we have either team prelocks or AI recommendations
-  if ((!recommendations || recommendations.recommendations.length === 0) && teamPrelocks.length === 0) {
+  if (
+    (!recommendations || recommendations.recommendations.length === 0) &&
+    teamPrelocks.length === 0
+  ) {
     return null;
   }
 
@@ -256,6 +275,42 @@ export default function RecommendationsPanel({
                 const champ = champions.get(rec.champion_id);
                 if (!champ) return null;
 
+                // Sent back by whoever watches the shared draft
+                if (rec.coach) {
+                  return (
+                    <div
+                      key={`coach-${rec.champion_id}`}
+                      className="flex items-center gap-3 p-3 bg-gradient-to-r from-amber-950/30 to-amber-900/20 rounded-lg border border-amber-500/20 backdrop-blur-sm"
+                    >
+                      {/* Coach Badge */}
+                      <div className="flex-shrink-0 px-1.5 h-8 flex items-center justify-center bg-black/70 rounded-lg border-2 border-amber-600/50 backdrop-blur-sm text-amber-300 font-bold text-[10px] uppercase">
+                        Coach
+                      </div>
+
+                      {/* Champion Image */}
+                      <div className="flex-shrink-0 w-12 h-12 rounded-lg overflow-hidden border-2 border-amber-500/20">
+                        <img
+                          src={getChampionCenteredImageUrl(rec.champion_id)}
+                          alt={champ.name}
+                          className="w-full h-full object-cover"
+                        />
+                      </div>
+
+                      {/* Champion Info */}
+                      <div className="flex-1 min-w-0">
+                        <div className="font-semibold text-white truncate">{champ.name}</div>
+                        <div className="text-xs text-amber-200">
+                          {rec.coach.action === "ban" ? "Coach says ban" : "Coach says pick"}
+                          {rec.coach.note && ` - ${rec.coach.note}`}
+                        </div>
+                      </div>
+                    </div>
+                  );
+                }
+
+                // The coach's entry is listed first and doesn't take a rank
+                const rank = recommendations.recommendations[0]?.coach ? idx : idx + 1;
+
                 return (
                   <div
                     key={rec.champion_id}
@@ -263,7 +318,7 @@ export default function RecommendationsPanel({
                   >
                     {/* Rank Badge */}
                     <div className="flex-shrink-0 w-8 h-8 flex items-center justify-center bg-black/70 rounded-lg border-2 border-gray-600/50 backdrop-blur-sm text-white font-bold text-sm">
-                      {idx + 1}
+                      {rank}
                     </div>
 
                     {/* Champion Image */}
@@ -283,6 +338,16 @@ export default function RecommendationsPanel({
                       <div className="font-semibold text-white truncate">{champ.name}</div>
                       <div className="text-xs text-gray-400">
                         {(rec.score * 100).toFixed(1)}% match
+                        {patchChanges.get(rec.champion_id)?.kind === "buff" && (
+                          <span className="ml-2 text-green-400" title={patchChanges.get(rec.champion_id)?.summary}>
+                            Recently buffed
+                          </span>
+                        )}
+                        {patchChanges.get(rec.champion_id)?.kind === "nerf" && (
+                          <span className="ml-2 text-red-400" title={patchChanges.get(rec.champion_id)?.summary}>
+                            Recently nerfed
+                          </span>
+                        )}
                       </div>
                     </div>
                   </div>
@@ -99,6 +99,9 @@ export default function TeamView({
           // Check if this is the current player's cell
           const isCurrentPlayer = currentPlayerCellId != null && cell.cell_id === currentPlayerCellId;
           const takenRoles = getTakenRoles(cell.cell_id);
+          const previousHovers = cell.previous_hover_names?.length
+            ? cell.previous_hover_names
+            : (cell.previous_hovers ?? []).map(getChampionName);
 
           return (
             <PickCard
@@ -115,6 +118,7 @@ export default function TeamView({
               isPlayerTeam={isPlayerTeam}
               takenRoles={takenRoles}
               onRoleSelect={(role) => onRoleSelect(cell.cell_id, role)}
+              previousHovers={previousHovers}
             />
           );
         })}
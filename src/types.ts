@@ -5,6 +5,33 @@ export interface DraftState {
   teams: Team[];
   actions: DraftAction[];
   local_player_cell_id?: number; // The current player's cell ID from LCU
+  is_custom_game: boolean;
+  queue_id?: number | null; // null when the client doesn't send it
+  aram?: AramState; // Modes with a shared bench
+  warnings?: string[]; // Parser corrections, empty for well-formed sessions
+  swap_offers?: SwapOffer[]; // Trades and swaps awaiting an answer
+  series_excluded?: number[]; // Picked earlier in a fearless series
+  raw_session?: unknown; // The untouched LCU session, only with include_raw
+}
+
+export type SwapKind = "champion" | "pick_order" | "position";
+
+export interface SwapOffer {
+  id?: number | null;
+  kind: SwapKind;
+  cell_id: number; // The ally on the other end
+  incoming: boolean; // Offered to the local player rather than by them
+}
+
+export interface AramState {
+  bench: BenchChampion[];
+  rerolls_remaining: number;
+  can_reroll: boolean; // Rerolling is allowed and one is left
+}
+
+export interface BenchChampion {
+  champion_id: number;
+  is_priority: boolean;
 }
 
 export interface Team {
@@ -21,6 +48,14 @@ export interface Cell {
   assigned_position?: string;
   spell1_id?: number;
   spell2_id?: number;
+  spell1_name?: string;
+  spell2_name?: string;
+  trade_available?: boolean; // Whether this cell can trade champions with the local player
+  pick_swap_available?: boolean; // Whether this cell can swap pick turns with the local player
+  position_swap_available?: boolean;
+  pick_turn?: number | null; // 1-based place in the team's pick order, after any swaps
+  previous_hovers?: number[]; // Enemy champions hovered earlier in this draft
+  previous_hover_names?: string[];
 }
 
 export interface ChampionPick {
@@ -44,6 +79,7 @@ export interface DraftAction {
   champion_id?: number;
   completed: boolean;
   is_in_progress: boolean; // Whether this action is currently active (player's turn)
+  is_ally_action?: boolean;
   type: string; // Will be serialized from action_type in Rust
 }
 
@@ -53,6 +89,15 @@ export interface Champion {
   name: string;
   title: string;
   tags: string[];
+  info?: ChampionInfo; // Missing from data cached by older builds
+}
+
+// Data Dragon's 0-10 ratings
+export interface ChampionInfo {
+  attack: number;
+  defense: number;
+  magic: number;
+  difficulty: number;
 }
 
 export interface SummonerInfo {
@@ -75,6 +120,20 @@ export interface RankedStats {
   league_points: number;
   wins: number;
   losses: number;
+  highest_tier?: string;
+  highest_division?: string;
+  previous_season_end_tier?: string;
+  previous_season_end_division?: string;
+  is_provisional?: boolean;
+  provisional_games_remaining?: number;
+  miniseries?: Miniseries;
+}
+
+export interface Miniseries {
+  progress: string;
+  wins: number;
+  losses: number;
+  target: number;
 }
 
 export interface MatchHistoryGame {
@@ -89,3 +148,939 @@ export interface MatchHistoryGame {
   deaths: number;
   assists: number;
 }
+
+export interface ChampionMastery {
+  champion_id: number;
+  champion_level: number;
+  champion_points: number;
+  last_play_time: number;
+  chest_granted?: boolean;
+}
+
+export interface PlayerProfile {
+  summoner: SummonerInfo;
+  ranked_stats: RankedStats[];
+  top_masteries: ChampionMastery[];
+  recent_matches: MatchHistoryGame[];
+}
+
+export interface Encounter {
+  puuid: string;
+  game_name?: string;
+  tag_line?: string;
+  games: number;
+  wins_with: number;
+  losses_with: number;
+  wins_against: number;
+  losses_against: number;
+  last_seen: number;
+}
+
+export interface ChampionTendency {
+  champion_id: number;
+  games: number;
+  wins: number;
+}
+
+export interface OpponentTendencies {
+  puuid: string;
+  cell_id?: number;
+  game_name?: string;
+  tag_line?: string;
+  games_against: number;
+  champions: ChampionTendency[];
+}
+
+export interface RankedSplit {
+  season_id?: number;
+  split_id?: number;
+  split_start?: number;
+  split_end?: number;
+  split_points: number;
+  reward_thresholds: number[];
+}
+
+export interface RankSnapshot {
+  timestamp: number;
+  queue_type: string;
+  tier: string;
+  rank: string;
+  league_points: number;
+  wins: number;
+  losses: number;
+  in_promos?: boolean;
+  change?: RankChangeKind;
+}
+
+export interface QueueSplitProgress {
+  queue_type: string;
+  current: RankedStats;
+  peak?: RankSnapshot;
+  wins_this_split?: number;
+  losses_this_split?: number;
+}
+
+export interface SplitProgress {
+  split: RankedSplit;
+  queues: QueueSplitProgress[];
+  next_reward_points?: number;
+  wins_needed_for_next_reward?: number;
+}
+
+export interface AppSettings {
+  rank_change_notifications: boolean;
+  boost_chest_eligible: boolean;
+  draft_event_windows?: string[];
+  auto_ban_enabled: boolean;
+  auto_ban_champion_ids: number[]; // Priority order; protected champions are skipped
+  auto_ban_delay_ms?: number;
+  auto_requeue_enabled: boolean;
+  auto_requeue_max_games?: number; // Requeues per app session
+  auto_requeue_max_loss_streak?: number; // Stop after this many losses in a row
+  auto_accept_enabled: boolean; // Accept ready checks as soon as they pop
+  auto_draft_enabled?: boolean; // Hover and lock picks from the rules below on my turns; bans go through auto-ban
+  auto_draft_picks?: Record<string, number[]>; // Position ("TOP", ..., or "ANY") -> champions in priority order
+  auto_draft_bans?: Record<string, number[]>; // Same for bans, tried after the auto-ban list
+  auto_draft_lock_seconds?: number | null; // Lock at this many seconds left; unset uses 5
+  lock_confirmation?: boolean; // Lock requests must be repeated within two seconds, and never lock during bans
+  patch_notes_url?: string; // JSON summary of the current patch's champion changes
+  comfort_mode?: boolean; // Favour easy champions similar to the player's pool
+  autofill_mode?: boolean; // Recommend only safe picks when autofilled
+  champion_pool?: number[]; // Losing one in draft suggests alternatives
+  pick_intents?: Record<string, Record<string, number>>; // Queue id -> position -> champion
+  coach_mode?: boolean;
+  share_draft?: boolean; // Publish draft snapshots to the relay
+  share_relay_url?: string;
+  share_token?: string;
+  obs_enabled?: boolean; // Switch OBS scenes on gameflow phase changes
+  obs_url?: string; // Defaults to ws://127.0.0.1:4455
+  obs_password?: string;
+  obs_scenes?: Record<string, string>; // Gameflow phase ("Lobby", "ChampSelect", "InProgress", "EndOfGame") -> scene
+  wine_prefixes?: string[]; // Linux: extra wine prefixes to find the client in; "~" allowed
+  feature_flags?: Record<string, boolean>; // Per-user overrides, by flag key
+  feature_flags_url?: string | null; // JSON object of flag key to bool, applied to everyone
+  enabled_scripts?: string[]; // Scripts to run, by name; all are off until enabled
+  metrics_enabled?: boolean; // Count feature use and error categories locally; opt-in
+  metrics_url?: string | null; // Where submit_metrics posts the report
+  afk_nudge_enabled?: boolean; // Notify when my turn is running out with nothing hovered
+  afk_nudge_seconds?: number[]; // Seconds left at which to nudge, each louder; empty uses [10, 5]
+  afk_auto_hover_seconds?: number | null; // With nudges on, hover the top recommendation at this many seconds left (needs the auto_hover flag); never locks
+  sound_alerts: boolean; // Play alert sounds in the app instead of through notifications
+  audio_output_device?: string; // AudioDevice id; unset uses the system default
+  voice_ducking: boolean; // Lower alert volume while a voice chat app is running
+  voice_ducking_volume?: number; // 0 to 1
+  voice_apps: string[]; // Process names that count as voice chat; empty uses the defaults
+  vision_benchmarks?: Record<string, number>; // Par vision score per minute by position; missing ones use the defaults
+  vision_callouts?: boolean; // Notify when jungle or support vision keeps falling below par
+  riot_api_key?: string | null; // Used for match timelines the client no longer has
+  riot_platform?: string | null; // Platform the account plays on, e.g. "EUW1"; needed with the API key
+  low_spec_mode?: boolean | null; // null picks it from the machine's RAM and cores
+  onnx_intra_threads?: number | null; // null follows the performance profile
+  onnx_inter_threads?: number | null; // null leaves ONNX Runtime's default
+  onnx_optimization_level?: OnnxOptimizationLevel | null; // null optimizes fully
+  lazy_model_loading?: boolean; // Load the draft model at the first champ select
+  lcu_polling_timeout_ms?: number | null; // Gameflow, champ select and other polled endpoints
+  lcu_polling_retries?: number | null;
+  lcu_polling_backoff_ms?: number | null; // Before the first retry, doubling after
+  lcu_history_timeout_ms?: number | null; // Match history, game details and timelines
+  lcu_history_retries?: number | null;
+  lcu_history_backoff_ms?: number | null;
+  event_verbosity?: EventVerbosity; // "minimal" drops draft-state-changed; "debug" adds draft-session-raw
+  draft_include_raw?: boolean; // Attach the untouched LCU session to draft-state-changed as raw_session
+}
+
+export type ClientProfile = "live" | "pbe";
+
+export interface DetectedClient {
+  process_id: number;
+  port: number;
+  source: string; // Lockfile path or process
+  profile: ClientProfile;
+  selected: boolean;
+}
+
+export interface PhaseTransition {
+  from?: string;
+  to?: string;
+}
+
+export type StateKey =
+  | "summoner"
+  | "ranked_stats"
+  | "ranked_stats_all_queues"
+  | "gameflow_phase"
+  | "draft_state";
+
+// Payload of `app-state-changed`
+export interface StateChange {
+  key: StateKey;
+  value: unknown | null; // null when the value is no longer known
+}
+
+export type RankChangeKind = "promoted" | "demoted" | "entered_promos";
+
+export interface RankChange {
+  kind: RankChangeKind;
+  queue_type: string;
+  before: RankSnapshot;
+  after: RankSnapshot;
+}
+
+export type GoalKind =
+  | { type: "reach_rank"; queue_type: string; tier: string; division?: string }
+  | { type: "games_played"; count: number; role?: string; queue_id?: number }
+  | { type: "max_average_deaths"; max_deaths: number; min_games: number };
+
+export interface Goal {
+  id: string;
+  title: string;
+  kind: GoalKind;
+  window_start: number;
+  window_end?: number;
+  completed_at?: number;
+}
+
+export interface GoalProgress {
+  goal: Goal;
+  current: number;
+  target: number;
+  completed: boolean;
+}
+
+export interface MissionObjective {
+  description: string;
+  current: number;
+  total: number;
+}
+
+export interface Mission {
+  id: string;
+  title: string;
+  description: string;
+  series_name?: string;
+  completed: boolean;
+  expires_at?: number;
+  objectives: MissionObjective[];
+  rewards: string[];
+}
+
+export type TimelineEventKind = "hover" | "ban_hover" | "lock" | "ban";
+
+export interface TimelineEvent {
+  elapsed_ms: number;
+  kind: TimelineEventKind;
+  cell_id?: number;
+  team_id?: number;
+  champion_id: number;
+  is_local_player: boolean;
+  phase: string;
+}
+
+export type RequeueStop =
+  | { reason: "session_limit"; games: number }
+  | { reason: "loss_streak"; losses: number }
+  | { reason: "match_sync_failed" }; // The game that ended couldn't be synced
+
+export type DownloadPhase = "started" | "downloading" | "finished" | "failed" | "cancelled";
+
+export interface DownloadProgress {
+  id: string;
+  label: string;
+  bytes: number;
+  total?: number; // Unknown while any file's size is unknown
+  phase: DownloadPhase;
+  files_done?: number; // Batch downloads count files instead of bytes
+  files_total?: number;
+}
+
+export interface StorageUsage {
+  champion_cache: number; // Bytes
+  image_cache: number;
+  database: number;
+  logs: number;
+  replays: number;
+}
+
+export type CacheKind = "images" | "champion_data";
+
+export interface StaticEntry {
+  name: string;
+  icon: string;
+}
+
+export interface StaticData {
+  version: string;
+  items: Record<number, StaticEntry>;
+  runes: Record<number, StaticEntry>;
+}
+
+export type AssetKind = "champion_icons" | "loading_screens";
+
+export interface PrefetchSummary {
+  downloaded: number;
+  skipped: number;
+  failed: number;
+  directory: string;
+}
+
+export type ChangeKind = "buff" | "nerf" | "adjusted";
+
+export interface ChampionChange {
+  champion_id: number;
+  kind: ChangeKind;
+  summary?: string;
+}
+
+export interface MetaMover {
+  champion_id: number;
+  win_rate_before: number;
+  win_rate_after: number;
+  games: number; // On the new patch
+}
+
+export interface RoleShift {
+  position: string;
+  risers: MetaMover[];
+  fallers: MetaMover[];
+}
+
+// Payload of the "meta-shift" event
+export interface MetaShiftReport {
+  patch: string;
+  previous_patch: string;
+  roles: RoleShift[];
+  seen: boolean;
+}
+
+export interface SimilarChampion {
+  champion_id: number;
+  name: string;
+  similarity: number; // 0 to 1
+}
+
+export type LossReason = "banned" | "picked_by_enemy" | "picked_by_ally";
+
+export interface PoolAlternative {
+  champion_id: number;
+  name: string;
+  similarity: number;
+  model_score?: number; // Missing when the model isn't loaded
+}
+
+// Payload of the "pool-champion-lost" event
+export interface PoolChampionLost {
+  champion_id: number;
+  reason: LossReason;
+  position?: string;
+  alternatives: PoolAlternative[];
+}
+
+// Payload of the "pick-intent-lost" event
+export interface PickIntentLost {
+  champion_id: number;
+  queue_id: number;
+  position: string;
+  reason: LossReason;
+  alternatives: PoolAlternative[];
+}
+
+export type CompGap = 'no_frontline' | 'full_ad' | 'full_ap';
+
+export interface CompGapWarning {
+  gap: CompGap;
+  message: string;
+  locked: number;
+  picks_left: number;
+}
+
+export interface RoleConflict {
+  position: string;
+  cell_ids: number[];
+  champion_ids: number[];
+  champion_names: string[];
+}
+
+export interface CoachLine {
+  action_id: number;
+  text: string;
+}
+
+export interface RosterPlayer {
+  game_name: string;
+  tag_line: string;
+  position: string;
+  champion_pool: number[];
+}
+
+export interface Roster {
+  id: string;
+  name: string;
+  players: RosterPlayer[];
+  updated_at: number;
+}
+
+export interface OpponentPlayer {
+  riot_id: string; // "Name#TAG"
+  position?: string;
+}
+
+export interface ScoutedChampion {
+  champion_id: number;
+  name: string;
+  games: number;
+  wins: number;
+  mastery_points: number;
+}
+
+export interface RecentPick {
+  champion_id: number;
+  name: string;
+  win: boolean;
+}
+
+export interface ScoutedPlayer {
+  riot_id: string;
+  position: string | null;
+  solo_rank: string | null;
+  champion_pool: ScoutedChampion[];
+  recent_picks: RecentPick[];
+  inferred_pool: RolePool[];
+  error: string | null;
+}
+
+export interface ContestedChampion {
+  champion_id: number;
+  name: string;
+  theirs: string[];
+  ours: string[];
+}
+
+export interface ScoutingReport {
+  opponent: string;
+  team: string | null;
+  generated_at: number;
+  players: ScoutedPlayer[];
+  contested: ContestedChampion[];
+  ban_suggestions: BanSuggestion[];
+}
+
+export interface PoolChampion {
+  champion_id: number;
+  games: number;
+  wins: number;
+  win_rate: number;
+}
+
+export interface RolePool {
+  position: string;
+  champions: PoolChampion[];
+}
+
+export interface BanSuggestion {
+  champion_id: number;
+  name: string;
+  riot_id: string;
+  games: number;
+  win_rate: number;
+}
+
+export interface SharedDraft {
+  published_at: number;
+  state: DraftState;
+}
+
+export type SuggestionAction = 'pick' | 'ban';
+
+export interface CoachSuggestion {
+  champion_id: number;
+  action: SuggestionAction;
+  note: string | null;
+  sent_at: number;
+}
+
+export type OverlayMode = "always_on_top" | "window_with_notification";
+
+export interface PlatformCapabilities {
+  os: string;
+  display_server?: "wayland" | "x11"; // Linux only
+  desktop?: string;
+  always_on_top: boolean;
+  overlay_mode: OverlayMode;
+}
+
+export interface LcuStatus {
+  connected: boolean;
+  phase: string | null;
+  port: number | null;
+  profile: ClientProfile | null;
+}
+
+export interface ChampionDataStatus {
+  version: string | null;
+  updated_at: number | null;
+  stale: boolean; // No data, or written more than a patch ago
+}
+
+export interface AppStatus {
+  lcu: LcuStatus;
+  model_loaded: boolean;
+  champion_data: ChampionDataStatus;
+  background_tasks: string[]; // e.g. "draft_monitor", "download:<id>"
+  in_game: boolean;
+  pending_update: string | null;
+}
+
+export type StartupStage = "settings" | "champion_data" | "stores" | "background_tasks" | "model";
+
+export interface StartupProgress {
+  stage: StartupStage;
+  status: "running" | "done" | "failed" | "deferred"; // deferred: the model with lazy loading on
+  error: string | null;
+  finished: number; // Stages done or failed so far
+  total: number;
+}
+
+export interface FlagState {
+  key: string; // e.g. "gpu_inference"
+  description: string;
+  enabled: boolean;
+  source: "default" | "remote" | "user";
+}
+
+export interface ScriptInfo {
+  name: string; // File name without ".rhai"
+  enabled: boolean;
+  hooks: string[]; // e.g. "on_draft_changed", "rerank"
+  error: string | null; // Why the script didn't load
+}
+
+export interface ScriptError {
+  script: string;
+  hook: string;
+  error: string;
+}
+
+// Exactly what submit_metrics sends
+export interface MetricsReport {
+  app_version: string;
+  os: string;
+  since: number | null;
+  until: number;
+  features: Record<string, number>; // e.g. "draft_recommendations" -> 12
+  errors: Record<string, number>; // e.g. "match_sync" -> 1
+}
+
+// Payload of the "afk-nudge" event
+export interface AfkNudge {
+  level: number; // 1 for the first nudge of a turn, rising as time runs out
+  final_level: boolean;
+  action_type: string; // "pick" or "ban"
+  seconds_left: number;
+}
+
+// Payload of the "honor-suggestion" event
+export interface HonorSuggestion {
+  game_id: number;
+  puuid: string;
+  name: string;
+  champion_id: number;
+  score: number;
+  reasons: string[]; // e.g. "Most vision", "Fewest deaths"
+}
+
+export type TrendMetric =
+  | "kda"
+  | "cs_per_min"
+  | "vision_per_min"
+  | "damage_share"
+  | "first_blood" // Average is the first blood participation rate
+  | "gold_at_10";
+
+export type TrendGrouping = "role" | "champion";
+
+export interface TrendPoint {
+  game_id: number;
+  game_creation: number;
+  value: number; // This game alone
+  average: number; // Rolling average over the window ending at this game
+}
+
+export interface TrendSeries {
+  metric: TrendMetric;
+  role: string | null;
+  champion_id: number | null;
+  points: TrendPoint[]; // Oldest first
+}
+
+export interface TimeBucket {
+  label: string;
+  games: number;
+  wins: number;
+  winrate: number | null; // Percent; null below the minimum sample
+  average_kda: number | null;
+}
+
+export interface ScheduleInsights {
+  by_hour: TimeBucket[]; // 24 entries, "00:00" first
+  by_weekday: TimeBucket[]; // Monday first
+  by_period: TimeBucket[];
+  insights: string[];
+  min_games: number;
+}
+
+export interface ContestedChampion {
+  champion_id: number;
+  taken_rate: number; // Share of the owner's other games where someone else played it
+}
+
+export interface PoolAddition {
+  champion_id: number;
+  name: string;
+  similarity: number;
+  covers: number[]; // Top champions it can stand in for
+}
+
+export interface PoolHealth {
+  games: number;
+  distinct_champions: number;
+  top_share: number;
+  concentration: number; // 1 for a single champion, near 0 for a wide pool
+  one_trick: boolean;
+  top: PoolChampion[]; // Most played first
+  top_win_rate: number | null;
+  rest: PoolChampion[];
+  rest_games: number;
+  rest_win_rate: number | null;
+  contested: ContestedChampion[]; // Most often taken first
+  additions: PoolAddition[];
+}
+
+export interface VisionReport {
+  game_id: number;
+  position: string;
+  vision_score: number;
+  vision_per_min: number;
+  benchmark: number;
+  rolling_average: number | null; // Earlier games in the role; null for the first
+  below_par: boolean;
+  callout: string | null; // Set when below par in most recent jungle or support games
+}
+
+export interface ParticipantFrame {
+  participant_id: number;
+  total_gold: number;
+  xp: number;
+  cs: number; // Lane minions plus jungle monsters
+}
+
+export interface TimelineFrame {
+  timestamp: number; // Millis since the game started
+  participants: ParticipantFrame[];
+}
+
+export interface MatchTimeline {
+  game_id: number;
+  frames: TimelineFrame[];
+}
+
+export interface GoldGraphPoint {
+  minute: number;
+  team_gold_diff: number; // My team minus theirs
+  team_xp_diff: number;
+  lane_gold_diff: number | null; // Me minus my lane opponent; null without one
+  lane_xp_diff: number | null;
+}
+
+export interface GoldGraph {
+  game_id: number;
+  lane_opponent_champion_id: number | null;
+  points: GoldGraphPoint[];
+}
+
+export interface MatchupStats {
+  my_champion_id: number;
+  their_champion_id: number;
+  games: number;
+  wins: number;
+  win_rate: number;
+  average_kda: number;
+  average_gold_diff_at_10: number | null; // Over games with gold at ten for both players
+  last_played: number;
+}
+
+export interface RuneStyleStats {
+  style_id: number;
+  games: number;
+  wins: number;
+}
+
+export interface KeystoneStats {
+  keystone_id: number;
+  games: number;
+  wins: number;
+  win_rate: number;
+  average_kda: number;
+  secondary_styles: RuneStyleStats[]; // Most played first
+}
+
+export interface SpellSetupStats {
+  spells: [number, number]; // Lowest id first
+  games: number;
+  wins: number;
+  win_rate: number;
+  share: number; // Of the champion's games in the role
+  no_flash: boolean;
+  unusual: boolean;
+  losing: boolean; // Enough games, and well below the champion's overall win rate
+}
+
+export interface SpellUsage {
+  champion_id: number;
+  position: string | null; // null covers every role
+  games: number;
+  win_rate: number;
+  setups: SpellSetupStats[]; // Most played first
+}
+
+export interface SpellInsight {
+  game_id: number;
+  champion_id: number;
+  setup: SpellSetupStats;
+  message: string;
+}
+
+export interface QueueStats {
+  queue_id: number;
+  games: number;
+  remakes: number; // Games shorter than five minutes
+  average_duration_seconds: number; // Remakes left out
+}
+
+export interface AutofillStats {
+  queue_id: number;
+  preferred_positions: string[]; // Two most played positions, most played first
+  games: number;
+  autofilled: number;
+  autofill_rate: number;
+}
+
+export interface DraftConversion {
+  drafts: number;
+  games: number;
+  dodges: number;
+  conversion_rate: number | null;
+}
+
+export interface QueueInsights {
+  queues: QueueStats[];
+  autofill: AutofillStats[];
+  drafts: DraftConversion;
+}
+
+export interface DraftGrade {
+  game_id: number;
+  champion_id: number;
+  best_champion_id: number; // The model's top pick when I locked in
+  rank: number | null; // 1 for the model's top pick
+  grade: number; // My pick's score over the top pick's, 0 to 1
+}
+
+export interface ReviewSummary {
+  drafts: number;
+  top_pick_rate: number | null;
+  top_pick_games: number;
+  top_pick_win_rate: number | null;
+  other_games: number;
+  other_win_rate: number | null;
+  average_grade: number | null;
+  grades: DraftGrade[]; // Most recent first
+}
+
+export interface BanCount {
+  champion_id: number;
+  drafts: number; // Drafts it was banned in
+  rate: number;
+}
+
+export interface BanStats {
+  drafts: number; // Recorded drafts with a ban phase
+  banned_against_me: BanCount[]; // Most banned first
+  my_bans: BanCount[];
+  pool_targeted: number; // Drafts where the enemy banned a champion from my pool
+  pool_target_rate: number | null;
+}
+
+export interface IntentRisk {
+  queue_id: number;
+  position: string;
+  champion_id: number;
+  ban_rate: number | null; // Share of recorded drafts it was banned in
+}
+
+export type RoleAssignment = 'secondary' | 'autofill';
+
+export interface RoleShortlistEntry {
+  champion_id: number;
+  games: number; // My games on it in this position; 0 for safe picks
+  wins: number;
+  safe_pick: boolean;
+}
+
+export interface RolePrompt {
+  position: string;
+  assignment: RoleAssignment;
+  games: number;
+  wins: number;
+  win_rate: number | null;
+  average_kda: number | null;
+  shortlist: RoleShortlistEntry[]; // Most played first, then safe picks
+}
+
+export interface SystemSpecs {
+  total_memory_mb: number;
+  cpu_cores: number;
+}
+
+export type OnnxOptimizationLevel = 'disable' | 'basic' | 'extended' | 'all';
+
+export type EventVerbosity = 'minimal' | 'normal' | 'debug';
+
+export interface PerformanceProfile {
+  low_spec: boolean;
+  automatic: boolean; // Chosen from the machine's specs rather than the setting
+  specs: SystemSpecs;
+  draft_polling_ms: number;
+  gameflow_polling_ms: number;
+  onnx_threads: number;
+  quantized_model: boolean;
+  prefetch_assets: boolean;
+  overlay: boolean; // Open the in-game overlay
+}
+
+export interface RequestStats {
+  requests: number; // Sent to the client, coalesced ones excluded
+  failures: number;
+  coalesced: number; // Served from a response fetched moments before
+  credential_refreshes: number; // Lockfile reads after credentials were dropped
+  unknown_shapes: number; // Response values none of the known variations matched
+  slow_requests: number; // Slower than the draft polling interval
+  average_ms: number | null; // Over the most recent requests
+  max_ms: number | null;
+}
+
+export type LcuErrorKind =
+  | "client_not_running"
+  | "not_in_champ_select"
+  | "unauthorized"
+  | "auth_expired" // Still rejected after re-reading the lockfile
+  | "timeout"
+  | "deserialize"
+  | "http"
+  | "other";
+
+// Rejection value of the LCU commands and payload of `draft-error`
+export interface LcuError {
+  kind: LcuErrorKind;
+  message: string;
+  status?: number; // For "http"
+}
+
+// Granular draft events, each emitted under its own name
+export interface LockedChampion { // "pick-locked" and "ban-locked"
+  action_id: number;
+  cell_id?: number;
+  champion_id: number;
+  is_ally: boolean;
+}
+
+export interface HoverChanged { // "hover-changed"
+  cell_id: number;
+  champion_id?: number; // Unset when the hover was cleared
+}
+
+export interface TurnStarted { // "turn-started"
+  action_id: number;
+  cell_id?: number;
+  action_type: string;
+  is_local_player: boolean;
+}
+
+export interface TimerTick { // "timer-tick"
+  seconds_left?: number;
+}
+
+export interface PhaseChanged { // "phase-changed"
+  phase: string;
+  previous?: string;
+}
+
+export interface SwapOccurred { // "swap-occurred"
+  kind: SwapKind;
+  cell_id: number; // The local player
+  other_cell_id: number;
+}
+
+// Resolved value of `lock_champion`
+export type LockOutcome = "locked" | "awaiting_confirmation" | "ban_phase";
+
+// Payload of `auto-draft-applied`
+export interface AutoDraftStep {
+  action_id: number;
+  action_type: "pick"; // Ban rules are applied by auto-ban
+  champion_id: number;
+  locked: boolean; // False for a hover
+  position?: string;
+}
+
+export interface FriendLiveGame {
+  puuid: string;
+  game_name?: string;
+  tag_line?: string;
+  champion_id?: number;
+  queue_type?: string;
+  started_at?: number; // Epoch millis
+  observable: boolean; // False when the friend turned spectating off
+}
+
+export interface AudioDevice {
+  id: string;
+  name: string;
+  is_default: boolean;
+}
+
+// Payload of `sound-alert`
+export interface SoundAlert {
+  sound: string;
+  device?: string; // Unset plays on the system default
+  volume: number; // 0 to 1
+  ducked: boolean; // Lowered because a voice app is running
+}
+
+// Result of `get_aram_bench_recommendations`
+export interface AramAdvice {
+  options: AramOption[]; // Current champion and the bench, best first
+  source: "model" | "stats";
+  swap_to?: number | null; // Best bench champion, when clearly better than the current one
+  reroll: boolean;
+}
+
+export interface AramOption {
+  champion_id: number;
+  score: number;
+  on_bench: boolean; // false for the champion the player holds
+  games: number; // The player's stored ARAM games on it
+  wins: number;
+}
+
+// Result of `get_series_state` and payload of `series-changed`
+export interface SeriesState {
+  active: boolean;
+  started_at?: number | null; // Epoch millis
+  games: SeriesGame[];
+  excluded: number[]; // Every champion picked so far, sorted
+}
+
+export interface SeriesGame {
+  game_id?: number | null;
+  champion_ids: number[]; // Locked by either team
+}